@@ -0,0 +1,196 @@
+//! Read the machine architecture of a library from its header (ELF, Mach-O or PE), so that a
+//! library built for another architecture (e.g., an `x86_64` library on an `arm64` Mac) is skipped
+//! rather than failing later, when linked.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// The architectures whose libraries can be recognized, named as in `cfg(target_arch)`.
+const KNOWN: &[&str] = &["x86_64", "x86", "aarch64", "arm", "riscv64"];
+
+/// The name given to a machine type that is not in [`KNOWN`].
+const UNKNOWN: &str = "unknown";
+
+/// The architecture libraries must be built for (see [`crate::target::arch`]); `None` for
+/// architectures whose libraries cannot be recognized, which are not checked.
+pub(crate) fn target() -> Option<&'static str> {
+    let arch = crate::target::arch();
+    KNOWN.iter().copied().find(|known| *known == arch)
+}
+
+/// Check whether the library at `path` is built for the [`target`] architecture; if not, return
+/// the architecture it is built for. Files whose format is not recognized (e.g., linker scripts or
+/// Windows import libraries) are assumed to match.
+pub(crate) fn check(path: &Path) -> Result<(), &'static str> {
+    let Some(target) = target() else {
+        return Ok(());
+    };
+    let Some(architectures) = of_library(path) else {
+        return Ok(());
+    };
+    if architectures.contains(&target) {
+        Ok(())
+    } else {
+        let library = architectures.first().copied().unwrap_or(UNKNOWN);
+        log::debug!(
+            "Skipping library at path: {} (built for {}, not for the target {})",
+            path.display(),
+            library,
+            target
+        );
+        Err(library)
+    }
+}
+
+/// Read the architectures the library at `path` is built for; a universal Mach-O library may be
+/// built for several. `None` if the file cannot be read or its format is not recognized.
+fn of_library(path: &Path) -> Option<Vec<&'static str>> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    File::open(path)
+        .ok()?
+        .take(HEADER_SIZE as u64)
+        .read_to_end(&mut header)
+        .ok()?;
+    from_header(&header)
+}
+
+/// The number of bytes read from the start of a library; this covers the PE header of the DLLs
+/// produced by the usual linkers and the architecture list of a universal Mach-O library.
+const HEADER_SIZE: usize = 1024;
+
+/// Parse the architectures out of the first bytes of a library.
+fn from_header(header: &[u8]) -> Option<Vec<&'static str>> {
+    let u16_at = |offset: usize, big_endian: bool| {
+        let bytes: [u8; 2] = header.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(bytes)
+        } else {
+            u16::from_le_bytes(bytes)
+        })
+    };
+    let u32_at = |offset: usize, big_endian: bool| {
+        let bytes: [u8; 4] = header.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+    match header.get(..4)? {
+        // ELF: `e_machine` follows the identification bytes, in the byte order of `EI_DATA`.
+        b"\x7fELF" => {
+            let big_endian = *header.get(5)? == 2;
+            let is_64_bit = *header.get(4)? == 2;
+            let arch = match u16_at(18, big_endian)? {
+                0x3e => "x86_64",
+                0x03 => "x86",
+                0xb7 => "aarch64",
+                0x28 => "arm",
+                0xf3 if is_64_bit => "riscv64",
+                _ => UNKNOWN,
+            };
+            Some(vec![arch])
+        }
+        // Mach-O, 32- or 64-bit, in the byte order of the host it runs on (always little-endian
+        // for the architectures above): `cputype` follows the magic number.
+        [0xce | 0xcf, 0xfa, 0xed, 0xfe] => Some(vec![mach_o_cpu(u32_at(4, false)?)]),
+        // A universal Mach-O library, big-endian: `nfat_arch` entries of 20 bytes, each starting
+        // with its `cputype`.
+        [0xca, 0xfe, 0xba, 0xbe] => {
+            let count = u32_at(4, true)?;
+            (0..usize::try_from(count).ok()?)
+                .map(|i| u32_at(8 + i * 20, true).map(mach_o_cpu))
+                .collect()
+        }
+        // PE: the DOS header points to the PE signature, which `Machine` follows.
+        [b'M', b'Z', ..] => {
+            let offset = usize::try_from(u32_at(0x3c, false)?).ok()?;
+            if header.get(offset..offset + 4)? != b"PE\0\0" {
+                return None;
+            }
+            let arch = match u16_at(offset + 4, false)? {
+                0x8664 => "x86_64",
+                0x014c => "x86",
+                0xaa64 => "aarch64",
+                0x01c4 => "arm",
+                _ => UNKNOWN,
+            };
+            Some(vec![arch])
+        }
+        _ => None,
+    }
+}
+
+/// Name a Mach-O `cputype`.
+fn mach_o_cpu(cpu: u32) -> &'static str {
+    const ABI64: u32 = 0x0100_0000;
+    const X86: u32 = 0x07;
+    const ARM: u32 = 0x0c;
+    match cpu {
+        c if c == X86 | ABI64 => "x86_64",
+        X86 => "x86",
+        c if c == ARM | ABI64 => "aarch64",
+        ARM => "arm",
+        _ => UNKNOWN,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_headers() {
+        let mut elf = b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x03\0".to_vec();
+        elf.extend(0xb7u16.to_le_bytes());
+        assert_eq!(from_header(&elf), Some(vec!["aarch64"]));
+
+        let mut mach_o = vec![0xcf, 0xfa, 0xed, 0xfe];
+        mach_o.extend(0x0100_0007u32.to_le_bytes());
+        assert_eq!(from_header(&mach_o), Some(vec!["x86_64"]));
+
+        let mut universal = vec![0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 2];
+        for cpu in [0x0100_0007u32, 0x0100_000c] {
+            universal.extend(cpu.to_be_bytes());
+            universal.extend([0; 16]);
+        }
+        assert_eq!(from_header(&universal), Some(vec!["x86_64", "aarch64"]));
+
+        let mut pe = vec![0; 0x40];
+        pe[..2].copy_from_slice(b"MZ");
+        pe[0x3c..].copy_from_slice(&0x40u32.to_le_bytes());
+        pe.extend(b"PE\0\0");
+        pe.extend(0x8664u16.to_le_bytes());
+        assert_eq!(from_header(&pe), Some(vec!["x86_64"]));
+
+        assert_eq!(from_header(b"/* GNU ld script */"), None);
+        assert_eq!(from_header(b"!<arch>\n"), None);
+        assert_eq!(from_header(b"MZ"), None);
+    }
+
+    #[test]
+    fn check_libraries() {
+        let dir = std::env::temp_dir().join("openvino-finder-architectures");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let script = dir.join("libfoo.so");
+        std::fs::write(&script, "INPUT(libfoo.so.1)").unwrap();
+        assert_eq!(check(&script), Ok(()));
+
+        // Build an ELF header for an architecture other than the one running the test.
+        let foreign = if target() == Some("aarch64") {
+            0x3eu16
+        } else {
+            0xb7
+        };
+        let mut elf = b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x03\0".to_vec();
+        elf.extend(foreign.to_le_bytes());
+        let library = dir.join("libbar.so");
+        std::fs::write(&library, elf).unwrap();
+        if target().is_some() {
+            assert!(check(&library).is_err());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,178 @@
+//! Remember the libraries found by earlier searches on disk, so that builds and processes do not
+//! probe every search location again (which is slow, e.g., on network filesystems); see [`Cache`].
+
+use crate::{finder, Config, ENVIRONMENT_VARIABLES};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+const ENV_OPENVINO_FINDER_CACHE: &str = "OPENVINO_FINDER_CACHE";
+
+/// An on-disk cache of the libraries found by [`crate::Finder::find`] (and therefore by
+/// [`crate::find`]), enabled with [`crate::Finder::with_cache`] or by setting the
+/// `OPENVINO_FINDER_CACHE` environment variable to the cache directory (e.g.,
+/// `$CARGO_TARGET_DIR/openvino-finder`).
+///
+/// Each result is keyed by the inputs of the search: the library file name, the [`crate::Finder`]
+/// options, the environment variables the search reads, the contents of the configuration file
+/// (see [`Config`]) and the current directory. A result is discarded once the modification time of
+/// the library or of its directory changes (e.g., the installation is upgraded or removed);
+/// libraries installed later in a location searched _earlier_ are not noticed, so [`Cache::clear`]
+/// the cache after installing OpenVINO elsewhere. Failed searches are not cached.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    /// Use the cache in directory `dir`, which is created when needed.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Use the cache in the user's cache directory: `openvino-rs/finder` in `$XDG_CACHE_HOME` or
+    /// `~/.cache` on Linux, `~/Library/Caches` on `MacOS` and `%LOCALAPPDATA%` on Windows; `None`
+    /// if that directory is unknown.
+    pub fn user() -> Option<Self> {
+        let cache_dir = if cfg!(target_os = "windows") {
+            finder::var_os("LOCALAPPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            finder::var_os("HOME").map(|h| PathBuf::from(h).join("Library").join("Caches"))
+        } else {
+            finder::var_os("XDG_CACHE_HOME")
+                .map(PathBuf::from)
+                .or_else(|| finder::var_os("HOME").map(|h| PathBuf::from(h).join(".cache")))
+        };
+        Some(Self::new(cache_dir?.join("openvino-rs").join("finder")))
+    }
+
+    /// Use the cache named by the `OPENVINO_FINDER_CACHE` environment variable, if set.
+    pub(crate) fn from_env() -> Option<Self> {
+        finder::var_os(ENV_OPENVINO_FINDER_CACHE).map(Self::new)
+    }
+
+    /// The directory holding the cached results.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Remove all of the cached results.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache directory exists but cannot be removed.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Retrieve the library found by the search keyed by `key` (see [`key`]), unless it has changed
+    /// since; a stale result is removed.
+    pub(crate) fn get(&self, key: &str) -> Option<PathBuf> {
+        let entry = self.dir.join(key);
+        let contents = fs::read_to_string(&entry).ok()?;
+        let mut lines = contents.lines();
+        let path = PathBuf::from(lines.next()?);
+        let stamps = lines.next()?;
+        if stamps == modification_stamps(&path)? {
+            return Some(path);
+        }
+        log::debug!("Discarding stale cached library: {}", path.display());
+        let _ = fs::remove_file(entry);
+        None
+    }
+
+    /// Record that the search keyed by `key` found the library at `path`; failures (e.g., a
+    /// read-only cache directory or a path which is not Unicode) are logged and ignored.
+    pub(crate) fn insert(&self, key: &str, path: &Path) {
+        let (Some(path_str), Some(stamps)) = (path.to_str(), modification_stamps(path)) else {
+            return;
+        };
+        let result = fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(self.dir.join(key), format!("{path_str}\n{stamps}\n")));
+        if let Err(e) = result {
+            log::warn!("Unable to cache library in {}: {}", self.dir.display(), e);
+        }
+    }
+}
+
+/// Compute the cache key of a search for `file` with the [`crate::Finder`] options `options`.
+pub(crate) fn key(file: &str, options: &impl Hash) -> String {
+    let mut hasher = DefaultHasher::new();
+    file.hash(&mut hasher);
+    options.hash(&mut hasher);
+    for name in ENVIRONMENT_VARIABLES {
+        finder::var_os(name).hash(&mut hasher);
+    }
+    Config::path()
+        .and_then(|p| fs::read(p).ok())
+        .hash(&mut hasher);
+    std::env::current_dir().ok().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Describe the modification times of the file at `path` and of its directory; `None` if either
+/// cannot be read.
+fn modification_stamps(path: &Path) -> Option<String> {
+    let stamp = |path: &Path| -> Option<u128> {
+        let modified = fs::metadata(path).ok()?.modified().ok()?;
+        Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+    };
+    let file = stamp(path)?;
+    let dir = stamp(path.parent()?)?;
+    Some(format!("{file} {dir}"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn invalidation() {
+        let dir = env::temp_dir().join("openvino-finder-cache");
+        let _ = fs::remove_dir_all(&dir);
+        let library = dir.join("lib").join("libopenvino_finder_cache.so");
+        fs::create_dir_all(library.parent().unwrap()).unwrap();
+        fs::write(&library, "").unwrap();
+        let cache = Cache::new(dir.join("cache"));
+
+        assert_eq!(cache.get("key"), None);
+        cache.insert("key", &library);
+        assert_eq!(cache.get("key"), Some(library.clone()));
+        assert_eq!(cache.get("other"), None);
+
+        let modified = SystemTime::now() - Duration::from_secs(10);
+        fs::File::options()
+            .write(true)
+            .open(&library)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        assert_eq!(cache.get("key"), None);
+        assert!(!cache.dir().join("key").exists());
+
+        cache.insert("key", &library);
+        cache.clear().unwrap();
+        assert!(!cache.dir().exists());
+        cache.clear().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keys() {
+        let key = key("libopenvino_c.so", &vec![PathBuf::from("/opt/openvino")]);
+        assert_eq!(key.len(), 16);
+        assert_ne!(key, super::key("libopenvino_c.so", &Vec::<PathBuf>::new()));
+        assert_ne!(
+            key,
+            super::key("libopenvino.so", &vec![PathBuf::from("/opt/openvino")])
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Read the user's finder configuration file, which pins choices (e.g., the installation to use)
+//! across shells and builds without environment variables; see [`Config`].
+
+use crate::{finder, VersionReq};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+const ENV_OPENVINO_FINDER_CONFIG: &str = "OPENVINO_FINDER_CONFIG";
+
+/// The user's finder configuration, read from a TOML file: the file named by the
+/// `OPENVINO_FINDER_CONFIG` environment variable, otherwise `openvino-rs/finder.toml` in the
+/// user's configuration directory (`$XDG_CONFIG_HOME` or `~/.config` on Linux and `MacOS`,
+/// `%APPDATA%` on Windows).
+///
+/// ```toml
+/// # Use this installation rather than the one `setupvars.sh` selects.
+/// install_dir = "/opt/intel/openvino_2025.1.0"
+/// # Use this `plugins.xml` rather than the one beside the library.
+/// plugins_xml = "/etc/openvino/plugins.xml"
+/// # Prefer a library of these versions when several are installed.
+/// version = ">=2025.1, <2026"
+/// ```
+///
+/// Every key is optional; the environment variables take precedence over the file (e.g.,
+/// `OPENVINO_INSTALL_DIR` over `install_dir`), except those an OpenVINO environment script sets
+/// (`INTEL_OPENVINO_DIR` and the OS library path), so that switching installations does not
+/// require editing a shell profile. See [`crate::find`] and [`crate::find_plugins_xml`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct Config {
+    /// The root of the OpenVINO installation to search first, with the layouts of
+    /// `OPENVINO_INSTALL_DIR` (e.g., `runtime/lib/intel64`).
+    pub install_dir: Option<PathBuf>,
+    /// The `plugins.xml` file to use, as with `OPENVINO_PLUGINS_XML`.
+    pub plugins_xml: Option<PathBuf>,
+    /// The versions of OpenVINO to prefer (e.g., `~2024.6`): [`crate::find`] picks the first
+    /// library of a matching version, if any, as [`crate::find_version`] does.
+    pub version: Option<VersionReq>,
+}
+
+impl Config {
+    /// Find the path of the configuration file, whether or not it exists; `None` if the user's
+    /// configuration directory is unknown.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = finder::var_os(ENV_OPENVINO_FINDER_CONFIG) {
+            return Some(PathBuf::from(path));
+        }
+        let config_dir = if cfg!(target_os = "windows") {
+            finder::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            finder::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| finder::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+        };
+        Some(config_dir?.join("openvino-rs").join("finder.toml"))
+    }
+
+    /// Read the configuration file; if it does not exist or is invalid (which is logged), the
+    /// configuration is empty.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let Ok(contents) = fs::read_to_string(&path) else {
+            log::debug!("No finder configuration at: {}", path.display());
+            return Self::default();
+        };
+        match contents.parse() {
+            Ok(config) => {
+                log::info!("Read finder configuration from: {}", path.display());
+                config
+            }
+            Err(e) => {
+                log::warn!("Ignoring finder configuration {}: {}", path.display(), e);
+                Self::default()
+            }
+        }
+    }
+}
+
+/// The keys of the configuration file, as the fields of [`Config`].
+const KEYS: &[&str] = &["install_dir", "plugins_xml", "version"];
+
+impl FromStr for Config {
+    type Err = ParseConfigError;
+
+    /// Parse the configuration, a TOML document; unknown keys are ignored (which is logged).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = toml::from_str(s).map_err(|e| ParseConfigError(e.to_string()))?;
+        for key in table.keys().filter(|k| !KEYS.contains(&k.as_str())) {
+            log::warn!("Ignoring unknown finder configuration key: {}", key);
+        }
+        table
+            .try_into()
+            .map_err(|e: toml::de::Error| ParseConfigError(e.to_string()))
+    }
+}
+
+/// An error parsing a [`Config`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseConfigError(String);
+
+impl std::fmt::Display for ParseConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseConfigError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let config: Config = "
+            # Pin the installation.
+            install_dir = \"/opt/intel/openvino_2025.1.0\"
+            plugins_xml = 'C:\\OpenVINO\\plugins.xml' # A literal string.
+            version = \">=2025.1, <2026\"
+            unknown = \"ignored\"
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                install_dir: Some(PathBuf::from("/opt/intel/openvino_2025.1.0")),
+                plugins_xml: Some(PathBuf::from("C:\\OpenVINO\\plugins.xml")),
+                version: Some(">=2025.1, <2026".parse().unwrap()),
+            }
+        );
+        assert_eq!("".parse(), Ok(Config::default()));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |s: &str| s.parse::<Config>().unwrap_err().to_string();
+        assert!(error("install_dir").contains("line 1"));
+        assert!(error("\ninstall_dir = /opt").contains("line 2"));
+        assert!(error("install_dir = \"/opt").contains("line 1"));
+        assert!(error("install_dir = \"/opt\" extra").contains("line 1"));
+        assert!(error("install_dir = 1").contains("install_dir"));
+        assert!(error("version = \"latest\"").contains("version"));
+    }
+}
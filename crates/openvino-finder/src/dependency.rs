@@ -0,0 +1,233 @@
+//! Find the third-party libraries OpenVINO depends on (e.g., TBB or pugixml), which some
+//! installations bundle (e.g., in `runtime/3rdparty/tbb/lib`) and others leave to the system; see
+//! [`find_dependency`].
+
+use crate::{
+    check_target, default_installation_directories, finder, homebrew_prefixes, linker_cache,
+    list_directory, openvino_c_library, split_install_dirs, system_installation_directories,
+    target, ENV_INTEL_OPENVINO_DIR, ENV_LIBRARY_PATH, ENV_OPENVINO_INSTALL_DIR,
+};
+use std::env;
+use std::path::{Path, PathBuf};
+
+const ENV_TBBROOT: &str = "TBBROOT";
+
+/// Find the path to a third-party library OpenVINO depends on, by name (e.g., `tbb`, `tbbmalloc`
+/// or `pugixml`).
+///
+/// These libraries do not follow the layouts [`crate::find`] knows, so this function probes:
+/// - for TBB, the `TBBROOT` environment variable (set by oneTBB's environment script), with `lib`
+///   and `bin` appended
+/// - the installation of the `openvino_c` library (the one loaded in the process or else the one
+///   discovered by [`crate::find`]): its directory, as in `pip` packages, and the
+///   `3rdparty/<name>/{lib,bin}` directories above it, as in archives (e.g.,
+///   `runtime/3rdparty/tbb/lib`)
+/// - the `3rdparty` directories of the installations named by `OPENVINO_INSTALL_DIR` and
+///   `INTEL_OPENVINO_DIR` and of the default installation directories (e.g.,
+///   `/opt/intel/openvino`)
+/// - the OS library path (e.g., `LD_LIBRARY_PATH`)
+/// - on Linux, the directories in which the dynamic linker's cache records the library, then the
+///   system library directories (e.g., `/usr/lib/x86_64-linux-gnu`), where distributions install
+///   these libraries, and, on `MacOS`, the Homebrew formula of the same name (e.g.,
+///   `/opt/homebrew/opt/tbb/lib`)
+///
+/// In each directory, the plain file name (e.g., `libtbb.so`) is preferred; otherwise, the latest
+/// version-suffixed copy is accepted, e.g., `libtbb.so.12` on Linux, `libtbb.12.dylib` on `MacOS`
+/// or `tbb12.dll` on Windows, since packages often only ship the library under its soname.
+///
+/// # Errors
+///
+/// Returns a [`DependencyNotFound`] error listing the directories searched if no copy of the
+/// library is found.
+pub fn find_dependency(name: &str) -> Result<PathBuf, DependencyNotFound> {
+    let (prefix, suffix) = target::dynamic_library_affixes();
+    let file = format!("{prefix}{name}{suffix}");
+    log::info!("Attempting to find dependency: {}", file);
+    let mut searched = vec![];
+    if let Err(e) = check_target() {
+        log::error!("Not searching for {}: {}", file, e);
+    } else {
+        for dir in dependency_directories(name, &file) {
+            if searched.contains(&dir) {
+                continue;
+            }
+            log::debug!("Searching in: {}", dir.display());
+            if let Some(path) = dependency_file(&dir, name) {
+                log::info!("Found dependency at path: {}", path.display());
+                return Ok(path);
+            }
+            searched.push(dir);
+        }
+    }
+    Err(DependencyNotFound {
+        library: file,
+        searched,
+    })
+}
+
+/// List, in search order, the directories that may hold the dependency `name`; see
+/// [`find_dependency`].
+fn dependency_directories(name: &str, file: &str) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    let bundled = |root: &Path| ["lib", "bin"].map(|d| root.join("3rdparty").join(name).join(d));
+
+    if name.starts_with("tbb") {
+        if let Some(root) = finder::var_os(ENV_TBBROOT).map(PathBuf::from) {
+            dirs.extend(["lib", "bin"].map(|d| root.join(d)));
+        }
+    }
+
+    if let Some(library_dir) = openvino_c_library().as_deref().and_then(Path::parent) {
+        dirs.push(library_dir.to_path_buf());
+        // E.g., `runtime` is two levels above `runtime/lib/intel64` (three on `MacOS`, with the
+        // `Release` directory).
+        for ancestor in library_dir.ancestors().skip(1).take(3) {
+            dirs.extend(bundled(ancestor));
+        }
+    }
+
+    let install_dirs = finder::var_os(ENV_OPENVINO_INSTALL_DIR)
+        .into_iter()
+        .flat_map(|v| split_install_dirs(&v))
+        .chain(finder::var_os(ENV_INTEL_OPENVINO_DIR).map(PathBuf::from))
+        .chain(default_installation_directories());
+    for install_dir in install_dirs {
+        dirs.extend(bundled(&install_dir.join("runtime")));
+    }
+
+    if let Some(path) = finder::var_os(ENV_LIBRARY_PATH) {
+        dirs.extend(env::split_paths(&path));
+    }
+
+    dirs.extend(linker_cache::directories(file));
+    dirs.extend(system_installation_directories());
+    for prefix in homebrew_prefixes() {
+        dirs.push(prefix.join("opt").join(name).join("lib"));
+        dirs.push(prefix.join("lib"));
+    }
+    dirs
+}
+
+/// Find the dependency `name` in `dir`: the plain file name if it exists, otherwise the latest
+/// version-suffixed copy.
+fn dependency_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    let (prefix, suffix) = target::dynamic_library_affixes();
+    let plain = dir.join(format!("{prefix}{name}{suffix}"));
+    if plain.is_file() {
+        return Some(plain);
+    }
+    list_directory(dir)?
+        .into_iter()
+        .filter_map(|f| Some((file_version(&f, name)?, f)))
+        .filter(|(_, f)| dir.join(f).is_file())
+        .max()
+        .map(|(_, f)| dir.join(f))
+}
+
+/// Parse the version of a version-suffixed library file name, e.g., `[12, 0]` from
+/// `libtbb.so.12.0` (Linux), `libtbb.12.dylib` (`MacOS`) or `tbb12.dll` (Windows) for `tbb`;
+/// `None` if `file_name` is another library (e.g., `libtbbmalloc.so.2`).
+fn file_version(file_name: &str, name: &str) -> Option<Vec<u64>> {
+    let (prefix, suffix) = target::dynamic_library_affixes();
+    let rest = file_name.strip_prefix(prefix)?.strip_prefix(name)?;
+    let version = if let Some(after) = rest.strip_prefix(suffix) {
+        after.strip_prefix('.')?
+    } else {
+        let before = rest.strip_suffix(suffix)?;
+        before.strip_prefix('.').unwrap_or(before)
+    };
+    version
+        .split('.')
+        .map(|c| c.parse().ok())
+        .collect::<Option<Vec<u64>>>()
+        .filter(|v| !v.is_empty())
+}
+
+/// An error indicating that a dependency of OpenVINO could not be found; see
+/// [`find_dependency`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyNotFound {
+    /// The library file name searched for, e.g., `libtbb.so`.
+    pub library: String,
+    /// The directories searched, in search order.
+    pub searched: Vec<PathBuf>,
+}
+
+impl std::fmt::Display for DependencyNotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unable to find library `{}` (or a version-suffixed copy); searched {} directories:",
+            self.library,
+            self.searched.len()
+        )?;
+        for dir in &self.searched {
+            write!(f, "\n  - {}", dir.display())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for DependencyNotFound {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    fn library(name: &str) -> String {
+        let (prefix, suffix) = target::dynamic_library_affixes();
+        format!("{prefix}{name}{suffix}")
+    }
+
+    #[test]
+    fn file_versions() {
+        let (prefix, suffix) = target::dynamic_library_affixes();
+        let version = |f: String| file_version(&f, "tbb");
+        assert_eq!(
+            version(format!("{prefix}tbb{suffix}.12.0")),
+            Some(vec![12, 0])
+        );
+        assert_eq!(version(format!("{prefix}tbb.12{suffix}")), Some(vec![12]));
+        assert_eq!(version(format!("{prefix}tbb12{suffix}")), Some(vec![12]));
+        assert_eq!(version(format!("{prefix}tbbmalloc{suffix}.2")), None);
+        assert_eq!(version(format!("{prefix}tbb12_debug{suffix}")), None);
+        assert_eq!(version(library("tbb")), None);
+    }
+
+    #[test]
+    fn find_dependencies() {
+        let dir = env::temp_dir().join("openvino-finder-dependencies");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for version in ["2", "12", "9"] {
+            fs::write(dir.join(format!("{}.{version}", library("tbb"))), "").unwrap();
+        }
+        fs::write(dir.join(format!("{}.20", library("tbbmalloc"))), "").unwrap();
+        assert_eq!(
+            dependency_file(&dir, "tbb"),
+            Some(dir.join(format!("{}.12", library("tbb"))))
+        );
+        assert_eq!(dependency_file(&dir, "pugixml"), None);
+
+        fs::write(dir.join(library("tbb")), "").unwrap();
+        assert_eq!(dependency_file(&dir, "tbb"), Some(dir.join(library("tbb"))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn not_found() {
+        let error = DependencyNotFound {
+            library: "libtbb.so".into(),
+            searched: vec![PathBuf::from(
+                "/opt/intel/openvino/runtime/3rdparty/tbb/lib",
+            )],
+        };
+        assert_eq!(
+            error.to_string(),
+            "unable to find library `libtbb.so` (or a version-suffixed copy); searched 1 \
+             directories:\n  - /opt/intel/openvino/runtime/3rdparty/tbb/lib"
+        );
+    }
+}
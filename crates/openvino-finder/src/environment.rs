@@ -0,0 +1,95 @@
+//! Detect special host environments (WSL, containers) that commonly explain why an OpenVINO
+//! installation cannot be found.
+
+use std::fs;
+use std::path::Path;
+
+/// Describe the kind of environment the finder is running in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostEnvironment {
+    /// A regular host: no special handling is necessary.
+    Native,
+    /// Windows Subsystem for Linux; the Windows drives are mounted under `/mnt/<drive letter>`.
+    Wsl,
+    /// A container (e.g., Docker, Podman, Kubernetes); the OpenVINO installation must be part of
+    /// the image or mounted into it.
+    Container,
+}
+
+impl HostEnvironment {
+    /// Detect the current environment by examining `/proc` (and a few well-known marker files).
+    /// WSL takes precedence over containers, since the former determines where installations are
+    /// likely to be found.
+    pub fn detect() -> Self {
+        if !cfg!(target_os = "linux") {
+            return Self::Native;
+        }
+        let os_release = fs::read_to_string("/proc/sys/kernel/osrelease").unwrap_or_default();
+        if is_wsl_release(&os_release) {
+            return Self::Wsl;
+        }
+        let cgroup = fs::read_to_string("/proc/1/cgroup").unwrap_or_default();
+        if Path::new("/.dockerenv").exists()
+            || Path::new("/run/.containerenv").exists()
+            || is_container_cgroup(&cgroup)
+        {
+            return Self::Container;
+        }
+        Self::Native
+    }
+
+    /// Provide a troubleshooting hint for this environment, if any.
+    pub fn hint(self) -> Option<&'static str> {
+        match self {
+            Self::Native => None,
+            Self::Wsl => Some(
+                "running under WSL: an OpenVINO installation on the Windows side (e.g., \
+                 `/mnt/c/Program Files (x86)/Intel/openvino`) contains Windows DLLs that cannot be \
+                 loaded by Linux binaries; install the Linux version of OpenVINO inside WSL or set \
+                 `OPENVINO_INSTALL_DIR` to a Linux installation",
+            ),
+            Self::Container => Some(
+                "running inside a container: the OpenVINO libraries must be installed in the image \
+                 or mounted into it (e.g., `-v /opt/intel/openvino:/opt/intel/openvino`); set \
+                 `OPENVINO_INSTALL_DIR` to the location inside the container",
+            ),
+        }
+    }
+}
+
+/// Check the contents of `/proc/sys/kernel/osrelease` for the WSL kernel marker, e.g.,
+/// `5.15.90.1-microsoft-standard-WSL2`.
+fn is_wsl_release(os_release: &str) -> bool {
+    let os_release = os_release.to_ascii_lowercase();
+    os_release.contains("microsoft") || os_release.contains("wsl")
+}
+
+/// Check the contents of `/proc/1/cgroup` for well-known container runtimes.
+fn is_container_cgroup(cgroup: &str) -> bool {
+    ["docker", "kubepods", "containerd", "lxc", "libpod"]
+        .iter()
+        .any(|runtime| cgroup.contains(runtime))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn detect_wsl() {
+        assert!(is_wsl_release("5.15.90.1-microsoft-standard-WSL2\n"));
+        assert!(is_wsl_release("4.4.0-19041-Microsoft"));
+        assert!(!is_wsl_release("6.5.0-35-generic"));
+    }
+
+    #[test]
+    fn detect_container() {
+        assert!(is_container_cgroup(
+            "0::/system.slice/docker-0123abcd.scope"
+        ));
+        assert!(is_container_cgroup(
+            "12:pids:/kubepods/besteffort/pod1234/5678"
+        ));
+        assert!(!is_container_cgroup("0::/init.scope"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Explain why a library could not be found, listing the environment variables and directories the
+//! search depended on; see [`FindError`].
+
+use crate::{
+    MultipleInstallations, SearchReport, UnsupportedPlatform, ENV_OPENVINO_BUILD_DIR,
+    ENV_OPENVINO_INSTALL_DIR,
+};
+use std::fmt;
+
+/// An error indicating that an OpenVINO library could not be found; see [`crate::try_find`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FindError {
+    /// No copy of the library is in the locations searched; the report lists the environment
+    /// variables which point the search at an installation and why each path probed was rejected.
+    NotFound(Box<SearchReport>),
+    /// In strict mode (see [`crate::strict_mode`]), more than one distinct copy of the library was
+    /// found.
+    MultipleInstallations(MultipleInstallations),
+    /// The compilation target cannot load dynamic libraries, so nothing was searched.
+    UnsupportedPlatform(UnsupportedPlatform),
+}
+
+impl From<MultipleInstallations> for FindError {
+    fn from(e: MultipleInstallations) -> Self {
+        Self::MultipleInstallations(e)
+    }
+}
+
+impl From<UnsupportedPlatform> for FindError {
+    fn from(e: UnsupportedPlatform) -> Self {
+        Self::UnsupportedPlatform(e)
+    }
+}
+
+impl fmt::Display for FindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(report) => {
+                writeln!(
+                    f,
+                    "unable to find library `{}`; set `{}` to the root of an OpenVINO installation \
+                     (or `{}` to a source build), or run its `setupvars` script",
+                    report.file, ENV_OPENVINO_INSTALL_DIR, ENV_OPENVINO_BUILD_DIR
+                )?;
+                writeln!(f, "environment variables:")?;
+                for variable in &report.variables {
+                    match &variable.value {
+                        Some(value) => writeln!(f, "  - {}={}", variable.name, value)?,
+                        None => writeln!(f, "  - {} (not set)", variable.name)?,
+                    }
+                }
+                writeln!(f, "paths probed:")?;
+                report.write_probes(f, "  ")
+            }
+            Self::MultipleInstallations(e) => e.fmt(f),
+            Self::UnsupportedPlatform(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for FindError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotFound(_) => None,
+            Self::MultipleInstallations(e) => Some(e),
+            Self::UnsupportedPlatform(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Probe, Provenance, Rejection, SearchVariable, ENV_INTEL_OPENVINO_DIR};
+    use std::path::PathBuf;
+
+    #[test]
+    fn display() {
+        let error = FindError::NotFound(Box::new(SearchReport {
+            library: "openvino_c".into(),
+            file: "libopenvino_c.so".into(),
+            found: None,
+            probes: vec![
+                Probe {
+                    path: PathBuf::from("/opt/intel/openvino/runtime/lib/intel64/libopenvino_c.so"),
+                    provenance: Provenance::EnvironmentVariable(ENV_INTEL_OPENVINO_DIR),
+                    rejection: Some(Rejection::NotADirectory),
+                },
+                Probe {
+                    path: PathBuf::from("/usr/lib/x86_64-linux-gnu/libopenvino_c.so"),
+                    provenance: Provenance::SystemDirectory,
+                    rejection: Some(Rejection::WrongArchitecture {
+                        library: "aarch64",
+                        target: "x86_64",
+                    }),
+                },
+            ],
+            variables: vec![
+                SearchVariable {
+                    name: ENV_OPENVINO_INSTALL_DIR,
+                    value: None,
+                },
+                SearchVariable {
+                    name: ENV_INTEL_OPENVINO_DIR,
+                    value: Some("/opt/intel/openvino".into()),
+                },
+            ],
+            unsupported: None,
+        }));
+        assert_eq!(
+            error.to_string(),
+            "unable to find library `libopenvino_c.so`; set `OPENVINO_INSTALL_DIR` to the root of \
+             an OpenVINO installation (or `OPENVINO_BUILD_DIR` to a source build), or run its \
+             `setupvars` script\n\
+             environment variables:\n  \
+             - OPENVINO_INSTALL_DIR (not set)\n  \
+             - INTEL_OPENVINO_DIR=/opt/intel/openvino\n\
+             paths probed:\n  \
+             - /opt/intel/openvino/runtime/lib/intel64/libopenvino_c.so (from \
+             `INTEL_OPENVINO_DIR`): no such directory\n  \
+             - /usr/lib/x86_64-linux-gnu/libopenvino_c.so (from a system directory): built for \
+             aarch64, not for the target x86_64\n"
+        );
+    }
+}
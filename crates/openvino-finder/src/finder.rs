@@ -0,0 +1,461 @@
+//! Configure the search for OpenVINO libraries programmatically, e.g., from a build script that
+//! knows of directories the heuristics do not or must ignore part of the environment; see
+//! [`Finder`].
+
+use crate::{
+    cache, candidates, check_target, distinct_files, find_unique_paths, library_filename,
+    library_version, list_directory, strict_mode, versioned_paths, Cache, Config, FindError, Found,
+    Linking, MultipleInstallations, Provenance, SearchReport, ENV_LIBRARY_PATH,
+    ENV_OPENVINO_BUILD_DIR,
+};
+use std::cell::RefCell;
+use std::env;
+use std::ffi::OsString;
+use std::path::PathBuf;
+
+/// The kinds of installation [`Finder::prefer`] can move ahead in the search order; each groups
+/// the [`Provenance`]s of one installation method.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InstallKind {
+    /// The directories given to [`Finder::with_extra_paths`].
+    Extra,
+    /// A source build, named by `OPENVINO_BUILD_DIR`.
+    Build,
+    /// An extracted archive or a Windows installation: named by `OPENVINO_INSTALL_DIR`,
+    /// `INTEL_OPENVINO_DIR` or the configuration file (see [`Config`]), recorded in the registry
+    /// or in a default installation directory.
+    Archive,
+    /// A directory of the OS library path (e.g., `LD_LIBRARY_PATH`).
+    LibraryPath,
+    /// A vcpkg installation tree.
+    Vcpkg,
+    /// A DEB or RPM package, in a system library directory.
+    SystemPackage,
+    /// A `pip`-installed `openvino` package.
+    PythonPackage,
+    /// A snap.
+    Snap,
+    /// A Homebrew keg.
+    Homebrew,
+    /// An `Intel.OpenVINO` `NuGet` package.
+    NuGet,
+}
+
+impl From<Provenance> for InstallKind {
+    fn from(provenance: Provenance) -> Self {
+        match provenance {
+            Provenance::ExtraPath => Self::Extra,
+            Provenance::EnvironmentVariable(ENV_OPENVINO_BUILD_DIR) => Self::Build,
+            Provenance::EnvironmentVariable(ENV_LIBRARY_PATH) => Self::LibraryPath,
+            Provenance::EnvironmentVariable(_)
+            | Provenance::ConfigFile
+            | Provenance::Registry
+            | Provenance::DefaultInstallDirectory
+            | Provenance::SetupScript
+            | Provenance::WslDirectory => Self::Archive,
+            Provenance::Vcpkg => Self::Vcpkg,
+            Provenance::SystemDirectory | Provenance::LinkerCache => Self::SystemPackage,
+            Provenance::PythonPackage => Self::PythonPackage,
+            Provenance::Snap => Self::Snap,
+            Provenance::Homebrew => Self::Homebrew,
+            Provenance::NuGet => Self::NuGet,
+        }
+    }
+}
+
+/// A configurable search for OpenVINO libraries: the free functions (e.g., [`crate::find`]) search
+/// like `Finder::new()`.
+///
+/// ```no_run
+/// # use openvino_finder::{Cache, Finder, InstallKind, Linking};
+/// let library = Finder::new()
+///     .with_extra_paths(["/nix/store/abc-openvino/lib"])
+///     .skip_env("LD_LIBRARY_PATH")
+///     .with_env_var("OPENVINO_INSTALL_DIR", "/opt/intel/openvino_2025.1.0")
+///     .prefer(InstallKind::Archive)
+///     .with_cache(Cache::new("target/openvino-finder"))
+///     .find("openvino_c", Linking::Dynamic);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Finder {
+    extra_paths: Vec<PathBuf>,
+    env: Vec<(String, Option<OsString>)>,
+    preferred: Vec<InstallKind>,
+    cache: Option<Cache>,
+}
+
+impl Finder {
+    /// Create a finder searching the locations described in [`crate::find`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Search the directories `paths` (e.g., a vendored copy of OpenVINO) before any other
+    /// location; as in the system directories, version-suffixed libraries (e.g.,
+    /// `libopenvino_c.so.2025.1.0`) are found there too.
+    #[must_use]
+    pub fn with_extra_paths<P: Into<PathBuf>>(
+        mut self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> Self {
+        self.extra_paths.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Search as if the environment variable `name` were unset (e.g., `LD_LIBRARY_PATH` or
+    /// `OPENVINO_FINDER_STRICT`), without modifying the process environment.
+    #[must_use]
+    pub fn skip_env(mut self, name: &str) -> Self {
+        self.env.push((name.to_string(), None));
+        self
+    }
+
+    /// Search as if the environment variable `name` were set to `value` (e.g.,
+    /// `OPENVINO_INSTALL_DIR` or `OPENVINO_FINDER_CONFIG`), without modifying the process
+    /// environment.
+    #[must_use]
+    pub fn with_env_var(mut self, name: &str, value: impl Into<OsString>) -> Self {
+        self.env.push((name.to_string(), Some(value.into())));
+        self
+    }
+
+    /// Search the locations of installation `kind` before the others; the kinds preferred first
+    /// come first and the order within each kind is unchanged.
+    #[must_use]
+    pub fn prefer(mut self, kind: InstallKind) -> Self {
+        if !self.preferred.contains(&kind) {
+            self.preferred.push(kind);
+        }
+        self
+    }
+
+    /// Remember the libraries [`Finder::find`] finds in `cache`, rather than in the cache named by
+    /// the `OPENVINO_FINDER_CACHE` environment variable, if any (see [`Cache`]).
+    #[must_use]
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Find the path to an OpenVINO library like [`crate::find`], including its strict mode; the
+    /// result is cached, if so configured (see [`Finder::with_cache`]), except in strict mode.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it cannot list the contents of a search directory.
+    pub fn find(&self, library_name: &str, kind: Linking) -> Option<PathBuf> {
+        let file = library_filename(library_name, kind);
+        log::info!("Attempting to find library: {}", file);
+        if self.with_env(strict_mode) {
+            return match find_unique_paths(&file, self.candidate_paths(&file)) {
+                Ok(path) => path,
+                Err(e) => {
+                    log::error!("{}", e);
+                    None
+                }
+            };
+        }
+        let Some(cache) = self.with_env(|| self.cache.clone().or_else(Cache::from_env)) else {
+            return self.find_uncached(library_name, kind, &file);
+        };
+        let key = self.with_env(|| {
+            let options = (&self.extra_paths, &self.env, &self.preferred);
+            cache::key(&file, &options)
+        });
+        if let Some(path) = cache.get(&key) {
+            log::info!("Found library at path: {} (cached)", path.display());
+            return Some(path);
+        }
+        let found = self.find_uncached(library_name, kind, &file);
+        if let Some(path) = &found {
+            cache.insert(&key, path);
+        }
+        found
+    }
+
+    /// Find the path to `file` like [`Finder::find`], outside of strict mode and without the cache.
+    fn find_uncached(&self, library_name: &str, kind: Linking, file: &str) -> Option<PathBuf> {
+        if let Some(req) = self.with_env(Config::load).version {
+            let found = self.find_all(library_name, kind).into_iter().find_map(|f| {
+                let version = library_version(&f.path)?;
+                req.matches(&version).then_some((f.path, version))
+            });
+            if let Some((path, version)) = found {
+                log::info!(
+                    "Found library at path: {} (version {}, preferred by the configuration)",
+                    path.display(),
+                    version
+                );
+                return Some(path);
+            }
+            log::warn!(
+                "No library of the configured version {}; searching for any version",
+                req
+            );
+        }
+        self.search(library_name, file).found
+    }
+
+    /// Find the path to an OpenVINO library like [`crate::try_find`].
+    ///
+    /// # Errors
+    ///
+    /// See [`crate::try_find`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if it cannot list the contents of a search directory.
+    pub fn try_find(&self, library_name: &str, kind: Linking) -> Result<PathBuf, FindError> {
+        self.with_env(check_target)?;
+        let file = library_filename(library_name, kind);
+        if self.with_env(strict_mode) {
+            log::info!("Attempting to find a unique library: {}", file);
+            if let Some(path) = find_unique_paths(&file, self.candidate_paths(&file))? {
+                return Ok(path);
+            }
+        } else if let Some(path) = self.find(library_name, kind) {
+            return Ok(path);
+        }
+        Err(FindError::NotFound(Box::new(
+            self.search(library_name, &file),
+        )))
+    }
+
+    /// Find the path to an OpenVINO library like [`crate::find_with_report`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if it cannot list the contents of a search directory.
+    pub fn find_with_report(&self, library_name: &str, kind: Linking) -> SearchReport {
+        let file = library_filename(library_name, kind);
+        log::info!("Attempting to find library: {}", file);
+        self.search(library_name, &file)
+    }
+
+    /// Find the path to an OpenVINO library like [`crate::find_unique`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`MultipleInstallations`] error listing all of the discovered libraries when more
+    /// than one distinct library is found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it cannot list the contents of a search directory.
+    pub fn find_unique(
+        &self,
+        library_name: &str,
+        kind: Linking,
+    ) -> Result<Option<PathBuf>, MultipleInstallations> {
+        let file = library_filename(library_name, kind);
+        log::info!("Attempting to find a unique library: {}", file);
+        find_unique_paths(&file, self.candidate_paths(&file))
+    }
+
+    /// Find all of the distinct copies of an OpenVINO library like [`crate::find_all`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if it cannot list the contents of a search directory.
+    pub fn find_all(&self, library_name: &str, kind: Linking) -> Vec<Found> {
+        let file = library_filename(library_name, kind);
+        log::info!("Attempting to find all copies of library: {}", file);
+        distinct_files(self.candidates(&file, true))
+    }
+
+    /// Probe the paths for `file`, in this finder's search order, until one is a file.
+    fn search(&self, library_name: &str, file: &str) -> SearchReport {
+        self.with_env(|| SearchReport::search(library_name, file, &self.candidates(file, true)))
+    }
+
+    /// List every path at which `file` could be located, including all version-suffixed files.
+    fn candidate_paths(&self, file: &str) -> Vec<PathBuf> {
+        self.candidates(file, true)
+            .into_iter()
+            .map(|c| c.path)
+            .collect()
+    }
+
+    /// List, in this finder's search order, every path at which `file` could be located (see
+    /// [`candidates`] for `all_versions`).
+    pub(crate) fn candidates(&self, file: &str, all_versions: bool) -> Vec<Found> {
+        self.with_env(|| {
+            let mut found = candidates(file, all_versions);
+            if check_target().is_err() {
+                return found;
+            }
+            let extra = self
+                .extra_paths
+                .iter()
+                .flat_map(|dir| match list_directory(dir) {
+                    Some(filenames) => versioned_paths(dir, file, filenames, all_versions),
+                    None => vec![dir.join(file)],
+                })
+                .map(|path| Found {
+                    path,
+                    provenance: Provenance::ExtraPath,
+                });
+            found.splice(0..0, extra);
+            // A stable sort, so that the search order is kept within each kind.
+            found.sort_by_key(|f| {
+                let kind = InstallKind::from(f.provenance);
+                self.preferred
+                    .iter()
+                    .position(|p| *p == kind)
+                    .unwrap_or(self.preferred.len())
+            });
+            found
+        })
+    }
+
+    /// Run `f` with this finder's environment variables hidden from, or set for, [`var_os`] and
+    /// [`var`].
+    fn with_env<T>(&self, f: impl FnOnce() -> T) -> T {
+        /// Restore the variables of the enclosing search, even if `f` panics.
+        struct Restore(Vec<(String, Option<OsString>)>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                FINDER_ENV.with(|e| *e.borrow_mut() = std::mem::take(&mut self.0));
+            }
+        }
+        let mut overridden = FINDER_ENV.with(|e| e.borrow().clone());
+        overridden.extend(self.env.iter().cloned());
+        let _restore = Restore(FINDER_ENV.with(|e| e.replace(overridden)));
+        f()
+    }
+}
+
+thread_local! {
+    /// The environment variables hidden (`None`) or set by the [`Finder`] searching on this thread,
+    /// if any; the last entry for a variable wins.
+    static FINDER_ENV: RefCell<Vec<(String, Option<OsString>)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Read the environment variable `name` like [`env::var_os`], unless the current [`Finder`] skips
+/// or sets it.
+pub(crate) fn var_os(name: &str) -> Option<OsString> {
+    let overridden = FINDER_ENV.with(|e| {
+        let env = e.borrow();
+        env.iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.clone())
+    });
+    overridden.unwrap_or_else(|| env::var_os(name))
+}
+
+/// Read the environment variable `name` as a string, unless the current [`Finder`] skips it;
+/// `None` if it is unset or not Unicode.
+pub(crate) fn var(name: &str) -> Option<String> {
+    var_os(name)?.into_string().ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{target, ENV_INTEL_OPENVINO_DIR, ENV_OPENVINO_INSTALL_DIR};
+    use std::fs;
+
+    #[test]
+    fn install_kinds() {
+        assert_eq!(
+            InstallKind::from(Provenance::EnvironmentVariable(ENV_INTEL_OPENVINO_DIR)),
+            InstallKind::Archive
+        );
+        assert_eq!(
+            InstallKind::from(Provenance::EnvironmentVariable(ENV_OPENVINO_INSTALL_DIR)),
+            InstallKind::Archive
+        );
+        assert_eq!(
+            InstallKind::from(Provenance::EnvironmentVariable(ENV_LIBRARY_PATH)),
+            InstallKind::LibraryPath
+        );
+        assert_eq!(
+            InstallKind::from(Provenance::SystemDirectory),
+            InstallKind::SystemPackage
+        );
+    }
+
+    #[test]
+    fn skip_env() {
+        let name = "OPENVINO_FINDER_TEST_SKIP";
+        let set = Finder::new().with_env_var(name, "1");
+        assert_eq!(var(name), None);
+        assert_eq!(set.with_env(|| var(name)).as_deref(), Some("1"));
+        assert_eq!(var(name), None);
+
+        // The last setting wins, including when searches nest.
+        let skipped = set.clone().skip_env(name);
+        assert_eq!(skipped.with_env(|| var(name)), None);
+        let nested = skipped.with_env(|| set.with_env(|| var(name)));
+        assert_eq!(nested.as_deref(), Some("1"));
+        let reset = skipped.with_env_var(name, "2");
+        assert_eq!(reset.with_env(|| var(name)).as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn config_file() {
+        let dir = env::temp_dir().join("openvino-finder-config");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let config = dir.join("finder.toml");
+        fs::write(&config, format!("install_dir = '{}'\n", dir.display())).unwrap();
+        let found = Finder::new()
+            .with_env_var("OPENVINO_FINDER_CONFIG", &config)
+            .candidates("libopenvino_finder_config.so", false);
+        let unconfigured = Finder::new()
+            .with_env_var("OPENVINO_FINDER_CONFIG", dir.join("missing.toml"))
+            .candidates("libopenvino_finder_config.so", false);
+
+        let configured = |f: &Found| f.provenance == Provenance::ConfigFile;
+        assert!(found
+            .iter()
+            .filter(|f| configured(f))
+            .all(|f| f.path.starts_with(&dir)));
+        assert!(found.iter().any(configured));
+        assert!(!unconfigured.iter().any(configured));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn extra_paths() {
+        let (prefix, suffix) = target::dynamic_library_affixes();
+        let file = format!("{prefix}openvino_finder_extra{suffix}");
+        let first = env::temp_dir().join("openvino-finder-extra-1");
+        let second = env::temp_dir().join("openvino-finder-extra-2");
+        for dir in [&first, &second] {
+            let _ = fs::remove_dir_all(dir);
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(second.join(format!("{file}.2025.1.0")), "").unwrap();
+
+        let finder = Finder::new().with_extra_paths([&first, &second]);
+        let found = finder.candidates(&file, false);
+        assert_eq!(
+            found[..3]
+                .iter()
+                .map(|f| (f.path.clone(), f.provenance))
+                .collect::<Vec<_>>(),
+            vec![
+                (first.join(&file), Provenance::ExtraPath),
+                (second.join(&file), Provenance::ExtraPath),
+                (
+                    second.join(format!("{file}.2025.1.0")),
+                    Provenance::ExtraPath
+                ),
+            ]
+        );
+        let found = finder
+            .prefer(InstallKind::SystemPackage)
+            .candidates(&file, false);
+        assert!(found
+            .iter()
+            .skip_while(|f| f.provenance == Provenance::SystemDirectory)
+            .all(|f| f.provenance != Provenance::SystemDirectory));
+
+        for dir in [&first, &second] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+}
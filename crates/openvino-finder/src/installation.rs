@@ -0,0 +1,147 @@
+//! Enumerate the OpenVINO installations reachable from the search locations, e.g., to let a user
+//! pick one rather than taking the first match; see [`list_installations`].
+
+use crate::{
+    build_subdirectories, find_all, installation_subdirectories, library_version,
+    snap_library_subdirectories, Found, Linking, Provenance, Version,
+};
+use std::path::{Path, PathBuf};
+
+/// An OpenVINO installation found by [`list_installations`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Installation {
+    /// The root of the installation, e.g., the directory an archive was extracted to, a Homebrew
+    /// keg, the `openvino` package of a `pip` installation or, for DEB and RPM packages, the system
+    /// library directory.
+    pub root: PathBuf,
+    /// The path to the installation's `openvino_c` library.
+    pub library: PathBuf,
+    /// The version of the installation, if known; see [`crate::library_version`].
+    pub version: Option<Version>,
+    /// How the installation was found, which indicates how it was installed.
+    pub provenance: Provenance,
+}
+
+impl std::fmt::Display for Installation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.root.display())?;
+        match &self.version {
+            Some(version) => write!(f, " (version {version}, from {})", self.provenance),
+            None => write!(f, " (unknown version, from {})", self.provenance),
+        }
+    }
+}
+
+/// List every OpenVINO installation reachable from the locations described in [`crate::find`], in
+/// search order, with its root, version and provenance.
+///
+/// An installation is detected by its `openvino_c` library (see [`find_all`]); installations whose
+/// libraries resolve to the same root are only listed once. The first installation is the one
+/// [`crate::find`] picks, unless a later version-suffixed library supersedes it.
+///
+/// # Panics
+///
+/// Panics if it cannot list the contents of a search directory.
+pub fn list_installations() -> Vec<Installation> {
+    let mut installations: Vec<Installation> = vec![];
+    for found in find_all("openvino_c", Linking::Dynamic) {
+        let root = installation_root(&found);
+        if installations.iter().any(|i| i.root == root) {
+            continue;
+        }
+        installations.push(Installation {
+            root,
+            version: library_version(&found.path),
+            library: found.path,
+            provenance: found.provenance,
+        });
+    }
+    installations
+}
+
+/// Find the root of the installation holding the library `found`, by removing the layout of the
+/// installation method from the library's directory.
+fn installation_root(found: &Found) -> PathBuf {
+    let Some(dir) = found.path.parent() else {
+        return found.path.clone();
+    };
+    let mut layouts = installation_subdirectories();
+    layouts.extend(build_subdirectories());
+    if found.provenance == Provenance::Snap {
+        layouts.extend(snap_library_subdirectories());
+    }
+    if let Some(root) = layouts.iter().find_map(|l| strip_layout(dir, l)) {
+        return root;
+    }
+    let levels = match found.provenance {
+        // E.g., `<keg>/lib`, `<site-packages>/openvino/libs` or `<triplet>/bin`.
+        Provenance::Homebrew | Provenance::PythonPackage | Provenance::Vcpkg => 1,
+        // E.g., `<package>/<version>/runtimes/win-x64/native`.
+        Provenance::NuGet => 3,
+        _ => 0,
+    };
+    dir.ancestors().nth(levels).unwrap_or(dir).to_path_buf()
+}
+
+/// Remove the relative `layout` (e.g., `runtime/lib/intel64`) from the end of `dir`, if it is
+/// there.
+fn strip_layout(dir: &Path, layout: &str) -> Option<PathBuf> {
+    if !dir.ends_with(layout) {
+        return None;
+    }
+    let depth = Path::new(layout).components().count();
+    dir.ancestors().nth(depth).map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn root(path: &str, provenance: Provenance) -> PathBuf {
+        installation_root(&Found {
+            path: PathBuf::from(path),
+            provenance,
+        })
+    }
+
+    #[test]
+    fn installation_roots() {
+        let target_arch = crate::target::arch();
+        let arch = crate::target::known_archive_arches(crate::target::os(), &target_arch)[0];
+        assert_eq!(
+            root(
+                &format!("/opt/intel/openvino_2025.1.0/runtime/lib/{arch}/libopenvino_c.so"),
+                Provenance::DefaultInstallDirectory
+            ),
+            PathBuf::from("/opt/intel/openvino_2025.1.0")
+        );
+        assert_eq!(
+            root(
+                "/opt/homebrew/Cellar/openvino/2025.1.0/lib/libopenvino_c.dylib",
+                Provenance::Homebrew
+            ),
+            PathBuf::from("/opt/homebrew/Cellar/openvino/2025.1.0")
+        );
+        assert_eq!(
+            root(
+                "/venv/lib/python3.12/site-packages/openvino/libs/libopenvino_c.so",
+                Provenance::PythonPackage
+            ),
+            PathBuf::from("/venv/lib/python3.12/site-packages/openvino")
+        );
+        assert_eq!(
+            root(
+                "/usr/lib/x86_64-linux-gnu/libopenvino_c.so",
+                Provenance::SystemDirectory
+            ),
+            PathBuf::from("/usr/lib/x86_64-linux-gnu")
+        );
+        assert_eq!(
+            root(
+                "/packages/intel.openvino.runtime.win/2025.1.0/runtimes/win-x64/native/openvino_c.dll",
+                Provenance::NuGet
+            ),
+            PathBuf::from("/packages/intel.openvino.runtime.win/2025.1.0")
+        );
+    }
+}
@@ -27,12 +27,17 @@
 //!
 //! | Installation Method | Path                                               | Available on            | Notes                            |
 //! | ------------------- | -------------------------------------------------- | ----------------------- | -------------------------------- |
-//! | Archive (`.tar.gz`) | `<extracted folder>/runtime/lib/<arch>`            | Linux                   | `<arch>`: `intel64,armv7l,arm64` |
-//! | Archive (`.tar.gz`) | `<extracted folder>/runtime/lib/<arch>/Release`    | `MacOS`                 | `<arch>`: `intel64,armv7l,arm64` |
-//! | Archive (`.zip`)    | `<unzipped folder>/runtime/bin/<arch>/Release`     | Windows                 | `<arch>`: `intel64,armv7l,arm64` |
-//! | `PyPI`              | `<pip install folder>/site-packages/openvino/libs` | Linux, `MacOS`, Windows | Find install folder with `pip show openvino` |
+//! | Archive (`.tar.gz`) | `<extracted folder>/runtime/lib/<arch>`            | Linux                   | `<arch>`: `intel64,armv7l,aarch64,arm64` (see `OPENVINO_ARCHIVE_ARCH`) |
+//! | Archive (`.tar.gz`) | `<extracted folder>/runtime/lib/<arch>/Release`    | `MacOS`                 | `<arch>`: `intel64,armv7l,aarch64,arm64` (see `OPENVINO_ARCHIVE_ARCH`) |
+//! | Archive (`.zip`)    | `<unzipped folder>/runtime/bin/<arch>/Release`     | Windows                 | `<arch>`: `intel64,armv7l,aarch64,arm64` (see `OPENVINO_ARCHIVE_ARCH`) |
+//! | Installer (`.exe`)  | `<install folder>/runtime/bin/<arch>/Release`      | Windows                 | `<install folder>` is recorded in the registry under `HKLM\SOFTWARE\Intel\OpenVINO` |
+//! | `PyPI`              | `<pip install folder>/site-packages/openvino/libs` | Linux, `MacOS`, Windows | Find install folder with `pip show openvino`; virtual environment, user and system folders are searched |
 //! | DEB                 | `/usr/lib/x86_64-linux-gnu/openvino-<version>/`    | Linux (APT-based)       | This path is for plugins; the libraries are one directory above |
 //! | RPM                 | `/usr/lib64/`                                      | Linux (YUM-based)       |                                  |
+//! | vcpkg               | `<vcpkg root>/installed/<triplet>/{bin,lib}`       | Linux, `MacOS`, Windows | `<triplet>`: e.g., `x64-windows`, from `VCPKG_DEFAULT_TRIPLET` or the target |
+//! | NuGet               | `<packages>/intel.openvino.runtime.*/runtimes/win-x64/native` | Windows   | `<packages>`: the global NuGet cache or a solution's `packages` folder |
+//! | Snap                | `/snap/openvino/current/{runtime/lib/intel64,usr/lib/x86_64-linux-gnu}` | Linux | Inside a snap, `$SNAP` replaces `/snap/openvino/current` |
+//! | Homebrew            | `<brew prefix>/Cellar/openvino/<version>/lib`      | `MacOS`                 | `<brew prefix>`: `/opt/homebrew` (arm64), `/usr/local` (x86_64) |
 
 #![deny(missing_docs)]
 #![deny(clippy::all)]
@@ -40,10 +45,42 @@
 #![warn(clippy::cargo)]
 #![allow(clippy::must_use_candidate)]
 
+mod architecture;
+mod cache;
+mod config;
+mod dependency;
+mod environment;
+mod error;
+mod finder;
+mod installation;
+mod linker_cache;
+mod loaded;
+mod plugins;
+mod registry;
+mod report;
+mod setupvars;
+mod target;
+mod version;
+
+pub use cache::Cache;
+pub use config::{Config, ParseConfigError};
+pub use dependency::{find_dependency, DependencyNotFound};
+pub use environment::HostEnvironment;
+pub use error::FindError;
+pub use finder::{Finder, InstallKind};
+pub use installation::{list_installations, Installation};
+pub use loaded::find_loaded;
+pub use plugins::{find_plugin, generate_plugins_xml};
+pub use report::{LinkReport, Probe, Rejection, SearchReport, SearchVariable};
+pub use setupvars::find_setupvars;
+pub use version::{library_version, Version, VersionReq};
+
 use cfg_if::cfg_if;
 use std::env;
+use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
+use target::{in_sysroot, TargetOs};
 
 // We search for the library in various different places and early-return if we find it.
 macro_rules! check_and_return {
@@ -87,14 +124,80 @@ pub enum Linking {
 /// This function will probe:
 /// - the `OPENVINO_BUILD_DIR` environment variable with known build subdirectories appended &mdash;
 ///   this is useful for finding libraries built from source
-/// - the `OPENVINO_INSTALL_DIR`, `INTEL_OPENVINO_DIR`, and `LD_LIBRARY_PATH` (or OS-equivalent)
-///   environment variables with known install subdirectories appended &mdash; one of these is set
-///   by a version of OpenVINO's environment script (e.g., `source
-///   /opt/intel/openvino/setupvars.sh`)
-/// - OpenVINO's package installation paths for the OS (e.g., `/usr/lib64`) &mdash; this is useful
-///   for DEB or RPM installations
+/// - the `OPENVINO_INSTALL_DIR` environment variable, then the `install_dir` of the user's
+///   configuration file (see [`Config`]), with known install subdirectories appended &mdash; the
+///   former may list several installations, separated as in the OS' `PATH`, to search in order
+///   (e.g., the extracted archives of a CI image) and the latter is useful for switching between
+///   installations without editing a shell profile
+/// - the `INTEL_OPENVINO_DIR` and `LD_LIBRARY_PATH` (or OS-equivalent) environment variables with
+///   known install subdirectories appended &mdash; one of these is set by a version of OpenVINO's
+///   environment script (e.g., `source /opt/intel/openvino/setupvars.sh`)
+/// - the vcpkg installation tree for the target's triplet (e.g.,
+///   `$VCPKG_ROOT/installed/x64-windows/bin`), under `VCPKG_INSTALLED_DIR` (manifest mode) or
+///   `VCPKG_ROOT`; the triplet is `VCPKG_DEFAULT_TRIPLET` if set, otherwise it is derived from the
+///   `TARGET` of a build script or from the host &mdash; this is useful for `vcpkg install openvino`
+/// - on Linux, the directories in which the dynamic linker's cache (`/etc/ld.so.cache`, as listed
+///   by `ldconfig -p`) records the library, then OpenVINO's package installation paths for the OS
+///   (e.g., `/usr/lib64`) &mdash; this is useful for DEB or RPM installations
+/// - the `openvino/libs` directory of a `pip`-installed `openvino` package, in the `site-packages`
+///   of the active virtual environment (`VIRTUAL_ENV` or `CONDA_PREFIX`), then of the user and of
+///   the system Python installations, newest Python version first &mdash; this is useful for
+///   `pip install openvino`; for other environments, add their `openvino/libs` directory to the
+///   OS library path
+/// - on Linux, the current revision of the `openvino` snap (`/snap/openvino/current`) or, when
+///   running inside a snap, the snap itself (`SNAP`), with either the archive layout (e.g.,
+///   `runtime/lib/intel64`) or the Debian one (e.g., `usr/lib/x86_64-linux-gnu`) &mdash; this is
+///   useful for `snap install openvino`
+/// - on `MacOS`, the latest version of the Homebrew keg (e.g.,
+///   `/opt/homebrew/Cellar/openvino/<version>/lib`), under `HOMEBREW_PREFIX` or the default
+///   prefixes &mdash; this is useful for `brew install openvino`
+/// - on Windows, the installation directories recorded in the registry by the OpenVINO installer
+///   (under `HKLM\SOFTWARE\Intel\OpenVINO`) &mdash; this is useful for installations outside of
+///   the default `Program Files (x86)` paths
+/// - on Windows, the native libraries of the `Intel.OpenVINO` `NuGet` packages (e.g.,
+///   `intel.openvino.runtime.win/<version>/runtimes/win-x64/native`), latest version first, in
+///   the global package cache (`NUGET_PACKAGES` or `%USERPROFILE%\.nuget\packages`) and then in
+///   the `packages` folder of the current directory or of its closest ancestor which has one
+///   &mdash; this is useful for .NET solutions which also use the Rust bindings
 /// - OpenVINO's documented extract paths &mdash; this is useful for users who extract the TAR or
 ///   ZIP archive to the default locations or use the Docker images
+/// - the directory (with known install subdirectories appended) of the `setupvars` script of an
+///   archive extracted to a conventional location, e.g., `/opt/intel/openvino_2025.1.0` (see
+///   [`find_setupvars`]) &mdash; this is useful for building from a shell which has not sourced
+///   the script
+/// - when running under WSL, the default Windows extract paths as mounted in WSL (e.g.,
+///   `/mnt/c/Program Files (x86)/Intel/openvino`); see [`HostEnvironment`]
+///
+/// The library file names and the OS- and architecture-specific locations above (e.g.,
+/// `/usr/lib/aarch64-linux-gnu` or `runtime/lib/arm64`) follow the compilation target: in a build
+/// script, Cargo's `CARGO_CFG_TARGET_OS` and `CARGO_CFG_TARGET_ARCH`, otherwise the platform of the
+/// running program. The archive layouts are searched for each name of the architecture (e.g.,
+/// `intel64` then `x86_64`) with and without a `Release` or `Debug` directory; set
+/// `OPENVINO_ARCHIVE_ARCH` to the comma-separated names to search instead for an archive which
+/// names it otherwise. When cross-compiling, set `OPENVINO_SYSROOT` to the target's root filesystem
+/// (e.g., a Yocto sysroot) to search the fixed system locations (the package installation paths,
+/// the snap, the Homebrew prefixes and the extract paths) below it rather than on the host.
+///
+/// Libraries built for an architecture other than the compilation target (`CARGO_CFG_TARGET_ARCH`
+/// in a build script, otherwise the architecture of the running program), as read from their ELF,
+/// Mach-O or PE header, are skipped, e.g., an `x86_64` library on an `arm64` Mac. On targets that
+/// cannot load dynamic libraries at all (e.g., `wasm32-wasip1`), nothing is searched; see
+/// [`check_target`].
+///
+/// When the user's configuration file names a `version` (e.g., `~2024.6`), the first library of a
+/// matching version is picked, as by [`find_version`], unless there is none.
+///
+/// When the `OPENVINO_FINDER_STRICT` environment variable is set, all of the locations above are
+/// searched and, if more than one distinct library is found, an error listing them is logged and
+/// `None` is returned (see [`find_unique`]).
+///
+/// When the `OPENVINO_FINDER_CACHE` environment variable names a directory, the library found is
+/// remembered there and returned directly by later searches with the same inputs until it changes
+/// (see [`Cache`]).
+///
+/// To search additional directories, ignore some of the environment variables above or search one
+/// kind of installation first without changing the process environment (e.g., from a build
+/// script), use a [`Finder`].
 ///
 /// The locations above may change over time. As OpenVINO has released new versions, the documented
 /// locations of the shared libraries has changed. New versions of this function will reflect this,
@@ -104,97 +207,549 @@ pub enum Linking {
 ///
 /// Panics if it cannot list the contents of a search directory.
 pub fn find(library_name: &str, kind: Linking) -> Option<PathBuf> {
+    Finder::new().find(library_name, kind)
+}
+
+/// Find the path to an OpenVINO library like [`find`], but explain a failure: the returned
+/// [`FindError`] holds the [`SearchReport`] of the search, listing the environment variables which
+/// point the search at an installation (set or not) and why each path probed was rejected, so
+/// that, e.g., a build script can tell the user what to set rather than merely fail.
+///
+/// # Errors
+///
+/// Returns a [`FindError`] if no library is found, if several distinct libraries are found in
+/// strict mode (see [`find_unique`]) or if the target cannot load dynamic libraries (see
+/// [`check_target`]).
+///
+/// # Panics
+///
+/// Panics if it cannot list the contents of a search directory.
+pub fn try_find(library_name: &str, kind: Linking) -> Result<PathBuf, FindError> {
+    Finder::new().try_find(library_name, kind)
+}
+
+/// Find the path to an OpenVINO library like [`find`], recording every path probed and why each
+/// was rejected.
+///
+/// The report's `Display` implementation lists the probes, e.g., for a
+/// build script or application to explain a failed search. Strict mode (see [`find_unique`]) is not
+/// applied.
+///
+/// # Panics
+///
+/// Panics if it cannot list the contents of a search directory.
+pub fn find_with_report(library_name: &str, kind: Linking) -> SearchReport {
+    Finder::new().find_with_report(library_name, kind)
+}
+
+/// Find the path to an OpenVINO library, failing if more than one distinct copy of the library is
+/// reachable.
+///
+/// Unlike [`find`], which returns the first match, this function probes _all_ of the locations
+/// described in [`find`] (including every version-suffixed library in the system directories).
+/// Paths that resolve to the same file (e.g., symbolic links or an environment variable pointing to
+/// a system directory) are only counted once. Mixing libraries from different installations (e.g.,
+/// a `pip` install and an extracted archive) can cause subtle crashes when the library and its
+/// plugins come from different OpenVINO versions; this function makes that situation an error.
+///
+/// Setting the `OPENVINO_FINDER_STRICT` environment variable makes [`find`] (and therefore
+/// [`find_plugins_xml`]) behave this way as well.
+///
+/// # Errors
+///
+/// Returns a [`MultipleInstallations`] error listing all of the discovered libraries when more than
+/// one distinct library is found.
+///
+/// # Panics
+///
+/// Panics if it cannot list the contents of a search directory.
+pub fn find_unique(
+    library_name: &str,
+    kind: Linking,
+) -> Result<Option<PathBuf>, MultipleInstallations> {
+    Finder::new().find_unique(library_name, kind)
+}
+
+/// Find all of the distinct copies of an OpenVINO library reachable from the locations described in
+/// [`find`], in search order, along with the location that led to each (see [`Provenance`]).
+///
+/// This includes every version-suffixed library in the system directories; paths that resolve to
+/// the same file are only returned once. This is useful for selecting a specific installation
+/// (e.g., by version) when several are present, or for explaining which one [`find`] picks.
+///
+/// # Panics
+///
+/// Panics if it cannot list the contents of a search directory.
+pub fn find_all(library_name: &str, kind: Linking) -> Vec<Found> {
+    Finder::new().find_all(library_name, kind)
+}
+
+/// Find the first copy of an OpenVINO library, in the search order of [`find_all`], whose version
+/// satisfies `req` (e.g., `>=2025.1`), along with that version.
+///
+/// Unlike [`find`], which may return a library from an incompatible installation that only fails
+/// later, when loaded, this function skips the libraries of other versions. The version of each
+/// library is determined by [`library_version`]; libraries whose version cannot be determined
+/// are skipped as well.
+///
+/// # Panics
+///
+/// Panics if it cannot list the contents of a search directory.
+pub fn find_version(
+    library_name: &str,
+    req: &VersionReq,
+    kind: Linking,
+) -> Option<(PathBuf, Version)> {
+    for found in find_all(library_name, kind) {
+        match library_version(&found.path) {
+            Some(version) if req.matches(&version) => {
+                log::info!(
+                    "Found library at path: {} (version {})",
+                    found.path.display(),
+                    version
+                );
+                return Some((found.path, version));
+            }
+            Some(version) => log::debug!(
+                "Skipping library at path: {} (version {} does not match {})",
+                found.path.display(),
+                version,
+                req
+            ),
+            None => log::debug!(
+                "Skipping library at path: {} (unknown version)",
+                found.path.display()
+            ),
+        }
+    }
+    None
+}
+
+/// A library found by [`find_all`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Found {
+    /// The path to the library.
+    pub path: PathBuf,
+    /// The location that led to the library.
+    pub provenance: Provenance,
+}
+
+impl AsRef<Path> for Found {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl std::fmt::Display for Found {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (from {})", self.path.display(), self.provenance)
+    }
+}
+
+/// The kind of location at which a library was found; [`find`] searches these in order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Provenance {
+    /// A directory given to [`Finder::with_extra_paths`].
+    ExtraPath,
+    /// The `install_dir` of the user's configuration file; see [`Config`].
+    ConfigFile,
+    /// A directory named by an environment variable, e.g., `OPENVINO_INSTALL_DIR` or
+    /// `LD_LIBRARY_PATH`.
+    EnvironmentVariable(&'static str),
+    /// A vcpkg installation tree (e.g., `$VCPKG_ROOT/installed/x64-linux/lib`).
+    Vcpkg,
+    /// A system library directory (e.g., `/usr/lib64`), where DEB and RPM packages are installed.
+    SystemDirectory,
+    /// A directory recorded in the dynamic linker's cache (e.g., `/etc/ld.so.cache`) on Linux.
+    LinkerCache,
+    /// A snap (e.g., `/snap/openvino/current/usr/lib/x86_64-linux-gnu`).
+    Snap,
+    /// A Homebrew keg (e.g., `/opt/homebrew/Cellar/openvino/2024.6.0/lib`).
+    Homebrew,
+    /// The `openvino/libs` directory of a `pip`-installed package (e.g.,
+    /// `.venv/lib/python3.12/site-packages/openvino/libs`).
+    PythonPackage,
+    /// An installation directory recorded in the Windows registry by the OpenVINO installer.
+    Registry,
+    /// The native libraries of an `Intel.OpenVINO` `NuGet` package.
+    NuGet,
+    /// One of OpenVINO's documented extract directories (e.g., `/opt/intel/openvino`).
+    DefaultInstallDirectory,
+    /// The directory of the environment script of an extracted archive; see [`find_setupvars`].
+    SetupScript,
+    /// A default Windows extract directory as mounted in WSL; see [`HostEnvironment`].
+    WslDirectory,
+}
+
+impl std::fmt::Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ExtraPath => write!(f, "an extra path of the `Finder`"),
+            Self::ConfigFile => write!(f, "the finder configuration file"),
+            Self::EnvironmentVariable(name) => write!(f, "`{name}`"),
+            Self::Vcpkg => write!(f, "a vcpkg installation"),
+            Self::SystemDirectory => write!(f, "a system directory"),
+            Self::LinkerCache => write!(f, "the dynamic linker cache"),
+            Self::Snap => write!(f, "a snap"),
+            Self::Homebrew => write!(f, "a Homebrew keg"),
+            Self::PythonPackage => write!(f, "a Python package"),
+            Self::Registry => write!(f, "the Windows registry"),
+            Self::NuGet => write!(f, "a NuGet package"),
+            Self::DefaultInstallDirectory => write!(f, "a default installation directory"),
+            Self::SetupScript => write!(f, "an archive's setupvars script"),
+            Self::WslDirectory => write!(f, "a Windows installation directory under WSL"),
+        }
+    }
+}
+
+/// Check if the finder should run in strict mode, i.e., whether the `OPENVINO_FINDER_STRICT`
+/// environment variable is set to anything other than `0` or `false`.
+pub fn strict_mode() -> bool {
+    finder::var(ENV_OPENVINO_FINDER_STRICT)
+        .is_some_and(|v| v != "0" && !v.eq_ignore_ascii_case("false"))
+}
+
+/// Pick the only distinct library among the `paths` to `file`; see [`find_unique`].
+fn find_unique_paths(
+    file: &str,
+    paths: Vec<PathBuf>,
+) -> Result<Option<PathBuf>, MultipleInstallations> {
+    let found = distinct_files(paths);
+    match found.len() {
+        0 => Ok(None),
+        1 => {
+            let path = found.into_iter().next();
+            if let Some(path) = &path {
+                log::info!("Found library at path: {}", path.display());
+            }
+            Ok(path)
+        }
+        _ => Err(MultipleInstallations {
+            library: file.to_string(),
+            paths: found,
+        }),
+    }
+}
+
+/// An error indicating that more than one distinct OpenVINO library was found; see
+/// [`find_unique`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultipleInstallations {
+    /// The library file name searched for, e.g., `libopenvino_c.so`.
+    pub library: String,
+    /// The distinct paths at which the library was found, in search order.
+    pub paths: Vec<PathBuf>,
+}
+
+impl std::fmt::Display for MultipleInstallations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "found {} distinct copies of library `{}`; use `OPENVINO_INSTALL_DIR` to select one \
+             installation or remove the others:",
+            self.paths.len(),
+            self.library
+        )?;
+        for path in &self.paths {
+            write!(f, "\n  - {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MultipleInstallations {}
+
+/// An error indicating that the compilation target cannot load dynamic libraries at all (e.g.,
+/// `wasm32-wasip1`), so there are no OpenVINO libraries to find; see [`check_target`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedPlatform {
+    /// The architecture of the target, e.g., `wasm32`.
+    pub arch: String,
+    /// The operating system of the target, e.g., `wasi`.
+    pub os: String,
+}
+
+impl std::fmt::Display for UnsupportedPlatform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the target `{}-{}` cannot load OpenVINO's dynamic libraries; OpenVINO must run on the \
+             host instead (e.g., behind a WASI-NN implementation)",
+            self.arch, self.os
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedPlatform {}
+
+/// Check whether OpenVINO libraries can exist for the compilation target (see [`find`]).
+///
+/// The finder does not search on targets without dynamic libraries, e.g., `WebAssembly`
+/// (`wasm32-wasip1`, `wasm32-unknown-unknown`) or bare metal: [`find`] and the other functions
+/// find nothing and the reports ([`SearchReport`], [`LinkReport`]) record this error. A crate
+/// supporting such targets (e.g., one embedding WASI-NN) can call this first to explain why.
+///
+/// # Errors
+///
+/// Returns an [`UnsupportedPlatform`] error naming the target when it cannot load dynamic
+/// libraries.
+pub fn check_target() -> Result<(), UnsupportedPlatform> {
+    let (arch, os) = (target::arch(), target::os_name());
+    if target::supports_dynamic_libraries(&arch, &os) {
+        Ok(())
+    } else {
+        Err(UnsupportedPlatform { arch, os })
+    }
+}
+
+/// Build the platform-specific file name of a library, e.g., `libopenvino_c.so`.
+///
+/// The platform is the compilation target: in a build script, this follows Cargo's
+/// `CARGO_CFG_TARGET_OS` rather than the host, e.g., `openvino_c.lib` when cross-compiling for
+/// Windows.
+pub fn library_filename(library_name: &str, kind: Linking) -> String {
+    let (prefix, dynamic_suffix) = target::dynamic_library_affixes();
     let suffix = if kind == Linking::Static {
         // This is a bit rudimentary but works for the top three supported platforms: `linux`,
         // `macos`, and `windows`.
-        if cfg!(target_os = "windows") {
+        if target::os() == TargetOs::Windows {
             ".lib"
         } else {
             ".a"
         }
     } else {
-        env::consts::DLL_SUFFIX
+        dynamic_suffix
     };
-    let file = format!("{}{}{}", env::consts::DLL_PREFIX, library_name, suffix);
-    log::info!("Attempting to find library: {}", file);
+    format!("{prefix}{library_name}{suffix}")
+}
 
-    // Search using the `OPENVINO_BUILD_DIR` environment variable; this may be set by users of the
-    // `openvino-rs` library.
-    if let Some(build_dir) = env::var_os(ENV_OPENVINO_BUILD_DIR) {
-        let install_dir = PathBuf::from(build_dir);
-        for lib_dir in KNOWN_BUILD_SUBDIRECTORIES {
-            let search_path = install_dir.join(lib_dir).join(&file);
-            check_and_return!(search_path);
-        }
+/// List, in search order, every path at which `file` could be located, with the location that led
+/// to it. If `all_versions` is set, every version-suffixed file in the system directories is
+/// included, not only the latest one. The [`Finder`] adds its extra paths and reorders these.
+fn candidates(file: &str, all_versions: bool) -> Vec<Found> {
+    if let Err(e) = check_target() {
+        log::error!("Not searching for {}: {}", file, e);
+        return vec![];
     }
+    let mut paths = vec![];
+    let mut push = |path, provenance| paths.push(Found { path, provenance });
 
-    // Search using the `OPENVINO_INSTALL_DIR` environment variable; this may be set by users of the
+    // Search using the `OPENVINO_BUILD_DIR` environment variable; this may be set by users of the
     // `openvino-rs` library.
-    if let Some(install_dir) = env::var_os(ENV_OPENVINO_INSTALL_DIR) {
-        let install_dir = PathBuf::from(install_dir);
-        for lib_dir in KNOWN_INSTALLATION_SUBDIRECTORIES {
-            let search_path = install_dir.join(lib_dir).join(&file);
-            check_and_return!(search_path);
+    if let Some(build_dir) = finder::var_os(ENV_OPENVINO_BUILD_DIR) {
+        let install_dir = PathBuf::from(build_dir);
+        for lib_dir in build_subdirectories() {
+            push(
+                install_dir.join(lib_dir).join(file),
+                Provenance::EnvironmentVariable(ENV_OPENVINO_BUILD_DIR),
+            );
         }
     }
 
-    // Search using the `INTEL_OPENVINO_DIR` environment variable; this is set up by an OpenVINO
-    // installation (e.g. `source /opt/intel/openvino/setupvars.sh`).
-    if let Some(install_dir) = env::var_os(ENV_INTEL_OPENVINO_DIR) {
-        let install_dir = PathBuf::from(install_dir);
-        for lib_dir in KNOWN_INSTALLATION_SUBDIRECTORIES {
-            let search_path = install_dir.join(lib_dir).join(&file);
-            check_and_return!(search_path);
+    // Search using the `OPENVINO_INSTALL_DIR` environment variable, which may be set by users of the
+    // `openvino-rs` library, then in the installation pinned by the user's configuration file and
+    // using the `INTEL_OPENVINO_DIR` environment variable, which is set up by an OpenVINO
+    // installation (e.g. `source /opt/intel/openvino/setupvars.sh`); the configuration file comes
+    // first so that it can override a shell profile. `OPENVINO_INSTALL_DIR` may list several
+    // installations, searched in order.
+    let config_dir = Config::load().install_dir;
+    let intel_dir = finder::var_os(ENV_INTEL_OPENVINO_DIR).map(PathBuf::from);
+    let install_dirs = finder::var_os(ENV_OPENVINO_INSTALL_DIR)
+        .into_iter()
+        .flat_map(|v| split_install_dirs(&v))
+        .map(|d| (d, Provenance::EnvironmentVariable(ENV_OPENVINO_INSTALL_DIR)))
+        .chain(config_dir.map(|d| (d, Provenance::ConfigFile)))
+        .chain(intel_dir.map(|d| (d, Provenance::EnvironmentVariable(ENV_INTEL_OPENVINO_DIR))));
+    for (install_dir, provenance) in install_dirs {
+        for lib_dir in installation_subdirectories() {
+            push(install_dir.join(lib_dir).join(file), provenance);
         }
     }
 
     // Search in the OS library path (i.e. `LD_LIBRARY_PATH` on Linux, `PATH` on Windows, and
     // `DYLD_LIBRARY_PATH` on MacOS).
-    if let Some(path) = env::var_os(ENV_LIBRARY_PATH) {
+    if let Some(path) = finder::var_os(ENV_LIBRARY_PATH) {
         for lib_dir in env::split_paths(&path) {
-            let search_path = lib_dir.join(&file);
-            check_and_return!(search_path);
+            push(
+                lib_dir.join(file),
+                Provenance::EnvironmentVariable(ENV_LIBRARY_PATH),
+            );
         }
     }
 
-    // Search in OpenVINO's installation directories; after v2022.3, Linux packages will be
-    // installed in the system's default library locations.
-    for install_dir in SYSTEM_INSTALLATION_DIRECTORIES
-        .iter()
-        .map(PathBuf::from)
+    // Search in the vcpkg installation tree, if vcpkg is set up.
+    for lib_dir in vcpkg_lib_dirs() {
+        push(lib_dir.join(file), Provenance::Vcpkg);
+    }
+
+    // Search in the system's library directories: where the linker cache records the library, then
+    // where OpenVINO's Linux packages are installed (after v2022.3).
+    for (install_dir, provenance) in system_library_directories(file) {
+        let filenames = list_directory(&install_dir).expect("cannot list installation directory");
+        for path in versioned_paths(&install_dir, file, filenames, all_versions) {
+            push(path, provenance);
+        }
+    }
+
+    // Search in the `openvino` packages installed by `pip`; like the system directories, these may
+    // hold version-suffixed libraries.
+    for libs_dir in python_site_packages()
+        .into_iter()
+        .map(|d| d.join("openvino").join("libs"))
         .filter(|d| d.is_dir())
     {
-        // Check if the file is located in the installation directory.
-        let search_path = install_dir.join(&file);
-        check_and_return!(search_path);
+        let Some(filenames) = list_directory(&libs_dir) else {
+            continue;
+        };
+        for path in versioned_paths(&libs_dir, file, filenames, all_versions) {
+            push(path, Provenance::PythonPackage);
+        }
+    }
 
-        // Otherwise, check for version terminators: e.g., `libfoo.so.3.1.2`.
-        let filenames = list_directory(&install_dir).expect("cannot list installation directory");
-        let versions = get_suffixes(filenames, &file);
-        if let Some(path) = build_latest_version(&install_dir, &file, versions) {
-            check_and_return!(path);
+    // Search in the snaps; these are mounted read-only, at a fixed location.
+    for path in snap_paths(file, all_versions) {
+        push(path, Provenance::Snap);
+    }
+
+    // Search in the Homebrew kegs; each installed version has its own directory in the `Cellar`.
+    for path in homebrew_paths(file, all_versions) {
+        push(path, Provenance::Homebrew);
+    }
+
+    // Search in the installation directories recorded by the Windows installer.
+    for install_dir in registry::install_directories()
+        .into_iter()
+        .filter(|d| d.is_dir())
+    {
+        for lib_dir in installation_subdirectories() {
+            push(install_dir.join(lib_dir).join(file), Provenance::Registry);
         }
     }
 
+    // Search in the NuGet packages, on Windows.
+    for native_dir in nuget_native_dirs() {
+        push(native_dir.join(file), Provenance::NuGet);
+    }
+
     // Search in OpenVINO's default installation directories (if they exist).
-    for default_dir in DEFAULT_INSTALLATION_DIRECTORIES
-        .iter()
-        .map(PathBuf::from)
+    for default_dir in default_installation_directories()
+        .into_iter()
         .filter(|d| d.is_dir())
     {
-        for lib_dir in KNOWN_INSTALLATION_SUBDIRECTORIES {
-            let search_path = default_dir.join(lib_dir).join(&file);
-            check_and_return!(search_path);
+        for lib_dir in installation_subdirectories() {
+            push(
+                default_dir.join(lib_dir).join(file),
+                Provenance::DefaultInstallDirectory,
+            );
         }
     }
 
-    None
+    // Search in the archive of a `setupvars` script, without sourcing it.
+    for lib_dir in setupvars::library_directories() {
+        push(lib_dir.join(file), Provenance::SetupScript);
+    }
+
+    // When running under WSL, search the Windows default installation directories as they are
+    // mounted in the Linux filesystem.
+    if HostEnvironment::detect() == HostEnvironment::Wsl {
+        for wsl_dir in WSL_INSTALLATION_DIRECTORIES
+            .iter()
+            .map(PathBuf::from)
+            .filter(|d| d.is_dir())
+        {
+            for lib_dir in installation_subdirectories() {
+                push(wsl_dir.join(lib_dir).join(file), Provenance::WslDirectory);
+            }
+        }
+    }
+
+    paths
+}
+
+/// List the paths to `file` in `dir`: the file itself, then the version-suffixed files (e.g.,
+/// `libfoo.so.3.1.2`) among `filenames`, either all of them or only the latest.
+fn versioned_paths(
+    dir: &Path,
+    file: &str,
+    filenames: impl IntoIterator<Item = String>,
+    all_versions: bool,
+) -> Vec<PathBuf> {
+    let mut paths = vec![dir.join(file)];
+    let versions = get_suffixes(filenames, file);
+    if all_versions {
+        paths.extend(versions.iter().map(|v| dir.join(format!("{file}{v}"))));
+    } else {
+        paths.extend(build_latest_version(dir, file, versions));
+    }
+    paths
+}
+
+/// Split the value of `OPENVINO_INSTALL_DIR` into the installations it lists, in search order:
+/// these are separated as in the OS' `PATH` (i.e., by `:`, or `;` on Windows) and empty entries are
+/// ignored.
+pub(crate) fn split_install_dirs(value: &OsStr) -> Vec<PathBuf> {
+    env::split_paths(value)
+        .filter(|d| !d.as_os_str().is_empty())
+        .collect()
+}
+
+/// Filter `paths` down to the existing files, keeping only the first path of any that resolve to
+/// the same file (e.g., through symbolic links).
+fn distinct_files<P: AsRef<Path>>(paths: impl IntoIterator<Item = P>) -> Vec<P> {
+    let mut seen = vec![];
+    let mut distinct = vec![];
+    for path in paths {
+        let file = path.as_ref();
+        log::debug!("Searching in: {}", file.display());
+        if !file.is_file() || architecture::check(file).is_err() {
+            continue;
+        }
+        let canonical = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        if !seen.contains(&canonical) {
+            seen.push(canonical);
+            distinct.push(path);
+        }
+    }
+    distinct
 }
 
+/// The environment variables which change the outcome of a search (e.g., `OPENVINO_INSTALL_DIR`,
+/// `LD_LIBRARY_PATH` or `HOME`), e.g., to clear them for a reproducible search; their values are
+/// also part of the key of each result in a [`Cache`].
+pub const ENVIRONMENT_VARIABLES: &[&str] = &[
+    "OPENVINO_BUILD_DIR",
+    "OPENVINO_INSTALL_DIR",
+    "OPENVINO_FINDER_CONFIG",
+    "OPENVINO_FINDER_STRICT",
+    "OPENVINO_SYSROOT",
+    "OPENVINO_ARCHIVE_ARCH",
+    "INTEL_OPENVINO_DIR",
+    "LD_LIBRARY_PATH",
+    "DYLD_LIBRARY_PATH",
+    "PATH",
+    "VCPKG_ROOT",
+    "VCPKG_INSTALLED_DIR",
+    "VCPKG_DEFAULT_TRIPLET",
+    "TARGET",
+    "CARGO_CFG_TARGET_OS",
+    "CARGO_CFG_TARGET_ARCH",
+    "VIRTUAL_ENV",
+    "CONDA_PREFIX",
+    "SNAP",
+    "HOMEBREW_PREFIX",
+    "NUGET_PACKAGES",
+    "HOME",
+    "APPDATA",
+    "USERPROFILE",
+    "XDG_CONFIG_HOME",
+];
+
 const ENV_OPENVINO_INSTALL_DIR: &str = "OPENVINO_INSTALL_DIR";
 const ENV_OPENVINO_BUILD_DIR: &str = "OPENVINO_BUILD_DIR";
 const ENV_INTEL_OPENVINO_DIR: &str = "INTEL_OPENVINO_DIR";
 const ENV_OPENVINO_PLUGINS_XML: &str = "OPENVINO_PLUGINS_XML";
+const ENV_OPENVINO_FINDER_STRICT: &str = "OPENVINO_FINDER_STRICT";
 
 cfg_if! {
     if #[cfg(any(target_os = "linux"))] {
@@ -209,51 +764,399 @@ cfg_if! {
     }
 }
 
-cfg_if! {
-    if #[cfg(any(target_os = "linux", target_os = "macos"))] {
-        const DEFAULT_INSTALLATION_DIRECTORIES: &[&str] = &[
-            "/opt/intel/openvino_2022",
-            "/opt/intel/openvino",
-        ];
-    } else if #[cfg(target_os = "windows")] {
-        const DEFAULT_INSTALLATION_DIRECTORIES: &[&str] = &[
+/// List OpenVINO's documented extract directories for the target OS, in the sysroot.
+fn default_installation_directories() -> Vec<PathBuf> {
+    let dirs: &[&str] = match target::os() {
+        TargetOs::Linux | TargetOs::MacOs => &["/opt/intel/openvino_2022", "/opt/intel/openvino"],
+        TargetOs::Windows => &[
             "C:\\Program Files (x86)\\Intel\\openvino_2022",
             "C:\\Program Files (x86)\\Intel\\openvino",
-        ];
+        ],
+        TargetOs::Other => &[],
+    };
+    dirs.iter().map(|d| in_sysroot(d)).collect()
+}
+
+/// List the system library directories in which the DEB and RPM packages install OpenVINO, for the
+/// target architecture (on Linux), in the sysroot.
+fn system_installation_directories() -> Vec<PathBuf> {
+    if target::os() != TargetOs::Linux {
+        return vec![];
+    }
+    let multiarch = target::multiarch_tuple(&target::arch());
+    [
+        "/lib".to_string(),              // DEB-installed package (OpenVINO >= 2023.2)
+        format!("/usr/lib/{multiarch}"), // DEB-installed package (OpenVINO >= 2022.3)
+        format!("/lib/{multiarch}"),     // DEB-installed package (TBB)
+        "/usr/lib64".to_string(),        // RPM-installed package >= 2022.3
+    ]
+    .iter()
+    .map(|d| in_sysroot(d))
+    .collect()
+}
+
+/// List the existing system library directories that may hold `file`, in search order: those in
+/// which the dynamic linker cache records it, which covers the layouts of any distribution, then
+/// the [`system_installation_directories`] not already listed.
+fn system_library_directories(file: &str) -> Vec<(PathBuf, Provenance)> {
+    let mut dirs: Vec<_> = linker_cache::directories(file)
+        .into_iter()
+        .map(|d| (d, Provenance::LinkerCache))
+        .collect();
+    for dir in system_installation_directories() {
+        if !dirs.iter().any(|(d, _)| *d == dir) {
+            dirs.push((dir, Provenance::SystemDirectory));
+        }
+    }
+    dirs.retain(|(d, _)| d.is_dir());
+    dirs
+}
+
+/// List the default Homebrew prefixes (on `MacOS`), in the sysroot.
+fn homebrew_prefixes() -> Vec<PathBuf> {
+    if target::os() != TargetOs::MacOs {
+        return vec![];
+    }
+    [
+        "/opt/homebrew", // Apple silicon
+        "/usr/local",    // Intel
+    ]
+    .iter()
+    .map(|d| in_sysroot(d))
+    .collect()
+}
+
+const ENV_VCPKG_ROOT: &str = "VCPKG_ROOT";
+const ENV_VCPKG_INSTALLED_DIR: &str = "VCPKG_INSTALLED_DIR";
+const ENV_VCPKG_DEFAULT_TRIPLET: &str = "VCPKG_DEFAULT_TRIPLET";
+
+/// List the library directories of the vcpkg installation trees for the target's triplet: under
+/// `VCPKG_INSTALLED_DIR` (set in manifest mode, e.g., `<project>/vcpkg_installed`), then under
+/// `$VCPKG_ROOT/installed`. DLLs are installed in `bin`, import and shared libraries in `lib`.
+fn vcpkg_lib_dirs() -> Vec<PathBuf> {
+    let installed_dirs = [
+        finder::var_os(ENV_VCPKG_INSTALLED_DIR).map(PathBuf::from),
+        finder::var_os(ENV_VCPKG_ROOT).map(|root| PathBuf::from(root).join("installed")),
+    ];
+    let triplet = finder::var(ENV_VCPKG_DEFAULT_TRIPLET).or_else(|| {
+        // A build script is told its target; otherwise, assume the host is the target.
+        let target = finder::var("TARGET")
+            .unwrap_or_else(|| format!("{}-{}", env::consts::ARCH, env::consts::OS));
+        vcpkg_triplet(&target)
+    });
+    let Some(triplet) = triplet else {
+        return vec![];
+    };
+    installed_dirs
+        .into_iter()
+        .flatten()
+        .map(|d| d.join(&triplet))
+        .filter(|d| d.is_dir())
+        .flat_map(|d| [d.join("bin"), d.join("lib")])
+        .collect()
+}
+
+/// Map a target (e.g., `x86_64-pc-windows-msvc`, or `x86_64-windows` for the host) to vcpkg's
+/// default triplet for it (e.g., `x64-windows`).
+fn vcpkg_triplet(target: &str) -> Option<String> {
+    let arch = match target.split('-').next()? {
+        "x86_64" => "x64",
+        "x86" | "i686" => "x86",
+        "aarch64" => "arm64",
+        "arm" | "armv7" => "arm",
+        _ => return None,
+    };
+    let os = if target.contains("windows") {
+        "windows"
+    } else if target.contains("darwin") || target.contains("macos") {
+        "osx"
+    } else if target.contains("linux") {
+        "linux"
     } else {
-        const DEFAULT_INSTALLATION_DIRECTORIES: &[&str] = &[];
+        return None;
+    };
+    Some(format!("{arch}-{os}"))
+}
+
+const ENV_NUGET_PACKAGES: &str = "NUGET_PACKAGES";
+
+/// The prefix of the (case-insensitive) identifiers of OpenVINO's `NuGet` packages, e.g.,
+/// `Intel.OpenVINO.Runtime.Win`.
+const NUGET_PACKAGE_PREFIX: &str = "intel.openvino";
+
+/// List the directories holding the native libraries of OpenVINO's `NuGet` packages, on Windows:
+/// first in the global package cache, then in the closest solution `packages` folder.
+fn nuget_native_dirs() -> Vec<PathBuf> {
+    if !cfg!(target_os = "windows") {
+        return vec![];
+    }
+    let runtime = if cfg!(target_arch = "aarch64") {
+        "win-arm64"
+    } else {
+        "win-x64"
+    };
+    let mut dirs = vec![];
+    let global_cache = finder::var_os(ENV_NUGET_PACKAGES)
+        .map(PathBuf::from)
+        .or_else(|| {
+            finder::var_os("USERPROFILE")
+                .map(|home| PathBuf::from(home).join(".nuget").join("packages"))
+        });
+    if let Some(global_cache) = global_cache {
+        dirs.extend(nuget_package_dirs(&global_cache, true));
+    }
+    let solution_packages = env::current_dir().ok().and_then(|cwd| {
+        cwd.ancestors()
+            .map(|a| a.join("packages"))
+            .find(|p| p.is_dir())
+    });
+    if let Some(solution_packages) = solution_packages {
+        dirs.extend(nuget_package_dirs(&solution_packages, false));
     }
+    dirs.into_iter()
+        .map(|d| d.join("runtimes").join(runtime).join("native"))
+        .filter(|d| d.is_dir())
+        .collect()
 }
 
+/// List the OpenVINO package directories in `packages`, latest version first. The global cache
+/// (`versioned`) holds a `<id>/<version>` directory per version, a solution's `packages` folder
+/// an `<Id>.<version>` directory.
+fn nuget_package_dirs(packages: &Path, versioned: bool) -> Vec<PathBuf> {
+    let is_openvino = |name: &str| name.to_ascii_lowercase().starts_with(NUGET_PACKAGE_PREFIX);
+    let Some(filenames) = list_directory(packages) else {
+        return vec![];
+    };
+    let mut dirs: Vec<(Vec<u64>, PathBuf)> = vec![];
+    for name in filenames.into_iter().filter(|n| is_openvino(n)) {
+        if versioned {
+            let package = packages.join(&name);
+            for version in list_directory(&package).into_iter().flatten() {
+                dirs.push((keg_version(&version), package.join(version)));
+            }
+        } else {
+            // The non-numeric parts of the identifier parse as zeroes, so only the versions differ.
+            dirs.push((keg_version(&name), packages.join(name)));
+        }
+    }
+    dirs.sort_by(|a, b| b.0.cmp(&a.0));
+    dirs.into_iter().map(|(_, d)| d).collect()
+}
+
+const SNAP_DIRECTORY: &str = "/snap/openvino/current";
+
+const ENV_SNAP: &str = "SNAP";
+
+/// List the library directories of a snap built from Debian packages rather than from the archive,
+/// for the target architecture.
+fn snap_library_subdirectories() -> Vec<String> {
+    let multiarch = target::multiarch_tuple(&target::arch());
+    vec![
+        format!("usr/lib/{multiarch}"),
+        "usr/lib".to_string(),
+        "lib".to_string(),
+    ]
+}
+
+/// List the paths to `file` in the snaps which may hold OpenVINO: when running confined, the
+/// application's own snap (`SNAP`, which may bundle OpenVINO), then the `openvino` snap (on Linux).
+/// See [`versioned_paths`] for `all_versions`.
+fn snap_paths(file: &str, all_versions: bool) -> Vec<PathBuf> {
+    if target::os() != TargetOs::Linux {
+        return vec![];
+    }
+    let mut snaps = vec![in_sysroot(SNAP_DIRECTORY)];
+    if let Some(snap) = finder::var_os(ENV_SNAP).map(PathBuf::from) {
+        snaps.retain(|d| d != &snap);
+        snaps.insert(0, snap);
+    }
+    paths_in_snaps(&snaps, file, all_versions)
+}
+
+/// List the paths to `file` in the library directories of the existing `snaps`; like the system
+/// directories, those of a snap built from Debian packages may only hold version-suffixed
+/// libraries (e.g., `libopenvino_c.so.2025.0.0`).
+fn paths_in_snaps(snaps: &[PathBuf], file: &str, all_versions: bool) -> Vec<PathBuf> {
+    let lib_dirs: Vec<String> = installation_subdirectories()
+        .into_iter()
+        .chain(snap_library_subdirectories())
+        .collect();
+    let mut paths = vec![];
+    for snap in snaps.iter().filter(|d| d.is_dir()) {
+        for lib_dir in lib_dirs.iter().map(|l| snap.join(l)) {
+            match list_directory(&lib_dir) {
+                Some(filenames) => {
+                    paths.extend(versioned_paths(&lib_dir, file, filenames, all_versions));
+                }
+                None => paths.push(lib_dir.join(file)),
+            }
+        }
+    }
+    paths
+}
+
+const ENV_HOMEBREW_PREFIX: &str = "HOMEBREW_PREFIX";
+
+/// List the Homebrew `Cellar` directories holding the OpenVINO kegs: first under the prefix set by
+/// `brew shellenv` (on `MacOS`), then under the default prefixes.
+fn homebrew_cellars() -> Vec<PathBuf> {
+    let mut prefixes = homebrew_prefixes();
+    if target::os() == TargetOs::MacOs {
+        if let Some(prefix) = finder::var_os(ENV_HOMEBREW_PREFIX).map(PathBuf::from) {
+            prefixes.retain(|p| p != &prefix);
+            prefixes.insert(0, prefix);
+        }
+    }
+    prefixes
+        .into_iter()
+        .map(|p| p.join("Cellar").join("openvino"))
+        .collect()
+}
+
+/// List the paths to `file` in the `lib` directory of the Homebrew kegs, latest version first;
+/// unless `all_versions` is set, only the latest keg of each `Cellar` is included.
+fn homebrew_paths(file: &str, all_versions: bool) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    for cellar in homebrew_cellars().into_iter().filter(|d| d.is_dir()) {
+        let Some(filenames) = list_directory(&cellar) else {
+            continue;
+        };
+        let mut versions: Vec<String> = filenames.into_iter().collect();
+        versions.sort_by_cached_key(|v| keg_version(v));
+        if !all_versions {
+            versions = versions.pop().into_iter().collect();
+        }
+        for version in versions.iter().rev() {
+            paths.push(cellar.join(version).join("lib").join(file));
+        }
+    }
+    paths
+}
+
+/// Parse the name of a keg directory (e.g., `2024.6.0_1`, with a Homebrew revision) into numbers
+/// that sort by version, unlike the names themselves (e.g., `2024.10.0` < `2024.9.0`).
+fn keg_version(name: &str) -> Vec<u64> {
+    name.split(['.', '_'])
+        .map(|n| n.parse().unwrap_or_default())
+        .collect()
+}
+
+const ENV_VIRTUAL_ENV: &str = "VIRTUAL_ENV";
+const ENV_CONDA_PREFIX: &str = "CONDA_PREFIX";
+
+// The `site-packages` layouts, as `(parent, version prefix, child)`: below a Python prefix (e.g., a
+// virtual environment), below the user's base directory and in the system's Python installations.
 cfg_if! {
-    if #[cfg(target_os = "linux")] {
-        const SYSTEM_INSTALLATION_DIRECTORIES: &[&str] = &[
-            "/lib", // DEB-installed package (OpenVINO >= 2023.2)
-            "/usr/lib/x86_64-linux-gnu", // DEB-installed package (OpenVINO >= 2022.3)
-            "/lib/x86_64-linux-gnu", // DEB-installed package (TBB)
-            "/usr/lib64", // RPM-installed package >= 2022.3
+    if #[cfg(target_os = "windows")] {
+        const PREFIX_SITE_PACKAGES: (&str, &str, &str) = ("Lib", "", "site-packages");
+        const ENV_USER_BASE: &str = "APPDATA"; // e.g., `Python/Python312/site-packages`
+        const USER_SITE_PACKAGES: (&str, &str, &str) = ("Python", "Python3", "site-packages");
+        const SYSTEM_SITE_PACKAGES: &[(&str, &str, &str)] = &[];
+    } else if #[cfg(target_os = "macos")] {
+        const PREFIX_SITE_PACKAGES: (&str, &str, &str) = ("lib", "python3.", "site-packages");
+        const ENV_USER_BASE: &str = "HOME";
+        const USER_SITE_PACKAGES: (&str, &str, &str) =
+            ("Library/Python", "3.", "lib/python/site-packages");
+        const SYSTEM_SITE_PACKAGES: &[(&str, &str, &str)] = &[
+            ("/opt/homebrew/lib", "python3.", "site-packages"),
+            ("/usr/local/lib", "python3.", "site-packages"),
         ];
     } else {
-        const SYSTEM_INSTALLATION_DIRECTORIES: &[&str] = &[];
+        const PREFIX_SITE_PACKAGES: (&str, &str, &str) = ("lib", "python3.", "site-packages");
+        const ENV_USER_BASE: &str = "HOME";
+        const USER_SITE_PACKAGES: (&str, &str, &str) =
+            (".local/lib", "python3.", "site-packages");
+        const SYSTEM_SITE_PACKAGES: &[(&str, &str, &str)] = &[
+            ("/usr/local/lib", "python3.", "dist-packages"), // Debian, Ubuntu
+            ("/usr/local/lib", "python3.", "site-packages"),
+            ("/usr/lib64", "python3.", "site-packages"), // Fedora, RHEL
+            ("/usr/lib", "python3.", "site-packages"),
+        ];
     }
 }
 
-const KNOWN_INSTALLATION_SUBDIRECTORIES: &[&str] = &[
-    "runtime/lib/intel64/Release",
-    "runtime/lib/intel64",
-    "runtime/3rdparty/tbb/lib",
-    "runtime/bin/intel64/Release",
-    "runtime/bin/intel64",
-    "runtime/3rdparty/tbb/bin",
-];
+/// List the existing `site-packages` directories in which `pip` may have installed `openvino`: those
+/// of the active virtual environment (`VIRTUAL_ENV`, or `CONDA_PREFIX` for Conda), then the user's,
+/// then the system's. Python is not run; the directories are those of the standard layouts.
+fn python_site_packages() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    for prefix in [ENV_VIRTUAL_ENV, ENV_CONDA_PREFIX]
+        .into_iter()
+        .filter_map(finder::var_os)
+        .map(PathBuf::from)
+    {
+        dirs.extend(python_versioned_dirs(&prefix, PREFIX_SITE_PACKAGES));
+    }
+    if let Some(user_base) = finder::var_os(ENV_USER_BASE).map(PathBuf::from) {
+        dirs.extend(python_versioned_dirs(&user_base, USER_SITE_PACKAGES));
+    }
+    for layout in SYSTEM_SITE_PACKAGES {
+        dirs.extend(python_versioned_dirs(Path::new(""), *layout));
+    }
+    dirs.into_iter().filter(|d| d.is_dir()).collect()
+}
+
+/// Expand a `(parent, version prefix, child)` layout below `base` into the directories
+/// `<base>/<parent>/<version prefix><version>/<child>`, newest Python version first (e.g.,
+/// `lib/python3.12/site-packages` before `lib/python3.9/site-packages`). An empty version prefix
+/// means the layout has no version directory.
+fn python_versioned_dirs(
+    base: &Path,
+    (parent, version_prefix, child): (&str, &str, &str),
+) -> Vec<PathBuf> {
+    let parent = base.join(parent);
+    if version_prefix.is_empty() {
+        return vec![parent.join(child)];
+    }
+    let Some(filenames) = list_directory(&parent) else {
+        return vec![];
+    };
+    let mut versions = get_suffixes(filenames, version_prefix);
+    versions.sort_by_cached_key(|v| std::cmp::Reverse(keg_version(v)));
+    versions
+        .into_iter()
+        .map(|v| parent.join(format!("{version_prefix}{v}")).join(child))
+        .collect()
+}
 
-const KNOWN_BUILD_SUBDIRECTORIES: &[&str] = &[
-    "bin/intel64/Debug/lib",
-    "bin/intel64/Debug",
-    "bin/intel64/Release/lib",
-    "temp/tbb/lib",
+// The Windows default installation directories, as seen from WSL.
+const WSL_INSTALLATION_DIRECTORIES: &[&str] = &[
+    "/mnt/c/Program Files (x86)/Intel/openvino_2022",
+    "/mnt/c/Program Files (x86)/Intel/openvino",
 ];
 
+/// List the library directories of an extracted archive for the target architecture: every
+/// `runtime/{lib,bin}/<arch>/{Release,Debug,}` permutation for the names of the architecture (e.g.,
+/// `runtime/lib/intel64` or, for Apple silicon, `runtime/lib/arm64/Release`; see
+/// `OPENVINO_ARCHIVE_ARCH`), along with the bundled TBB.
+fn installation_subdirectories() -> Vec<String> {
+    let arches = target::archive_arches();
+    let mut dirs = vec![];
+    for kind in ["lib", "bin"] {
+        for arch in &arches {
+            for config in ["/Release", "/Debug", ""] {
+                dirs.push(format!("runtime/{kind}/{arch}{config}"));
+            }
+        }
+        dirs.push(format!("runtime/3rdparty/tbb/{kind}"));
+    }
+    dirs
+}
+
+/// List the library directories of an OpenVINO build tree for the target architecture: every
+/// `bin/<arch>/{Debug,Release}{/lib,}` permutation, along with the TBB fetched by the build.
+fn build_subdirectories() -> Vec<String> {
+    let mut dirs = vec![];
+    for arch in target::archive_arches() {
+        for config in ["Debug", "Release"] {
+            dirs.push(format!("bin/{arch}/{config}/lib"));
+            dirs.push(format!("bin/{arch}/{config}"));
+        }
+    }
+    dirs.push("temp/tbb/lib".to_string());
+    dirs
+}
+
 /// Find the path to the `plugins.xml` configuration file.
 ///
 /// OpenVINO records the location of its plugin libraries in a `plugins.xml` file. This file is
@@ -264,14 +1167,21 @@ const KNOWN_BUILD_SUBDIRECTORIES: &[&str] = &[
 ///
 /// This file can be found in multiple locations, depending on the installation mechanism. For TAR
 /// installations, it is found in the same directory as the OpenVINO libraries themselves. For
-/// DEB/RPM installations, it is found in a version-suffixed directory beside the OpenVINO libraries
-/// (e.g., `openvino-2022.3.0/plugins.xml`).
+/// DEB/RPM and Homebrew installations, it is found in a version-suffixed directory beside the
+/// OpenVINO libraries (e.g., `openvino-2022.3.0/plugins.xml`); for Homebrew, this is in the latest
+/// keg found by [find] (e.g., `/opt/homebrew/Cellar/openvino/<version>/lib/openvino-<version>/`).
 ///
 /// This function will probe:
 /// - the `OPENVINO_PLUGINS_XML` environment variable &mdash; this is specific to this library
-/// - the same directory as the `openvino_c` shared library, as discovered by [find]
+/// - the `plugins_xml` of the user's configuration file (see [`Config`])
+/// - the same directory as the `openvino_c` shared library: the one already loaded in the process
+///   (see [`find_loaded`]), so that the plugins match the library in use, or else the one
+///   discovered by [find]
 /// - the latest version directory beside the `openvino_c` shared library (i.e.,
 ///   `openvino-<latest version>/`)
+///
+/// Some installations (e.g., some `pip` and distribution packages) ship the plugin libraries but
+/// no `plugins.xml`; in this case, [`generate_plugins_xml`] can write one.
 pub fn find_plugins_xml() -> Option<PathBuf> {
     const FILE_NAME: &str = "plugins.xml";
 
@@ -280,9 +1190,14 @@ pub fn find_plugins_xml() -> Option<PathBuf> {
         return Some(PathBuf::from(path));
     }
 
+    // The user's configuration file may pin the file as well.
+    if let Some(path) = Config::load().plugins_xml {
+        return Some(path);
+    }
+
     // Check in the same directory as the `openvino_c` library; e.g.,
     // `/opt/intel/openvino_.../runtime/lib/intel64/plugins.xml`.
-    let library = find("openvino_c", Linking::Dynamic)?;
+    let library = openvino_c_library()?;
     let library_parent_dir = library.parent()?;
     check_and_return!(library_parent_dir.join(FILE_NAME));
 
@@ -296,6 +1211,46 @@ pub fn find_plugins_xml() -> Option<PathBuf> {
     None
 }
 
+/// Find the model frontends installed beside the `openvino_c` library (the one loaded in the
+/// process, see [`find_loaded`], or else the one discovered by [find]), by name (e.g., `ir`,
+/// `onnx`, `tensorflow`, `tensorflow_lite`, `paddle`, `pytorch`), sorted.
+///
+/// OpenVINO reads models in formats other than its IR through frontend libraries (e.g.,
+/// `libopenvino_onnx_frontend.so`) which it loads on demand from its library directory; packages
+/// may omit some of them. The list is empty if the library cannot be found.
+pub fn find_frontends() -> Vec<String> {
+    let Some(library) = openvino_c_library() else {
+        return vec![];
+    };
+    library
+        .parent()
+        .and_then(list_directory)
+        .map(frontend_names)
+        .unwrap_or_default()
+}
+
+/// Locate the `openvino_c` library whose installation holds the files OpenVINO loads at runtime:
+/// the library already loaded in the process, if any, rather than one rediscovered by [find].
+fn openvino_c_library() -> Option<PathBuf> {
+    find_loaded("openvino_c").or_else(|| find("openvino_c", Linking::Dynamic))
+}
+
+/// Extract the frontend names from the `filenames` of a library directory, e.g., `onnx` from
+/// `libopenvino_onnx_frontend.so.2024.6.0`.
+fn frontend_names(filenames: impl IntoIterator<Item = String>) -> Vec<String> {
+    let prefix = format!("{}openvino_", target::dynamic_library_affixes().0);
+    let mut names: Vec<String> = get_suffixes(filenames, &prefix)
+        .into_iter()
+        .filter_map(|f| {
+            let (name, rest) = f.split_once("_frontend")?;
+            rest.starts_with('.').then(|| name.to_string())
+        })
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
 #[inline]
 fn list_directory(dir: &Path) -> Option<impl IntoIterator<Item = String>> {
     let traversal = fs::read_dir(dir).ok()?;
@@ -340,6 +1295,239 @@ mod test {
         assert!(find("openvino_c", Linking::Dynamic).is_some());
     }
 
+    /// This test checks that the archive layouts are generated for every name of the target
+    /// architecture.
+    #[test]
+    fn archive_layouts() {
+        let dirs = installation_subdirectories();
+        for arch in target::known_archive_arches(target::os(), &target::arch()) {
+            for config in ["/Release", "/Debug", ""] {
+                assert!(dirs.contains(&format!("runtime/lib/{arch}{config}")));
+                assert!(dirs.contains(&format!("runtime/bin/{arch}{config}")));
+            }
+        }
+        let position = |dir: &str| dirs.iter().position(|d| d == dir).unwrap();
+        assert!(position("runtime/3rdparty/tbb/lib") < position("runtime/3rdparty/tbb/bin"));
+        assert!(build_subdirectories().contains(&"temp/tbb/lib".to_string()));
+    }
+
+    /// This test checks that `OPENVINO_INSTALL_DIR` may list several installations, in order.
+    #[test]
+    fn install_dir_lists() {
+        let dirs = [
+            PathBuf::from("/opt/openvino_2025.1"),
+            PathBuf::new(),
+            PathBuf::from("/opt/openvino_2024.6"),
+        ];
+        let value = env::join_paths(&dirs).unwrap();
+        assert_eq!(
+            split_install_dirs(&value),
+            vec![dirs[0].clone(), dirs[2].clone()]
+        );
+        assert_eq!(
+            split_install_dirs(OsStr::new("/opt/openvino")),
+            vec![PathBuf::from("/opt/openvino")]
+        );
+        assert!(split_install_dirs(OsStr::new("")).is_empty());
+    }
+
+    /// This test checks that strict mode only counts paths resolving to the same file once.
+    #[test]
+    fn distinct_files_are_deduplicated() {
+        let dir = env::temp_dir().join("openvino-finder-distinct-files");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("a")).unwrap();
+        fs::create_dir_all(dir.join("b")).unwrap();
+        fs::write(dir.join("a/libfoo.so"), b"").unwrap();
+        fs::write(dir.join("b/libfoo.so"), b"").unwrap();
+
+        // The same file reached through two different (but equivalent) paths is only found once.
+        let found = distinct_files(vec![
+            dir.join("a/libfoo.so"),
+            dir.join("a/../a/libfoo.so"),
+            dir.join("missing/libfoo.so"),
+        ]);
+        assert_eq!(found, vec![dir.join("a/libfoo.so")]);
+
+        // Two separate files are both reported, in search order.
+        let found = distinct_files(vec![dir.join("b/libfoo.so"), dir.join("a/libfoo.so")]);
+        assert_eq!(
+            found,
+            vec![dir.join("b/libfoo.so"), dir.join("a/libfoo.so")]
+        );
+
+        // The provenance of the first path to a file is kept.
+        let found = distinct_files(vec![
+            Found {
+                path: dir.join("a/libfoo.so"),
+                provenance: Provenance::EnvironmentVariable(ENV_OPENVINO_INSTALL_DIR),
+            },
+            Found {
+                path: dir.join("a/../a/libfoo.so"),
+                provenance: Provenance::SystemDirectory,
+            },
+        ]);
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].provenance,
+            Provenance::EnvironmentVariable(ENV_OPENVINO_INSTALL_DIR)
+        );
+        assert_eq!(
+            found[0].to_string(),
+            format!(
+                "{} (from `OPENVINO_INSTALL_DIR`)",
+                dir.join("a/libfoo.so").display()
+            )
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// This test checks that `site-packages` directories are listed newest Python version first.
+    #[test]
+    fn site_packages_versions() {
+        let dir = env::temp_dir().join("openvino-finder-site-packages");
+        let _ = fs::remove_dir_all(&dir);
+        for version in ["python3.9", "python3.12", "python3.10"] {
+            fs::create_dir_all(dir.join("lib").join(version).join("site-packages")).unwrap();
+        }
+        fs::create_dir_all(dir.join("lib/other")).unwrap();
+
+        let found = python_versioned_dirs(&dir, ("lib", "python3.", "site-packages"));
+        assert_eq!(
+            found,
+            vec![
+                dir.join("lib/python3.12/site-packages"),
+                dir.join("lib/python3.10/site-packages"),
+                dir.join("lib/python3.9/site-packages"),
+            ]
+        );
+        assert_eq!(
+            python_versioned_dirs(&dir, ("Lib", "", "site-packages")),
+            vec![dir.join("Lib/site-packages")]
+        );
+        assert!(python_versioned_dirs(&dir, ("missing", "python3.", "site-packages")).is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// This test checks that targets map to vcpkg's default triplets.
+    #[test]
+    fn vcpkg_triplets() {
+        assert_eq!(
+            vcpkg_triplet("x86_64-pc-windows-msvc").as_deref(),
+            Some("x64-windows")
+        );
+        assert_eq!(
+            vcpkg_triplet("x86_64-unknown-linux-gnu").as_deref(),
+            Some("x64-linux")
+        );
+        assert_eq!(
+            vcpkg_triplet("aarch64-apple-darwin").as_deref(),
+            Some("arm64-osx")
+        );
+        assert_eq!(vcpkg_triplet("aarch64-macos").as_deref(), Some("arm64-osx"));
+        assert_eq!(
+            vcpkg_triplet("i686-pc-windows-msvc").as_deref(),
+            Some("x86-windows")
+        );
+        assert_eq!(vcpkg_triplet("wasm32-unknown-unknown"), None);
+        assert_eq!(vcpkg_triplet("x86_64-unknown-freebsd"), None);
+    }
+
+    /// This test checks that frontends are recognized by their library names, once each.
+    #[test]
+    fn frontends() {
+        let filenames = [
+            "openvino_onnx_frontend",
+            "openvino_tensorflow_lite_frontend.2024.6.0",
+            "openvino_onnx_frontend.2460",
+            "openvino_c",
+            "openvino_frontend_extension.1",
+            "openvino_ir_frontend.",
+        ]
+        .map(|f| format!("{}{f}", env::consts::DLL_PREFIX));
+        assert_eq!(frontend_names(filenames), ["ir", "onnx", "tensorflow_lite"]);
+    }
+
+    /// This test checks that OpenVINO's `NuGet` packages are listed latest version first, in both
+    /// the global cache and solution layouts.
+    #[test]
+    fn nuget_packages() {
+        let dir = env::temp_dir().join("openvino-finder-nuget");
+        let _ = fs::remove_dir_all(&dir);
+        for package in [
+            "global/intel.openvino.runtime.win/2024.10.0",
+            "global/intel.openvino.runtime.win/2024.6.0",
+            "global/newtonsoft.json/13.0.3",
+            "solution/Intel.OpenVINO.Runtime.Win.2024.6.0",
+            "solution/Intel.OpenVINO.Runtime.Win.2025.0.0",
+            "solution/Newtonsoft.Json.13.0.3",
+        ] {
+            fs::create_dir_all(dir.join(package)).unwrap();
+        }
+
+        assert_eq!(
+            nuget_package_dirs(&dir.join("global"), true),
+            vec![
+                dir.join("global/intel.openvino.runtime.win/2024.10.0"),
+                dir.join("global/intel.openvino.runtime.win/2024.6.0"),
+            ]
+        );
+        assert_eq!(
+            nuget_package_dirs(&dir.join("solution"), false),
+            vec![
+                dir.join("solution/Intel.OpenVINO.Runtime.Win.2025.0.0"),
+                dir.join("solution/Intel.OpenVINO.Runtime.Win.2024.6.0"),
+            ]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// This test checks that snaps are searched in order, in both the archive and the Debian
+    /// layouts, including for version-suffixed libraries.
+    #[test]
+    fn snap_layouts() {
+        let dir = env::temp_dir().join("openvino-finder-snaps");
+        let _ = fs::remove_dir_all(&dir);
+        let debian_dir = dir.join("app").join(&snap_library_subdirectories()[0]);
+        fs::create_dir_all(&debian_dir).unwrap();
+        fs::write(debian_dir.join("libopenvino_c.so.2024.6.0"), b"").unwrap();
+        fs::write(debian_dir.join("libopenvino_c.so.2025.0.0"), b"").unwrap();
+        fs::create_dir_all(dir.join("openvino")).unwrap();
+        let snaps = [dir.join("app"), dir.join("missing"), dir.join("openvino")];
+
+        let paths = paths_in_snaps(&snaps, "libopenvino_c.so", false);
+        let position = |path: &Path| paths.iter().position(|p| p == path).unwrap();
+        assert!(paths.contains(&debian_dir.join("libopenvino_c.so.2025.0.0")));
+        assert!(!paths.contains(&debian_dir.join("libopenvino_c.so.2024.6.0")));
+        assert!(
+            position(&debian_dir.join("libopenvino_c.so"))
+                < position(&debian_dir.join("libopenvino_c.so.2025.0.0"))
+        );
+        let archive_path = |snap: &str| {
+            dir.join(snap)
+                .join(&installation_subdirectories()[0])
+                .join("libopenvino_c.so")
+        };
+        assert!(position(&archive_path("app")) < position(&archive_path("openvino")));
+        assert!(!paths.iter().any(|p| p.starts_with(dir.join("missing"))));
+
+        let paths = paths_in_snaps(&snaps, "libopenvino_c.so", true);
+        assert!(paths.contains(&debian_dir.join("libopenvino_c.so.2024.6.0")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// This test checks that Homebrew kegs are ordered by version, including revisions.
+    #[test]
+    fn keg_versions() {
+        let mut kegs = vec!["2024.10.0", "2024.9.0_1", "2024.9.0", "2025.0.0"];
+        kegs.sort_by_cached_key(|v| keg_version(v));
+        assert_eq!(kegs, ["2024.9.0", "2024.9.0_1", "2024.10.0", "2025.0.0"]);
+    }
+
     /// This test shows how the finder would discover the latest shared library on an
     /// APT installation.
     #[test]
@@ -0,0 +1,134 @@
+//! Read the dynamic linker's cache on Linux (`/etc/ld.so.cache`, which `ldconfig -p` lists): it
+//! records where system-wide installations put their libraries, whatever the layout of the
+//! distribution; see [`directories`].
+
+use crate::target::{self, in_sysroot, TargetOs};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const LD_SO_CACHE: &str = "/etc/ld.so.cache";
+
+/// The magic number and version of the cache format written by glibc (alone since glibc 2.32,
+/// after the entries of the older format before).
+const MAGIC: &[u8] = b"glibc-ld.so.cache1.1";
+const HEADER_LEN: usize = 48;
+const ENTRY_LEN: usize = 24;
+
+/// List the directories in which the linker cache, in the sysroot, records `file` (e.g.,
+/// `libopenvino_c.so`) or one of its version-suffixed names (e.g., `libopenvino_c.so.2520`), in
+/// the order of the cache; empty if the target is not Linux or the cache cannot be read.
+pub(crate) fn directories(file: &str) -> Vec<PathBuf> {
+    if target::os() != TargetOs::Linux {
+        return vec![];
+    }
+    let path = in_sysroot(LD_SO_CACHE);
+    let Ok(cache) = fs::read(&path) else {
+        log::debug!("No linker cache at: {}", path.display());
+        return vec![];
+    };
+    let Some(entries) = parse(&cache) else {
+        log::warn!("Ignoring unrecognized linker cache: {}", path.display());
+        return vec![];
+    };
+    let mut dirs = vec![];
+    for (name, library) in entries {
+        let matches = name == file
+            || name
+                .strip_prefix(file)
+                .is_some_and(|version| version.starts_with('.'));
+        let Some(dir) = Path::new(library).parent().filter(|_| matches) else {
+            continue;
+        };
+        let dir = in_sysroot(&dir.to_string_lossy());
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Parse the entries of a linker cache: the file name of each library (e.g.,
+/// `libopenvino_c.so.2520`) and its path. `None` if the cache is not in the expected format.
+fn parse(cache: &[u8]) -> Option<Vec<(&str, &str)>> {
+    let start = cache
+        .windows(MAGIC.len())
+        .position(|window| window == MAGIC)?;
+    let u32_at = |offset: usize| -> Option<usize> {
+        let bytes = cache.get(offset..offset + 4)?.try_into().ok()?;
+        usize::try_from(u32::from_ne_bytes(bytes)).ok()
+    };
+    // The offsets of the strings are relative to the header.
+    let string_at = |offset: usize| -> Option<&str> {
+        let bytes = cache.get(start + offset..)?;
+        let end = bytes.iter().position(|b| *b == 0)?;
+        std::str::from_utf8(&bytes[..end]).ok()
+    };
+    let count = u32_at(start + MAGIC.len())?;
+    let mut entries = Vec::with_capacity(count.min(cache.len() / ENTRY_LEN));
+    for i in 0..count {
+        let entry = start + HEADER_LEN + i * ENTRY_LEN;
+        let name = string_at(u32_at(entry + 4)?)?;
+        let path = string_at(u32_at(entry + 8)?)?;
+        entries.push((name, path));
+    }
+    Some(entries)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Build a linker cache in the format of glibc 2.32 and later, preceded by `prefix` (e.g., the
+    /// older format).
+    fn cache(prefix: &[u8], entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut strings = vec![];
+        let strings_start = HEADER_LEN + entries.len() * ENTRY_LEN;
+        let mut offset = |s: &str| {
+            let offset = u32::try_from(strings_start + strings.len()).unwrap();
+            strings.extend_from_slice(s.as_bytes());
+            strings.push(0);
+            offset
+        };
+        let mut body = vec![];
+        for (name, path) in entries {
+            body.extend_from_slice(&0x0303_i32.to_ne_bytes()); // Flags: x86-64 ELF.
+            body.extend_from_slice(&offset(name).to_ne_bytes());
+            body.extend_from_slice(&offset(path).to_ne_bytes());
+            body.extend_from_slice(&[0; 12]); // OS version and hardware capabilities.
+        }
+        let mut cache = prefix.to_vec();
+        cache.extend_from_slice(MAGIC);
+        cache.extend_from_slice(&u32::try_from(entries.len()).unwrap().to_ne_bytes());
+        cache.extend_from_slice(&u32::try_from(strings.len()).unwrap().to_ne_bytes());
+        cache.resize(prefix.len() + HEADER_LEN, 0);
+        cache.extend(body);
+        cache.extend(strings);
+        cache
+    }
+
+    #[test]
+    fn parse_entries() {
+        let entries = [
+            (
+                "libopenvino_c.so.2520",
+                "/usr/lib/x86_64-linux-gnu/libopenvino_c.so.2520",
+            ),
+            (
+                "libopenvino_c.so",
+                "/usr/lib/x86_64-linux-gnu/libopenvino_c.so",
+            ),
+            ("libz.so.1", "/lib/x86_64-linux-gnu/libz.so.1"),
+        ];
+        assert_eq!(parse(&cache(b"", &entries)), Some(entries.to_vec()));
+        assert_eq!(
+            parse(&cache(b"ld.so-1.7.0\0\0\0\0\0", &entries)),
+            Some(entries.to_vec())
+        );
+        assert_eq!(parse(&cache(b"", &[])), Some(vec![]));
+
+        // Truncated or unrecognized caches are rejected.
+        let truncated = cache(b"", &entries);
+        assert_eq!(parse(&truncated[..HEADER_LEN + ENTRY_LEN]), None);
+        assert_eq!(parse(b"ld.so-1.7.0"), None);
+    }
+}
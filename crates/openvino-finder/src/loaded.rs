@@ -0,0 +1,189 @@
+//! Ask the running process where it loaded a library from, so that files found relative to the
+//! library (e.g., `plugins.xml`) come from the installation actually in use rather than from one
+//! rediscovered by searching again.
+//!
+//! The process is queried through its loader: the mappings in `/proc/self/maps` on Linux, the
+//! `dyld` image list on `MacOS` and `GetModuleFileNameW` on Windows. Unlike the search, this is
+//! always about the host, since it only makes sense at runtime.
+
+use cfg_if::cfg_if;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::path::{Path, PathBuf};
+
+/// Find the path from which the library `library_name` (e.g., `openvino_c`) is loaded in the
+/// current process, e.g., after runtime linking or by the system loader; `None` if it is not
+/// loaded or the platform cannot be queried.
+///
+/// Version-suffixed libraries (e.g., `libopenvino_c.so.2025.1.0`) count as well. If several copies
+/// are loaded (e.g., after switching between installations), the first one reported by the loader
+/// is returned.
+pub fn find_loaded(library_name: &str) -> Option<PathBuf> {
+    let path = loaded_path(library_name)?;
+    log::info!("Found loaded library at path: {}", path.display());
+    Some(path)
+}
+
+/// Check whether the file at `path` is the library `library_name`, possibly version-suffixed:
+/// either after the suffix, as on Linux (e.g., `libfoo.so.1`), or before it, as on `MacOS` (e.g.,
+/// `libfoo.1.dylib`).
+#[cfg(any(test, target_os = "linux", target_os = "android", target_os = "macos"))]
+fn is_library(path: &Path, library_name: &str) -> bool {
+    let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else {
+        return false;
+    };
+    let file = format!("{DLL_PREFIX}{library_name}{DLL_SUFFIX}");
+    let stem = format!("{DLL_PREFIX}{library_name}.");
+    file_name == file
+        || file_name.starts_with(&format!("{file}."))
+        || (file_name.starts_with(&stem) && file_name.ends_with(DLL_SUFFIX))
+}
+
+cfg_if! {
+    if #[cfg(any(target_os = "linux", target_os = "android"))] {
+        /// Find the library among the files mapped into the process, in address order.
+        fn loaded_path(library_name: &str) -> Option<PathBuf> {
+            let maps = std::fs::read_to_string("/proc/self/maps").ok()?;
+            parse_maps(&maps)
+                .into_iter()
+                .find(|path| is_library(path, library_name))
+        }
+    } else if #[cfg(target_os = "macos")] {
+        /// Find the library among the images loaded by `dyld`, in load order.
+        fn loaded_path(library_name: &str) -> Option<PathBuf> {
+            use std::ffi::{c_char, CStr, OsStr};
+            use std::os::unix::ffi::OsStrExt;
+            extern "C" {
+                fn _dyld_image_count() -> u32;
+                fn _dyld_get_image_name(image_index: u32) -> *const c_char;
+            }
+            // SAFETY: `dyld` returns a null pointer for an index that is out of range (e.g., if an
+            // image is unloaded concurrently) and otherwise a string it owns.
+            (0..unsafe { _dyld_image_count() })
+                .filter_map(|i| {
+                    let name = unsafe { _dyld_get_image_name(i) };
+                    (!name.is_null()).then(|| unsafe { CStr::from_ptr(name) })
+                })
+                .map(|name| PathBuf::from(OsStr::from_bytes(name.to_bytes())))
+                .find(|path| is_library(path, library_name))
+        }
+    } else if #[cfg(target_os = "windows")] {
+        /// Look up the loaded module by its file name (e.g., `openvino_c.dll`), which Windows
+        /// matches against the modules already loaded, and ask for its path.
+        fn loaded_path(library_name: &str) -> Option<PathBuf> {
+            use std::ffi::{c_void, OsStr, OsString};
+            use std::os::windows::ffi::{OsStrExt, OsStringExt};
+            #[link(name = "kernel32")]
+            extern "system" {
+                fn GetModuleHandleW(module_name: *const u16) -> *mut c_void;
+                fn GetModuleFileNameW(module: *mut c_void, filename: *mut u16, size: u32) -> u32;
+            }
+            let file: Vec<u16> = OsStr::new(&format!("{DLL_PREFIX}{library_name}{DLL_SUFFIX}"))
+                .encode_wide()
+                .chain(Some(0))
+                .collect();
+            // SAFETY: `file` is NUL-terminated; the returned handle is not reference-counted and is
+            // only used to ask for the module's path, which is truncated (and retried) if longer
+            // than `buffer`.
+            let module = unsafe { GetModuleHandleW(file.as_ptr()) };
+            if module.is_null() {
+                return None;
+            }
+            let mut buffer = vec![0u16; 260];
+            loop {
+                let size = u32::try_from(buffer.len()).ok()?;
+                let len = unsafe { GetModuleFileNameW(module, buffer.as_mut_ptr(), size) };
+                match usize::try_from(len).ok()? {
+                    0 => return None,
+                    len if len < buffer.len() => {
+                        return Some(PathBuf::from(OsString::from_wide(&buffer[..len])));
+                    }
+                    _ => buffer.resize(buffer.len() * 2, 0),
+                }
+            }
+        }
+    } else {
+        /// The loaded libraries cannot be listed on this platform.
+        fn loaded_path(_library_name: &str) -> Option<PathBuf> {
+            None
+        }
+    }
+}
+
+/// Parse the paths of the files mapped in `/proc/self/maps` (e.g., `7f...-7f... r-xp 00000000
+/// 08:01 1234 /usr/lib/libfoo.so`), each once; deleted files are skipped.
+#[cfg(any(test, target_os = "linux", target_os = "android"))]
+fn parse_maps(maps: &str) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = vec![];
+    for line in maps.lines() {
+        let Some(start) = line.find('/') else {
+            continue;
+        };
+        let path = &line[start..];
+        if path.ends_with(" (deleted)") {
+            continue;
+        }
+        let path = PathBuf::from(path);
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn library_names() {
+        let file = |name: &str| PathBuf::from(format!("/lib/{DLL_PREFIX}{name}"));
+        assert!(is_library(
+            &file(&format!("openvino_c{DLL_SUFFIX}")),
+            "openvino_c"
+        ));
+        assert!(is_library(
+            &file(&format!("openvino_c{DLL_SUFFIX}.2025.1.0")),
+            "openvino_c"
+        ));
+        assert!(is_library(
+            &file(&format!("openvino_c.2510{DLL_SUFFIX}")),
+            "openvino_c"
+        ));
+        assert!(!is_library(
+            &file(&format!("openvino{DLL_SUFFIX}")),
+            "openvino_c"
+        ));
+        assert!(!is_library(
+            &file(&format!("openvino_c_extra{DLL_SUFFIX}")),
+            "openvino_c"
+        ));
+    }
+
+    #[test]
+    fn parse_proc_maps() {
+        let maps = "\
+55d0c0a00000-55d0c0a02000 r--p 00000000 08:01 1 /usr/bin/app
+7f0000000000-7f0000001000 r--p 00000000 08:01 2 /opt/intel/openvino/lib/libopenvino_c.so
+7f0000001000-7f0000002000 r-xp 00001000 08:01 2 /opt/intel/openvino/lib/libopenvino_c.so
+7f0000002000-7f0000003000 rw-p 00000000 00:00 0
+7f0000003000-7f0000004000 r--p 00000000 08:01 3 /tmp/my lib.so (deleted)
+7ffc00000000-7ffc00021000 rw-p 00000000 00:00 0 [stack]
+";
+        assert_eq!(
+            parse_maps(maps),
+            vec![
+                PathBuf::from("/usr/bin/app"),
+                PathBuf::from("/opt/intel/openvino/lib/libopenvino_c.so"),
+            ]
+        );
+    }
+
+    /// This test checks that the C library of a running Linux program is found.
+    #[test]
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    fn find_loaded_libc() {
+        let path = find_loaded("c").unwrap();
+        assert!(path.is_absolute());
+        assert!(find_loaded("openvino_not_loaded").is_none());
+    }
+}
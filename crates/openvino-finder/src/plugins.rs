@@ -0,0 +1,261 @@
+//! Locate the device plugin libraries of an installation (see [`find_plugin`]) and write a
+//! `plugins.xml` for an installation that ships the plugin libraries but not the file itself (e.g.,
+//! some `pip` and distribution packages); see [`generate_plugins_xml`].
+
+use crate::{build_latest_version, get_suffixes, list_directory, openvino_c_library, target};
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The devices each known plugin library (e.g., `libopenvino_intel_cpu_plugin.so`) implements, by
+/// the name between `openvino_` and `_plugin`.
+const PLUGIN_DEVICES: &[(&str, &[&str])] = &[
+    ("intel_cpu", &["CPU"]),
+    ("arm_cpu", &["CPU"]),
+    ("intel_gpu", &["GPU"]),
+    ("intel_npu", &["NPU"]),
+    ("auto", &["AUTO", "MULTI"]),
+    ("auto_batch", &["BATCH"]),
+    ("hetero", &["HETERO"]),
+];
+
+/// Find the plugin library implementing `device` (e.g., `CPU`, `GPU` or `NPU`; a device index, as
+/// in `GPU.1`, is ignored), e.g., `libopenvino_intel_cpu_plugin.so` for `CPU`.
+///
+/// The plugin is looked up in the installation of the `openvino_c` library (the one loaded in the
+/// process, see [`crate::find_loaded`], or else the one discovered by [`crate::find`]): in its
+/// directory, as in archives and `pip` packages, then in the latest `openvino-<version>` directory
+/// beside it, as in DEB packages and Homebrew kegs. This helps diagnose a device OpenVINO reports
+/// as unavailable (e.g., a package without its GPU plugin) or register a plugin explicitly. `None`
+/// if the device is unknown or its plugin is not installed.
+pub fn find_plugin(device: &str) -> Option<PathBuf> {
+    let library = openvino_c_library()?;
+    find_plugin_in(library.parent()?, device)
+}
+
+/// Find the plugin library implementing `device` in `dir` or in its latest `openvino-<version>`
+/// subdirectory; see [`find_plugin`].
+fn find_plugin_in(dir: &Path, device: &str) -> Option<PathBuf> {
+    let device = device.split('.').next().unwrap_or_default();
+    let plugins: Vec<&str> = PLUGIN_DEVICES
+        .iter()
+        .filter(|(_, devices)| devices.iter().any(|d| d.eq_ignore_ascii_case(device)))
+        .map(|(plugin, _)| *plugin)
+        .collect();
+    if plugins.is_empty() {
+        log::warn!("Unknown device, with no known plugin library: {}", device);
+        return None;
+    }
+    let mut dirs = vec![dir.to_path_buf()];
+    if let Some(filenames) = list_directory(dir) {
+        let versions = get_suffixes(filenames, "openvino-");
+        dirs.extend(build_latest_version(dir, "openvino-", versions));
+    }
+    let (prefix, suffix) = target::dynamic_library_affixes();
+    for dir in dirs {
+        for plugin in &plugins {
+            let path = dir.join(format!("{prefix}openvino_{plugin}_plugin{suffix}"));
+            log::debug!("Searching in: {}", path.display());
+            if path.is_file() {
+                log::info!("Found plugin library at path: {}", path.display());
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// Generate a `plugins.xml` file registering the plugin libraries found in `dir` (e.g., the
+/// directory of the `openvino_c` library) and write it to `path`.
+///
+/// OpenVINO fails late (e.g., with `Inference(GeneralError)`) when it cannot map a device to its
+/// plugin library. This scans `dir` for the known `openvino_*_plugin` libraries (e.g.,
+/// `libopenvino_intel_cpu_plugin.so` for `CPU`, also `GPU`, `NPU`, `AUTO`, `MULTI`, `BATCH` and
+/// `HETERO`) and writes a file mapping each device to its library, by absolute path. The file is
+/// replaced at once, so concurrent readers never see a partial one, and can be passed to, e.g.,
+/// `Core::new_with_config`. Since OpenVINO loads the libraries it lists, `path` should be somewhere
+/// only the current user can write to.
+///
+/// # Errors
+///
+/// Returns an error of kind [`io::ErrorKind::NotFound`] if `dir` holds no known plugin library, or
+/// any error from writing the file.
+pub fn generate_plugins_xml(dir: &Path, path: &Path) -> io::Result<()> {
+    let dir = fs::canonicalize(dir)?;
+    let filenames = list_directory(&dir).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("cannot list directory: {}", dir.display()),
+        )
+    })?;
+    let plugins = plugin_libraries(filenames);
+    if plugins.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("no plugin libraries in: {}", dir.display()),
+        ));
+    }
+    let xml = plugins_xml(&dir, &plugins);
+
+    // Write to a file of this process first, then move it into place.
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(format!(".{}", std::process::id()));
+    let partial = PathBuf::from(partial);
+    fs::write(&partial, xml)?;
+    if let Err(e) = fs::rename(&partial, path) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    log::info!(
+        "Generated plugins.xml for {} at path: {}",
+        dir.display(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Pick the known plugin libraries among `filenames`, as `(device, file name)` pairs sorted by
+/// device; a device is only mapped to the first library implementing it, by file name.
+fn plugin_libraries(filenames: impl IntoIterator<Item = String>) -> Vec<(&'static str, String)> {
+    let (prefix, suffix) = target::dynamic_library_affixes();
+    let mut filenames: Vec<String> = filenames.into_iter().collect();
+    filenames.sort();
+    let mut plugins: Vec<(&'static str, String)> = vec![];
+    for filename in filenames {
+        let Some(name) = filename
+            .strip_prefix(prefix)
+            .and_then(|f| f.strip_prefix("openvino_"))
+            .and_then(|f| f.strip_suffix(suffix))
+            .and_then(|f| f.strip_suffix("_plugin"))
+        else {
+            continue;
+        };
+        let Some((_, devices)) = PLUGIN_DEVICES.iter().find(|(plugin, _)| *plugin == name) else {
+            log::debug!("Skipping unknown plugin library: {}", filename);
+            continue;
+        };
+        for device in *devices {
+            if !plugins.iter().any(|(d, _)| d == device) {
+                plugins.push((device, filename.clone()));
+            }
+        }
+    }
+    plugins.sort();
+    plugins
+}
+
+/// Render the `plugins.xml` contents mapping each device of `plugins` to its library in `dir`.
+fn plugins_xml(dir: &Path, plugins: &[(&str, String)]) -> String {
+    let mut xml = String::from("<ie>\n    <plugins>\n");
+    for (device, filename) in plugins {
+        let location = dir.join(filename);
+        let _ = writeln!(
+            xml,
+            "        <plugin name=\"{device}\" location=\"{}\"/>",
+            escape_attribute(&location.to_string_lossy())
+        );
+    }
+    xml.push_str("    </plugins>\n</ie>\n");
+    xml
+}
+
+/// Escape `value` for use in a double-quoted XML attribute.
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    fn library(name: &str) -> String {
+        let (prefix, suffix) = target::dynamic_library_affixes();
+        format!("{prefix}{name}{suffix}")
+    }
+
+    #[test]
+    fn pick_plugin_libraries() {
+        let filenames = [
+            "openvino_intel_gpu_plugin",
+            "openvino_c",
+            "openvino_intel_cpu_plugin",
+            "openvino_auto_plugin",
+            "openvino_template_plugin",
+            "openvino_onnx_frontend",
+        ]
+        .map(library);
+        assert_eq!(
+            plugin_libraries(filenames),
+            vec![
+                ("AUTO", library("openvino_auto_plugin")),
+                ("CPU", library("openvino_intel_cpu_plugin")),
+                ("GPU", library("openvino_intel_gpu_plugin")),
+                ("MULTI", library("openvino_auto_plugin")),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_plugins() {
+        let dir = env::temp_dir().join("openvino-finder-find-plugin");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("openvino-2024.6.0")).unwrap();
+        fs::create_dir_all(dir.join("openvino-2025.1.0")).unwrap();
+        let cpu = dir.join(library("openvino_intel_cpu_plugin"));
+        let old_gpu = dir
+            .join("openvino-2024.6.0")
+            .join(library("openvino_intel_gpu_plugin"));
+        let gpu = dir
+            .join("openvino-2025.1.0")
+            .join(library("openvino_intel_gpu_plugin"));
+        for path in [&cpu, &old_gpu, &gpu] {
+            fs::write(path, "").unwrap();
+        }
+
+        assert_eq!(find_plugin_in(&dir, "CPU"), Some(cpu));
+        assert_eq!(find_plugin_in(&dir, "gpu.1"), Some(gpu));
+        assert_eq!(find_plugin_in(&dir, "NPU"), None);
+        assert_eq!(find_plugin_in(&dir, "FPGA"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn generate() {
+        let dir = env::temp_dir().join("openvino-finder-generate-plugins & more");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("out")).unwrap();
+        let path = dir.join("out").join("plugins.xml");
+        assert_eq!(
+            generate_plugins_xml(&dir, &path).unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+        assert!(!path.exists());
+
+        fs::write(dir.join(library("openvino_intel_npu_plugin")), "").unwrap();
+        generate_plugins_xml(&dir, &path).unwrap();
+        let location = fs::canonicalize(&dir)
+            .unwrap()
+            .join(library("openvino_intel_npu_plugin"));
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!(
+                "<ie>\n    <plugins>\n        <plugin name=\"NPU\" location=\"{}\"/>\n    </plugins>\n</ie>\n",
+                escape_attribute(&location.to_string_lossy())
+            )
+        );
+        assert!(dir
+            .join("out")
+            .read_dir()
+            .unwrap()
+            .all(|f| f.unwrap().file_name() == "plugins.xml"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,240 @@
+//! Read the installation directories that the OpenVINO installer for Windows records in the
+//! registry.
+
+use cfg_if::cfg_if;
+use std::path::PathBuf;
+
+/// The registry keys, under `HKEY_LOCAL_MACHINE`, in which the installer records its installations,
+/// one subkey per version; the second is the view of 32-bit installers on 64-bit Windows.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const REGISTRY_KEYS: &[&str] = &[
+    r"SOFTWARE\Intel\OpenVINO",
+    r"SOFTWARE\WOW6432Node\Intel\OpenVINO",
+];
+
+/// List the installation directories recorded in the registry, on Windows; elsewhere, or if the
+/// keys do not exist, the list is empty.
+pub(crate) fn install_directories() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    for data in string_values() {
+        if let Some(dir) = installation_directory(&data) {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs
+}
+
+cfg_if! {
+    if #[cfg(target_os = "windows")] {
+        use windows_sys::Win32::Foundation::{ERROR_MORE_DATA, ERROR_SUCCESS};
+        use windows_sys::Win32::System::Registry::{
+            RegCloseKey, RegEnumKeyExW, RegEnumValueW, RegGetValueW, RegOpenKeyExW, HKEY,
+            HKEY_LOCAL_MACHINE, KEY_READ, REG_EXPAND_SZ, REG_SZ, RRF_RT_ANY,
+        };
+
+        /// Read the string values of the [`REGISTRY_KEYS`] and of all their subkeys: the value
+        /// names are not documented, so every string is returned (`REG_EXPAND_SZ` ones expanded).
+        fn string_values() -> Vec<String> {
+            let mut values = vec![];
+            for key in REGISTRY_KEYS {
+                if let Some(key) = Key::open(HKEY_LOCAL_MACHINE, key) {
+                    log::debug!(
+                        "Reading installation directories from registry key: HKLM\\{}",
+                        key.path
+                    );
+                    key.collect_string_values(&mut values);
+                }
+            }
+            values
+        }
+
+        /// An open registry key, closed on drop.
+        struct Key {
+            handle: HKEY,
+            path: String,
+        }
+
+        impl Key {
+            /// Open the subkey `path` of `parent` for reading; `None` if it does not exist.
+            fn open(parent: HKEY, path: &str) -> Option<Self> {
+                let name = wide(path);
+                let mut handle = std::ptr::null_mut();
+                // SAFETY: `name` is NUL-terminated and `handle` is only used if the key is opened.
+                let result =
+                    unsafe { RegOpenKeyExW(parent, name.as_ptr(), 0, KEY_READ, &mut handle) };
+                (result == ERROR_SUCCESS).then(|| Self {
+                    handle,
+                    path: path.to_string(),
+                })
+            }
+
+            /// Append the string values of this key, then those of its subkeys, recursively.
+            fn collect_string_values(&self, values: &mut Vec<String>) {
+                values.extend(self.value_names().iter().filter_map(|n| self.string_value(n)));
+                for name in self.subkey_names() {
+                    if let Some(subkey) = Key::open(self.handle, &name) {
+                        subkey.collect_string_values(values);
+                    }
+                }
+            }
+
+            /// List the names of the subkeys, which are at most 255 characters long.
+            fn subkey_names(&self) -> Vec<String> {
+                let mut names = vec![];
+                let mut buffer = [0u16; 256];
+                for index in 0.. {
+                    let mut len = 256;
+                    // SAFETY: `len` is the length of `buffer`, which receives the name; the other
+                    // outputs are optional.
+                    let result = unsafe {
+                        RegEnumKeyExW(
+                            self.handle,
+                            index,
+                            buffer.as_mut_ptr(),
+                            &mut len,
+                            std::ptr::null(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                        )
+                    };
+                    if result != ERROR_SUCCESS {
+                        break;
+                    }
+                    names.push(String::from_utf16_lossy(&buffer[..len as usize]));
+                }
+                names
+            }
+
+            /// List the names of the values, which are at most 16383 characters long.
+            fn value_names(&self) -> Vec<String> {
+                let mut names = vec![];
+                let mut buffer = vec![0u16; 16384];
+                for index in 0.. {
+                    let mut len = 16384;
+                    // SAFETY: `len` is the length of `buffer`, which receives the name; the type
+                    // and data are not requested.
+                    let result = unsafe {
+                        RegEnumValueW(
+                            self.handle,
+                            index,
+                            buffer.as_mut_ptr(),
+                            &mut len,
+                            std::ptr::null(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                            std::ptr::null_mut(),
+                        )
+                    };
+                    if result != ERROR_SUCCESS {
+                        break;
+                    }
+                    names.push(String::from_utf16_lossy(&buffer[..len as usize]));
+                }
+                names
+            }
+
+            /// Read the value `name` if it is a string, expanding the environment variables of a
+            /// `REG_EXPAND_SZ`.
+            fn string_value(&self, name: &str) -> Option<String> {
+                let name = wide(name);
+                let mut buffer: Vec<u16> = vec![];
+                loop {
+                    let mut kind = 0;
+                    let mut size = u32::try_from(buffer.len() * 2).ok()?;
+                    // SAFETY: `size` is the size of `buffer` in bytes (a null pointer, when empty,
+                    // only asks for the size), which `RegGetValueW` NUL-terminates.
+                    let result = unsafe {
+                        RegGetValueW(
+                            self.handle,
+                            std::ptr::null(),
+                            name.as_ptr(),
+                            RRF_RT_ANY,
+                            &mut kind,
+                            if buffer.is_empty() {
+                                std::ptr::null_mut()
+                            } else {
+                                buffer.as_mut_ptr().cast()
+                            },
+                            &mut size,
+                        )
+                    };
+                    if kind != REG_SZ && kind != REG_EXPAND_SZ {
+                        return None;
+                    }
+                    match result {
+                        ERROR_SUCCESS if !buffer.is_empty() => {
+                            let len = (size as usize / 2).min(buffer.len());
+                            let data = &buffer[..len];
+                            let end = data.iter().position(|c| *c == 0).unwrap_or(len);
+                            return Some(String::from_utf16_lossy(&data[..end]));
+                        }
+                        // The size is known (or the value grew, e.g., when expanded): retry.
+                        ERROR_SUCCESS | ERROR_MORE_DATA => {
+                            buffer.resize((size as usize).div_ceil(2).max(buffer.len() + 1), 0);
+                        }
+                        _ => return None,
+                    }
+                }
+            }
+        }
+
+        impl Drop for Key {
+            fn drop(&mut self) {
+                // SAFETY: the handle was opened by `RegOpenKeyExW` and is closed once.
+                unsafe { RegCloseKey(self.handle) };
+            }
+        }
+
+        /// Encode `s` for the Win32 API, NUL-terminated.
+        fn wide(s: &str) -> Vec<u16> {
+            s.encode_utf16().chain(Some(0)).collect()
+        }
+    } else {
+        /// There is no registry to read on this platform.
+        fn string_values() -> Vec<String> {
+            vec![]
+        }
+    }
+}
+
+/// Extract an installation directory from a string value of the registry: the value names are not
+/// documented, so every value holding an absolute path is kept, e.g.,
+/// `C:\Program Files (x86)\Intel\openvino_2022.1.0.643`.
+fn installation_directory(data: &str) -> Option<PathBuf> {
+    let data = data.trim().trim_end_matches(['\\', '/']);
+    is_absolute_windows_path(data).then(|| PathBuf::from(data))
+}
+
+/// Check whether `path` starts with a drive (e.g., `C:\`); unlike [`std::path::Path::is_absolute`],
+/// this does not depend on the host.
+fn is_absolute_windows_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && matches!(bytes[2], b'\\' | b'/')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_installation_directories() {
+        assert_eq!(
+            installation_directory("C:\\Program Files (x86)\\Intel\\openvino_2022.1.0.643\\"),
+            Some(PathBuf::from(
+                "C:\\Program Files (x86)\\Intel\\openvino_2022.1.0.643"
+            ))
+        );
+        assert_eq!(
+            installation_directory(" D:/openvino_2021.4.752 "),
+            Some(PathBuf::from("D:/openvino_2021.4.752"))
+        );
+        assert_eq!(installation_directory("2022.1.0.643"), None);
+        assert_eq!(installation_directory("openvino\\runtime"), None);
+    }
+}
@@ -0,0 +1,608 @@
+//! Summarize the search for OpenVINO libraries so that build scripts can explain linking failures.
+
+use crate::{
+    architecture, check_target, finder, target, Finder, Found, HostEnvironment, Linking,
+    Provenance, UnsupportedPlatform, ENV_CONDA_PREFIX, ENV_INTEL_OPENVINO_DIR, ENV_LIBRARY_PATH,
+    ENV_OPENVINO_BUILD_DIR, ENV_OPENVINO_INSTALL_DIR, ENV_VCPKG_INSTALLED_DIR, ENV_VCPKG_ROOT,
+    ENV_VIRTUAL_ENV,
+};
+use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The environment variables a user can set to point the search at an installation, in the order
+/// [`crate::find`] reads them.
+const SEARCH_VARIABLES: &[&str] = &[
+    ENV_OPENVINO_BUILD_DIR,
+    ENV_OPENVINO_INSTALL_DIR,
+    ENV_INTEL_OPENVINO_DIR,
+    ENV_LIBRARY_PATH,
+    ENV_VCPKG_INSTALLED_DIR,
+    ENV_VCPKG_ROOT,
+    ENV_VIRTUAL_ENV,
+    ENV_CONDA_PREFIX,
+    target::ENV_OPENVINO_SYSROOT,
+    target::ENV_OPENVINO_ARCHIVE_ARCH,
+];
+
+/// A record of how [`crate::find`] searched for a single library: the environment variables which
+/// point the search at an installation and every path probed, in order, with the reason each was
+/// rejected; see [`crate::find_with_report`].
+///
+/// This explains _why_ each path was passed over, e.g., to print actionable diagnostics without
+/// enabling `RUST_LOG=debug`; it is also the error of a failed [`crate::try_find`] (see
+/// [`crate::FindError::NotFound`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchReport {
+    /// The library name, e.g., `openvino_c`.
+    pub library: String,
+    /// The platform-specific file name, e.g., `libopenvino_c.so`.
+    pub file: String,
+    /// The path found, if any; this is the path of the last probe.
+    pub found: Option<PathBuf>,
+    /// Every path probed, in search order; the search stops at the first path accepted.
+    pub probes: Vec<Probe>,
+    /// The environment variables that point the search at an installation, set or not.
+    pub variables: Vec<SearchVariable>,
+    /// Why nothing was searched, if the target cannot load dynamic libraries; see
+    /// [`check_target`].
+    pub unsupported: Option<UnsupportedPlatform>,
+}
+
+/// An environment variable read by the search; see [`SearchReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchVariable {
+    /// The variable name, e.g., `OPENVINO_INSTALL_DIR`.
+    pub name: &'static str,
+    /// The value of the variable, or `None` if it is not set (or ignored by the
+    /// [`crate::Finder`]).
+    pub value: Option<String>,
+}
+
+/// A path probed while searching for a library; see [`SearchReport`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Probe {
+    /// The path probed.
+    pub path: PathBuf,
+    /// The location that led to the path.
+    pub provenance: Provenance,
+    /// Why the path was rejected, or `None` if the library was found there.
+    pub rejection: Option<Rejection>,
+}
+
+/// The reason a probed path was rejected; see [`Probe`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Rejection {
+    /// The directory exists but holds no such file.
+    Missing,
+    /// The path exists but is not a file (e.g., it is a directory).
+    NotAFile,
+    /// The directory of the path does not exist (or is not a directory).
+    NotADirectory,
+    /// The file has a version suffix (e.g., `libopenvino_c.so.2024.0.0`) and a file with a later
+    /// suffix exists in the same directory, or the file is in a Homebrew keg and a later keg
+    /// exists.
+    WrongSuffix,
+    /// The file is a library built for another architecture than the compilation target.
+    WrongArchitecture {
+        /// The architecture the library is built for, e.g., `x86_64`.
+        library: &'static str,
+        /// The architecture of the compilation target, e.g., `aarch64`.
+        target: &'static str,
+    },
+}
+
+impl SearchReport {
+    /// Probe the `candidates` for `file`, in order, until one is a file.
+    pub(crate) fn search(library_name: &str, file: &str, candidates: &[Found]) -> Self {
+        let mut probes = vec![];
+        let mut found = None;
+        for (i, candidate) in candidates.iter().enumerate() {
+            log::debug!("Searching in: {}", candidate.path.display());
+            let rejection = if is_superseded(&candidate.path, file, candidates)
+                || is_older_keg(&candidates[..=i])
+            {
+                Some(Rejection::WrongSuffix)
+            } else if candidate.path.is_file() {
+                architecture::check(&candidate.path).err().map(|library| {
+                    Rejection::WrongArchitecture {
+                        library,
+                        target: architecture::target().unwrap_or_default(),
+                    }
+                })
+            } else if candidate.path.exists() {
+                Some(Rejection::NotAFile)
+            } else if candidate.path.parent().is_some_and(Path::is_dir) {
+                Some(Rejection::Missing)
+            } else {
+                Some(Rejection::NotADirectory)
+            };
+            probes.push(Probe {
+                path: candidate.path.clone(),
+                provenance: candidate.provenance,
+                rejection,
+            });
+            if rejection.is_none() {
+                log::info!("Found library at path: {}", candidate.path.display());
+                found = Some(candidate.path.clone());
+                break;
+            }
+        }
+        if found.is_none() {
+            if let Some(hint) = HostEnvironment::detect().hint() {
+                log::warn!("Unable to find library {}; {}", file, hint);
+            }
+        }
+        let variables = SEARCH_VARIABLES
+            .iter()
+            .map(|name| SearchVariable {
+                name,
+                value: finder::var_os(name).map(|v| v.to_string_lossy().into_owned()),
+            })
+            .collect();
+        Self {
+            library: library_name.to_string(),
+            file: file.to_string(),
+            found,
+            probes,
+            variables,
+            unsupported: check_target().err(),
+        }
+    }
+
+    /// Write the probes of the report, one per line, indented by `indent`.
+    pub(crate) fn write_probes(&self, f: &mut fmt::Formatter<'_>, indent: &str) -> fmt::Result {
+        for probe in &self.probes {
+            write!(
+                f,
+                "{indent}- {} (from {}): ",
+                probe.path.display(),
+                probe.provenance
+            )?;
+            match probe.rejection {
+                None => writeln!(f, "found")?,
+                Some(rejection) => writeln!(f, "{rejection}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check whether `path` is a version-suffixed `file` (e.g., `libfoo.so.1`) with a later version
+/// among the other `candidates` in the same directory; only the latest version is considered.
+fn is_superseded(path: &Path, file: &str, candidates: &[Found]) -> bool {
+    let version = |p: &Path| {
+        p.file_name()?
+            .to_str()?
+            .strip_prefix(file)
+            .filter(|v| !v.is_empty())
+            .map(ToString::to_string)
+    };
+    let Some(current) = version(path) else {
+        return false;
+    };
+    candidates
+        .iter()
+        .filter(|c| {
+            matches!(
+                c.provenance,
+                Provenance::SystemDirectory
+                    | Provenance::LinkerCache
+                    | Provenance::PythonPackage
+                    | Provenance::Snap
+            ) && c.path.parent() == path.parent()
+        })
+        .filter_map(|c| version(&c.path))
+        .any(|v| v > current)
+}
+
+/// Check whether the last of `candidates` is in a Homebrew keg older than one listed before it in
+/// the same `Cellar` (kegs are listed from the latest); only the latest keg is considered.
+fn is_older_keg(candidates: &[Found]) -> bool {
+    fn cellar(c: &Found) -> Option<&Path> {
+        (c.provenance == Provenance::Homebrew)
+            .then(|| c.path.ancestors().nth(3))
+            .flatten()
+    }
+    let Some((last, earlier)) = candidates.split_last() else {
+        return false;
+    };
+    cellar(last).is_some_and(|l| earlier.iter().any(|c| cellar(c) == Some(l)))
+}
+
+impl fmt::Display for SearchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.found {
+            Some(path) => writeln!(f, "Found {} at {}", self.file, path.display())?,
+            None => writeln!(f, "Unable to find {}", self.file)?,
+        }
+        if let Some(unsupported) = &self.unsupported {
+            writeln!(f, "  {unsupported}")?;
+        }
+        self.write_probes(f, "  ")
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "no such file"),
+            Self::NotAFile => write!(f, "not a file"),
+            Self::NotADirectory => write!(f, "no such directory"),
+            Self::WrongSuffix => write!(f, "a later version exists"),
+            Self::WrongArchitecture { library, target } => {
+                write!(f, "built for {library}, not for the target {target}")
+            }
+        }
+    }
+}
+
+/// A report of the search for a set of libraries (e.g., all of the libraries `openvino-sys` links
+/// against). Its [`fmt::Display`] implementation prints a compact, actionable summary and
+/// [`LinkReport::to_json`] produces a machine-readable version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkReport {
+    /// The search results for each library.
+    pub libraries: Vec<SearchReport>,
+    /// The environment the search ran in.
+    pub environment: HostEnvironment,
+    /// Why nothing was searched, if the target cannot load dynamic libraries; see
+    /// [`check_target`].
+    pub unsupported: Option<UnsupportedPlatform>,
+}
+
+impl LinkReport {
+    /// Search for each of the `library_names` (see [`crate::find_with_report`]) and collect the
+    /// results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if it cannot list the contents of a search directory.
+    pub fn new(library_names: &[&str], kind: Linking) -> Self {
+        let finder = Finder::new();
+        Self::from_searches(
+            library_names
+                .iter()
+                .map(|l| finder.find_with_report(l, kind))
+                .collect(),
+        )
+    }
+
+    /// Collect the results of searches already made (see [`crate::find_with_report`]), e.g., to
+    /// explain a failed [`crate::try_find`] without searching again.
+    pub fn from_searches(libraries: Vec<SearchReport>) -> Self {
+        Self {
+            libraries,
+            environment: HostEnvironment::detect(),
+            unsupported: check_target().err(),
+        }
+    }
+
+    /// List the files of the libraries which were not found, e.g., `libopenvino_c.so`.
+    pub fn missing(&self) -> Vec<&str> {
+        self.libraries
+            .iter()
+            .filter(|l| l.found.is_none())
+            .map(|l| l.file.as_str())
+            .collect()
+    }
+
+    /// Check whether all of the libraries were found.
+    pub fn is_success(&self) -> bool {
+        self.missing().is_empty()
+    }
+
+    /// List the environment variables a user could set to fix a failed search, with a description
+    /// of each.
+    pub fn suggested_env_vars() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "OPENVINO_INSTALL_DIR",
+                "the root of an OpenVINO installation, e.g., an extracted archive",
+            ),
+            (
+                "OPENVINO_BUILD_DIR",
+                "the root of an OpenVINO repository built from source",
+            ),
+            (
+                ENV_LIBRARY_PATH,
+                "a list of directories containing the OpenVINO libraries",
+            ),
+            (
+                "OPENVINO_SYSROOT",
+                "the root filesystem of the target when cross-compiling, e.g., a Yocto sysroot",
+            ),
+        ]
+    }
+
+    /// Serialize the report as a JSON object.
+    pub fn to_json(&self) -> String {
+        let libraries = self
+            .libraries
+            .iter()
+            .map(|l| {
+                let found = l.found.as_ref().map_or("null".to_string(), |p| {
+                    json_string(&p.display().to_string())
+                });
+                let probes = l
+                    .probes
+                    .iter()
+                    .map(|p| {
+                        let rejection = p
+                            .rejection
+                            .map_or("null".to_string(), |r| json_string(&r.to_string()));
+                        format!(
+                            r#"{{"path":{},"provenance":{},"rejection":{}}}"#,
+                            json_string(&p.path.display().to_string()),
+                            json_string(&p.provenance.to_string()),
+                            rejection
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+                format!(
+                    r#"{{"library":{},"file":{},"found":{},"probes":[{}]}}"#,
+                    json_string(&l.library),
+                    json_string(&l.file),
+                    found,
+                    probes
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let suggestions = Self::suggested_env_vars()
+            .iter()
+            .map(|(var, description)| {
+                format!(
+                    r#"{{"name":{},"description":{}}}"#,
+                    json_string(var),
+                    json_string(description)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let hint = self
+            .environment
+            .hint()
+            .map_or("null".to_string(), json_string);
+        let unsupported = self
+            .unsupported
+            .as_ref()
+            .map_or("null".to_string(), |u| json_string(&u.to_string()));
+        format!(
+            r#"{{"success":{},"environment":{},"hint":{},"unsupported":{},"libraries":[{}],"suggested_env_vars":[{}]}}"#,
+            self.is_success(),
+            json_string(&format!("{:?}", self.environment)),
+            hint,
+            unsupported,
+            libraries,
+            suggestions
+        )
+    }
+}
+
+impl fmt::Display for LinkReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_success() {
+            writeln!(f, "Found all OpenVINO libraries:")?;
+        } else {
+            writeln!(f, "Unable to find all OpenVINO libraries:")?;
+        }
+        if let Some(unsupported) = &self.unsupported {
+            // None of the suggestions below would help.
+            return writeln!(f, "  {unsupported}.");
+        }
+        for library in &self.libraries {
+            if let Some(path) = &library.found {
+                writeln!(f, "  - {}: found at {}", library.file, path.display())?;
+            } else {
+                writeln!(
+                    f,
+                    "  - {}: not found; probed {} paths:",
+                    library.file,
+                    library.probes.len()
+                )?;
+                library.write_probes(f, "      ")?;
+            }
+        }
+        if !self.is_success() {
+            writeln!(f, "To fix this, set one of:")?;
+            for (var, description) in Self::suggested_env_vars() {
+                writeln!(f, "  - `{var}`: {description}")?;
+            }
+            writeln!(
+                f,
+                "or build with `--features runtime-linking` to find the libraries at run time."
+            )?;
+        }
+        if let Some(hint) = self.environment.hint() {
+            writeln!(f, "Hint: {hint}.")?;
+        }
+        Ok(())
+    }
+}
+
+/// Quote and escape a string for inclusion in a JSON document.
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn search_report() {
+        let dir = std::env::temp_dir().join("openvino-finder-search-report");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("libfoo.so.0")).unwrap();
+        std::fs::write(dir.join("libfoo.so.1"), b"").unwrap();
+        std::fs::write(dir.join("libfoo.so.2"), b"").unwrap();
+        let probe = |path: PathBuf| Found {
+            path,
+            provenance: Provenance::SystemDirectory,
+        };
+        let candidates = [
+            probe(dir.join("libfoo.so.1")),
+            probe(dir.join("libfoo.so.2")),
+        ];
+        assert!(is_superseded(
+            &dir.join("libfoo.so.1"),
+            "libfoo.so",
+            &candidates
+        ));
+        assert!(!is_superseded(
+            &dir.join("libfoo.so.2"),
+            "libfoo.so",
+            &candidates
+        ));
+        assert!(!is_superseded(
+            &dir.join("libfoo.so"),
+            "libfoo.so",
+            &candidates
+        ));
+
+        let report = SearchReport {
+            library: "foo".into(),
+            file: "libfoo.so".into(),
+            found: None,
+            probes: vec![
+                Probe {
+                    path: dir.join("libfoo.so.0"),
+                    provenance: Provenance::EnvironmentVariable("OPENVINO_INSTALL_DIR"),
+                    rejection: Some(Rejection::NotAFile),
+                },
+                Probe {
+                    path: PathBuf::from("/missing/libfoo.so"),
+                    provenance: Provenance::DefaultInstallDirectory,
+                    rejection: Some(Rejection::NotADirectory),
+                },
+            ],
+            variables: vec![],
+            unsupported: None,
+        };
+        let summary = report.to_string();
+        assert!(summary.starts_with("Unable to find libfoo.so\n"));
+        assert!(summary.contains(
+            "  - /missing/libfoo.so (from a default installation directory): no such directory\n"
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn older_kegs() {
+        let keg = |version: &str| Found {
+            path: PathBuf::from(format!(
+                "/opt/homebrew/Cellar/openvino/{version}/lib/libopenvino_c.dylib"
+            )),
+            provenance: Provenance::Homebrew,
+        };
+        let candidates = [keg("2024.6.0"), keg("2024.5.0")];
+        assert!(!is_older_keg(&candidates[..1]));
+        assert!(is_older_keg(&candidates));
+    }
+
+    fn failed_report() -> LinkReport {
+        let probe = |path: &str, rejection| Probe {
+            path: PathBuf::from(path),
+            provenance: Provenance::DefaultInstallDirectory,
+            rejection,
+        };
+        LinkReport {
+            libraries: vec![
+                SearchReport {
+                    library: "openvino".into(),
+                    file: "libopenvino.so".into(),
+                    found: Some(PathBuf::from("/opt/intel/openvino/libopenvino.so")),
+                    probes: vec![probe("/opt/intel/openvino/libopenvino.so", None)],
+                    variables: vec![],
+                    unsupported: None,
+                },
+                SearchReport {
+                    library: "tbb".into(),
+                    file: "libtbb.so".into(),
+                    found: None,
+                    probes: vec![
+                        probe("/opt/intel/openvino/libtbb.so", Some(Rejection::Missing)),
+                        probe(
+                            "C:\\Program Files\\\"tbb\"/libtbb.so",
+                            Some(Rejection::WrongArchitecture {
+                                library: "x86_64",
+                                target: "aarch64",
+                            }),
+                        ),
+                    ],
+                    variables: vec![],
+                    unsupported: None,
+                },
+            ],
+            environment: HostEnvironment::Native,
+            unsupported: None,
+        }
+    }
+
+    #[test]
+    fn json_escaping() {
+        assert_eq!(json_string(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(json_string("line\nbreak\u{1}"), r#""line\nbreak\u0001""#);
+    }
+
+    #[test]
+    fn report_as_json() {
+        let json = failed_report().to_json();
+        assert!(json.starts_with(r#"{"success":false,"environment":"Native","hint":null,"#));
+        assert!(json.contains(
+            r#"{"library":"tbb","file":"libtbb.so","found":null,"probes":[{"path":"/opt/intel/openvino/libtbb.so","provenance":"a default installation directory","rejection":"no such file"},{"path":"C:\\Program Files\\\"tbb\"/libtbb.so","provenance":"a default installation directory","rejection":"built for x86_64, not for the target aarch64"}]}"#
+        ));
+        assert!(json.contains(r#""name":"OPENVINO_INSTALL_DIR""#));
+    }
+
+    #[test]
+    fn report_summary() {
+        assert_eq!(failed_report().missing(), ["libtbb.so"]);
+        let summary = failed_report().to_string();
+        assert!(summary.starts_with("Unable to find all OpenVINO libraries:\n"));
+        assert!(
+            summary.contains("  - libopenvino.so: found at /opt/intel/openvino/libopenvino.so\n")
+        );
+        assert!(summary.contains(
+            "  - libtbb.so: not found; probed 2 paths:\n      - /opt/intel/openvino/libtbb.so (from \
+             a default installation directory): no such file\n"
+        ));
+        // The reason a library was rejected is kept.
+        assert!(summary.contains("): built for x86_64, not for the target aarch64\n"));
+        assert!(summary.contains("`OPENVINO_INSTALL_DIR`"));
+    }
+
+    #[test]
+    fn unsupported_target() {
+        let mut report = failed_report();
+        report.unsupported = Some(UnsupportedPlatform {
+            arch: "wasm32".into(),
+            os: "wasi".into(),
+        });
+        let summary = report.to_string();
+        assert!(summary.contains("  the target `wasm32-wasi` cannot load OpenVINO's dynamic"));
+        assert!(!summary.contains("`OPENVINO_INSTALL_DIR`"));
+        assert!(report
+            .to_json()
+            .contains(r#""unsupported":"the target `wasm32-wasi` cannot"#));
+    }
+}
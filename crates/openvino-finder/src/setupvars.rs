@@ -0,0 +1,124 @@
+//! Locate the environment script of an extracted OpenVINO archive (`setupvars.sh` or
+//! `setupvars.bat`), so that the archive is found from a shell which has not sourced it; see
+//! [`find_setupvars`].
+
+use crate::target::{self, in_sysroot, TargetOs};
+use crate::{finder, installation_subdirectories, list_directory, version};
+use std::path::PathBuf;
+
+/// Find the environment script of an OpenVINO archive extracted in a conventional location: an
+/// `openvino` or `openvino_<version>` directory in `/opt/intel` or `~/intel` (in
+/// `C:\Program Files (x86)\Intel` or `C:\Program Files\Intel` on Windows). The `openvino`
+/// directory (usually a link to the preferred version) comes first, then the others, latest version
+/// first. `None` if there is no such script.
+///
+/// The script is `setupvars.bat` on Windows and `setupvars.sh` elsewhere. It sits at the root of
+/// the archive, which [`crate::find`] searches after the other installation directories, so that
+/// `cargo build` works without sourcing the script.
+pub fn find_setupvars() -> Option<PathBuf> {
+    let file = if target::os() == TargetOs::Windows {
+        "setupvars.bat"
+    } else {
+        "setupvars.sh"
+    };
+    for parent in archive_parent_directories() {
+        let Some(filenames) = list_directory(&parent) else {
+            continue;
+        };
+        let mut archives: Vec<(Option<version::Version>, String)> = filenames
+            .into_iter()
+            .filter(|n| n == "openvino" || n.starts_with("openvino_"))
+            .map(|n| (version::scan(&n), n))
+            .collect();
+        // The unversioned `openvino` first, then the latest version.
+        archives.sort_by(|a, b| a.0.is_some().cmp(&b.0.is_some()).then(b.0.cmp(&a.0)));
+        for (_, name) in archives {
+            let path = parent.join(name).join(file);
+            log::debug!("Searching for setupvars in: {}", path.display());
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// List the library directories of the archive found by [`find_setupvars`]: the known install
+/// subdirectories of the directory of its script, which is the `INTEL_OPENVINO_DIR` the script
+/// sets.
+pub(crate) fn library_directories() -> Vec<PathBuf> {
+    let Some(install_dir) = find_setupvars().and_then(|s| s.parent().map(PathBuf::from)) else {
+        return vec![];
+    };
+    installation_subdirectories()
+        .into_iter()
+        .map(|l| install_dir.join(l))
+        .collect()
+}
+
+/// List the directories in which OpenVINO archives are extracted by convention.
+fn archive_parent_directories() -> Vec<PathBuf> {
+    match target::os() {
+        TargetOs::Linux | TargetOs::MacOs => {
+            let mut dirs = vec![in_sysroot("/opt/intel")];
+            dirs.extend(finder::var_os("HOME").map(|h| PathBuf::from(h).join("intel")));
+            dirs
+        }
+        TargetOs::Windows => vec![
+            in_sysroot("C:\\Program Files (x86)\\Intel"),
+            in_sysroot("C:\\Program Files\\Intel"),
+        ],
+        TargetOs::Other => vec![],
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{installation_subdirectories, target, Finder, Provenance};
+    use std::{env, fs};
+
+    #[test]
+    fn find_archives() {
+        let dir = env::temp_dir().join("openvino-finder-setupvars");
+        let _ = fs::remove_dir_all(&dir);
+        let (script, archives) = if target::os() == target::TargetOs::Windows {
+            (
+                "setupvars.bat",
+                dir.join("Program Files (x86)").join("Intel"),
+            )
+        } else {
+            ("setupvars.sh", dir.join("opt").join("intel"))
+        };
+        for archive in [
+            "openvino_2024.6.0",
+            "openvino_2025.1.0",
+            "openvino_2025.2.0",
+        ] {
+            fs::create_dir_all(archives.join(archive)).unwrap();
+        }
+        for archive in ["openvino_2024.6.0", "openvino_2025.1.0"] {
+            fs::write(archives.join(archive).join(script), "").unwrap();
+        }
+
+        // The latest archive with a script is searched, with the archive layouts.
+        let candidates = Finder::new()
+            .with_env_var("OPENVINO_SYSROOT", &dir)
+            .with_env_var("HOME", dir.join("home"))
+            .candidates("libopenvino_c.so", false);
+        let archive: Vec<_> = candidates
+            .iter()
+            .filter(|c| c.provenance == Provenance::SetupScript)
+            .collect();
+        let install_dir = archives.join("openvino_2025.1.0");
+        let expected: Vec<_> = installation_subdirectories()
+            .into_iter()
+            .map(|l| install_dir.join(l).join("libopenvino_c.so"))
+            .collect();
+        assert_eq!(
+            archive.iter().map(|c| c.path.clone()).collect::<Vec<_>>(),
+            expected
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
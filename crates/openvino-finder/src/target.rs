@@ -0,0 +1,164 @@
+//! Describe the platform whose OpenVINO libraries are searched for: the target of the build script
+//! running this crate, which differs from the host when cross-compiling, or otherwise the platform
+//! this crate was compiled for.
+
+use crate::finder;
+use std::env;
+use std::path::{Path, PathBuf};
+
+pub(crate) const ENV_OPENVINO_SYSROOT: &str = "OPENVINO_SYSROOT";
+pub(crate) const ENV_OPENVINO_ARCHIVE_ARCH: &str = "OPENVINO_ARCHIVE_ARCH";
+
+/// The operating systems whose OpenVINO layouts are known.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TargetOs {
+    Linux,
+    MacOs,
+    Windows,
+    Other,
+}
+
+/// The operating system of the target, from `CARGO_CFG_TARGET_OS` in a build script.
+pub(crate) fn os() -> TargetOs {
+    match os_name().as_str() {
+        "linux" => TargetOs::Linux,
+        "macos" => TargetOs::MacOs,
+        "windows" => TargetOs::Windows,
+        _ => TargetOs::Other,
+    }
+}
+
+/// The name of the target's operating system, as in `cfg(target_os)` (e.g., `linux`, or `none` for
+/// bare-metal targets).
+pub(crate) fn os_name() -> String {
+    cargo_cfg("CARGO_CFG_TARGET_OS", env::consts::OS)
+}
+
+/// The architecture of the target, as in `cfg(target_arch)` (e.g., `aarch64`), from
+/// `CARGO_CFG_TARGET_ARCH` in a build script.
+pub(crate) fn arch() -> String {
+    cargo_cfg("CARGO_CFG_TARGET_ARCH", env::consts::ARCH)
+}
+
+/// Check whether the target can load dynamic libraries at all; `WebAssembly` (e.g.,
+/// `wasm32-wasip1`) and bare-metal targets cannot, so OpenVINO is of no use there.
+pub(crate) fn supports_dynamic_libraries(arch: &str, os: &str) -> bool {
+    !(arch.starts_with("wasm") || os == "none")
+}
+
+/// Read a `cfg` value Cargo passes to build scripts, falling back to the `host` value.
+fn cargo_cfg(name: &str, host: &str) -> String {
+    finder::var(name).unwrap_or_else(|| host.to_string())
+}
+
+/// The prefix and suffix of the target's file name for a dynamic library, e.g., `lib` and `.so`.
+pub(crate) fn dynamic_library_affixes() -> (&'static str, &'static str) {
+    match os() {
+        TargetOs::Windows => ("", ".dll"),
+        TargetOs::MacOs => ("lib", ".dylib"),
+        TargetOs::Linux | TargetOs::Other => ("lib", ".so"),
+    }
+}
+
+/// The Debian multiarch tuple of the target, e.g., `aarch64-linux-gnu`, which names the system
+/// library directories (e.g., `/usr/lib/aarch64-linux-gnu`).
+pub(crate) fn multiarch_tuple(arch: &str) -> String {
+    match arch {
+        "x86" => "i386-linux-gnu".to_string(),
+        "arm" => "arm-linux-gnueabihf".to_string(),
+        arch => format!("{arch}-linux-gnu"),
+    }
+}
+
+/// The names OpenVINO's archives may give the target's architecture in their library directories
+/// (e.g., `runtime/lib/intel64`), most likely first: the comma-separated names of
+/// `OPENVINO_ARCHIVE_ARCH` if set, otherwise the known name for the target followed by its aliases
+/// (e.g., `arm64` and `aarch64`, which name the same architecture in different archives).
+pub(crate) fn archive_arches() -> Vec<String> {
+    if let Some(names) = finder::var(ENV_OPENVINO_ARCHIVE_ARCH) {
+        return names
+            .split(',')
+            .map(str::trim)
+            .filter(|n| !n.is_empty())
+            .map(str::to_string)
+            .collect();
+    }
+    known_archive_arches(os(), &arch())
+        .into_iter()
+        .map(str::to_string)
+        .collect()
+}
+
+/// List the names of `arch` in the archives of `os`, most likely first; see [`archive_arches`].
+pub(crate) fn known_archive_arches(os: TargetOs, arch: &str) -> Vec<&str> {
+    match (os, arch) {
+        (_, "x86_64") => vec!["intel64", "x86_64"],
+        (TargetOs::MacOs, "aarch64") => vec!["arm64", "aarch64"],
+        (_, "aarch64") => vec!["aarch64", "arm64"],
+        (_, "arm") => vec!["armv7l", "arm"],
+        (_, arch) => vec![arch],
+    }
+}
+
+/// Locate a fixed system path (e.g., `/usr/lib64`) in the target's root filesystem: below
+/// `OPENVINO_SYSROOT` when it is set (e.g., to a Yocto sysroot when cross-compiling), otherwise
+/// at the path itself.
+pub(crate) fn in_sysroot(path: &str) -> PathBuf {
+    match finder::var_os(ENV_OPENVINO_SYSROOT) {
+        Some(sysroot) => rebase(Path::new(&sysroot), path),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Append the absolute `path` to `root`, dropping its root (e.g., `/` or `C:\`).
+fn rebase(root: &Path, path: &str) -> PathBuf {
+    let relative: PathBuf = Path::new(path)
+        .components()
+        .filter(|c| matches!(c, std::path::Component::Normal(_)))
+        .collect();
+    root.join(relative)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn target_layouts() {
+        assert_eq!(multiarch_tuple("aarch64"), "aarch64-linux-gnu");
+        assert_eq!(multiarch_tuple("arm"), "arm-linux-gnueabihf");
+        assert_eq!(
+            known_archive_arches(TargetOs::Linux, "x86_64"),
+            ["intel64", "x86_64"]
+        );
+        assert_eq!(
+            known_archive_arches(TargetOs::Linux, "aarch64"),
+            ["aarch64", "arm64"]
+        );
+        assert_eq!(
+            known_archive_arches(TargetOs::MacOs, "aarch64"),
+            ["arm64", "aarch64"]
+        );
+        assert_eq!(
+            known_archive_arches(TargetOs::Linux, "riscv64"),
+            ["riscv64"]
+        );
+    }
+
+    #[test]
+    fn dynamic_library_support() {
+        assert!(supports_dynamic_libraries("x86_64", "linux"));
+        assert!(supports_dynamic_libraries("aarch64", "macos"));
+        assert!(!supports_dynamic_libraries("wasm32", "wasi"));
+        assert!(!supports_dynamic_libraries("wasm32", "unknown"));
+        assert!(!supports_dynamic_libraries("arm", "none"));
+    }
+
+    #[test]
+    fn sysroot_paths() {
+        assert_eq!(
+            rebase(Path::new("/build/sysroot"), "/usr/lib/aarch64-linux-gnu"),
+            PathBuf::from("/build/sysroot/usr/lib/aarch64-linux-gnu")
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Determine the OpenVINO version of a discovered library and match it against a constraint (e.g.,
+//! `>=2025.1`); see [`crate::find_version`].
+
+pub use semver::{Version, VersionReq};
+use std::fs;
+use std::path::Path;
+
+/// Determine the OpenVINO version of the library at `path`, if the installation records it where
+/// this crate can see it: in the name of the file the path resolves to (e.g.,
+/// `libopenvino_c.so.2025.1.0`), in the name of one of its directories (e.g., `openvino_2025.1.0`,
+/// `Cellar/openvino/2025.1.0`), or, for a `pip`-installed package, in the package's
+/// `openvino-<version>.dist-info` directory.
+pub fn library_version(path: &Path) -> Option<Version> {
+    let resolved = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    for dir in resolved.ancestors() {
+        let Some(name) = dir.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if let Some(version) = scan(name) {
+            return Some(version);
+        }
+        if name == "openvino" {
+            if let Some(version) = dir.parent().and_then(dist_info_version) {
+                return Some(version);
+            }
+        }
+    }
+    None
+}
+
+/// Find the first version in `text`, i.e., a year followed by at least one more numeric component
+/// (e.g., the `2024.6.0` in `openvino_toolkit_ubuntu22_2024.6.0.17404.4c0f47d2335_x86_64`); any
+/// further components (e.g., a build number) are ignored.
+pub(crate) fn scan(text: &str) -> Option<Version> {
+    let bytes = text.as_bytes();
+    for start in 0..bytes.len() {
+        let at_boundary = start == 0 || !bytes[start - 1].is_ascii_alphanumeric();
+        if !at_boundary || !bytes[start..].starts_with(b"20") {
+            continue;
+        }
+        let components: Vec<u64> = text[start..]
+            .split('.')
+            .map_while(|c| {
+                let digits = c.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(c.len());
+                c[..digits].parse().ok()
+            })
+            .take(3)
+            .collect();
+        let year = text[start..].split('.').next().unwrap_or_default();
+        if components.len() >= 2 && year.len() == 4 {
+            return Some(Version::new(
+                components[0],
+                components[1],
+                components.get(2).copied().unwrap_or_default(),
+            ));
+        }
+    }
+    None
+}
+
+/// Read the version of the `openvino` package installed in `site_packages`.
+fn dist_info_version(site_packages: &Path) -> Option<Version> {
+    fs::read_dir(site_packages)
+        .ok()?
+        .filter_map(|e| e.ok()?.file_name().into_string().ok())
+        .filter_map(|n| {
+            let version = n.strip_prefix("openvino-")?.strip_suffix(".dist-info")?;
+            scan(version)
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn matches(req: &str, version: &str) -> bool {
+        VersionReq::parse(req)
+            .unwrap()
+            .matches(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn parse_requirements() {
+        let req: VersionReq = ">= 2025.1, <2026".parse().unwrap();
+        assert_eq!(req.to_string(), ">=2025.1, <2026");
+        assert_eq!("2024".parse::<VersionReq>().unwrap().to_string(), "^2024");
+        assert!("".parse::<VersionReq>().is_err());
+        assert!(">=2025.1.0.0".parse::<VersionReq>().is_err());
+        assert!("latest".parse::<VersionReq>().is_err());
+        assert!("2025.1".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn match_requirements() {
+        assert!(matches(">=2025.1", "2025.1.0"));
+        assert!(matches(">=2025.1", "2026.0.0"));
+        assert!(!matches(">=2025.1", "2024.6.0"));
+        assert!(matches(">=2025.1, <2026", "2025.4.1"));
+        assert!(!matches(">=2025.1, <2026", "2026.0.0"));
+        assert!(!matches(">2025.1", "2025.1.3"));
+        assert!(matches("<=2025.1", "2025.1.3"));
+        assert!(matches("=2024.6", "2024.6.1"));
+        assert!(matches("~2024.6.1", "2024.6.2"));
+        assert!(!matches("~2024.6.1", "2024.7.0"));
+        assert!(matches("2024.2", "2024.6.0"));
+        assert!(!matches("2024.2", "2024.1.0"));
+    }
+
+    #[test]
+    fn scan_versions() {
+        assert_eq!(
+            scan("libopenvino_c.so.2025.1.0"),
+            Some(Version::new(2025, 1, 0))
+        );
+        assert_eq!(
+            scan("libopenvino_c.2024.6.0.dylib"),
+            Some(Version::new(2024, 6, 0))
+        );
+        assert_eq!(
+            scan("openvino_toolkit_ubuntu22_2024.6.0.17404.4c0f47d2335_x86_64"),
+            Some(Version::new(2024, 6, 0))
+        );
+        assert_eq!(scan("2024.6.0_1"), Some(Version::new(2024, 6, 0)));
+        assert_eq!(scan("libopenvino_c.so.2510"), None);
+        assert_eq!(scan("python3.12"), None);
+    }
+
+    #[test]
+    fn library_versions() {
+        let dir = std::env::temp_dir().join("openvino-finder-library-versions");
+        let _ = fs::remove_dir_all(&dir);
+        let libs = dir.join("site-packages/openvino/libs");
+        fs::create_dir_all(&libs).unwrap();
+        fs::create_dir_all(dir.join("site-packages/openvino-2025.1.0.dist-info")).unwrap();
+        let library = libs.join("libopenvino_c.so");
+        fs::write(&library, "").unwrap();
+        assert_eq!(library_version(&library), Some(Version::new(2025, 1, 0)));
+
+        let keg = dir.join("Cellar/openvino/2024.6.0_1/lib");
+        fs::create_dir_all(&keg).unwrap();
+        assert_eq!(
+            library_version(&keg.join("libopenvino_c.dylib")),
+            Some(Version::new(2024, 6, 0))
+        );
+        assert_eq!(library_version(&PathBuf::from("/usr/lib/libfoo.so")), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,37 @@
+//! A probe for the runtime-linking tests: this attempts to create a [`Core`] in a fresh process
+//! (the loaded library is global state) and prints what happened in a line-oriented format:
+//!
+//! ```text
+//! found: <the path openvino-finder settled on, or "none">
+//! result: <"ok" or the `Debug` representation of the error>
+//! message: <the `Display` representation of the error, if any>
+//! ```
+//!
+//! Usage: `openvino-linking-probe [new | new-for-version <constraint>]`.
+
+use openvino::Core;
+use openvino_finder::Linking;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let found = openvino_finder::find("openvino_c", Linking::Dynamic);
+    println!(
+        "found: {}",
+        found.map_or("none".to_string(), |p| p.display().to_string())
+    );
+    let result = match args.next().as_deref() {
+        None | Some("new") => Core::new(),
+        // SAFETY: no OpenVINO object has been created yet in this process.
+        Some("new-for-version") => unsafe {
+            Core::new_for_version(&args.next().expect("a version constraint"))
+        },
+        Some(other) => panic!("unknown probe command: {other}"),
+    };
+    match result {
+        Ok(_) => println!("result: ok"),
+        Err(e) => {
+            println!("result: {e:?}");
+            println!("message: {e}");
+        }
+    }
+}
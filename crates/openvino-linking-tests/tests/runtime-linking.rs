@@ -0,0 +1,163 @@
+//! These tests exercise the runtime-linking code paths: each runs the `openvino-linking-probe`
+//! binary in a separate process, with the library discovery environment variables unset, pointing
+//! to the wrong place, or left as-is, and checks the resulting errors.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{env, fs};
+
+/// The environment variables `openvino-finder` consults which the tests remove from the probe's
+/// environment unless explicitly set: all but `PATH`, which the probe needs to run.
+fn discovery_vars() -> impl Iterator<Item = &'static str> {
+    openvino_finder::ENVIRONMENT_VARIABLES
+        .iter()
+        .copied()
+        .filter(|var| *var != "PATH")
+}
+
+/// The parsed output of the probe binary.
+#[derive(Debug)]
+struct Probe {
+    found: Option<PathBuf>,
+    result: String,
+    message: String,
+}
+
+impl Probe {
+    /// Run the probe with `args`, a clean discovery environment and the `vars` set.
+    fn run(args: &[&str], vars: &[(&str, &OsStr)]) -> Self {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_openvino-linking-probe"));
+        command.args(args);
+        for var in discovery_vars() {
+            command.env_remove(var);
+        }
+        command.envs(vars.iter().copied());
+        Self::parse(command)
+    }
+
+    /// Run the probe with `args` in the unmodified environment of this test.
+    fn run_in_current_environment(args: &[&str]) -> Self {
+        let mut command = Command::new(env!("CARGO_BIN_EXE_openvino-linking-probe"));
+        command.args(args);
+        Self::parse(command)
+    }
+
+    fn parse(mut command: Command) -> Self {
+        let output = command.output().expect("the probe to run");
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(
+            output.status.success(),
+            "probe failed: {stdout}{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let field = |name: &str| {
+            stdout
+                .lines()
+                .find_map(|l| l.strip_prefix(name))
+                .unwrap_or_default()
+                .to_string()
+        };
+        let found = field("found: ");
+        Self {
+            found: (found != "none").then(|| PathBuf::from(found)),
+            result: field("result: "),
+            message: field("message: "),
+        }
+    }
+}
+
+/// Create a fake OpenVINO installation in a temporary directory, containing an `openvino_c` library
+/// file that cannot be loaded.
+fn fake_installation(name: &str) -> PathBuf {
+    let install_dir = env::temp_dir().join(format!("openvino-linking-tests-{name}"));
+    let lib_dir = install_dir.join("runtime/lib/intel64");
+    fs::create_dir_all(&lib_dir).unwrap();
+    fs::write(lib_dir.join(library_file_name()), b"not a shared library").unwrap();
+    install_dir
+}
+
+fn library_file_name() -> String {
+    format!(
+        "{}openvino_c{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    )
+}
+
+#[test]
+fn unset_environment() {
+    let probe = Probe::run(&[], &[]);
+    if let Some(path) = probe.found {
+        // A system-wide installation (e.g., `/usr/lib`) does not need any environment variables.
+        eprintln!("found a system-wide installation: {}", path.display());
+        assert_eq!(probe.result, "ok");
+    } else {
+        assert_eq!(
+            probe.result,
+            "Loading(SystemFailure(\"Unable to find the `openvino_c` library to load\"))"
+        );
+        assert!(probe
+            .message
+            .starts_with("library loading error: system failed to load shared libraries"));
+    }
+}
+
+#[test]
+fn nonexistent_install_dir() {
+    let install_dir = env::temp_dir().join("openvino-linking-tests-nonexistent");
+    let probe = Probe::run(&[], &[("OPENVINO_INSTALL_DIR", install_dir.as_os_str())]);
+    if let Some(path) = probe.found {
+        assert!(!path.starts_with(&install_dir));
+    } else {
+        assert!(probe.result.starts_with("Loading(SystemFailure("));
+    }
+}
+
+#[test]
+fn invalid_library_file() {
+    let install_dir = fake_installation("invalid-library");
+    let probe = Probe::run(&[], &[("OPENVINO_INSTALL_DIR", install_dir.as_os_str())]);
+    let expected = install_dir
+        .join("runtime/lib/intel64")
+        .join(library_file_name());
+    assert_eq!(probe.found.as_deref(), Some(expected.as_path()));
+    assert!(probe.result.starts_with("Loading(SystemFailure("));
+    assert!(probe.message.contains("could not be opened"));
+    assert!(probe.message.contains(&expected.display().to_string()));
+}
+
+#[test]
+fn invalid_version_constraint() {
+    let probe = Probe::run(&["new-for-version", "not a version"], &[]);
+    assert!(probe
+        .result
+        .starts_with("Loading(InvalidVersionConstraint("));
+    assert!(probe.message.contains("invalid version constraint"));
+}
+
+#[test]
+fn no_matching_version() {
+    let install_dir = fake_installation("no-matching-version");
+    let probe = Probe::run(
+        &["new-for-version", "<2000"],
+        &[("OPENVINO_INSTALL_DIR", install_dir.as_os_str())],
+    );
+    assert!(probe.result.starts_with("Loading(NoMatchingVersion("));
+    // The unloadable library is listed among the rejected ones.
+    assert!(probe.message.contains(&display(&install_dir)));
+}
+
+#[test]
+fn current_environment() {
+    // This only checks something when the environment is set up to find OpenVINO (e.g., in CI).
+    let probe = Probe::run_in_current_environment(&[]);
+    match probe.found {
+        Some(_) => assert_eq!(probe.result, "ok", "{}", probe.message),
+        None => eprintln!("no OpenVINO installation found; skipping"),
+    }
+}
+
+fn display(path: &Path) -> String {
+    path.display().to_string()
+}
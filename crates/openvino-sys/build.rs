@@ -16,6 +16,16 @@ const ENV_OPENVINO_LIB_PATH: &str = "OPENVINO_LIB_PATH";
 // `openvino-finder` for how this is used to find library paths.
 const ENV_OPENVINO_BUILD_DIR: &str = "OPENVINO_BUILD_DIR";
 
+// User-specified environment variables that bypass `openvino-finder` entirely (e.g., for Nix, Bazel
+// or vendored setups with unusual layouts): `OPENVINO_C_LIB_FILE` points to the exact `openvino_c`
+// library file to link against and `OPENVINO_LIB_DIRS` lists the directories (separated as in the
+// OS' `PATH`) to add to the library search path.
+const ENV_OPENVINO_C_LIB_FILE: &str = "OPENVINO_C_LIB_FILE";
+const ENV_OPENVINO_LIB_DIRS: &str = "OPENVINO_LIB_DIRS";
+
+// The name of the machine-readable report written to `OUT_DIR` when the libraries cannot be found.
+const REPORT_FILE_NAME: &str = "openvino-link-report.json";
+
 fn main() {
     // This allows us to log the `openvino-finder` search paths, for troubleshooting.
     let _ = env_logger::try_init();
@@ -45,29 +55,55 @@ fn main() {
         Linking::Dynamic
     };
 
+    // Targets without dynamic libraries (e.g., `wasm32-wasip1`) cannot use OpenVINO at all; say so
+    // rather than searching for libraries that could never be loaded.
+    if let Err(e) = openvino_finder::check_target() {
+        assert!(linking == Linking::None, "{e}");
+        println!("cargo:warning=openvino-sys: {e}");
+        record_library_path(PathBuf::new());
+        return;
+    }
+
+    // If the user specified exactly what to link against, skip the search entirely.
+    println!("cargo:rerun-if-env-changed={ENV_OPENVINO_C_LIB_FILE}");
+    println!("cargo:rerun-if-env-changed={ENV_OPENVINO_LIB_DIRS}");
+    let c_lib_file = env::var_os(ENV_OPENVINO_C_LIB_FILE).map(PathBuf::from);
+    let lib_dirs = env::var_os(ENV_OPENVINO_LIB_DIRS).map(|p| env::split_paths(&p).collect());
+    if c_lib_file.is_some() || lib_dirs.is_some() {
+        link_user_specified_libraries(&linking, c_lib_file, lib_dirs.unwrap_or_default());
+        return;
+    }
+
     // Find the OpenVINO libraries to link to, either from a pre-installed location or by building
     // from source. We always look for the dynamic libraries here.
     let link_kind = openvino_finder::Linking::Dynamic;
+    if openvino_finder::strict_mode() {
+        // In strict mode, refuse to continue if several OpenVINO installations are reachable
+        // (e.g., a `pip` install and an extracted archive); print all of them instead.
+        for library in LIBRARIES {
+            if let Err(e) = openvino_finder::find_unique(library, link_kind) {
+                panic!("{e}");
+            }
+        }
+    }
     let (c_api_library_path, library_search_paths) = if linking == Linking::None {
         // Why try to find the library if we're not going to link against it? Well, this is for the
-        // helpful Cargo warnings that get printed below if we can't find the library on the system.
-        (openvino_finder::find("openvino_c", link_kind), vec![])
-    } else if let Some(path) = openvino_finder::find("openvino_c", link_kind) {
-        (Some(path), find_libraries_in_existing_installation())
+        // helpful Cargo warning that gets printed here if we can't find the library on the system.
+        let search = openvino_finder::find_with_report("openvino_c", link_kind);
+        let found = search.found.clone();
+        if found.is_none() {
+            let report = openvino_finder::LinkReport::from_searches(vec![search]);
+            println!("cargo:warning=openvino-sys cannot find the `openvino_c` library ({}); proceeding with an empty value of {}: users must specify this location at runtime, e.g. `Core::new(Some(...))`.", describe_report(&report), ENV_OPENVINO_LIB_PATH);
+        }
+        (found, vec![])
     } else {
-        panic!("Unable to find an OpenVINO installation on your system; build with runtime linking using `--features runtime-linking` or build from source with `OPENVINO_BUILD_DIR`.")
+        find_libraries_in_existing_installation()
     };
 
     // Capture the path to the library we are using. The reason we do this is to provide a mechanism
     // for finding the `plugins.xml` file at runtime (usually it is found in the same directory as
     // the inference engine libraries).
-    if let Some(path) = c_api_library_path {
-        record_library_path(path);
-    } else {
-        println!("cargo:warning=openvino-sys cannot find the `openvino_c` library in any of the library search paths: {:?}", &library_search_paths);
-        println!("cargo:warning=Proceeding with an empty value of {}; users must specify this location at runtime, e.g. `Core::new(Some(...))`.", ENV_OPENVINO_LIB_PATH);
-        record_library_path(PathBuf::new());
-    }
+    record_library_path(c_api_library_path.unwrap_or_default());
 
     // If necessary, dynamically link the necessary OpenVINO libraries.
     if linking == Linking::Dynamic {
@@ -81,6 +117,59 @@ fn main() {
     }
 }
 
+/// Link against the libraries specified by `OPENVINO_C_LIB_FILE` and `OPENVINO_LIB_DIRS`, without
+/// using `openvino-finder`. When `c_lib_file` is set, that exact file is linked (whatever its name);
+/// otherwise, the libraries are linked by name from the `lib_dirs`.
+fn link_user_specified_libraries(
+    linking: &Linking,
+    c_lib_file: Option<PathBuf>,
+    mut lib_dirs: Vec<PathBuf>,
+) {
+    let c_lib_name =
+        openvino_finder::library_filename("openvino_c", openvino_finder::Linking::Dynamic);
+    let c_lib_path = if let Some(file) = &c_lib_file {
+        assert!(
+            file.is_file(),
+            "{} does not point to a file: {}",
+            ENV_OPENVINO_C_LIB_FILE,
+            file.display()
+        );
+        let dir = file.parent().unwrap().to_owned();
+        if !lib_dirs.contains(&dir) {
+            lib_dirs.push(dir);
+        }
+        file.clone()
+    } else {
+        // Without an exact file, record the first directory that contains the library (for finding
+        // `plugins.xml` at runtime).
+        let dir = lib_dirs
+            .iter()
+            .find(|d| d.join(&c_lib_name).is_file())
+            .or(lib_dirs.first())
+            .expect("at least one directory in OPENVINO_LIB_DIRS");
+        dir.join(&c_lib_name)
+    };
+    println!(
+        "cargo:warning=Using user-specified library to link against: {}",
+        c_lib_path.display()
+    );
+    record_library_path(c_lib_path);
+
+    if *linking == Linking::Dynamic {
+        lib_dirs.iter().for_each(add_library_search_path);
+        add_dynamically_linked_library("openvino");
+        match c_lib_file.as_ref().and_then(|f| f.file_name()) {
+            // Link the exact file, even if its name does not follow the `lib<name>.so` convention
+            // (e.g., `libopenvino_c.so.2024.6.0`).
+            Some(file_name) => println!(
+                "cargo:rustc-link-lib=dylib:+verbatim={}",
+                file_name.to_string_lossy()
+            ),
+            None => add_dynamically_linked_library("openvino_c"),
+        }
+    }
+}
+
 /// Enumerate the possible linking states for this build script:
 /// - either we don't want to link to anything during compile time
 /// - or we want to link to the OpenVINO libraries dynamically.
@@ -131,17 +220,19 @@ fn add_dynamically_linked_library(library: &str) {
     println!("cargo:rustc-link-lib=dylib={}", library);
 }
 
-/// Find all of the necessary libraries to link using the `openvino_finder`. This will return the
-/// directories that should contain the necessary libraries to link to.
+/// Find all of the necessary libraries to link using the `openvino_finder`, stopping the build if
+/// any is missing. This returns the `openvino_c` library to load at runtime and the directories
+/// that contain the necessary libraries to link to.
 ///
 /// It would be preferable to use pkg-config here to retrieve the libraries when they are installed
 /// system-wide but there are issues:
 ///  - OpenVINO does not install itself as a system library, e.g., through `ldconfig`;
 ///  - OpenVINO relies on a `plugins.xml` file for finding target-specific libraries and it is
 ///    unclear how we would discover this in a system-install scenario.
-fn find_libraries_in_existing_installation() -> Vec<PathBuf> {
+fn find_libraries_in_existing_installation() -> (Option<PathBuf>, Vec<PathBuf>) {
     let mut dirs = vec![];
-    let link_kind = if cfg!(target_os = "windows") {
+    let windows = env::var("CARGO_CFG_TARGET_OS").is_ok_and(|os| os == "windows");
+    let link_kind = if windows {
         // Retrieve `*.lib` files on Windows. This is important because, when linking, Windows
         // expects `*.lib` files. See
         // https://learn.microsoft.com/en-us/windows/win32/dlls/dynamic-link-library-creation#creating-an-import-library.
@@ -149,22 +240,63 @@ fn find_libraries_in_existing_installation() -> Vec<PathBuf> {
     } else {
         openvino_finder::Linking::Dynamic
     };
-    for library in LIBRARIES {
-        if let Some(path) = openvino_finder::find(library, link_kind) {
-            println!(
-                "cargo:warning=Found library to link against: {}",
-                path.display()
-            );
-            let dir = path.parent().unwrap().to_owned();
-            if !dirs.iter().any(|d| d == &dir) {
-                dirs.push(dir);
-            }
-        } else {
-            panic!(
-                "Unable to find an existing installation of library: {}",
-                library
-            );
+    let mut report = openvino_finder::LinkReport::new(LIBRARIES, link_kind);
+    let c_api_library_path = if windows {
+        // The `*.lib` files are only for the linker; the `*.dll` is what is loaded at runtime.
+        let search =
+            openvino_finder::find_with_report("openvino_c", openvino_finder::Linking::Dynamic);
+        let found = search.found.clone();
+        if found.is_none() {
+            report.libraries.push(search);
         }
+        found
+    } else {
+        report
+            .libraries
+            .iter()
+            .find(|l| l.library == "openvino_c")
+            .and_then(|l| l.found.clone())
+    };
+    if !report.is_success() {
+        fail_with_report(&report);
+    }
+    for path in report.libraries.iter().filter_map(|l| l.found.as_ref()) {
+        println!(
+            "cargo:warning=Found library to link against: {}",
+            path.display()
+        );
+        let dir = path.parent().unwrap().to_owned();
+        if !dirs.iter().any(|d| d == &dir) {
+            dirs.push(dir);
+        }
+    }
+    (c_api_library_path, dirs)
+}
+
+/// Stop the build, explaining why in a single line; see [`describe_report`].
+fn fail_with_report(report: &openvino_finder::LinkReport) -> ! {
+    println!(
+        "cargo:warning=openvino-sys cannot link against OpenVINO ({})",
+        describe_report(report)
+    );
+    std::process::exit(1)
+}
+
+/// Write a machine-readable report of the library search to `OUT_DIR` and describe it briefly:
+/// which libraries are missing and where the report, with every path probed and the environment
+/// variables to set, is.
+fn describe_report(report: &openvino_finder::LinkReport) -> String {
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("Cargo to set OUT_DIR"));
+    let report_path = out_dir.join(REPORT_FILE_NAME);
+    let missing = report.missing().join(", ");
+    match std::fs::write(&report_path, report.to_json()) {
+        Ok(()) => format!(
+            "unable to find {missing}; see the report of the search: {}",
+            report_path.display()
+        ),
+        Err(e) => format!(
+            "unable to find {missing}; failed to write a report of the search to {}: {e}",
+            report_path.display()
+        ),
     }
-    dirs
 }
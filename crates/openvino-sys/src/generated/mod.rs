@@ -1,4 +1,6 @@
 mod functions;
 mod types;
+mod version;
 pub use functions::*;
 pub use types::*;
+pub(crate) use version::*;
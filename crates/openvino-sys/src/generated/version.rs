@@ -0,0 +1,2 @@
+/// The release of the OpenVINO C API headers these bindings were generated from.
+pub(crate) const BINDING_VERSION: &str = "2025.0";
@@ -38,9 +38,80 @@ mod linking;
 mod generated;
 pub use generated::*;
 
+/// Replace the OpenVINO functions with test doubles on the current thread or, for code which calls
+/// them from threads of its own, on all threads (only available with the `mock` feature), so that
+/// code built on these bindings (e.g., error mapping or property parsing) can be unit-tested
+/// without an OpenVINO installation.
+///
+/// ```
+/// use openvino_sys::{mock, ov_status_e, ov_version_t};
+/// unsafe extern "C" fn get_version(version: *mut ov_version_t) -> ov_status_e {
+///     ov_status_e::NOT_IMPLEMENTED
+/// }
+/// let functions = mock::Functions {
+///     ov_get_openvino_version: Some(get_version),
+///     ..Default::default()
+/// };
+/// mock::with_functions(functions, || {
+///     openvino_sys::library::load().unwrap();
+///     let mut version = std::mem::MaybeUninit::uninit();
+///     let code = unsafe { openvino_sys::ov_get_openvino_version(version.as_mut_ptr()) };
+///     assert_eq!(code, ov_status_e::NOT_IMPLEMENTED);
+/// });
+/// ```
+#[cfg(feature = "mock")]
+pub mod mock {
+    pub use super::generated::Functions;
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    /// Run `f` with the functions of `functions` replacing those of the loaded library on the
+    /// current thread; meanwhile, [`crate::library::load`] succeeds without loading anything.
+    /// Calling a function left as `None` panics. The previous test doubles, if any, are restored
+    /// afterwards, even if `f` panics.
+    pub fn with_functions<T>(functions: Functions, f: impl FnOnce() -> T) -> T {
+        struct Restore(Option<Arc<Functions>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                super::generated::replace_mock(self.0.take());
+            }
+        }
+        let _restore = Restore(super::generated::replace_mock(Some(Arc::new(functions))));
+        f()
+    }
+
+    /// Run `f` with the functions of `functions` replacing those of the loaded library on all of
+    /// the threads without test doubles of their own (see [`with_functions`]), e.g., on the threads
+    /// which the code under test spawns. Other tests running meanwhile see them too, unless they
+    /// use [`with_functions`]; calls to this function wait for each other. The previous test
+    /// doubles, if any, are restored afterwards, even if `f` panics.
+    pub fn with_global_functions<T>(functions: Functions, f: impl FnOnce() -> T) -> T {
+        static LOCK: Mutex<()> = Mutex::new(());
+        struct Restore(Option<Arc<Functions>>);
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                super::generated::replace_global_mock(self.0.take());
+            }
+        }
+        let _lock = LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        let _restore = Restore(super::generated::replace_global_mock(Some(Arc::new(
+            functions,
+        ))));
+        f()
+    }
+
+    /// Check whether test doubles replace the OpenVINO functions on the current thread; see
+    /// [`with_functions`] and [`with_global_functions`].
+    pub fn is_active() -> bool {
+        super::generated::is_mocked()
+    }
+}
+
 /// Contains extra utilities for finding and loading the OpenVINO shared libraries.
 pub mod library {
+    use super::generated::BINDING_VERSION;
     use std::path::PathBuf;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
 
     /// When compiled with the `runtime-linking` feature, load the function definitions from a
     /// shared library; with the `dynamic-linking` feature, this function does nothing since the
@@ -52,15 +123,55 @@ pub mod library {
     /// cannot discover the library on the current system. This may also fail if we link to a
     /// version of OpenVINO that is too old for these Rust bindings: the upstream library changed
     /// the `ov_element_type_e` enum in a backwards-incompatible way in v2024.2, meaning users would
-    /// unintentionally use the wrong type when creating tensors (see [#143]).
+    /// unintentionally use the wrong type when creating tensors (see [#143]). A version newer than
+    /// [`binding_version`] is accepted, with a warning if so configured (see
+    /// [`set_newer_version_policy`]).
     ///
     /// [#143]: https://github.com/intel/openvino-rs/issues/143
     pub fn load() -> Result<(), String> {
+        #[cfg(feature = "mock")]
+        if super::mock::is_active() {
+            return Ok(());
+        }
         super::generated::load()?;
+        check_version()
+    }
+
+    /// Load the function definitions from the shared library at `path`, replacing any library
+    /// loaded previously (only available with the `runtime-linking` feature). This allows selecting
+    /// between several installed versions of OpenVINO. Objects created using a previously-loaded
+    /// library must not be used once another library is loaded.
+    ///
+    /// # Errors
+    ///
+    /// This may fail if the library cannot be opened or if it is too old for these Rust bindings
+    /// (see [`load`]).
+    #[cfg(feature = "runtime-linking")]
+    pub fn load_from(path: impl Into<PathBuf>) -> Result<(), String> {
+        super::generated::load_from(path.into())?;
+        check_version()
+    }
+
+    /// Check the version of the loaded library: fail if it is too old for these Rust bindings and,
+    /// depending on the [`NewerVersionPolicy`], warn if it is newer.
+    fn check_version() -> Result<(), String> {
         let version = get_version()?;
         if is_pre_2024_2_version(&version) {
             return Err(format!("OpenVINO version is too old (see https://github.com/intel/openvino-rs/issues/143): {version}"));
         }
+        if newer_version_policy() == NewerVersionPolicy::Warn && is_newer_than_bindings(&version) {
+            let mut warned = WARNED_VERSION
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            if warned.as_deref() != Some(version.as_str()) {
+                log::warn!(
+                    "OpenVINO version {version} is newer than the version these Rust bindings \
+                     were generated from ({BINDING_VERSION}); newer functionality is unavailable \
+                     and changed C APIs may misbehave"
+                );
+                *warned = Some(version);
+            }
+        }
         Ok(())
     }
 
@@ -91,10 +202,78 @@ pub mod library {
         year < 2024 || (year == 2024 && minor < 2)
     }
 
-    /// Return the location of the shared library `openvino-sys` will link to. If compiled with
-    /// runtime linking, this will attempt to discover the location of a `openvino_c` shared library
-    /// on the system. Otherwise (with dynamic linking or compilation from source), this relies on a
-    /// static path discovered at build time.
+    /// Parse the version string and return true if its release (e.g., `2025.1` for
+    /// `2025.1.0-18503-...`) is newer than [`BINDING_VERSION`]; unparseable versions (e.g., of
+    /// custom builds) are not considered newer.
+    fn is_newer_than_bindings(version: &str) -> bool {
+        fn release(version: &str) -> Option<(usize, usize)> {
+            let mut parts = version.split(['.', '-']);
+            Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+        }
+        matches!(
+            (release(version), release(BINDING_VERSION)),
+            (Some(runtime), Some(bindings)) if runtime > bindings
+        )
+    }
+
+    /// Return the release of the OpenVINO C API headers these bindings were generated from (e.g.,
+    /// `2025.0`). A library of an older release may lack some functions; one of a newer release
+    /// may have functionality these bindings cannot reach (see [`NewerVersionPolicy`]).
+    ///
+    /// ```
+    /// let version = openvino_sys::library::binding_version();
+    /// assert!(version.starts_with("20"));
+    /// ```
+    pub fn binding_version() -> &'static str {
+        BINDING_VERSION
+    }
+
+    /// What [`load`] and [`load_from`] do when the library is newer than [`binding_version`]; see
+    /// [`set_newer_version_policy`].
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub enum NewerVersionPolicy {
+        /// Proceed silently (the default): OpenVINO's C API is mostly backwards-compatible.
+        #[default]
+        Allow,
+        /// Proceed, but log a warning (with the `log` crate) with both versions, once per library
+        /// version, e.g., to notice a deployment running ahead of the bindings.
+        Warn,
+    }
+
+    static WARN_IF_NEWER: AtomicBool = AtomicBool::new(false);
+    static WARNED_VERSION: Mutex<Option<String>> = Mutex::new(None);
+
+    /// Set what subsequent calls to [`load`] and [`load_from`] do when the library is newer than
+    /// [`binding_version`], for the whole process.
+    pub fn set_newer_version_policy(policy: NewerVersionPolicy) {
+        WARN_IF_NEWER.store(policy == NewerVersionPolicy::Warn, Ordering::Relaxed);
+    }
+
+    /// Return the current [`NewerVersionPolicy`].
+    pub fn newer_version_policy() -> NewerVersionPolicy {
+        if WARN_IF_NEWER.load(Ordering::Relaxed) {
+            NewerVersionPolicy::Warn
+        } else {
+            NewerVersionPolicy::Allow
+        }
+    }
+
+    /// Return the location of the `openvino_c` shared library in use by this process: with runtime
+    /// linking, the library loaded by [`load`] or [`load_from`]; otherwise (with dynamic linking),
+    /// the library the system loader picked, which may differ from the one found at build time
+    /// (e.g., through `LD_LIBRARY_PATH`). `None` if no library is loaded or the process cannot
+    /// tell.
+    pub fn loaded() -> Option<PathBuf> {
+        super::generated::loaded_path()
+    }
+
+    /// Return the location of the shared library `openvino-sys` will link to. If a library is
+    /// already loaded (see [`loaded`]), this is its location, so that reloading and finding the
+    /// files beside it (e.g., `plugins.xml`) do not switch to another installation. Otherwise, if
+    /// compiled with runtime linking, this will use an `openvino_c` shared library already loaded
+    /// into the process (e.g., by a host application) or attempt to discover the location of one on
+    /// the system; with dynamic linking or compilation from source, this relies on a static path
+    /// discovered at build time.
     ///
     /// Knowing the location of the OpenVINO libraries is critical to avoid errors, unfortunately.
     /// OpenVINO loads target-specific libraries on demand for performing inference. To do so, it
@@ -104,8 +283,12 @@ pub mod library {
     /// default, the `plugins.xml` file is found in the same directory as the libraries, e.g.
     /// `find().unwrap().parent()`.
     pub fn find() -> Option<PathBuf> {
+        if let Some(path) = loaded() {
+            return Some(path);
+        }
         if cfg!(feature = "runtime-linking") {
-            openvino_finder::find("openvino_c", openvino_finder::Linking::Dynamic)
+            openvino_finder::find_loaded("openvino_c")
+                .or_else(|| openvino_finder::find("openvino_c", openvino_finder::Linking::Dynamic))
         } else {
             Some(PathBuf::from(env!("OPENVINO_LIB_PATH")))
         }
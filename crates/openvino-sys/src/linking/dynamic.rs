@@ -20,6 +20,12 @@ macro_rules! link {
             Ok(())
         }
 
+        /// Return the path from which the system loader loaded the shared library, if the process
+        /// can tell (see [`openvino_finder::find_loaded`]).
+        pub fn loaded_path() -> Option<std::path::PathBuf> {
+            openvino_finder::find_loaded("openvino_c")
+        }
+
         // Re-export all of the shared functions as-is.
         extern "C" {
             $(
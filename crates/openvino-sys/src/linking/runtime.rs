@@ -36,6 +36,10 @@ macro_rules! link {
         // `LIBRARY` holds the shared library reference.
         static LIBRARY: Lazy<RwLock<Option<Arc<SharedLibrary>>>> = Lazy::new(|| RwLock::new(None));
 
+        // `RETIRED` keeps previously-loaded libraries open after `load_from` replaces them, one
+        // per path.
+        static RETIRED: std::sync::Mutex<Vec<Arc<SharedLibrary>>> = std::sync::Mutex::new(Vec::new());
+
         // Helper function for accessing the thread-local version of the library.
         fn with_library<T, F>(f: F) -> Option<T>
         where
@@ -44,13 +48,74 @@ macro_rules! link {
             LIBRARY.read().unwrap().as_ref().map(|library| f(&library))
         }
 
-        // The set of functions loaded dynamically.
-        #[derive(Debug, Default)]
-        pub(crate) struct Functions {
-            $(
-                $(#[doc=$doc])* $(#[cfg($cfg)])*
-                pub $name: Option<unsafe extern fn($($pname: $pty), *) $(-> $ret)*>,
-            )+
+        // The set of functions loaded dynamically; it is public with the `mock` feature, for
+        // building test doubles (see `crate::mock`).
+        mod table {
+            use super::*;
+
+            /// The table of OpenVINO functions, each `None` until loaded (or mocked).
+            #[derive(Clone, Debug, Default)]
+            pub struct Functions {
+                $(
+                    $(#[doc=$doc])* $(#[cfg($cfg)])*
+                    pub $name: Option<unsafe extern fn($($pname: $pty), *) $(-> $ret)*>,
+                )+
+            }
+        }
+        #[cfg(feature = "mock")]
+        pub use table::Functions;
+        #[cfg(not(feature = "mock"))]
+        pub(crate) use table::Functions;
+
+        // `MOCK` holds the test doubles which, when set, replace the functions of `LIBRARY` on
+        // this thread; `GLOBAL_MOCK` those which replace them on the threads without test doubles
+        // of their own (only with the `mock` feature).
+        #[cfg(feature = "mock")]
+        thread_local! {
+            static MOCK: std::cell::RefCell<Option<Arc<Functions>>> =
+                const { std::cell::RefCell::new(None) };
+        }
+        #[cfg(feature = "mock")]
+        static GLOBAL_MOCK: RwLock<Option<Arc<Functions>>> = RwLock::new(None);
+
+        // Replace the test doubles of this thread, returning the previous ones.
+        #[cfg(feature = "mock")]
+        pub(crate) fn replace_mock(functions: Option<Arc<Functions>>) -> Option<Arc<Functions>> {
+            MOCK.with(|mock| mock.replace(functions))
+        }
+
+        // Replace the test doubles of all threads, returning the previous ones.
+        #[cfg(feature = "mock")]
+        pub(crate) fn replace_global_mock(
+            functions: Option<Arc<Functions>>,
+        ) -> Option<Arc<Functions>> {
+            std::mem::replace(&mut *GLOBAL_MOCK.write().unwrap(), functions)
+        }
+
+        // Get the test doubles in use on this thread, if any.
+        #[cfg(feature = "mock")]
+        fn mock() -> Option<Arc<Functions>> {
+            MOCK.with(|mock| mock.borrow().clone())
+                .or_else(|| GLOBAL_MOCK.read().unwrap().clone())
+        }
+
+        // Check whether test doubles replace the loaded functions on this thread.
+        #[cfg(feature = "mock")]
+        pub(crate) fn is_mocked() -> bool {
+            mock().is_some()
+        }
+
+        // Helper function for accessing the functions in use on this thread: the test doubles, if
+        // any, or those of the loaded library.
+        fn with_functions<T, F>(f: F) -> Option<T>
+        where
+            F: FnOnce(&Functions) -> T,
+        {
+            #[cfg(feature = "mock")]
+            if let Some(functions) = mock() {
+                return Some(f(&functions));
+            }
+            with_library(|library| f(&library.functions))
         }
 
         // Provide functions to load each name from the shared library into the `SharedLibrary`
@@ -68,6 +133,12 @@ macro_rules! link {
             )+
         }
 
+        /// Return the path of the shared library currently loaded by [`load`] or [`load_from`],
+        /// if any.
+        pub fn loaded_path() -> Option<PathBuf> {
+            with_library(|library| library.path.clone())
+        }
+
         /// Load all of the function definitions from a shared library.
         ///
         /// # Errors
@@ -79,9 +150,28 @@ macro_rules! link {
                 Some(path) => load_from(path),
             }
         }
-        fn load_from(path: PathBuf) -> Result<(), String> {
-            let library = Arc::new(SharedLibrary::load(path)?);
-            *LIBRARY.write().unwrap() = Some(library);
+        /// Load all of the function definitions from the shared library at `path`, replacing any
+        /// previously-loaded library; this does nothing if the library at `path` is already loaded.
+        /// The previous library is never unloaded: objects created with it may still be alive
+        /// (though they must not be used once another library is loaded). Loading it again later
+        /// reuses it.
+        ///
+        /// # Errors
+        ///
+        /// May fail if the shared library cannot be opened.
+        pub fn load_from(path: PathBuf) -> Result<(), String> {
+            let mut current = LIBRARY.write().unwrap();
+            if current.as_ref().is_some_and(|library| library.path == path) {
+                return Ok(());
+            }
+            let mut retired = RETIRED.lock().unwrap();
+            let library = match retired.iter().position(|library| library.path == path) {
+                Some(index) => retired.swap_remove(index),
+                None => Arc::new(SharedLibrary::load(path)?),
+            };
+            if let Some(previous) = current.replace(library) {
+                retired.push(previous);
+            }
             Ok(())
         }
         impl SharedLibrary {
@@ -108,8 +198,8 @@ macro_rules! link {
             $(#[doc=$doc])* $(#[cfg($cfg)])*
             #[allow(clippy::missing_safety_doc)] // These are bindgen-generated functions.
             pub unsafe fn $name($($pname: $pty), *) $(-> $ret)* {
-                let f = with_library(|l| {
-                    l.functions.$name.expect(concat!(
+                let f = with_functions(|functions| {
+                    functions.$name.expect(concat!(
                         "`openvino_c` function not loaded: `",
                         stringify!($name)
                     ))
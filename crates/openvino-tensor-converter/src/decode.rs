@@ -0,0 +1,245 @@
+//! Decode source images: with `OpenCV` by default or, for the formats `OpenCV` builds often lack
+//! (WebP, AVIF) or decodes at a lower precision (16-bit TIFF, floating-point EXR), with the decoders
+//! of the `image` crate, enabled by the `webp`, `avif`, `tiff` and `exr` features.
+
+use crate::{ConversionError, Range};
+use opencv::core::Mat;
+#[cfg(any(feature = "webp", feature = "avif", feature = "tiff", feature = "exr"))]
+use std::convert::TryFrom;
+use std::path::Path;
+
+/// The image formats decoded without `OpenCV`, when enabled.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Format {
+    WebP,
+    Avif,
+    Tiff,
+    Exr,
+    /// Any other format, left to `OpenCV`.
+    Other,
+}
+
+/// Recognize an image format from the first bytes of a file: WebP files are RIFF containers of
+/// type `WEBP`, AVIF files are ISO-BMFF containers with an `avif` (image) or `avis` (sequence)
+/// brand, and TIFF and EXR files start with their magic numbers.
+pub(crate) fn sniff(header: &[u8]) -> Format {
+    match header.get(..12) {
+        Some([b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P']) => Format::WebP,
+        Some([_, _, _, _, b'f', b't', b'y', b'p', b'a', b'v', b'i', b's' | b'f']) => Format::Avif,
+        _ => match header.get(..4) {
+            Some(b"II*\0" | b"MM\0*") => Format::Tiff,
+            Some([0x76, 0x2f, 0x31, 0x01]) => Format::Exr,
+            _ => Format::Other,
+        },
+    }
+}
+
+/// Decode the image at `path` into a 3-channel BGR `Mat`, as `OpenCV`'s `IMREAD_COLOR` does: 8-bit
+/// unless the image has a higher precision (16-bit TIFF or floating-point EXR, when enabled), in
+/// which case the `Mat` holds 32-bit floats mapped according to `range`.
+pub(crate) fn decode(path: &Path, range: Range) -> Result<Mat, ConversionError> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| ConversionError(format!("Unable to read {}: {e}", path.display())))?;
+    match sniff(&bytes) {
+        Format::WebP => decode_webp(&bytes),
+        Format::Avif => decode_avif(&bytes),
+        Format::Tiff => decode_tiff(path, &bytes, range),
+        Format::Exr => decode_exr(&bytes, range),
+        Format::Other => decode_with_opencv(path),
+    }
+}
+
+/// Decode with `OpenCV`. This uses the default flags (see
+/// https://docs.opencv.org/master/d4/da8/group__imgcodecs.html#ga288b8b3da0892bd651fce07b3bbd3a56) to match what
+/// OpenVINO's wrapper does (see
+/// https://github.com/openvinotoolkit/openvino/blob/7566e8202fa6c00f27de27889e7bf99d7ddf2636/inference-engine/ie_bridges/c/samples/common/opencv_c_wraper.cpp#L25).
+fn decode_with_opencv(path: &Path) -> Result<Mat, ConversionError> {
+    use opencv::core::MatTraitConst;
+    let path_as_str = path
+        .to_str()
+        .ok_or(ConversionError("Unable to stringify the path.".to_string()))?;
+    let src = opencv::imgcodecs::imread(path_as_str, opencv::imgcodecs::IMREAD_COLOR)?;
+    if src.empty() {
+        return Err(ConversionError(format!(
+            "OpenCV is unable to decode {}; its build may lack support for this format.",
+            path.display()
+        )));
+    }
+    Ok(src)
+}
+
+#[cfg(feature = "webp")]
+fn decode_webp(bytes: &[u8]) -> Result<Mat, ConversionError> {
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::WebP)
+        .map_err(|e| ConversionError(format!("Unable to decode WebP image: {e}")))?;
+    to_bgr_mat(&image)
+}
+
+#[cfg(not(feature = "webp"))]
+fn decode_webp(_: &[u8]) -> Result<Mat, ConversionError> {
+    Err(ConversionError(
+        "Decoding WebP images requires the `webp` feature.".to_string(),
+    ))
+}
+
+/// Unlike the other decoders, this one is not pure Rust: the `image` crate decodes AVIF with the
+/// `dav1d` C library, which the `avif` feature links.
+#[cfg(feature = "avif")]
+fn decode_avif(bytes: &[u8]) -> Result<Mat, ConversionError> {
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::Avif)
+        .map_err(|e| ConversionError(format!("Unable to decode AVIF image: {e}")))?;
+    to_bgr_mat(&image)
+}
+
+#[cfg(not(feature = "avif"))]
+fn decode_avif(_: &[u8]) -> Result<Mat, ConversionError> {
+    Err(ConversionError(
+        "Decoding AVIF images requires the `avif` feature.".to_string(),
+    ))
+}
+
+/// Without the `tiff` feature, TIFF images are decoded by `OpenCV`, at 8 bits.
+#[cfg(feature = "tiff")]
+fn decode_tiff(_: &Path, bytes: &[u8], range: Range) -> Result<Mat, ConversionError> {
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::Tiff)
+        .map_err(|e| ConversionError(format!("Unable to decode TIFF image: {e}")))?;
+    to_bgr_f32_mat(&image, range)
+}
+
+#[cfg(not(feature = "tiff"))]
+fn decode_tiff(path: &Path, _: &[u8], _: Range) -> Result<Mat, ConversionError> {
+    decode_with_opencv(path)
+}
+
+#[cfg(feature = "exr")]
+fn decode_exr(bytes: &[u8], range: Range) -> Result<Mat, ConversionError> {
+    let image = image::load_from_memory_with_format(bytes, image::ImageFormat::OpenExr)
+        .map_err(|e| ConversionError(format!("Unable to decode EXR image: {e}")))?;
+    to_bgr_f32_mat(&image, range)
+}
+
+#[cfg(not(feature = "exr"))]
+fn decode_exr(_: &[u8], _: Range) -> Result<Mat, ConversionError> {
+    Err(ConversionError(
+        "Decoding EXR images requires the `exr` feature.".to_string(),
+    ))
+}
+
+/// Copy a decoded image into a 32-bit floating-point BGR `Mat`, dropping any alpha channel and
+/// mapping the values according to `range`. Integer images keep their full precision (e.g., 16
+/// bits); their maximum value is the maximum of their type (e.g., 65535).
+#[cfg(any(feature = "tiff", feature = "exr"))]
+fn to_bgr_f32_mat(image: &image::DynamicImage, range: Range) -> Result<Mat, ConversionError> {
+    use image::DynamicImage;
+    use opencv::core::{MatTrait, Scalar_};
+    let (mut values, max) = match image {
+        DynamicImage::ImageRgb32F(_) | DynamicImage::ImageRgba32F(_) => {
+            (image.to_rgb32f().into_raw(), None)
+        }
+        _ if image.color().bytes_per_pixel() > image.color().channel_count() => {
+            let values = image.to_rgb16().into_raw();
+            (values.into_iter().map(f32::from).collect(), Some(65535.0))
+        }
+        _ => {
+            let values = image.to_rgb8().into_raw();
+            (values.into_iter().map(f32::from).collect(), Some(255.0))
+        }
+    };
+    apply_range(&mut values, max, range);
+    let too_large = |_| ConversionError("The image is too large.".to_string());
+    let rows = i32::try_from(image.height()).map_err(too_large)?;
+    let cols = i32::try_from(image.width()).map_err(too_large)?;
+    let mut mat =
+        Mat::new_rows_cols_with_default(rows, cols, opencv::core::CV_32FC3, Scalar_::all(0.0))?;
+    for (bgr, rgb) in mat
+        .data_typed_mut::<opencv::core::Vec3f>()?
+        .iter_mut()
+        .zip(values.chunks_exact(3))
+    {
+        *bgr = opencv::core::Vec3f::from([rgb[2], rgb[1], rgb[0]]);
+    }
+    Ok(mat)
+}
+
+/// Map decoded `values` according to `range`; `max` is the maximum of the values' integer type, or
+/// `None` for floating-point values.
+pub(crate) fn apply_range(values: &mut [f32], max: Option<f32>, range: Range) {
+    match (range, max) {
+        (Range::Preserve, _) => {}
+        (Range::Normalize | Range::Clamp, Some(max)) => values.iter_mut().for_each(|v| *v /= max),
+        (Range::Normalize, None) => {
+            let (low, high) = values
+                .iter()
+                .filter(|v| v.is_finite())
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(low, high), &v| {
+                    (low.min(v), high.max(v))
+                });
+            let span = high - low;
+            for v in values.iter_mut() {
+                *v = if span > 0.0 {
+                    ((*v - low) / span).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+            }
+        }
+        (Range::Clamp, None) => values.iter_mut().for_each(|v| *v = v.clamp(0.0, 1.0)),
+    }
+}
+
+/// Copy a decoded image into an 8-bit BGR `Mat`, dropping any alpha channel, so that the rest of
+/// the conversion sees the same pixels as for images decoded by `OpenCV`.
+#[cfg(any(feature = "webp", feature = "avif"))]
+fn to_bgr_mat(image: &image::DynamicImage) -> Result<Mat, ConversionError> {
+    use opencv::core::{MatTrait, Scalar_};
+    let rgb = image.to_rgb8();
+    let too_large = |_| ConversionError("The image is too large.".to_string());
+    let rows = i32::try_from(rgb.height()).map_err(too_large)?;
+    let cols = i32::try_from(rgb.width()).map_err(too_large)?;
+    let mut mat =
+        Mat::new_rows_cols_with_default(rows, cols, opencv::core::CV_8UC3, Scalar_::all(0.0))?;
+    for (bgr, rgb) in mat
+        .data_bytes_mut()?
+        .chunks_exact_mut(3)
+        .zip(rgb.as_raw().chunks_exact(3))
+    {
+        bgr.copy_from_slice(&[rgb[2], rgb[1], rgb[0]]);
+    }
+    Ok(mat)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sniffing() {
+        assert_eq!(sniff(b"RIFF\x24\0\0\0WEBPVP8L"), Format::WebP);
+        assert_eq!(sniff(b"\0\0\0\x1cftypavif\0\0\0\0"), Format::Avif);
+        assert_eq!(sniff(b"\0\0\0\x1cftypavis\0\0\0\0"), Format::Avif);
+        assert_eq!(sniff(b"\0\0\0\x1cftypheic\0\0\0\0"), Format::Other);
+        assert_eq!(sniff(b"\xff\xd8\xff\xe0\0\x10JFIF\0\x01"), Format::Other);
+        assert_eq!(sniff(b"RIFF"), Format::Other);
+        assert_eq!(sniff(b"II*\0\x08\0\0\0\0\0\0\0"), Format::Tiff);
+        assert_eq!(sniff(b"MM\0*\0\0\0\x08\0\0\0\0"), Format::Tiff);
+        assert_eq!(sniff(b"\x76\x2f\x31\x01\x02\0\0\0\0\0\0\0"), Format::Exr);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn ranges() {
+        let mut values = [0.0, 32767.5, 65535.0];
+        apply_range(&mut values, Some(65535.0), Range::Normalize);
+        assert_eq!(values, [0.0, 0.5, 1.0]);
+
+        let mut values = [-1.0, 0.5, 3.0];
+        apply_range(&mut values, None, Range::Preserve);
+        assert_eq!(values, [-1.0, 0.5, 3.0]);
+        apply_range(&mut values, None, Range::Clamp);
+        assert_eq!(values, [0.0, 0.5, 1.0]);
+
+        let mut values = [2.0, 4.0, f32::INFINITY, 6.0];
+        apply_range(&mut values, None, Range::Normalize);
+        assert_eq!(values, [0.0, 0.5, 1.0, 1.0]);
+    }
+}
@@ -2,18 +2,35 @@
 //! OpenVINO-compatible tensors.
 //!
 //! > WARNING: this is still experimental--no correctness guarantees!
+//!
+//! Images are decoded with `OpenCV`, except WebP and AVIF images, which many `OpenCV` builds cannot
+//! decode: enable the `webp` and `avif` features to decode these with the `image` crate (AVIF with
+//! the `dav1d` C library, which must be installed). Likewise, `OpenCV` reduces images to 8 bits:
+//! enable the `tiff` and `exr` features to keep the precision of 16-bit TIFF and floating-point EXR
+//! images (see [`convert_with_range`]).
+//!
+//! [`convert`] decodes, resizes and converts an image as described by [`Dimensions`]; to customize
+//! the conversion (e.g., cropping, normalizing or planar layouts), compose a [`Pipeline`] of
+//! [`Step`]s, which may include custom steps.
 
 #![deny(missing_docs)]
 #![deny(clippy::all)]
 #![warn(clippy::pedantic)]
 #![warn(clippy::cargo)]
 
-use core::{fmt, slice};
+use core::fmt;
 use log::info;
-use opencv::core::{MatTraitConst, Scalar_};
+use opencv::core::MatTraitConst;
 use std::convert::TryInto;
 use std::{num::ParseIntError, path::Path, str::FromStr};
 
+mod decode;
+mod pipeline;
+mod verify;
+
+pub use pipeline::{CenterCrop, Layout, Normalize, Pipeline, Quantize, Resize, Step};
+pub use verify::{verify, ChannelError, Verification};
+
 /// Convert an image a path to a resized sequence of bytes.
 ///
 /// # Errors
@@ -23,61 +40,59 @@ use std::{num::ParseIntError, path::Path, str::FromStr};
 pub fn convert<P: AsRef<Path>>(
     path: P,
     dimensions: &Dimensions,
+) -> Result<Vec<u8>, ConversionError> {
+    convert_with_range(path, dimensions, Range::Preserve)
+}
+
+/// Convert an image a path to a resized sequence of bytes, mapping the values of high-precision
+/// images (16-bit TIFF or floating-point EXR, with the `tiff` and `exr` features) according to
+/// `range`; 8-bit images are converted as by [`convert`].
+///
+/// When converting a high-precision image to [`Precision::U8`], values mapped to `0..=1` (i.e.,
+/// with [`Range::Normalize`] or [`Range::Clamp`]) are scaled to `0..=255`; preserved values are
+/// saturated.
+///
+/// This is a [`Pipeline`] that resizes the image and then quantizes it to the precision of
+/// `dimensions`.
+///
+/// # Errors
+///
+/// See [`convert`]; also returns an error if the converted image does not have the number of
+/// channels of `dimensions`.
+pub fn convert_with_range<P: AsRef<Path>>(
+    path: P,
+    dimensions: &Dimensions,
+    range: Range,
 ) -> Result<Vec<u8>, ConversionError> {
     let path = path.as_ref();
     info!("Converting {} to {:?}", path.display(), dimensions);
-    if !path.is_file() {
-        return Err(ConversionError("The path is not a valid file.".to_string()));
+    let precision = dimensions.precision;
+    let tensor = Pipeline::new()
+        .with_range(range)
+        .then(Resize::new(dimensions.height, dimensions.width))
+        .then(move |image: opencv::core::Mat| {
+            // The scale is the default from C++, except when squeezing normalized high-precision
+            // values into bytes.
+            let scale = if image.depth() == opencv::core::CV_32F
+                && precision == Precision::U8
+                && range != Range::Preserve
+            {
+                255.0
+            } else {
+                1.0
+            };
+            Quantize { precision, scale }.apply(image)
+        })
+        .run(path)?;
+    if tensor.len() != dimensions.bytes() {
+        return Err(ConversionError(format!(
+            "The converted image has {} bytes, not the {} bytes of {:?}.",
+            tensor.len(),
+            dimensions.bytes(),
+            dimensions
+        )));
     }
-
-    // Decode the source image. This uses the default flags (see
-    // https://docs.opencv.org/master/d4/da8/group__imgcodecs.html#ga288b8b3da0892bd651fce07b3bbd3a56) to match what
-    // OpenVINO's wrapper does (see
-    // https://github.com/openvinotoolkit/openvino/blob/7566e8202fa6c00f27de27889e7bf99d7ddf2636/inference-engine/ie_bridges/c/samples/common/opencv_c_wraper.cpp#L25).
-    let path_as_str = path
-        .to_str()
-        .ok_or(ConversionError("Unable to stringify the path.".to_string()))?;
-    let src = opencv::imgcodecs::imread(path_as_str, opencv::imgcodecs::IMREAD_COLOR)?;
-    info!("The input image has size = {:?}, channels = {}, type = {}, total items = {}, item size (bytes) = {}", src.size()?, src.channels(), src.typ(), src.total(), src.elem_size1());
-
-    // Create a destination Mat of the right shape, filling it with 0s (see
-    // https://docs.rs/opencv/0.46.3/opencv/core/struct.Mat.html#method.new_rows_cols_with_default).
-    let mut resized = opencv::core::Mat::new_rows_cols_with_default(
-        dimensions.height,
-        dimensions.width,
-        dimensions.as_type(),
-        Scalar_::all(0.0),
-    )?;
-    info!("Before resizing, the `resize` image has size = {:?}, channels = {}, type = {}, total items = {}, item size (bytes) = {}", resized.size(), resized.channels(), resized.typ(), resized.total(), resized.elem_size1());
-
-    // Resize the `src` Mat into the `dst` Mat using bilinear interpolation (see
-    // https://docs.rs/opencv/0.46.3/opencv/imgproc/fn.resize.html).
-    let dst_size = resized.size()?;
-    opencv::imgproc::resize(
-        &src,
-        &mut resized,
-        dst_size,
-        0.0,
-        0.0,
-        opencv::imgproc::INTER_LINEAR,
-    )?;
-    info!("After resizing, the `resize` image has size = {:?}, channels = {}, type = {}, total items = {}, item size (bytes) = {}", resized.size(), resized.channels(), resized.typ(), resized.total(), resized.elem_size1());
-
-    // Because `imgproc::resize` can alter the depth/precision of our destination image, we convert the `resized` image
-    // to the appropriate `Precision`.
-    let mut dst = opencv::core::Mat::new_rows_cols_with_default(
-        dimensions.height,
-        dimensions.width,
-        dimensions.as_type(),
-        Scalar_::all(0.0),
-    )?;
-    // The alpha/beta values are the defaults from C++.
-    resized.convert_to(&mut dst, dimensions.as_type(), 1.0, 0.0)?;
-    info!("After conversion, the `dst` image has size = {:?}, channels = {}, type = {}, total items = {}, item size (bytes) = {}", dst.size(), dst.channels(), dst.typ(), dst.total(), dst.elem_size1());
-
-    // Copy the bytes of the Mat out to a Vec<u8>.
-    let dst_slice = unsafe { slice::from_raw_parts(dst.data(), dimensions.bytes()) };
-    Ok(dst_slice.to_vec())
+    Ok(tensor)
 }
 
 /// Container for the reasons a conversion can fail.
@@ -131,18 +146,6 @@ impl Dimensions {
             .expect("overflow in number of items");
         num_items * self.precision.bytes()
     }
-
-    /// See `OpenCV`'s [basic structures] for a description of the various primitive types.
-    ///
-    /// [basic structures]: https://docs.opencv.org/2.4/modules/core/doc/basic_structures.html
-    fn as_type(&self) -> i32 {
-        use Precision::{FP32, U8};
-        match (self.precision, self.channels) {
-            (FP32, 3) => opencv::core::CV_32FC3,
-            (U8, 3) => opencv::core::CV_8UC3,
-            _ => unimplemented!(),
-        }
-    }
 }
 impl FromStr for Dimensions {
     type Err = ConversionError;
@@ -195,6 +198,32 @@ impl FromStr for Precision {
     }
 }
 
+/// How to map the values of high-precision images (e.g., 16-bit TIFF or floating-point EXR) when
+/// converting them; see [`convert_with_range`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Range {
+    /// Keep the decoded values (e.g., `0..=65535` for 16-bit images).
+    Preserve,
+    /// Map the values to `0..=1`: integer values are divided by the maximum of their type (e.g.,
+    /// 65535), floating-point values are rescaled from the image's minimum and maximum.
+    Normalize,
+    /// Map the values to `0..=1` by clamping floating-point values; integer values are divided by
+    /// the maximum of their type, as with [`Range::Normalize`].
+    Clamp,
+}
+impl FromStr for Range {
+    type Err = ConversionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "preserve" => Ok(Self::Preserve),
+            "normalize" => Ok(Self::Normalize),
+            "clamp" => Ok(Self::Clamp),
+            _ => Err(ConversionError(format!("unrecognized range: {s}"))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -204,5 +233,7 @@ mod test {
             Dimensions::from_str("100x20x3xfp32").unwrap(),
             Dimensions::new(100, 20, 3, Precision::FP32)
         );
+        assert_eq!(Range::from_str("Normalize").unwrap(), Range::Normalize);
+        assert!(Range::from_str("scale").is_err());
     }
 }
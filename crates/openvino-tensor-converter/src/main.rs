@@ -1,4 +1,4 @@
-use openvino_tensor_converter::{convert, Dimensions};
+use openvino_tensor_converter::{convert_with_range, verify, Dimensions, Range};
 use std::{fs, path::PathBuf, str::FromStr};
 use structopt::StructOpt;
 
@@ -6,8 +6,20 @@ fn main() {
     env_logger::init();
     let options = Options::from_args();
     let dimensions = Dimensions::from_str(&options.dimensions).expect("Failed to parse dimensions");
-    let tensor_data = convert(options.input, &dimensions).expect("Failed to convert image");
-    fs::write(options.output, tensor_data).expect("Failed to write tensor")
+    let range = Range::from_str(&options.range).expect("Failed to parse range");
+    let tensor_data =
+        convert_with_range(options.input, &dimensions, range).expect("Failed to convert image");
+    fs::write(options.output, &tensor_data).expect("Failed to write tensor");
+
+    if let Some(reference) = options.verify {
+        let reference = fs::read(reference).expect("Failed to read reference");
+        let verification = verify(&tensor_data, &dimensions, &reference, options.tolerance)
+            .expect("Failed to verify tensor");
+        println!("{verification}");
+        if !verification.passed() {
+            std::process::exit(1);
+        }
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -27,4 +39,18 @@ struct Options {
     /// The dimensions of the output file as "[height]x[width]x[channels]x[precision]"; e.g. 300x300x3xfp32.
     #[structopt(name = "OUTPUT DIMENSIONS")]
     dimensions: String,
+
+    /// How to map the values of 16-bit and floating-point images: "preserve", "normalize" (to 0-1)
+    /// or "clamp" (to 0-1).
+    #[structopt(long, default_value = "preserve")]
+    range: String,
+
+    /// Compare the output against a reference tensor saved by NumPy (a `.npy` file), reporting the
+    /// error of each channel; exit with an error if any element differs by more than the tolerance.
+    #[structopt(long, parse(from_os_str))]
+    verify: Option<PathBuf>,
+
+    /// The maximum absolute error allowed by `--verify`.
+    #[structopt(long, default_value = "0")]
+    tolerance: f64,
 }
@@ -0,0 +1,329 @@
+//! Compose the conversion of an image into a tensor from individual steps; see [`Pipeline`].
+
+use crate::{decode, ConversionError, Precision, Range};
+use log::info;
+use opencv::core::{Mat, MatTrait, MatTraitConst, Rect, Size, Vector, CV_32F, CV_8U};
+use std::convert::TryFrom;
+use std::path::Path;
+
+/// A step of a [`Pipeline`], transforming the image produced by the previous step.
+///
+/// Library users can implement this trait (or pass a closure) to add their own preprocessing
+/// steps, e.g.:
+///
+/// ```no_run
+/// # use openvino_tensor_converter::{Pipeline, Resize};
+/// let pipeline = Pipeline::new()
+///     .then(Resize::new(227, 227))
+///     .then(|image: opencv::core::Mat| {
+///         // ...transform the image...
+///         Ok(image)
+///     });
+/// ```
+pub trait Step {
+    /// Transform `image`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the image cannot be transformed.
+    fn apply(&self, image: Mat) -> Result<Mat, ConversionError>;
+}
+
+impl<F> Step for F
+where
+    F: Fn(Mat) -> Result<Mat, ConversionError>,
+{
+    fn apply(&self, image: Mat) -> Result<Mat, ConversionError> {
+        self(image)
+    }
+}
+
+/// A sequence of [`Step`]s converting an image into a tensor: the image is decoded (see
+/// [`Pipeline::with_range`]), then transformed by each step in order (e.g., [`Resize`] →
+/// [`CenterCrop`] → [`Normalize`] → [`Layout`] → [`Quantize`]), and the bytes of the final image
+/// are the tensor.
+pub struct Pipeline {
+    range: Range,
+    steps: Vec<Box<dyn Step>>,
+}
+
+impl Pipeline {
+    /// Construct a pipeline without any steps, which only decodes the image.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            range: Range::Preserve,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Map the values of high-precision images according to `range` when decoding them; see
+    /// [`crate::convert_with_range`].
+    #[must_use]
+    pub fn with_range(mut self, range: Range) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Append a step to the pipeline.
+    #[must_use]
+    pub fn then(mut self, step: impl Step + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    /// Decode the image at `path` and run it through the steps, returning the bytes of the
+    /// resulting image.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the path is not a valid file, if the image cannot be decoded, or if any
+    /// step fails.
+    pub fn run<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, ConversionError> {
+        let path = path.as_ref();
+        if !path.is_file() {
+            return Err(ConversionError("The path is not a valid file.".to_string()));
+        }
+        let image = decode::decode(path, self.range)?;
+        let image = self.apply(image)?;
+        // `Mat::data_bytes` only reads continuous images (e.g., not a region of another image).
+        let image = if image.is_continuous() {
+            image
+        } else {
+            image.try_clone()?
+        };
+        Ok(image.data_bytes()?.to_vec())
+    }
+
+    /// Run an already-decoded image through the steps.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any step fails.
+    pub fn apply(&self, image: Mat) -> Result<Mat, ConversionError> {
+        let mut image = image;
+        for step in &self.steps {
+            image = step.apply(image)?;
+            info!("After a step, the image has size = {:?}, channels = {}, type = {}, total items = {}, item size (bytes) = {}", image.size()?, image.channels(), image.typ(), image.total(), image.elem_size1());
+        }
+        Ok(image)
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resize the image using bilinear interpolation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Resize {
+    /// The height of the resized image.
+    pub height: i32,
+    /// The width of the resized image.
+    pub width: i32,
+}
+
+impl Resize {
+    /// Construct a resizing step.
+    #[must_use]
+    pub fn new(height: i32, width: i32) -> Self {
+        Self { height, width }
+    }
+}
+
+impl Step for Resize {
+    fn apply(&self, image: Mat) -> Result<Mat, ConversionError> {
+        // See https://docs.rs/opencv/0.88.8/opencv/imgproc/fn.resize.html; `resize` keeps the type
+        // of the source image.
+        let mut resized = Mat::default();
+        opencv::imgproc::resize(
+            &image,
+            &mut resized,
+            Size::new(self.width, self.height),
+            0.0,
+            0.0,
+            opencv::imgproc::INTER_LINEAR,
+        )?;
+        Ok(resized)
+    }
+}
+
+/// Crop the center of the image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CenterCrop {
+    /// The height of the cropped image.
+    pub height: i32,
+    /// The width of the cropped image.
+    pub width: i32,
+}
+
+impl CenterCrop {
+    /// Construct a cropping step.
+    #[must_use]
+    pub fn new(height: i32, width: i32) -> Self {
+        Self { height, width }
+    }
+}
+
+impl Step for CenterCrop {
+    fn apply(&self, image: Mat) -> Result<Mat, ConversionError> {
+        let size = image.size()?;
+        if self.height > size.height || self.width > size.width {
+            return Err(ConversionError(format!(
+                "Cannot crop {}x{} from an image of {}x{}.",
+                self.height, self.width, size.height, size.width
+            )));
+        }
+        let region = Rect::new(
+            (size.width - self.width) / 2,
+            (size.height - self.height) / 2,
+            self.width,
+            self.height,
+        );
+        Ok(Mat::roi(&image, region)?.try_clone()?)
+    }
+}
+
+/// Convert the image to 32-bit floating-point values and normalize each channel, i.e., `(value -
+/// mean) / std`. The values are given in the channel order of the image (i.e., BGR for decoded
+/// images).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Normalize {
+    /// The mean of each channel.
+    pub mean: Vec<f32>,
+    /// The standard deviation of each channel.
+    pub std: Vec<f32>,
+}
+
+impl Normalize {
+    /// Construct a normalization step.
+    #[must_use]
+    pub fn new(mean: Vec<f32>, std: Vec<f32>) -> Self {
+        Self { mean, std }
+    }
+}
+
+impl Step for Normalize {
+    fn apply(&self, image: Mat) -> Result<Mat, ConversionError> {
+        let channels = image.channels();
+        let count = usize::try_from(channels).unwrap_or_default();
+        if count == 0 || [self.mean.len(), self.std.len()] != [count; 2] {
+            return Err(ConversionError(format!(
+                "Expected a mean and standard deviation for each of the {channels} channels."
+            )));
+        }
+        let mut normalized = Mat::default();
+        image.convert_to(
+            &mut normalized,
+            opencv::core::CV_MAKETYPE(CV_32F, channels),
+            1.0,
+            0.0,
+        )?;
+        for pixel in normalized.data_bytes_mut()?.chunks_exact_mut(4 * count) {
+            for ((value, mean), std) in pixel.chunks_exact_mut(4).zip(&self.mean).zip(&self.std) {
+                let v = f32::from_ne_bytes([value[0], value[1], value[2], value[3]]);
+                value.copy_from_slice(&((v - mean) / std).to_ne_bytes());
+            }
+        }
+        Ok(normalized)
+    }
+}
+
+/// Arrange the channels of the image.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// Keep the channels of each pixel together (e.g., `HWC`), as decoded.
+    Interleaved,
+    /// Store each channel in turn (e.g., `CHW`); the image becomes a single-channel image of
+    /// `channels * height` rows.
+    Planar,
+}
+
+impl Step for Layout {
+    fn apply(&self, image: Mat) -> Result<Mat, ConversionError> {
+        match self {
+            Self::Interleaved => Ok(image),
+            Self::Planar => {
+                let mut planes = Vector::<Mat>::new();
+                opencv::core::split(&image, &mut planes)?;
+                let mut planar = Mat::default();
+                opencv::core::vconcat(&planes, &mut planar)?;
+                Ok(planar)
+            }
+        }
+    }
+}
+
+/// Convert the values of the image to `precision`, multiplying them by `scale` (and saturating
+/// them to the range of 8-bit values, if necessary).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Quantize {
+    /// The precision of the converted values.
+    pub precision: Precision,
+    /// The factor by which to multiply the values.
+    pub scale: f64,
+}
+
+impl Quantize {
+    /// Construct a step converting the values to `precision` without scaling them.
+    #[must_use]
+    pub fn new(precision: Precision) -> Self {
+        Self {
+            precision,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Step for Quantize {
+    fn apply(&self, image: Mat) -> Result<Mat, ConversionError> {
+        let depth = match self.precision {
+            Precision::U8 => CV_8U,
+            Precision::FP32 => CV_32F,
+        };
+        let mut quantized = Mat::default();
+        image.convert_to(
+            &mut quantized,
+            opencv::core::CV_MAKETYPE(depth, image.channels()),
+            self.scale,
+            0.0,
+        )?;
+        Ok(quantized)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use opencv::core::{Scalar_, CV_8UC3};
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn steps() {
+        // Two BGR pixels.
+        let mut image = Mat::new_rows_cols_with_default(1, 2, CV_8UC3, Scalar_::all(0.0)).unwrap();
+        image
+            .data_bytes_mut()
+            .unwrap()
+            .copy_from_slice(&[10, 20, 30, 40, 50, 60]);
+        let pipeline = Pipeline::new()
+            .then(Normalize::new(vec![10.0, 20.0, 30.0], vec![1.0, 2.0, 3.0]))
+            .then(Layout::Planar)
+            .then(|image: Mat| Ok(image));
+        let tensor = pipeline.apply(image).unwrap();
+        let values: Vec<f32> = tensor
+            .data_bytes()
+            .unwrap()
+            .chunks_exact(4)
+            .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]))
+            .collect();
+        assert_eq!(values, [0.0, 30.0, 0.0, 15.0, 0.0, 10.0]);
+
+        let image = Mat::new_rows_cols_with_default(4, 4, CV_8UC3, Scalar_::all(0.0)).unwrap();
+        let cropped = CenterCrop::new(2, 3).apply(image.clone()).unwrap();
+        assert_eq!(cropped.size().unwrap(), Size::new(3, 2));
+        assert!(CenterCrop::new(5, 1).apply(image).is_err());
+    }
+}
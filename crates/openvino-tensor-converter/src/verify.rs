@@ -0,0 +1,259 @@
+//! Compare converted tensors against references saved by `NumPy` (i.e., `.npy` files), e.g., to
+//! check that a Rust preprocessing pipeline matches a Python one.
+
+use crate::{ConversionError, Dimensions, Precision};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The result of comparing a converted tensor against a reference; see [`verify`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+    /// The maximum absolute error allowed for each element.
+    pub tolerance: f64,
+    /// The errors of each channel, in the order of the converted tensor (e.g., BGR).
+    pub channels: Vec<ChannelError>,
+}
+
+/// The absolute errors of the elements of one channel.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ChannelError {
+    /// The maximum absolute error.
+    pub max: f64,
+    /// The mean absolute error.
+    pub mean: f64,
+}
+
+impl Verification {
+    /// Check whether every element is within the tolerance of the reference.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.channels.iter().all(|c| c.max <= self.tolerance)
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, channel) in self.channels.iter().enumerate() {
+            writeln!(
+                f,
+                "channel {i}: max error = {}, mean error = {}",
+                channel.max, channel.mean
+            )?;
+        }
+        let verdict = if self.passed() { "passed" } else { "failed" };
+        write!(f, "{verdict} (tolerance = {})", self.tolerance)
+    }
+}
+
+/// Compare `tensor`, as produced by [`crate::convert`] for `dimensions`, against the `reference`
+/// contents of a `.npy` file, reporting the error of each channel.
+///
+/// The reference may be laid out like the converted tensor (channels last, e.g., `[H, W, C]` or
+/// `[1, H, W, C]`) or with planar channels (e.g., `[1, C, H, W]`, as many Python pipelines
+/// produce); it must hold little-endian numbers (`f4`, `f8`, `u1`, `i1`, `u2` or `i2`).
+///
+/// # Errors
+///
+/// Returns an error if the reference is not a valid `.npy` file, has an unsupported element type
+/// or Fortran order, or does not have the shape of the converted tensor.
+pub fn verify(
+    tensor: &[u8],
+    dimensions: &Dimensions,
+    reference: &[u8],
+    tolerance: f64,
+) -> Result<Verification, ConversionError> {
+    let actual = tensor_values(tensor, dimensions.precision);
+    let (shape, expected) = parse_npy(reference)?;
+    let channels = usize::try_from(dimensions.channels)
+        .map_err(|_| ConversionError("invalid number of channels".to_string()))?;
+    if expected.len() != actual.len() || channels == 0 {
+        return Err(ConversionError(format!(
+            "the reference has shape {shape:?} but the tensor has {} elements",
+            actual.len()
+        )));
+    }
+    let planar = match shape.as_slice() {
+        [.., c] if *c == channels => false,
+        [.., c, _, _] if *c == channels => true,
+        _ => {
+            return Err(ConversionError(format!(
+                "the reference has shape {shape:?}, with no dimension of {channels} channels"
+            )))
+        }
+    };
+
+    let pixels = actual.len() / channels;
+    let mut errors = vec![ChannelError::default(); channels];
+    for (i, value) in actual.iter().enumerate() {
+        let (pixel, channel) = (i / channels, i % channels);
+        let reference = if planar {
+            expected[channel * pixels + pixel]
+        } else {
+            expected[i]
+        };
+        let error = (value - reference).abs();
+        let errors = &mut errors[channel];
+        errors.max = errors.max.max(error);
+        errors.mean += error;
+    }
+    #[allow(clippy::cast_precision_loss)]
+    for errors in &mut errors {
+        errors.mean /= pixels as f64;
+    }
+    Ok(Verification {
+        tolerance,
+        channels: errors,
+    })
+}
+
+/// Read the elements of a converted tensor.
+fn tensor_values(tensor: &[u8], precision: Precision) -> Vec<f64> {
+    match precision {
+        Precision::U8 => tensor.iter().copied().map(f64::from).collect(),
+        Precision::FP32 => tensor
+            .chunks_exact(4)
+            .map(|b| f64::from(f32::from_ne_bytes([b[0], b[1], b[2], b[3]])))
+            .collect(),
+    }
+}
+
+/// Parse the contents of a `.npy` file into its shape and its elements, in C order.
+fn parse_npy(bytes: &[u8]) -> Result<(Vec<usize>, Vec<f64>), ConversionError> {
+    let invalid = |msg: &str| ConversionError(format!("invalid .npy file: {msg}"));
+    let rest = bytes
+        .strip_prefix(b"\x93NUMPY")
+        .ok_or_else(|| invalid("no magic string"))?;
+    let (header_len, rest) = match rest {
+        [1, _, a, b, rest @ ..] => (usize::from(u16::from_le_bytes([*a, *b])), rest),
+        [2 | 3, _, a, b, c, d, rest @ ..] => {
+            let len = u32::from_le_bytes([*a, *b, *c, *d]);
+            let len = usize::try_from(len).map_err(|_| invalid("header too large"))?;
+            (len, rest)
+        }
+        _ => return Err(invalid("unsupported version")),
+    };
+    if rest.len() < header_len {
+        return Err(invalid("truncated header"));
+    }
+    let (header, data) = rest.split_at(header_len);
+    let header = std::str::from_utf8(header).map_err(|_| invalid("header is not text"))?;
+
+    let descr = header_value(header, "descr")
+        .and_then(|v| v.strip_prefix(['\'', '"']))
+        .and_then(|v| v.split(['\'', '"']).next())
+        .ok_or_else(|| invalid("no `descr`"))?;
+    if header_value(header, "fortran_order").is_some_and(|v| v.starts_with("True")) {
+        return Err(invalid("Fortran order is not supported"));
+    }
+    let shape = header_value(header, "shape").ok_or_else(|| invalid("no `shape`"))?;
+    let shape = shape
+        .trim_start_matches('(')
+        .split(')')
+        .next()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<usize>, _>>()
+        .map_err(|_| invalid("malformed `shape`"))?;
+
+    let values: Vec<f64> = match descr {
+        "<f4" => data
+            .chunks_exact(4)
+            .map(|b| f64::from(f32::from_le_bytes([b[0], b[1], b[2], b[3]])))
+            .collect(),
+        "<f8" => data
+            .chunks_exact(8)
+            .map(|b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]))
+            .collect(),
+        "|u1" | "<u1" => data.iter().copied().map(f64::from).collect(),
+        "|i1" | "<i1" => data
+            .iter()
+            .map(|&b| f64::from(i8::from_le_bytes([b])))
+            .collect(),
+        "<u2" => data
+            .chunks_exact(2)
+            .map(|b| f64::from(u16::from_le_bytes([b[0], b[1]])))
+            .collect(),
+        "<i2" => data
+            .chunks_exact(2)
+            .map(|b| f64::from(i16::from_le_bytes([b[0], b[1]])))
+            .collect(),
+        _ => return Err(invalid(&format!("unsupported element type `{descr}`"))),
+    };
+    if values.len() != shape.iter().product::<usize>() {
+        return Err(invalid("the data does not match the shape"));
+    }
+    Ok((shape, values))
+}
+
+/// Find the text following `'key':` in a `.npy` header (a Python dictionary literal).
+fn header_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let start = header.find(&format!("'{key}'"))? + key.len() + 2;
+    Some(header[start..].trim_start().strip_prefix(':')?.trim_start())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Write a version 1 `.npy` file, as `numpy.save` does.
+    fn npy(descr: &str, shape: &str, data: &[u8]) -> Vec<u8> {
+        let mut header =
+            format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}");
+        while (10 + header.len() + 1) % 64 != 0 {
+            header.push(' ');
+        }
+        header.push('\n');
+        let mut bytes = b"\x93NUMPY\x01\x00".to_vec();
+        bytes.extend_from_slice(&u16::try_from(header.len()).unwrap().to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn parsing() {
+        let (shape, values) = parse_npy(&npy("|u1", "(1, 2, 3)", &[1, 2, 3, 4, 5, 6])).unwrap();
+        assert_eq!(shape, [1, 2, 3]);
+        assert_eq!(values, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let data: Vec<u8> = [0.5f32, -1.0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let (shape, values) = parse_npy(&npy("<f4", "(2,)", &data)).unwrap();
+        assert_eq!(shape, [2]);
+        assert_eq!(values, [0.5, -1.0]);
+
+        assert!(parse_npy(b"not a numpy file").is_err());
+        assert!(parse_npy(&npy(">f4", "(2,)", &data)).is_err());
+        assert!(parse_npy(&npy("<f4", "(3,)", &data)).is_err());
+    }
+
+    #[test]
+    fn verification() {
+        // Two BGR pixels, interleaved.
+        let dimensions = Dimensions::new(1, 2, 3, Precision::U8);
+        let tensor = [10, 20, 30, 40, 50, 60];
+        let interleaved = npy("|u1", "(1, 2, 3)", &[10, 20, 30, 40, 52, 60]);
+        let verification = verify(&tensor, &dimensions, &interleaved, 1.0).unwrap();
+        assert_eq!(
+            verification.channels[1],
+            ChannelError {
+                max: 2.0,
+                mean: 1.0
+            }
+        );
+        assert_eq!(verification.channels[0], ChannelError::default());
+        assert!(!verification.passed());
+
+        let planar = npy("|u1", "(1, 3, 1, 2)", &[10, 40, 20, 50, 30, 60]);
+        let verification = verify(&tensor, &dimensions, &planar, 0.0).unwrap();
+        assert!(verification.passed());
+
+        let mismatched = npy("|u1", "(2, 3)", &[0; 6]);
+        assert!(verify(&tensor[..3], &dimensions, &mismatched, 0.0).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+//! Bound the time spent compiling models, which device drivers (e.g., of an NPU or a GPU) may
+//! never return from; see [`CompilePolicy`].
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// How [`crate::Core::compile_model_with_policy`] compiles a model: for how long to wait for each
+/// attempt and how many times to try again after a failed or timed-out attempt.
+///
+/// ```
+/// # use openvino::CompilePolicy;
+/// # use std::time::Duration;
+/// let policy = CompilePolicy {
+///     timeout: Some(Duration::from_secs(60)),
+///     retries: 2,
+///     ..CompilePolicy::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CompilePolicy {
+    /// The time to wait for each attempt, if limited; an attempt which takes longer fails with
+    /// [`crate::InferenceError::CompileTimedOut`].
+    pub timeout: Option<Duration>,
+    /// The number of attempts to make after the first one fails or times out.
+    pub retries: u32,
+    /// The time to wait before each retry, e.g., to let a driver recover.
+    pub retry_delay: Duration,
+}
+
+/// The outcome of [`run_with_timeout`].
+pub(crate) enum Outcome<T> {
+    /// The task finished in time, with this result.
+    Done(T),
+    /// The task is still running, detached.
+    TimedOut(Detached),
+}
+
+/// A task which outlived its timeout; see [`run_with_timeout`].
+pub(crate) struct Detached {
+    keep_alive: mpsc::Sender<Box<dyn Send>>,
+}
+
+impl Detached {
+    /// Keep `value` (e.g., what the task is still using) until the task finishes, then drop it on
+    /// the task's thread; a task which never finishes leaks it.
+    pub(crate) fn hand_over(self, value: impl Send + 'static) {
+        // The task may have finished in the meantime, in which case `value` is dropped here.
+        let _ = self.keep_alive.send(Box::new(value));
+    }
+}
+
+/// Run `task` on a new thread and wait for at most `timeout` for its result; if the task panics in
+/// time, the panic is resumed on the calling thread. The thread cannot be stopped, so a task which
+/// times out keeps running; whatever it uses must outlive it (see [`Detached::hand_over`]) and its
+/// result is dropped when it finishes.
+pub(crate) fn run_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Outcome<T> {
+    let (result_sender, result) = mpsc::channel();
+    let (keep_alive, kept) = mpsc::channel::<Box<dyn Send>>();
+    thread::spawn(move || {
+        if result_sender
+            .send(panic::catch_unwind(AssertUnwindSafe(task)))
+            .is_err()
+        {
+            // Timed out: drop what was handed over, once it has been.
+            drop(kept.recv());
+        }
+    });
+    match result.recv_timeout(timeout) {
+        Ok(Ok(result)) => Outcome::Done(result),
+        Ok(Err(payload)) => panic::resume_unwind(payload),
+        Err(RecvTimeoutError::Timeout) => Outcome::TimedOut(Detached { keep_alive }),
+        Err(RecvTimeoutError::Disconnected) => unreachable!("the task's outcome is always sent"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn done_in_time() {
+        let outcome = run_with_timeout(Duration::from_secs(10), || 42);
+        assert!(matches!(outcome, Outcome::Done(42)));
+    }
+
+    #[test]
+    fn panicked_in_time() {
+        let panicked = panic::catch_unwind(|| {
+            run_with_timeout(Duration::from_secs(10), || panic::panic_any(42));
+        });
+        assert_eq!(panicked.unwrap_err().downcast_ref::<i32>(), Some(&42));
+    }
+
+    #[test]
+    fn timed_out() {
+        struct Flag(Arc<AtomicBool>);
+        impl Drop for Flag {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let (finish, finished) = mpsc::channel::<()>();
+        let outcome = run_with_timeout(Duration::from_millis(10), move || finished.recv());
+        let Outcome::TimedOut(detached) = outcome else {
+            panic!("the task should time out");
+        };
+
+        // What is handed over stays alive until the task finishes.
+        let dropped = Arc::new(AtomicBool::new(false));
+        detached.hand_over(Flag(dropped.clone()));
+        thread::sleep(Duration::from_millis(10));
+        assert!(!dropped.load(Ordering::SeqCst));
+        finish.send(()).unwrap();
+        while !dropped.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
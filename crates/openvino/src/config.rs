@@ -0,0 +1,161 @@
+//! Describe the configuration of a [`crate::Core`] as data, e.g., to load it from a configuration
+//! file.
+
+use crate::{DeviceType, PropertyKey, RwPropertyKey, SetupError};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// The configuration of a [`crate::Core`], applied by [`crate::Core::from_config`].
+///
+/// With the `serde` feature, this can be deserialized from any format `serde` supports, e.g., this
+/// TOML:
+///
+/// ```toml
+/// device = "GPU"
+/// cache_dir = "/var/cache/my-app/openvino"
+///
+/// [properties]
+/// PERFORMANCE_HINT = "THROUGHPUT"
+/// NUM_STREAMS = "2"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct CoreConfig {
+    /// The device to configure (e.g., `CPU` or `AUTO:GPU,CPU`); defaults to `CPU`.
+    pub device: String,
+    /// The properties to set on the device, by key (e.g., `NUM_STREAMS`); see [`RwPropertyKey`].
+    pub properties: BTreeMap<String, String>,
+    /// The directory in which to cache compiled models (i.e., the `CACHE_DIR` property).
+    pub cache_dir: Option<PathBuf>,
+    /// An OpenVINO plugin configuration file (i.e., a `plugins.xml` file listing the paths to the
+    /// device plugins) to use instead of the default plugins; see
+    /// [`crate::Core::new_with_plugins_xml`].
+    pub plugins_xml: Option<PathBuf>,
+}
+
+impl Default for CoreConfig {
+    fn default() -> Self {
+        Self {
+            device: DeviceType::CPU.to_string(),
+            properties: BTreeMap::new(),
+            cache_dir: None,
+            plugins_xml: None,
+        }
+    }
+}
+
+impl CoreConfig {
+    /// Check the configuration without applying it and list the properties to set on the device,
+    /// including the cache directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SetupError::InvalidConfig`] if the device is empty, a property is read-only (e.g.,
+    /// `SUPPORTED_PROPERTIES`), `CACHE_DIR` is set twice, or a path is not valid UTF-8.
+    pub fn validate(&self) -> Result<Vec<(RwPropertyKey, String)>, SetupError> {
+        if self.device.trim().is_empty() {
+            return Err(SetupError::InvalidConfig(
+                "the device must not be empty".into(),
+            ));
+        }
+        let mut properties = Vec::with_capacity(self.properties.len() + 1);
+        for (key, value) in &self.properties {
+            let key = match PropertyKey::from(key.as_str()) {
+                PropertyKey::Rw(key) => key,
+                PropertyKey::Other(key) => RwPropertyKey::Other(key),
+                _ => {
+                    return Err(SetupError::InvalidConfig(format!(
+                        "property `{key}` is read-only"
+                    )))
+                }
+            };
+            properties.push((key, value.clone()));
+        }
+        if let Some(cache_dir) = &self.cache_dir {
+            if self
+                .properties
+                .contains_key(RwPropertyKey::CacheDir.as_ref())
+            {
+                return Err(SetupError::InvalidConfig(
+                    "`CACHE_DIR` is set both as `cache_dir` and as a property".into(),
+                ));
+            }
+            properties.push((RwPropertyKey::CacheDir, path_to_string(cache_dir)?));
+        }
+        if let Some(plugins_xml) = &self.plugins_xml {
+            path_to_string(plugins_xml)?;
+        }
+        Ok(properties)
+    }
+}
+
+/// Convert a path to a string, as needed to pass it to OpenVINO.
+pub(crate) fn path_to_string(path: &std::path::Path) -> Result<String, SetupError> {
+    path.to_str().map(ToString::to_string).ok_or_else(|| {
+        SetupError::InvalidConfig(format!("path is not valid UTF-8: {}", path.display()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation() {
+        let mut config = CoreConfig {
+            cache_dir: Some("/tmp/cache".into()),
+            ..CoreConfig::default()
+        };
+        config.properties.insert("NUM_STREAMS".into(), "2".into());
+        config
+            .properties
+            .insert("SOME_PLUGIN_OPTION".into(), "YES".into());
+        assert_eq!(
+            config.validate().unwrap(),
+            vec![
+                (RwPropertyKey::NumStreams, "2".into()),
+                (
+                    RwPropertyKey::Other("SOME_PLUGIN_OPTION".into()),
+                    "YES".into()
+                ),
+                (RwPropertyKey::CacheDir, "/tmp/cache".into()),
+            ]
+        );
+
+        config.properties.insert("CACHE_DIR".into(), "/tmp".into());
+        assert!(config.validate().is_err());
+
+        let mut config = CoreConfig::default();
+        config
+            .properties
+            .insert("SUPPORTED_PROPERTIES".into(), String::new());
+        assert!(config.validate().is_err());
+
+        let config = CoreConfig {
+            device: " ".into(),
+            ..CoreConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        let config: CoreConfig = toml::from_str(
+            r#"
+            device = "GPU"
+            cache_dir = "/tmp/cache"
+            [properties]
+            NUM_STREAMS = "2"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.device, "GPU");
+        assert_eq!(config.properties["NUM_STREAMS"], "2");
+        assert!(toml::from_str::<CoreConfig>("unknown = 1").is_err());
+    }
+}
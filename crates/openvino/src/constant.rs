@@ -0,0 +1,145 @@
+//! Read the constants (e.g., weights) of a model from its IR.
+
+use crate::util::{attribute_value, Result};
+use crate::{ElementType, InferenceError};
+
+/// A constant of a model (e.g., the weights of a convolution), as recorded in its IR; see
+/// [`crate::Model::constants`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Constant {
+    /// The name of the constant's layer.
+    pub name: String,
+    /// The element type of the constant.
+    pub element_type: ElementType,
+    /// The dimensions of the constant; empty for scalars.
+    pub shape: Vec<i64>,
+    /// The position of the constant's data in the weights (i.e., the `.bin` file).
+    pub offset: usize,
+    /// The size of the constant's data, in bytes.
+    pub size: usize,
+}
+
+impl Constant {
+    /// Get the constant's data from the model's `weights` (i.e., the contents of the `.bin` file),
+    /// e.g., to compute a checksum; return `None` if the weights are too short.
+    pub fn data<'a>(&self, weights: &'a [u8]) -> Option<&'a [u8]> {
+        weights.get(self.offset..self.offset.checked_add(self.size)?)
+    }
+
+    /// Check whether the constant is stored with a precision of 8 bits or less (e.g.,
+    /// [`ElementType::I8`], [`ElementType::U4`] or [`ElementType::NF4`]), as quantized or
+    /// compressed weights are.
+    pub fn is_low_precision(&self) -> bool {
+        self.element_type != ElementType::Boolean
+            && self.element_type.bit_width().is_some_and(|bits| bits <= 8)
+    }
+}
+
+/// Parse the `Const` layers of an IR (i.e., the `.xml` file), in order.
+pub(crate) fn from_ir(ir: &str) -> Result<Vec<Constant>> {
+    let mut constants = Vec::new();
+    // Splitting at `<layer` also yields the `<layers>` element, which has no `type`.
+    for layer in ir.split("<layer").skip(1) {
+        let header = &layer[..layer.find('>').unwrap_or(layer.len())];
+        if attribute_value(header, "type") != Some("Const") {
+            continue;
+        }
+        let name = attribute_value(header, "name").unwrap_or_default();
+        let body = &layer[..layer.find("</layer>").unwrap_or(layer.len())];
+        let data = body
+            .split("<data")
+            .nth(1)
+            .map(|d| &d[..d.find('>').unwrap_or(d.len())])
+            .ok_or(InferenceError::ParameterMismatch)?;
+        let attribute = |key| attribute_value(data, key).ok_or(InferenceError::ParameterMismatch);
+        constants.push(Constant {
+            name: name.to_string(),
+            element_type: parse_element_type(attribute("element_type")?)?,
+            shape: parse_shape(attribute("shape")?)?,
+            offset: parse_number(attribute("offset")?)?,
+            size: parse_number(attribute("size")?)?,
+        });
+    }
+    Ok(constants)
+}
+
+/// Parse the name of an element type as written in IRs (e.g., `f32`, `boolean`).
+fn parse_element_type(name: &str) -> Result<ElementType> {
+    ElementType::ALL
+        .iter()
+        .copied()
+        .find(|ty| ty.to_string().eq_ignore_ascii_case(name))
+        .ok_or(InferenceError::ParameterMismatch)
+}
+
+/// Parse a comma-separated list of dimensions (e.g., `96, 3, 11, 11`).
+fn parse_shape(shape: &str) -> Result<Vec<i64>> {
+    shape
+        .split(',')
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+        .map(|d| d.parse().map_err(|_| InferenceError::ParameterMismatch))
+        .collect()
+}
+
+fn parse_number(value: &str) -> Result<usize> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| InferenceError::ParameterMismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IR: &str = r#"<?xml version="1.0"?>
+<net name="model" version="11">
+    <layers>
+        <layer id="0" name="input" type="Parameter" version="opset1">
+            <data shape="1,3" element_type="f32"/>
+        </layer>
+        <layer id="1" name="weights" type="Const" version="opset1">
+            <data element_type="i8" shape="4, 3" offset="0" size="12"/>
+            <output><port id="0" precision="I8" names="weights"/></output>
+        </layer>
+        <layer id="2" name="scale" type="Const" version="opset1">
+            <data element_type="f32" shape="" offset="12" size="4"/>
+        </layer>
+    </layers>
+</net>"#;
+
+    #[test]
+    fn parse_constants() {
+        let constants = from_ir(IR).unwrap();
+        assert_eq!(
+            constants,
+            [
+                Constant {
+                    name: "weights".into(),
+                    element_type: ElementType::I8,
+                    shape: vec![4, 3],
+                    offset: 0,
+                    size: 12,
+                },
+                Constant {
+                    name: "scale".into(),
+                    element_type: ElementType::F32,
+                    shape: vec![],
+                    offset: 12,
+                    size: 4,
+                },
+            ]
+        );
+        assert!(constants[0].is_low_precision());
+        assert!(!constants[1].is_low_precision());
+
+        let weights = [0u8; 16];
+        assert_eq!(constants[1].data(&weights), Some(&weights[12..16]));
+        assert_eq!(constants[1].data(&weights[..15]), None);
+
+        let broken = IR.replace(r#"element_type="i8""#, r#"element_type="x9""#);
+        assert!(from_ir(&broken).is_err());
+        assert_eq!(from_ir("<net/>").unwrap(), []);
+    }
+}
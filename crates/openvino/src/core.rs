@@ -1,24 +1,48 @@
 //! Define the core interface between Rust and OpenVINO's C
 //! [API](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__core__c__api.html).
 
+use crate::compile_policy::{run_with_timeout, Outcome};
+use crate::config::path_to_string;
 use crate::error::LoadingError;
-use crate::{cstr, drop_using_function, try_unsafe, util::Result};
+use crate::property::{deterministic_properties, dump_properties};
+use crate::util::{slice_from_raw, wipe, Result};
+#[cfg(feature = "gpu")]
+use crate::GpuKernelCache;
+use crate::{cstr, drop_using_function, try_unsafe};
 use crate::{model::CompiledModel, Model};
-use crate::{DeviceType, PropertyKey, RwPropertyKey, SetupError, Tensor, Version};
+use crate::{
+    CompilePolicy, CoreConfig, CpuConfig, DeviceType, ElementType, InferenceError, ModelFormat,
+    ModelPriority, PropertyKey, RwPropertyKey, SetupError, Shape, Tensor, Version,
+};
 use openvino_sys::{
     self, ov_available_devices_free, ov_core_compile_model, ov_core_create,
     ov_core_create_with_config, ov_core_free, ov_core_get_available_devices, ov_core_get_property,
-    ov_core_get_versions_by_device_name, ov_core_read_model, ov_core_read_model_from_memory_buffer,
-    ov_core_set_property, ov_core_t, ov_core_versions_free,
+    ov_core_get_versions_by_device_name, ov_core_import_model, ov_core_read_model,
+    ov_core_read_model_from_memory_buffer, ov_core_set_property, ov_core_t, ov_core_versions_free,
 };
+use std::collections::BTreeMap;
 use std::ffi::{CStr, CString};
+use std::io::Read;
 use std::os::raw::c_char;
-use std::slice;
-use std::str::FromStr;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 /// See [`ov_core_t`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__core__c__api.html).
+///
+/// Custom operations cannot be registered: unlike the C++ API (`ov::Core::add_extension`), the C
+/// API offers no way to add an extension, whether an operation implemented in Rust (with shape
+/// inference and evaluation callbacks) or a C++ extension library. Models with custom operations
+/// must be converted so that these are decomposed into standard operations (e.g., by the model
+/// converter) before being read.
 pub struct Core {
     ptr: *mut ov_core_t,
+    plugins_xml: Option<PathBuf>,
+    /// The properties set with [`Core::set_property`], latest value per device and key, to set
+    /// again on the replacement of [`Core::refresh_devices`].
+    properties: Vec<(DeviceType<'static>, RwPropertyKey, String)>,
 }
 drop_using_function!(Core, ov_core_free);
 
@@ -26,34 +50,264 @@ unsafe impl Send for Core {}
 
 impl Core {
     /// Construct a new OpenVINO [`Core`].
+    ///
+    /// If OpenVINO finds no device and no `plugins.xml` file is found (see
+    /// [`openvino_finder::find_plugins_xml`]), e.g., because the installation is missing its
+    /// configuration file, this registers the plugin libraries found beside the loaded `openvino_c`
+    /// library (e.g., `libopenvino_intel_cpu_plugin.so` for `CPU`) through a configuration file
+    /// generated by [`openvino_finder::generate_plugins_xml`] in a private temporary directory,
+    /// removed once the [`Core`] is created.
     pub fn new() -> std::result::Result<Core, SetupError> {
         openvino_sys::library::load().map_err(LoadingError::SystemFailure)?;
+        Core::create()
+    }
+
+    /// Create a [`Core`] with the default plugins, as [`Core::new`] does, from the library
+    /// already loaded.
+    fn create() -> std::result::Result<Core, SetupError> {
         let mut ptr = std::ptr::null_mut();
         try_unsafe!(ov_core_create(std::ptr::addr_of_mut!(ptr)))?;
-        Ok(Core { ptr })
+        let core = Core {
+            ptr,
+            plugins_xml: None,
+            properties: vec![],
+        };
+        // Archive and pip installations ship no `plugins.xml` since their plugins are built in, so
+        // only a core without devices needs the generated file.
+        if let (None, Some(library)) = (
+            openvino_finder::find_plugins_xml(),
+            openvino_sys::library::loaded(),
+        ) {
+            if core
+                .available_devices()
+                .is_ok_and(|devices| devices.is_empty())
+            {
+                if let Some(generated) = Core::with_generated_plugins_xml(&library) {
+                    return Ok(generated);
+                }
+            }
+        }
+        Ok(core)
+    }
+
+    /// Create a [`Core`] with a `plugins.xml` generated for the plugin libraries beside the
+    /// `openvino_c` `library`; see [`Core::new`]. `None` if there are no such libraries or the file
+    /// cannot be generated or used.
+    fn with_generated_plugins_xml(library: &Path) -> Option<Core> {
+        static NEXT_DIR: AtomicUsize = AtomicUsize::new(0);
+        let library_dir = library.parent()?;
+        // A new directory only this user can write to, so that no other user can swap the file
+        // for one loading their own libraries.
+        let dir = std::env::temp_dir().join(format!(
+            "openvino-rs-plugins-{}-{}",
+            std::process::id(),
+            NEXT_DIR.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut builder = std::fs::DirBuilder::new();
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        if let Err(e) = builder.create(&dir) {
+            log::debug!("Cannot create directory {}: {e}", dir.display());
+            return None;
+        }
+        let path = dir.join("plugins.xml");
+        let create = || -> std::result::Result<*mut ov_core_t, String> {
+            openvino_finder::generate_plugins_xml(library_dir, &path).map_err(|e| e.to_string())?;
+            let ov_path = cstr!(path_to_string(&path).map_err(|e| e.to_string())?)
+                .map_err(|e| e.to_string())?;
+            let mut ptr = std::ptr::null_mut();
+            try_unsafe!(ov_core_create_with_config(
+                ov_path.as_ptr(),
+                std::ptr::addr_of_mut!(ptr)
+            ))
+            .map_err(|e| e.to_string())?;
+            Ok(ptr)
+        };
+        let created = create();
+        let _ = std::fs::remove_dir_all(&dir);
+        match created {
+            Ok(ptr) => {
+                log::debug!(
+                    "No device or plugins.xml found; registered the plugin libraries in: {}",
+                    library_dir.display()
+                );
+                Some(Core {
+                    ptr,
+                    plugins_xml: None,
+                    properties: vec![],
+                })
+            }
+            Err(e) => {
+                log::debug!(
+                    "Cannot register the plugin libraries in {}: {e}",
+                    library_dir.display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Construct a new OpenVINO [`Core`] using an installed OpenVINO library whose version
+    /// satisfies `constraint`, a semantic version requirement (e.g., `">=2024.4, <2025"`).
+    ///
+    /// This asks [`openvino_finder::find_all`] for every installed `openvino_c` library and loads
+    /// each in turn until one reports a matching version, which stays loaded. This allows, e.g.,
+    /// testing against 2024.x and 2025.x runtimes within one process, as long as this is done
+    /// _sequentially_. Later calls to [`Core::new`] keep using this library (see
+    /// [`openvino_sys::library::loaded`]). If no library matches, the library loaded before, if
+    /// any, is loaded again.
+    ///
+    /// # Safety
+    ///
+    /// Objects created with the previously-loaded library (including other [`Core`]s and
+    /// everything created from them) must not be used, nor dropped, after this call succeeds: they
+    /// would call into another library than the one that created them.
+    #[cfg(feature = "runtime-linking")]
+    pub unsafe fn new_for_version(constraint: &str) -> std::result::Result<Core, SetupError> {
+        let requirement = semver::VersionReq::parse(constraint)
+            .map_err(|e| LoadingError::InvalidVersionConstraint(e.to_string()))?;
+        let previous = openvino_sys::library::loaded();
+        let result = Core::load_matching(constraint, &requirement);
+        if result.is_err() {
+            if let Some(previous) = previous {
+                crate::version::forget_version();
+                openvino_sys::library::load_from(previous).map_err(LoadingError::SystemFailure)?;
+            }
+        }
+        result
+    }
+
+    /// Load the first installed library whose version satisfies `requirement` and create a
+    /// [`Core`] with it; see [`Core::new_for_version`].
+    #[cfg(feature = "runtime-linking")]
+    fn load_matching(
+        constraint: &str,
+        requirement: &semver::VersionReq,
+    ) -> std::result::Result<Core, SetupError> {
+        let mut rejected = vec![];
+        for found in openvino_finder::find_all("openvino_c", openvino_finder::Linking::Dynamic) {
+            let path = found.path;
+            if let Err(e) = openvino_sys::library::load_from(&path) {
+                rejected.push(format!("{} ({e})", path.display()));
+                continue;
+            }
+            crate::version::forget_version();
+            let version = crate::version::loaded_version()?;
+            if version.to_semver().is_some_and(|v| requirement.matches(&v)) {
+                let mut ptr = std::ptr::null_mut();
+                try_unsafe!(ov_core_create(std::ptr::addr_of_mut!(ptr)))?;
+                return Ok(Core {
+                    ptr,
+                    plugins_xml: None,
+                    properties: vec![],
+                });
+            }
+            rejected.push(format!("{} ({})", path.display(), version.build_number));
+        }
+        Err(LoadingError::NoMatchingVersion(format!(
+            "no library satisfies `{constraint}`; found: [{}]",
+            rejected.join(", ")
+        ))
+        .into())
     }
 
-    /// Construct a new OpenVINO [`Core`] with config specified in an xml file.
+    /// Construct a new OpenVINO [`Core`] with config specified in an xml file; see
+    /// [`Core::new_with_plugins_xml`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SetupError::InvalidConfig`] if the file cannot be read or is rejected by OpenVINO.
+    /// An empty path, which OpenVINO takes as "no file", is not checked.
     pub fn new_with_config(xml_config_file: &str) -> std::result::Result<Core, SetupError> {
-        let xml_config_file = cstr!(xml_config_file);
+        openvino_sys::library::load().map_err(LoadingError::SystemFailure)?;
+        Core::create_with_config(xml_config_file)
+    }
+
+    /// Create a [`Core`] with config specified in an xml file, as [`Core::new_with_config`] does,
+    /// from the library already loaded.
+    fn create_with_config(xml_config_file: &str) -> std::result::Result<Core, SetupError> {
+        if !xml_config_file.is_empty() {
+            std::fs::File::open(xml_config_file).map_err(|e| {
+                SetupError::InvalidConfig(format!("cannot read {xml_config_file}: {e}"))
+            })?;
+        }
+        let ov_xml_config_file = cstr!(xml_config_file)?;
         let mut ptr = std::ptr::null_mut();
         try_unsafe!(ov_core_create_with_config(
-            xml_config_file.as_ptr(),
+            ov_xml_config_file.as_ptr(),
             std::ptr::addr_of_mut!(ptr)
-        ))?;
-        Ok(Core { ptr })
+        ))
+        .map_err(|e| {
+            SetupError::InvalidConfig(format!("{xml_config_file}: rejected by OpenVINO: {e}"))
+        })?;
+        Ok(Core {
+            ptr,
+            plugins_xml: Some(PathBuf::from(xml_config_file)).filter(|p| !p.as_os_str().is_empty()),
+            properties: vec![],
+        })
     }
 
-    /// Gets device plugins version information.
+    /// Construct a new OpenVINO [`Core`] using the device plugins listed in the `plugins_xml`
+    /// configuration file rather than the default plugins (or those of the `plugins.xml` found by
+    /// [`openvino_finder::find_plugins_xml`]).
     ///
-    /// A device name can be complex and identify multiple devices at once, like `HETERO:CPU,GPU`.
-    /// In this case, the returned map contains multiple entries, each per device.
+    /// The file only applies to this [`Core`], so several [`Core`]s in one process can use
+    /// different plugin sets, e.g., to compare a custom-built plugin against the stock one. A plugin
+    /// library is loaded once per process for each `location`, though, so the plugins to compare
+    /// must be separate files. Relative locations are resolved by OpenVINO against the directory of
+    /// `plugins_xml`.
     ///
-    /// # Panics
+    /// # Errors
+    ///
+    /// Returns [`SetupError::InvalidConfig`] if the path is not valid UTF-8 or as described in
+    /// [`Core::new_with_config`].
+    pub fn new_with_plugins_xml(
+        plugins_xml: impl AsRef<Path>,
+    ) -> std::result::Result<Core, SetupError> {
+        Core::new_with_config(&path_to_string(plugins_xml.as_ref())?)
+    }
+
+    /// The plugin configuration file this [`Core`] was constructed with (see
+    /// [`Core::new_with_plugins_xml`]), or `None` if it uses the default plugins.
+    pub fn plugins_xml(&self) -> Option<&Path> {
+        self.plugins_xml.as_deref()
+    }
+
+    /// Construct a new OpenVINO [`Core`] configured by `config` (e.g., loaded from a configuration
+    /// file): this uses the plugin configuration file, if any, and sets the properties and cache
+    /// directory on the configured device. The device itself is not remembered; pass it to
+    /// [`Core::compile_model`] (e.g., as `DeviceType::from(config.device.as_str())`).
+    ///
+    /// # Errors
     ///
-    /// This function panics if OpenVINO returns a device name these bindings do not yet recognize.
+    /// Returns [`SetupError::InvalidConfig`] if the configuration is invalid (see
+    /// [`CoreConfig::validate`]) or if OpenVINO rejects one of the properties, naming it.
+    pub fn from_config(config: &CoreConfig) -> std::result::Result<Core, SetupError> {
+        let properties = config.validate()?;
+        let mut core = match &config.plugins_xml {
+            Some(plugins_xml) => Core::new_with_plugins_xml(plugins_xml)?,
+            None => Core::new()?,
+        };
+        let device = DeviceType::from(config.device.as_str());
+        for (key, value) in &properties {
+            core.set_property(&device, key, value).map_err(|e| {
+                SetupError::InvalidConfig(format!(
+                    "cannot set `{}` to `{value}` on {device}: {e}",
+                    key.as_ref()
+                ))
+            })?;
+        }
+        Ok(core)
+    }
+
+    /// Gets device plugins version information.
+    ///
+    /// A device name can be complex and identify multiple devices at once, like `HETERO:CPU,GPU`.
+    /// In this case, the returned map contains multiple entries, each per device. Each [`Version`]
+    /// describes the plugin implementing the device; print it (or see [`Version::build_info`]) to
+    /// find out which plugin build is in use.
     pub fn versions(&self, device_name: &str) -> Result<Vec<(DeviceType, Version)>> {
-        let device_name = cstr!(device_name);
+        let device_name = cstr!(device_name)?;
         let mut ov_version_list = openvino_sys::ov_core_version_list_t {
             versions: std::ptr::null_mut(),
             size: 0,
@@ -64,14 +318,13 @@ impl Core {
             std::ptr::addr_of_mut!(ov_version_list)
         ))?;
 
-        let ov_versions =
-            unsafe { slice::from_raw_parts(ov_version_list.versions, ov_version_list.size) };
+        let ov_versions = unsafe { slice_from_raw(ov_version_list.versions, ov_version_list.size) };
 
         let mut versions: Vec<(DeviceType, Version)> = Vec::with_capacity(ov_version_list.size);
         for ov_version in ov_versions {
             let c_str_device_name = unsafe { std::ffi::CStr::from_ptr(ov_version.device_name) };
             let device_name = c_str_device_name.to_string_lossy();
-            let device_type = DeviceType::from_str(device_name.as_ref()).unwrap();
+            let device_type = DeviceType::from(device_name.as_ref()).to_owned();
             versions.push((device_type, Version::from(&ov_version.version)));
         }
 
@@ -81,9 +334,10 @@ impl Core {
 
     /// Gets devices available for inference.
     ///
-    /// # Panics
-    ///
-    /// This function panics if OpenVINO returns a device name these bindings do not yet recognize.
+    /// The device plugins enumerate their devices once per [`Core`], when first used (e.g., by
+    /// this call or by compiling a model), and answer from that list afterwards: a device which
+    /// appears later (e.g., a GPU or NPU whose driver restarted) is not listed until
+    /// [`Core::refresh_devices`].
     pub fn available_devices(&self) -> Result<Vec<DeviceType>> {
         let mut ov_available_devices = openvino_sys::ov_available_devices_t {
             devices: std::ptr::null_mut(),
@@ -94,15 +348,14 @@ impl Core {
             std::ptr::addr_of_mut!(ov_available_devices)
         ))?;
 
-        let ov_devices = unsafe {
-            slice::from_raw_parts(ov_available_devices.devices, ov_available_devices.size)
-        };
+        let ov_devices =
+            unsafe { slice_from_raw(ov_available_devices.devices, ov_available_devices.size) };
 
         let mut devices = Vec::with_capacity(ov_available_devices.size);
         for ov_device in ov_devices {
             let c_str_device_name = unsafe { std::ffi::CStr::from_ptr(*ov_device) };
             let device_name = c_str_device_name.to_string_lossy();
-            let device_type = DeviceType::from_str(device_name.as_ref()).unwrap();
+            let device_type = DeviceType::from(device_name.as_ref()).to_owned();
             devices.push(device_type);
         }
 
@@ -110,16 +363,52 @@ impl Core {
         Ok(devices)
     }
 
-    /// Gets properties related to device behavior.
+    /// Enumerate the available devices again, e.g., so that a long-running service notices a GPU or
+    /// NPU which appeared after a driver restart (see [`Core::available_devices`]), and return them.
     ///
-    /// The method extracts information that can be set via the [`Core::set_property`] method.
+    /// This [`Core`] is kept, with its configuration, and need not be recreated: the models
+    /// already compiled with it keep working and need not be recompiled, as do the models already
+    /// read. Since the C API cannot reload the device plugins, only the underlying OpenVINO core is
+    /// replaced, with one created from the library already loaded, using the same plugin
+    /// configuration file (see [`Core::plugins_xml`]) and with the properties previously set with
+    /// [`Core::set_property`] set again; the devices of the plugins are re-enumerated on first
+    /// use.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// This function panics in the unlikely case OpenVINO returns a non-UTF8 string.
+    /// Returns an error if the new core cannot be created, if one of the properties cannot be set
+    /// again (in which case this [`Core`] is unchanged) or if the devices cannot be listed.
+    pub fn refresh_devices(&mut self) -> std::result::Result<Vec<DeviceType<'static>>, SetupError> {
+        let mut replacement = self.replacement()?;
+        // The previous OpenVINO core is freed with the replacement.
+        std::mem::swap(&mut self.ptr, &mut replacement.ptr);
+        let devices = self.available_devices()?;
+        Ok(devices.iter().map(DeviceType::to_owned).collect())
+    }
+
+    /// Create a new core like this one: with the same plugin configuration file and the properties
+    /// set with [`Core::set_property`]. It is created from the library already loaded, which this
+    /// core and its models use: loading a library again could load another one (e.g., after
+    /// [`Core::new_for_version`]).
+    fn replacement(&self) -> std::result::Result<Core, SetupError> {
+        let mut replacement = match &self.plugins_xml {
+            Some(plugins_xml) => Core::create_with_config(&path_to_string(plugins_xml)?)?,
+            None => Core::create()?,
+        };
+        for (device, key, value) in &self.properties {
+            replacement.set_property(device, key, value)?;
+        }
+        Ok(replacement)
+    }
+
+    /// Gets properties related to device behavior.
+    ///
+    /// The method extracts information that can be set via the [`Core::set_property`] method.
+    /// In the unlikely case OpenVINO returns a non-UTF8 value, invalid sequences are replaced with
+    /// `U+FFFD REPLACEMENT CHARACTER`.
     pub fn get_property(&self, device_name: &DeviceType, key: &PropertyKey) -> Result<String> {
-        let ov_device_name = cstr!(device_name.as_ref());
-        let ov_prop_key = cstr!(key.as_ref());
+        let ov_device_name = cstr!(device_name.as_ref())?;
+        let ov_prop_key = cstr!(key.as_ref())?;
         let mut ov_prop_value = std::ptr::null_mut();
         try_unsafe!(ov_core_get_property(
             self.ptr,
@@ -128,12 +417,26 @@ impl Core {
             std::ptr::addr_of_mut!(ov_prop_value)
         ))?;
         let rust_prop = unsafe { CStr::from_ptr(ov_prop_value) }
-            .to_str()
-            .unwrap()
-            .to_owned();
+            .to_string_lossy()
+            .into_owned();
         Ok(rust_prop)
     }
 
+    /// Read the current value of each property `device_name` supports (see
+    /// [`PropertyKey::SupportedProperties`]), by key, e.g., to log the configuration which
+    /// produced some results. Properties whose value cannot be read as a string are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the supported properties cannot be listed (e.g., the device is not
+    /// available).
+    pub fn dump_properties(&self, device_name: &DeviceType) -> Result<BTreeMap<String, String>> {
+        let supported = self.get_property(device_name, &PropertyKey::SupportedProperties)?;
+        Ok(dump_properties(&supported, |key| {
+            self.get_property(device_name, key)
+        }))
+    }
+
     /// Sets a property for a device.
     pub fn set_property(
         &mut self,
@@ -141,15 +444,19 @@ impl Core {
         key: &RwPropertyKey,
         value: &str,
     ) -> Result<()> {
-        let ov_device_name = cstr!(device_name.as_ref());
-        let ov_prop_key = cstr!(key.as_ref());
-        let ov_prop_value = cstr!(value);
+        let ov_device_name = cstr!(device_name.as_ref())?;
+        let ov_prop_key = cstr!(key.as_ref())?;
+        let ov_prop_value = cstr!(value)?;
         try_unsafe!(ov_core_set_property(
             self.ptr,
             ov_device_name.as_ptr(),
             ov_prop_key.as_ptr(),
             ov_prop_value.as_ptr(),
         ))?;
+        let device = device_name.to_owned();
+        self.properties.retain(|(d, k, _)| *d != device || k != key);
+        self.properties
+            .push((device, key.clone(), value.to_string()));
         Ok(())
     }
 
@@ -165,11 +472,215 @@ impl Core {
         Ok(())
     }
 
+    /// Set the priority of the models subsequently compiled for `device` (i.e.,
+    /// [`RwPropertyKey::HintModelPriority`]), e.g., to deprioritize background models in a
+    /// multi-tenant server.
+    pub fn set_model_priority(
+        &mut self,
+        device_name: &DeviceType,
+        priority: ModelPriority,
+    ) -> Result<()> {
+        self.set_property(
+            device_name,
+            &RwPropertyKey::HintModelPriority,
+            priority.as_ref(),
+        )
+    }
+
+    /// Compile `model` for `device` with `priority` (see [`ModelPriority`] for the devices which
+    /// honor it), leaving the priority of the models subsequently compiled for `device`
+    /// unchanged, unlike [`Core::set_model_priority`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device does not support [`RwPropertyKey::HintModelPriority`], or
+    /// any error from compiling the model.
+    pub fn compile_model_with_priority(
+        &mut self,
+        model: &Model,
+        device: DeviceType,
+        priority: ModelPriority,
+    ) -> Result<CompiledModel> {
+        self.compile_model_with_properties(
+            model,
+            device,
+            &[(RwPropertyKey::HintModelPriority, priority.as_ref())],
+        )
+    }
+
+    /// Configure how the CPU device runs the inference threads of the models subsequently
+    /// compiled for it (see [`CpuConfig`]), e.g., so that a latency-sensitive application can
+    /// co-exist with other workloads.
+    pub fn set_cpu_config(&mut self, config: &CpuConfig) -> Result<()> {
+        for (key, value) in config.properties() {
+            self.set_property(&DeviceType::CPU, &key, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Configure `device` so that the models subsequently compiled for it compute reproducible
+    /// outputs, e.g., to validate numerical parity against a reference implementation: a single
+    /// stream, the `ACCURACY` execution mode and `f32` inference precision (which disable
+    /// optimizations such as lower-precision or dynamically quantized kernels) and, on the CPU, a
+    /// fixed number of pinned `threads`, since the order of parallel reductions depends on it.
+    ///
+    /// The same outputs are then expected from run to run on the same machine and OpenVINO
+    /// version, not across devices or versions. This trades away throughput; do not use it in
+    /// production.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `threads` is zero, or an error if the
+    /// device does not support one of the properties or refuses its value; the device is then
+    /// left as it was.
+    pub fn set_deterministic_mode(&mut self, device: &DeviceType, threads: usize) -> Result<()> {
+        if threads == 0 {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let properties = deterministic_properties(device, threads);
+        // Reading the current values first fails, before any change, if a property is not
+        // supported; they are restored if a value is refused.
+        let previous = properties
+            .iter()
+            .map(|(key, _)| self.get_property(device, &key.clone().into()))
+            .collect::<Result<Vec<_>>>()?;
+        let recorded = self.recorded_properties();
+        for (i, (key, value)) in properties.iter().enumerate() {
+            if let Err(e) = self.set_property(device, key, value) {
+                for ((key, _), value) in properties.iter().zip(&previous).take(i) {
+                    let _ = self.set_property(device, key, value);
+                }
+                self.properties = recorded;
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the candidate devices, in priority order, of a virtual device such as
+    /// [`DeviceType::AUTO`] (i.e., [`RwPropertyKey::DevicePriorities`], which this overwrites).
+    /// Candidates may name a specific device, e.g., `GPU.1`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `candidates` is empty, names a device
+    /// twice, or contains an empty or comma-separated name; OpenVINO would otherwise only detect
+    /// this when compiling a model.
+    pub fn set_device_priorities(
+        &mut self,
+        device_name: &DeviceType,
+        candidates: &[DeviceType],
+    ) -> Result<()> {
+        let priorities = device_priorities(candidates)?;
+        self.set_property(device_name, &RwPropertyKey::DevicePriorities, &priorities)
+    }
+
+    /// Enable or disable the caching of compiled models by the NPU driver (i.e., the inverse of
+    /// [`RwPropertyKey::NpuBypassUmdCaching`]). The driver caches by default, which makes all but
+    /// the first compilation of a model fast; disable it, e.g., to measure compilation times or to
+    /// use OpenVINO's cache instead (see [`RwPropertyKey::CacheDir`]).
+    pub fn set_npu_driver_caching(&mut self, enabled: bool) -> Result<()> {
+        let bypass = if enabled { "NO" } else { "YES" };
+        self.set_property(
+            &DeviceType::NPU,
+            &RwPropertyKey::NpuBypassUmdCaching,
+            bypass,
+        )
+    }
+
+    /// Cache the kernels compiled by the GPU device in `cache` (i.e., set the GPU's
+    /// [`RwPropertyKey::CacheDir`]), after checking that the directory is writable; see
+    /// [`GpuKernelCache`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SetupError::InvalidConfig`] if the directory cannot be used (see
+    /// [`GpuKernelCache::check`]), or an error if the GPU device does not accept the property.
+    #[cfg(feature = "gpu")]
+    pub fn set_gpu_kernel_cache(
+        &mut self,
+        cache: &GpuKernelCache,
+    ) -> std::result::Result<(), SetupError> {
+        cache.check()?;
+        let dir = path_to_string(cache.dir())?;
+        self.set_property(&DeviceType::GPU, &RwPropertyKey::CacheDir, &dir)?;
+        Ok(())
+    }
+
+    /// The cache the GPU device compiles kernels into (i.e., the GPU's [`RwPropertyKey::CacheDir`]),
+    /// or `None` if caching is disabled.
+    #[cfg(feature = "gpu")]
+    pub fn gpu_kernel_cache(&self) -> Result<Option<GpuKernelCache>> {
+        let dir = self.get_property(&DeviceType::GPU, &RwPropertyKey::CacheDir.into())?;
+        Ok((!dir.is_empty()).then(|| GpuKernelCache::new(dir)))
+    }
+
+    /// Compile `model` for `device` and discard the result, so that the compiled model is cached
+    /// (by OpenVINO, if [`RwPropertyKey::CacheDir`] is set, or by the NPU driver) and later
+    /// compilations with the same properties load it from the cache. This is useful, e.g., during
+    /// installation or in the background at startup, since the first compilation for an NPU can
+    /// take much longer than inference.
+    pub fn prewarm_cache(&mut self, model: &Model, device: DeviceType) -> Result<()> {
+        self.compile_model(model, device).map(drop)
+    }
+
+    /// Import a compiled model previously exported for `device` (see
+    /// [`CompiledModel::export_model`]), skipping compilation.
+    pub fn import_model(&mut self, blob: &[u8], device: DeviceType) -> Result<CompiledModel> {
+        let device = device.into_c_string()?;
+        let mut compiled_model = std::ptr::null_mut();
+        try_unsafe!(ov_core_import_model(
+            self.ptr,
+            blob.as_ptr().cast::<c_char>(),
+            blob.len(),
+            device.as_ptr(),
+            std::ptr::addr_of_mut!(compiled_model)
+        ))?;
+        Ok(CompiledModel::from_ptr(compiled_model))
+    }
+
+    /// Import a compiled model from a file previously exported for `device` (see
+    /// [`CompiledModel::export_model`]), e.g., a precompiled NPU blob deployed with an
+    /// application.
+    ///
+    /// A blob can only be imported on the kind of device and, usually, with the OpenVINO release
+    /// it was compiled for. The C API does not expose the device or version recorded in a blob,
+    /// so this checks that `device` is available and lets the device's plugin check the blob.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SetupError::Io`] if the file cannot be read and [`SetupError::IncompatibleBlob`]
+    /// if the file is empty, the device is not available or its plugin rejects the blob (naming
+    /// the plugin's version), so that an application can fall back to compiling the model.
+    pub fn import_model_from_file(
+        &mut self,
+        path: &str,
+        device: DeviceType,
+    ) -> std::result::Result<CompiledModel, SetupError> {
+        let blob = std::fs::read(path)?;
+        if blob.is_empty() {
+            return Err(SetupError::IncompatibleBlob(format!("{path} is empty")));
+        }
+        let versions = self
+            .versions(device.as_ref())
+            .map_err(|e| SetupError::IncompatibleBlob(format!("{device} is not available: {e}")))?;
+        let versions: Vec<String> = versions
+            .iter()
+            .map(|(device, version)| format!("{device} {version}"))
+            .collect();
+        self.import_model(&blob, device).map_err(|e| {
+            SetupError::IncompatibleBlob(format!(
+                "{path} was rejected by {}: {e}",
+                versions.join(", ")
+            ))
+        })
+    }
+
     /// Read a Model from a pair of files: `model_path` points to an XML file containing the
     /// OpenVINO model IR and `weights_path` points to the binary weights file.
     pub fn read_model_from_file(&mut self, model_path: &str, weights_path: &str) -> Result<Model> {
-        let model_path = cstr!(model_path);
-        let weights_path = cstr!(weights_path);
+        let model_path = cstr!(model_path)?;
+        let weights_path = cstr!(weights_path)?;
         let mut ptr = std::ptr::null_mut();
         try_unsafe!(ov_core_read_model(
             self.ptr,
@@ -180,6 +691,40 @@ impl Core {
         Ok(Model::from_ptr(ptr))
     }
 
+    /// Read a model from a single file in any format OpenVINO has a frontend for (e.g., `.onnx`,
+    /// `.pb`, `.tflite`, or `.xml` with the weights in the `.bin` file of the same name).
+    ///
+    /// # Errors
+    ///
+    /// When a model in a format other than OpenVINO IR (see [`ModelFormat::detect`]) cannot be
+    /// read, fails with [`SetupError::UnsupportedFormat`], explaining which frontend the format
+    /// requires and which are installed (see [`openvino_finder::find_frontends`]); e.g., `PyTorch`
+    /// files must be converted to IR first; its source is the error OpenVINO returned. Otherwise,
+    /// fails with [`SetupError::Inference`].
+    pub fn read_model_from_path(&mut self, path: &str) -> std::result::Result<Model, SetupError> {
+        let c_path = cstr!(path)?;
+        let mut ptr = std::ptr::null_mut();
+        let read = try_unsafe!(ov_core_read_model(
+            self.ptr,
+            c_path.as_ptr(),
+            std::ptr::null(),
+            std::ptr::addr_of_mut!(ptr)
+        ));
+        match read {
+            Ok(()) => Ok(Model::from_ptr(ptr)),
+            Err(e) => match ModelFormat::detect(Path::new(path)) {
+                Some(format) if format != ModelFormat::Ir => {
+                    let frontends = openvino_finder::find_frontends();
+                    Err(SetupError::UnsupportedFormat {
+                        message: format!("{path}: {}", format.guidance(&frontends)),
+                        source: e,
+                    })
+                }
+                _ => Err(e.into()),
+            },
+        }
+    }
+
     /// Read model with model and weights loaded in memory.
     pub fn read_model_from_buffer(
         &mut self,
@@ -197,22 +742,271 @@ impl Core {
         Ok(Model::from_ptr(ptr))
     }
 
+    /// Read a model from a pair of readers: `model` produces the OpenVINO model IR (XML) and
+    /// `weights` the binary weights (an empty reader means the model has no weights). This allows
+    /// loading models from embedded resources, archives or network streams without temporary
+    /// files.
+    ///
+    /// Both readers are consumed entirely and buffered in memory; the weights are then copied into
+    /// a [`Tensor`] owned by OpenVINO (see [`Core::read_model_from_buffer`]), so the peak memory
+    /// use is roughly twice the size of the weights.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`SetupError::Io`] if either reader fails and with [`SetupError::Inference`] if
+    /// OpenVINO cannot read the model.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the unlikely case the size of the weights cannot be represented as an `i64`.
+    pub fn read_model_from_reader(
+        &mut self,
+        mut model: impl Read,
+        mut weights: impl Read,
+    ) -> std::result::Result<Model, SetupError> {
+        let mut model_buffer = vec![];
+        model.read_to_end(&mut model_buffer)?;
+        let mut weights_buffer = vec![];
+        weights.read_to_end(&mut weights_buffer)?;
+        let weights_tensor = if weights_buffer.is_empty() {
+            None
+        } else {
+            let tensor = weights_to_tensor(&weights_buffer)?;
+            // Release the intermediate copy before OpenVINO parses the model.
+            drop(weights_buffer);
+            Some(tensor)
+        };
+        Ok(self.read_model_from_buffer(&model_buffer, weights_tensor.as_ref())?)
+    }
+
+    /// Read an encrypted (or otherwise obfuscated) model: `decrypt` is applied to the `model` IR
+    /// and then to the `weights` (unless empty) before both are passed to
+    /// [`Core::read_model_from_buffer`].
+    ///
+    /// The decrypted IR and the intermediate copy of the weights are overwritten with zeroes as
+    /// soon as OpenVINO no longer needs them, including on failure. The decrypted weights do remain
+    /// in memory, in the [`Tensor`] OpenVINO reads them from, for as long as OpenVINO needs them
+    /// (the model's constants may share this memory). To keep plaintext out of swap and core
+    /// dumps:
+    /// - `decrypt` should allocate its output once, at its final size (e.g., with
+    ///   [`Vec::with_capacity`]), since growing a `Vec` leaves stale copies behind;
+    /// - lock the process' memory (e.g., `mlockall(MCL_CURRENT | MCL_FUTURE)` on Linux) before
+    ///   calling this, since the plaintext is spread across allocations made by both Rust and
+    ///   OpenVINO;
+    /// - disable core dumps (e.g., `setrlimit(RLIMIT_CORE, ...)` or `prctl(PR_SET_DUMPABLE, 0)` on
+    ///   Linux).
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`SetupError::Io`] if `decrypt` fails (e.g., with
+    /// [`std::io::ErrorKind::InvalidData`]) and with [`SetupError::Inference`] if OpenVINO cannot
+    /// read the decrypted model.
+    pub fn read_model_with_decryption(
+        &mut self,
+        model: &[u8],
+        weights: &[u8],
+        mut decrypt: impl FnMut(&[u8]) -> std::io::Result<Vec<u8>>,
+    ) -> std::result::Result<Model, SetupError> {
+        let mut model_plaintext = decrypt(model)?;
+        let weights_tensor = if weights.is_empty() {
+            None
+        } else {
+            let tensor = decrypt(weights)
+                .map_err(SetupError::from)
+                .and_then(|mut plaintext| {
+                    let tensor = weights_to_tensor(&plaintext);
+                    wipe(&mut plaintext);
+                    Ok(tensor?)
+                });
+            match tensor {
+                Ok(tensor) => Some(tensor),
+                Err(e) => {
+                    wipe(&mut model_plaintext);
+                    return Err(e);
+                }
+            }
+        };
+        let model = self.read_model_from_buffer(&model_plaintext, weights_tensor.as_ref());
+        wipe(&mut model_plaintext);
+        Ok(model?)
+    }
+
     /// Compile a model to `CompiledModel`.
     pub fn compile_model(&mut self, model: &Model, device: DeviceType) -> Result<CompiledModel> {
-        let device: CString = device.into();
-        let mut compiled_model = std::ptr::null_mut();
-        let num_property_args = 0;
-        try_unsafe!(ov_core_compile_model(
-            self.ptr,
-            model.as_ptr(),
-            device.as_ptr(),
-            num_property_args,
-            std::ptr::addr_of_mut!(compiled_model)
-        ))?;
-        Ok(CompiledModel::from_ptr(compiled_model))
+        compile(CorePtr(self.ptr), model, &device.into_c_string()?)
+    }
+
+    /// Compile a model to `CompiledModel` with `properties` set for this compilation only, leaving
+    /// those of the core (see [`Core::set_property`]) unchanged.
+    ///
+    /// `ov_core_compile_model` takes the properties of a single compilation as variadic arguments,
+    /// which the bindings cannot pass, so the properties are set on the core for the duration of
+    /// the compilation and then restored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a property cannot be read (to restore it) or set, if compiling the model
+    /// fails, or if a property cannot be restored.
+    pub(crate) fn compile_model_with_properties(
+        &mut self,
+        model: &Model,
+        device: DeviceType,
+        properties: &[(RwPropertyKey, &str)],
+    ) -> Result<CompiledModel> {
+        let device_name = device.to_owned();
+        let recorded = self.recorded_properties();
+        let mut previous = Vec::with_capacity(properties.len());
+        let mut result = Ok(());
+        for (key, value) in properties {
+            result = match self.get_property(&device_name, &key.clone().into()) {
+                Ok(current) => {
+                    previous.push((key, current));
+                    self.set_property(&device_name, key, value)
+                }
+                Err(e) => Err(e),
+            };
+            if result.is_err() {
+                break;
+            }
+        }
+        let compiled_model = result.and_then(|()| self.compile_model(model, device));
+        let mut restored = Ok(());
+        for (key, value) in previous.into_iter().rev() {
+            restored = restored.and(self.set_property(&device_name, key, &value));
+        }
+        self.properties = recorded;
+        let compiled_model = compiled_model?;
+        restored?;
+        Ok(compiled_model)
+    }
+
+    /// A copy of the properties set on this core, which [`Core::refresh_devices`] sets again.
+    fn recorded_properties(&self) -> Vec<(DeviceType<'static>, RwPropertyKey, String)> {
+        self.properties
+            .iter()
+            .map(|(device, key, value)| (device.to_owned(), key.clone(), value.clone()))
+            .collect()
+    }
+
+    /// Compile a model to `CompiledModel` as [`Core::compile_model`] does, but following `policy`:
+    /// give up on attempts which take longer than its timeout and try again after failures, e.g.,
+    /// so that a hung NPU or GPU driver does not block the calling thread forever.
+    ///
+    /// OpenVINO cannot cancel a compilation, so an attempt with a timeout runs on its own thread and
+    /// is left running when it times out; it keeps using the model (hence the [`Arc`]) and the
+    /// underlying OpenVINO core until it finishes, which it may never do. This [`Core`] stays
+    /// usable: after a timeout, it switches to a new OpenVINO core, set up as
+    /// [`Core::refresh_devices`] does. If that core cannot be created, this [`Core`] is left
+    /// without one: its methods fail until [`Core::refresh_devices`] succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::CompileTimedOut`] if the last attempt timed out, the error of the
+    /// last attempt if it failed, or an error if the new core cannot be created after a timeout.
+    pub fn compile_model_with_policy(
+        &mut self,
+        model: &Arc<Model>,
+        device: DeviceType,
+        policy: &CompilePolicy,
+    ) -> std::result::Result<CompiledModel, SetupError> {
+        let device = device.into_c_string()?;
+        let mut retries = policy.retries;
+        loop {
+            let result = match policy.timeout {
+                Some(timeout) => self.compile_with_timeout(model, &device, timeout)?,
+                None => compile(CorePtr(self.ptr), model, &device),
+            };
+            match result {
+                Err(_) if retries > 0 => {
+                    retries -= 1;
+                    thread::sleep(policy.retry_delay);
+                }
+                result => return Ok(result?),
+            }
+        }
+    }
+
+    /// Make one attempt of [`Core::compile_model_with_policy`] with a `timeout`.
+    fn compile_with_timeout(
+        &mut self,
+        model: &Arc<Model>,
+        device: &CString,
+        timeout: Duration,
+    ) -> std::result::Result<Result<CompiledModel>, SetupError> {
+        let (core, model, device) = (CorePtr(self.ptr), Arc::clone(model), device.clone());
+        let outcome = run_with_timeout(timeout, move || compile(core, &model, &device));
+        match outcome {
+            Outcome::Done(result) => Ok(result),
+            Outcome::TimedOut(detached) => {
+                // The compilation keeps using the OpenVINO core: hand it over, leaving a new one in
+                // its place or, if that cannot be created, none (i.e., null, which OpenVINO frees
+                // as a no-op).
+                let replacement = self.replacement();
+                let mut running = Core {
+                    ptr: std::ptr::null_mut(),
+                    plugins_xml: None,
+                    properties: vec![],
+                };
+                std::mem::swap(&mut self.ptr, &mut running.ptr);
+                detached.hand_over(running);
+                let mut replacement = replacement?;
+                std::mem::swap(&mut self.ptr, &mut replacement.ptr);
+                Ok(Err(InferenceError::CompileTimedOut))
+            }
+        }
     }
 }
 
+/// The pointer to an OpenVINO core, which may be used from any thread: OpenVINO cores are
+/// thread-safe.
+#[derive(Clone, Copy)]
+struct CorePtr(*mut ov_core_t);
+
+unsafe impl Send for CorePtr {}
+
+/// Compile `model` for `device` with the OpenVINO core `core`.
+fn compile(core: CorePtr, model: &Model, device: &CStr) -> Result<CompiledModel> {
+    let mut compiled_model = std::ptr::null_mut();
+    let num_property_args = 0;
+    try_unsafe!(ov_core_compile_model(
+        core.0,
+        model.as_ptr(),
+        device.as_ptr(),
+        num_property_args,
+        std::ptr::addr_of_mut!(compiled_model)
+    ))?;
+    Ok(CompiledModel::from_ptr(compiled_model).with_input_layouts(model.input_layouts()))
+}
+
+/// Validate and join a list of candidate devices as expected by
+/// [`RwPropertyKey::DevicePriorities`], e.g., `GPU,CPU`.
+fn device_priorities(candidates: &[DeviceType]) -> Result<String> {
+    let mut names: Vec<&str> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let name = candidate.as_ref();
+        if name.is_empty() || name.contains(',') || names.contains(&name) {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        names.push(name);
+    }
+    if names.is_empty() {
+        return Err(InferenceError::ParameterMismatch);
+    }
+    Ok(names.join(","))
+}
+
+/// Copy model weights into a [`Tensor`], as expected by [`Core::read_model_from_buffer`].
+///
+/// # Panics
+///
+/// Panics in the unlikely case the size of the weights cannot be represented as an `i64`.
+pub(crate) fn weights_to_tensor(weights: &[u8]) -> Result<Tensor> {
+    let shape = Shape::new(&[1, i64::try_from(weights.len()).unwrap()])?;
+    let mut tensor = Tensor::new(ElementType::U8, &shape)?;
+    tensor.get_raw_data_mut()?.copy_from_slice(weights);
+    Ok(tensor)
+}
+
 #[cfg(test)]
 mod core_tests {
     use super::*;
@@ -255,6 +1049,14 @@ mod core_tests {
         }
     }
 
+    #[test]
+    fn test_dump_properties() {
+        let core = Core::new().unwrap();
+        let properties = core.dump_properties(&DeviceType::CPU).unwrap();
+        assert!(properties.contains_key(DeviceFullName.as_ref()));
+        assert!(!properties.contains_key(SupportedProperties.as_ref()));
+    }
+
     #[test]
     fn test_get_core_properties_rw() {
         let core = Core::new().unwrap();
@@ -283,6 +1085,61 @@ mod core_tests {
         }
     }
 
+    #[test]
+    fn test_device_priorities() {
+        assert_eq!(
+            device_priorities(&[
+                DeviceType::GPU,
+                DeviceType::Other("NPU.1".into()),
+                DeviceType::CPU
+            ]),
+            Ok("GPU,NPU.1,CPU".to_string())
+        );
+        assert!(device_priorities(&[]).is_err());
+        assert!(device_priorities(&[DeviceType::GPU, DeviceType::GPU]).is_err());
+        assert!(device_priorities(&[DeviceType::Other("GPU,CPU".into())]).is_err());
+    }
+
+    #[test]
+    fn test_set_deterministic_mode() {
+        let mut core = Core::new().unwrap();
+        core.set_deterministic_mode(&DeviceType::CPU, 2).unwrap();
+        assert_eq!(
+            core.get_property(&DeviceType::CPU, &NumStreams.into())
+                .unwrap(),
+            "1"
+        );
+        assert_eq!(
+            core.get_property(&DeviceType::CPU, &InferenceNumThreads.into())
+                .unwrap(),
+            "2"
+        );
+        assert_eq!(
+            core.set_deterministic_mode(&DeviceType::CPU, 0),
+            Err(InferenceError::ParameterMismatch)
+        );
+    }
+
+    #[test]
+    fn test_set_auto_priorities() {
+        let mut core = Core::new().unwrap();
+        core.set_device_priorities(&DeviceType::AUTO, &[DeviceType::GPU, DeviceType::CPU])
+            .unwrap();
+        // This is the same property as `DevicePriorities`.
+        assert_eq!(
+            core.get_property(&DeviceType::AUTO, &DevicePriorities.into())
+                .unwrap(),
+            "GPU,CPU"
+        );
+        core.set_model_priority(&DeviceType::AUTO, ModelPriority::Low)
+            .unwrap();
+        assert_eq!(
+            core.get_property(&DeviceType::AUTO, &HintModelPriority.into())
+                .unwrap(),
+            "LOW"
+        );
+    }
+
     #[test]
     fn test_get_core_properties_unsupported() {
         let core = Core::new().unwrap();
@@ -305,3 +1162,561 @@ mod core_tests {
         }
     }
 }
+
+/// These tests replace the OpenVINO functions with test doubles (see `openvino_sys::mock`), so they
+/// check the logic of these bindings without an OpenVINO installation.
+#[cfg(all(test, feature = "mock"))]
+mod mock_tests {
+    use super::*;
+    use openvino_sys::mock::{self, Functions};
+    use openvino_sys::{ov_available_devices_t, ov_core_t, ov_status_e};
+    use std::ffi::c_char;
+
+    unsafe extern "C" fn create(core: *mut *mut ov_core_t) -> ov_status_e {
+        *core = std::ptr::NonNull::dangling().as_ptr();
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn free(_core: *mut ov_core_t) {}
+
+    /// The functions needed to create and drop a [`Core`].
+    fn core_functions() -> Functions {
+        Functions {
+            ov_core_create: Some(create),
+            ov_core_free: Some(free),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn creation_errors() {
+        unsafe extern "C" fn fail(_core: *mut *mut ov_core_t) -> ov_status_e {
+            ov_status_e::GENERAL_ERROR
+        }
+        let functions = Functions {
+            ov_core_create: Some(fail),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            assert!(matches!(
+                Core::new(),
+                Err(SetupError::Inference(InferenceError::GeneralError))
+            ));
+        });
+    }
+
+    #[test]
+    fn plugin_sets() {
+        unsafe extern "C" fn create_with_config(
+            xml_config_file: *const c_char,
+            core: *mut *mut ov_core_t,
+        ) -> ov_status_e {
+            if CStr::from_ptr(xml_config_file)
+                .to_bytes()
+                .ends_with(b"rejected.xml")
+            {
+                return ov_status_e::GENERAL_ERROR;
+            }
+            *core = std::ptr::NonNull::dangling().as_ptr();
+            ov_status_e::OK
+        }
+        let dir = std::env::temp_dir().join("openvino-plugin-sets");
+        std::fs::create_dir_all(&dir).unwrap();
+        let plugins_xml = |name: &str, location: &str| {
+            let path = dir.join(name);
+            let xml = format!(
+                r#"<ie><plugins><plugin name="CPU" location="{location}"/></plugins></ie>"#
+            );
+            std::fs::write(&path, xml).unwrap();
+            path
+        };
+        let stock = plugins_xml("stock.xml", "libopenvino_intel_cpu_plugin.so");
+        let custom = plugins_xml("custom.xml", "custom/libopenvino_intel_cpu_plugin.so");
+        let rejected = plugins_xml("rejected.xml", "libopenvino_intel_cpu_plugin.so");
+        let functions = Functions {
+            ov_core_create_with_config: Some(create_with_config),
+            ..core_functions()
+        };
+        mock::with_functions(functions, || {
+            let a = Core::new_with_plugins_xml(&stock).unwrap();
+            let b = Core::new_with_plugins_xml(&custom).unwrap();
+            assert_eq!(a.plugins_xml(), Some(stock.as_path()));
+            assert_eq!(b.plugins_xml(), Some(custom.as_path()));
+            assert_eq!(Core::new().unwrap().plugins_xml(), None);
+            assert!(matches!(
+                Core::new_with_plugins_xml(&rejected),
+                Err(SetupError::InvalidConfig(e)) if e.contains("rejected by OpenVINO")
+            ));
+            assert!(matches!(
+                Core::new_with_plugins_xml(dir.join("missing.xml")),
+                Err(SetupError::InvalidConfig(e)) if e.contains("cannot read")
+            ));
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn property_values() {
+        unsafe extern "C" fn get_property(
+            _core: *const ov_core_t,
+            _device_name: *const c_char,
+            key: *const c_char,
+            value: *mut *mut c_char,
+        ) -> ov_status_e {
+            let found: &'static CStr = match CStr::from_ptr(key).to_bytes() {
+                b"SUPPORTED_PROPERTIES" => c"SUPPORTED_PROPERTIES FULL_DEVICE_NAME, CACHE_DIR",
+                b"FULL_DEVICE_NAME" => c"Mock \xffCPU",
+                _ => return ov_status_e::NOT_FOUND,
+            };
+            *value = found.as_ptr().cast_mut().cast();
+            ov_status_e::OK
+        }
+        let functions = Functions {
+            ov_core_get_property: Some(get_property),
+            ..core_functions()
+        };
+        mock::with_functions(functions, || {
+            let core = Core::new().unwrap();
+            assert_eq!(
+                core.get_property(&DeviceType::CPU, &PropertyKey::DeviceFullName),
+                Ok("Mock \u{fffd}CPU".to_string())
+            );
+            assert_eq!(
+                core.get_property(&DeviceType::CPU, &PropertyKey::ModelName),
+                Err(InferenceError::NotFound)
+            );
+            assert_eq!(
+                core.dump_properties(&DeviceType::CPU).unwrap(),
+                BTreeMap::from([(
+                    "FULL_DEVICE_NAME".to_string(),
+                    "Mock \u{fffd}CPU".to_string()
+                )])
+            );
+        });
+    }
+
+    #[test]
+    fn deterministic_mode_rollback() {
+        use std::cell::RefCell;
+        thread_local! {
+            static SET: RefCell<Vec<(String, String)>> = const { RefCell::new(vec![]) };
+        }
+        unsafe extern "C" fn get_property(
+            _core: *const ov_core_t,
+            _device_name: *const c_char,
+            _key: *const c_char,
+            value: *mut *mut c_char,
+        ) -> ov_status_e {
+            *value = c"previous".as_ptr().cast_mut();
+            ov_status_e::OK
+        }
+        // The device refuses the precision.
+        unsafe extern "C" fn set_property(
+            _core: *const ov_core_t,
+            _device_name: *const c_char,
+            key: *const c_char,
+            value: *const c_char,
+        ) -> ov_status_e {
+            let string = |s| CStr::from_ptr(s).to_string_lossy().into_owned();
+            if string(key) == RwPropertyKey::HintInferencePrecision.as_ref() {
+                return ov_status_e::NOT_IMPLEMENTED;
+            }
+            SET.with_borrow_mut(|s| s.push((string(key), string(value))));
+            ov_status_e::OK
+        }
+        let functions = Functions {
+            ov_core_get_property: Some(get_property),
+            ov_core_set_property: Some(set_property),
+            ..core_functions()
+        };
+        mock::with_functions(functions, || {
+            let mut core = Core::new().unwrap();
+            assert_eq!(
+                core.set_deterministic_mode(&DeviceType::CPU, 2),
+                Err(InferenceError::NotImplemented)
+            );
+            let set = |key: &str, value: &str| (key.to_string(), value.to_string());
+            assert_eq!(
+                SET.take(),
+                [
+                    set("NUM_STREAMS", "1"),
+                    set("EXECUTION_MODE_HINT", "ACCURACY"),
+                    set("NUM_STREAMS", "previous"),
+                    set("EXECUTION_MODE_HINT", "previous"),
+                ]
+            );
+            assert!(core.properties.is_empty());
+        });
+    }
+
+    #[test]
+    fn unsupported_format() {
+        unsafe extern "C" fn read_model(
+            _core: *const ov_core_t,
+            _model_path: *const c_char,
+            _bin_path: *const c_char,
+            _model: *mut *mut openvino_sys::ov_model_t,
+        ) -> ov_status_e {
+            ov_status_e::NETWORK_NOT_READ
+        }
+        let functions = Functions {
+            ov_core_read_model: Some(read_model),
+            ..core_functions()
+        };
+        mock::with_functions(functions, || {
+            let mut core = Core::new().unwrap();
+            let Err(error) = core.read_model_from_path("model.onnx") else {
+                panic!("the model should not be read");
+            };
+            assert!(matches!(
+                &error,
+                SetupError::UnsupportedFormat { message, source: InferenceError::NetworkNotRead }
+                    if message.starts_with("model.onnx: ")
+            ));
+            let source = std::error::Error::source(&error).unwrap();
+            assert_eq!(
+                source.to_string(),
+                InferenceError::NetworkNotRead.to_string()
+            );
+            assert!(matches!(
+                core.read_model_from_path("model.xml"),
+                Err(SetupError::Inference(InferenceError::NetworkNotRead))
+            ));
+        });
+    }
+
+    #[test]
+    fn device_names() {
+        unsafe extern "C" fn get_available_devices(
+            _core: *const ov_core_t,
+            devices: *mut ov_available_devices_t,
+        ) -> ov_status_e {
+            let names: &'static mut [*mut c_char] = Box::leak(Box::new([
+                c"CPU".as_ptr().cast_mut(),
+                c"GPU.1".as_ptr().cast_mut(),
+            ]));
+            (*devices).devices = names.as_mut_ptr();
+            (*devices).size = names.len();
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn free_devices(_devices: *mut ov_available_devices_t) {}
+        let functions = Functions {
+            ov_core_get_available_devices: Some(get_available_devices),
+            ov_available_devices_free: Some(free_devices),
+            ..core_functions()
+        };
+        mock::with_functions(functions, || {
+            let core = Core::new().unwrap();
+            let devices = core.available_devices().unwrap();
+            let names: Vec<&str> = devices.iter().map(AsRef::as_ref).collect();
+            assert_eq!(names, ["CPU", "GPU.1"]);
+        });
+    }
+
+    #[test]
+    fn refreshed_devices() {
+        use std::cell::{Cell, RefCell};
+        thread_local! {
+            static CREATED: Cell<usize> = const { Cell::new(0) };
+            static FREED: RefCell<Vec<usize>> = const { RefCell::new(vec![]) };
+            static SET: RefCell<Vec<(usize, String, String)>> = const { RefCell::new(vec![]) };
+        }
+        // Each core is identified by its address: the first is 1, the second 2.
+        unsafe extern "C" fn create(core: *mut *mut ov_core_t) -> ov_status_e {
+            CREATED.set(CREATED.get() + 1);
+            *core = std::ptr::without_provenance_mut(CREATED.get());
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn free(core: *mut ov_core_t) {
+            FREED.with_borrow_mut(|f| f.push(core.addr()));
+        }
+        unsafe extern "C" fn set_property(
+            core: *const ov_core_t,
+            device_name: *const c_char,
+            key: *const c_char,
+            value: *const c_char,
+        ) -> ov_status_e {
+            let string = |s| CStr::from_ptr(s).to_string_lossy().into_owned();
+            let property = format!("{}:{}", string(device_name), string(key));
+            SET.with_borrow_mut(|s| s.push((core.addr(), property, string(value))));
+            ov_status_e::OK
+        }
+        // A GPU appears for the second core.
+        unsafe extern "C" fn get_available_devices(
+            core: *const ov_core_t,
+            devices: *mut ov_available_devices_t,
+        ) -> ov_status_e {
+            let names: &'static mut [*mut c_char] = if core.addr() == 1 {
+                Box::leak(Box::new([c"CPU".as_ptr().cast_mut()]))
+            } else {
+                Box::leak(Box::new([
+                    c"CPU".as_ptr().cast_mut(),
+                    c"GPU".as_ptr().cast_mut(),
+                ]))
+            };
+            (*devices).devices = names.as_mut_ptr();
+            (*devices).size = names.len();
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn free_devices(_devices: *mut ov_available_devices_t) {}
+        let functions = Functions {
+            ov_core_create: Some(create),
+            ov_core_free: Some(free),
+            ov_core_set_property: Some(set_property),
+            ov_core_get_available_devices: Some(get_available_devices),
+            ov_available_devices_free: Some(free_devices),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            let mut core = Core::new().unwrap();
+            core.set_property(&DeviceType::GPU, &RwPropertyKey::CacheDir, "/a")
+                .unwrap();
+            core.set_property(&DeviceType::CPU, &RwPropertyKey::NumStreams, "2")
+                .unwrap();
+            core.set_property(&DeviceType::GPU, &RwPropertyKey::CacheDir, "/b")
+                .unwrap();
+            assert_eq!(core.available_devices().unwrap(), [DeviceType::CPU]);
+
+            let devices = core.refresh_devices().unwrap();
+            assert_eq!(devices, [DeviceType::CPU, DeviceType::GPU]);
+            assert_eq!(FREED.take(), [1]);
+            let set_again: Vec<_> = SET.take().into_iter().filter(|s| s.0 == 2).collect();
+            assert_eq!(
+                set_again,
+                [
+                    (2, "CPU:NUM_STREAMS".to_string(), "2".to_string()),
+                    (2, "GPU:CACHE_DIR".to_string(), "/b".to_string()),
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn compile_properties() {
+        use openvino_sys::{ov_compiled_model_t, ov_model_t};
+        use std::cell::RefCell;
+        thread_local! {
+            static CALLS: RefCell<Vec<String>> = const { RefCell::new(vec![]) };
+        }
+        unsafe extern "C" fn get_property(
+            _core: *const ov_core_t,
+            _device_name: *const c_char,
+            _key: *const c_char,
+            value: *mut *mut c_char,
+        ) -> ov_status_e {
+            *value = c"previous".as_ptr().cast_mut();
+            ov_status_e::OK
+        }
+        // Record the properties set around the compilation.
+        unsafe extern "C" fn set_property(
+            _core: *const ov_core_t,
+            _device_name: *const c_char,
+            key: *const c_char,
+            value: *const c_char,
+        ) -> ov_status_e {
+            let string = |s| CStr::from_ptr(s).to_string_lossy().into_owned();
+            CALLS.with_borrow_mut(|c| c.push(format!("{}={}", string(key), string(value))));
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn compile_model(
+            _core: *const ov_core_t,
+            _model: *const ov_model_t,
+            _device_name: *const c_char,
+            _property_args_size: usize,
+            compiled_model: *mut *mut ov_compiled_model_t,
+        ) -> ov_status_e {
+            CALLS.with_borrow_mut(|c| c.push("compile".to_string()));
+            *compiled_model = std::ptr::NonNull::dangling().as_ptr();
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn free_model(_model: *mut ov_model_t) {}
+        unsafe extern "C" fn free_compiled_model(_compiled_model: *mut ov_compiled_model_t) {}
+        let functions = Functions {
+            ov_core_get_property: Some(get_property),
+            ov_core_set_property: Some(set_property),
+            ov_core_compile_model: Some(compile_model),
+            ov_model_free: Some(free_model),
+            ov_compiled_model_free: Some(free_compiled_model),
+            ..core_functions()
+        };
+        mock::with_functions(functions, || {
+            let mut core = Core::new().unwrap();
+            let model = Model::from_ptr(std::ptr::NonNull::dangling().as_ptr());
+            let properties = [
+                (RwPropertyKey::NumStreams, "2"),
+                (RwPropertyKey::CacheDir, "/cache"),
+            ];
+            core.compile_model_with_properties(&model, DeviceType::CPU, &properties)
+                .unwrap();
+            assert_eq!(
+                CALLS.take(),
+                [
+                    "NUM_STREAMS=2",
+                    "CACHE_DIR=/cache",
+                    "compile",
+                    "CACHE_DIR=previous",
+                    "NUM_STREAMS=previous",
+                ]
+            );
+            // The properties of the core are left as they were.
+            assert!(core.properties.is_empty());
+        });
+    }
+
+    #[test]
+    fn compile_retries() {
+        use openvino_sys::{ov_compiled_model_t, ov_model_t};
+        use std::cell::Cell;
+        thread_local! {
+            static ATTEMPTS: Cell<usize> = const { Cell::new(0) };
+        }
+        // The first two attempts fail.
+        unsafe extern "C" fn compile_model(
+            _core: *const ov_core_t,
+            _model: *const ov_model_t,
+            _device_name: *const c_char,
+            _property_args_size: usize,
+            compiled_model: *mut *mut ov_compiled_model_t,
+        ) -> ov_status_e {
+            ATTEMPTS.set(ATTEMPTS.get() + 1);
+            if ATTEMPTS.get() <= 2 {
+                return ov_status_e::GENERAL_ERROR;
+            }
+            *compiled_model = std::ptr::NonNull::dangling().as_ptr();
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn free_model(_model: *mut ov_model_t) {}
+        unsafe extern "C" fn free_compiled_model(_compiled_model: *mut ov_compiled_model_t) {}
+        let functions = Functions {
+            ov_core_create: Some(create),
+            ov_core_free: Some(free),
+            ov_core_compile_model: Some(compile_model),
+            ov_model_free: Some(free_model),
+            ov_compiled_model_free: Some(free_compiled_model),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            let mut core = Core::new().unwrap();
+            let model = Arc::new(Model::from_ptr(std::ptr::NonNull::dangling().as_ptr()));
+            let policy = CompilePolicy {
+                retries: 1,
+                ..CompilePolicy::default()
+            };
+            let result = core.compile_model_with_policy(&model, DeviceType::NPU, &policy);
+            assert!(matches!(
+                result,
+                Err(SetupError::Inference(InferenceError::GeneralError))
+            ));
+            assert_eq!(ATTEMPTS.get(), 2);
+
+            ATTEMPTS.set(0);
+            let policy = CompilePolicy {
+                retries: 2,
+                ..policy
+            };
+            let result = core.compile_model_with_policy(&model, DeviceType::NPU, &policy);
+            assert!(result.is_ok());
+            assert_eq!(ATTEMPTS.get(), 3);
+        });
+    }
+
+    #[test]
+    fn compile_timeouts() {
+        use openvino_sys::{ov_compiled_model_t, ov_model_t};
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Mutex;
+        // The compilations run on threads of their own, which share these (and the test doubles).
+        static CREATED: AtomicUsize = AtomicUsize::new(0);
+        static CREATE_FAILS: AtomicBool = AtomicBool::new(false);
+        static FREED: Mutex<Vec<usize>> = Mutex::new(vec![]);
+        static HANG: AtomicBool = AtomicBool::new(false);
+        // Each core is identified by its address: the first is 1, the second 2.
+        unsafe extern "C" fn create(core: *mut *mut ov_core_t) -> ov_status_e {
+            if CREATE_FAILS.load(Ordering::SeqCst) {
+                return ov_status_e::GENERAL_ERROR;
+            }
+            let created = CREATED.fetch_add(1, Ordering::SeqCst) + 1;
+            *core = std::ptr::without_provenance_mut(created);
+            ov_status_e::OK
+        }
+        // As OpenVINO does, freeing the null core does nothing.
+        unsafe extern "C" fn free(core: *mut ov_core_t) {
+            if !core.is_null() {
+                FREED.lock().unwrap().push(core.addr());
+            }
+        }
+        // The compilation hangs while `HANG` is set.
+        unsafe extern "C" fn compile_model(
+            _core: *const ov_core_t,
+            _model: *const ov_model_t,
+            _device_name: *const c_char,
+            _property_args_size: usize,
+            compiled_model: *mut *mut ov_compiled_model_t,
+        ) -> ov_status_e {
+            while HANG.load(Ordering::SeqCst) {
+                thread::sleep(Duration::from_millis(1));
+            }
+            *compiled_model = std::ptr::NonNull::dangling().as_ptr();
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn free_model(_model: *mut ov_model_t) {}
+        unsafe extern "C" fn free_compiled_model(_compiled_model: *mut ov_compiled_model_t) {}
+        let functions = Functions {
+            ov_core_create: Some(create),
+            ov_core_free: Some(free),
+            ov_core_compile_model: Some(compile_model),
+            ov_model_free: Some(free_model),
+            ov_compiled_model_free: Some(free_compiled_model),
+            ..Default::default()
+        };
+        let freed = || FREED.lock().unwrap().clone();
+        mock::with_global_functions(functions, || {
+            let mut core = Core::new().unwrap();
+            let model = Arc::new(Model::from_ptr(std::ptr::NonNull::dangling().as_ptr()));
+            let policy = CompilePolicy {
+                timeout: Some(Duration::from_secs(10)),
+                ..CompilePolicy::default()
+            };
+
+            // An attempt in time needs no new core.
+            assert!(core
+                .compile_model_with_policy(&model, DeviceType::NPU, &policy)
+                .is_ok());
+            assert_eq!(CREATED.load(Ordering::SeqCst), 1);
+
+            // After a timeout, the core switches to a new OpenVINO core; the previous one is freed
+            // once the compilation finishes.
+            HANG.store(true, Ordering::SeqCst);
+            let policy = CompilePolicy {
+                timeout: Some(Duration::from_millis(10)),
+                ..policy
+            };
+            assert!(matches!(
+                core.compile_model_with_policy(&model, DeviceType::NPU, &policy),
+                Err(SetupError::Inference(InferenceError::CompileTimedOut))
+            ));
+            assert_eq!(CREATED.load(Ordering::SeqCst), 2);
+            assert_eq!(core.ptr.addr(), 2);
+            assert!(freed().is_empty());
+            HANG.store(false, Ordering::SeqCst);
+            while freed().is_empty() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            assert_eq!(freed(), [1]);
+
+            // Without a new core, the core is left without one.
+            HANG.store(true, Ordering::SeqCst);
+            CREATE_FAILS.store(true, Ordering::SeqCst);
+            assert!(matches!(
+                core.compile_model_with_policy(&model, DeviceType::NPU, &policy),
+                Err(SetupError::Inference(InferenceError::GeneralError))
+            ));
+            assert!(core.ptr.is_null());
+            HANG.store(false, Ordering::SeqCst);
+            while freed().len() < 2 {
+                thread::sleep(Duration::from_millis(1));
+            }
+            assert_eq!(freed(), [1, 2]);
+        });
+    }
+}
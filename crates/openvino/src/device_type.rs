@@ -13,6 +13,9 @@ pub enum DeviceType<'a> {
     GPU,
     /// [NPU Device](https://docs.openvino.ai/2024/openvino-workflow/running-inference/inference-devices-and-modes/npu-device.html)
     NPU,
+    /// [Automatic Device Selection](https://docs.openvino.ai/2024/openvino-workflow/running-inference/inference-devices-and-modes/auto-device-selection.html):
+    /// a virtual device which picks among candidate devices (see [`crate::Core::set_device_priorities`]).
+    AUTO,
     /// [GNA Device](https://docs.openvino.ai/2024/openvino_docs_OV_UG_supported_plugins_GNA.html)
     #[deprecated = "Deprecated since OpenVINO 2024.0; use NPU device instead"]
     GNA,
@@ -20,13 +23,57 @@ pub enum DeviceType<'a> {
     Other(Cow<'a, str>),
 }
 
+impl DeviceType<'static> {
+    /// All of the known device types (i.e., excluding [`DeviceType::Other`]).
+    #[allow(deprecated)]
+    pub const ALL: &'static [DeviceType<'static>] = &[
+        DeviceType::CPU,
+        DeviceType::GPU,
+        DeviceType::NPU,
+        DeviceType::AUTO,
+        DeviceType::GNA,
+    ];
+}
+
+// Check that each known device type is listed in `DeviceType::ALL`, at its position.
+const _: () = {
+    let mut i = 0;
+    while i < DeviceType::ALL.len() {
+        assert!(matches!(DeviceType::ALL[i].position(), Some(position) if position == i));
+        i += 1;
+    }
+};
+
 impl DeviceType<'_> {
+    /// The position of a known device type in [`DeviceType::ALL`]. This match has no wildcard arm
+    /// so that a new variant fails to compile until it is given the next position here and listed
+    /// in [`DeviceType::ALL`].
+    #[allow(deprecated)]
+    const fn position(&self) -> Option<usize> {
+        match self {
+            DeviceType::CPU => Some(0),
+            DeviceType::GPU => Some(1),
+            DeviceType::NPU => Some(2),
+            DeviceType::AUTO => Some(3),
+            DeviceType::GNA => Some(4),
+            DeviceType::Other(_) => None,
+        }
+    }
+
+    /// Convert the device name into a C string, failing with
+    /// [`crate::InferenceError::ParameterMismatch`] if the name of a [`DeviceType::Other`] device
+    /// contains a NUL byte.
+    pub(crate) fn into_c_string(self) -> crate::util::Result<CString> {
+        crate::cstr!(self.as_ref())
+    }
+
     /// Creates a device type with owned string data.
     pub fn to_owned(&self) -> DeviceType<'static> {
         match self {
             DeviceType::CPU => DeviceType::CPU,
             DeviceType::GPU => DeviceType::GPU,
             DeviceType::NPU => DeviceType::NPU,
+            DeviceType::AUTO => DeviceType::AUTO,
             #[allow(deprecated)]
             DeviceType::GNA => DeviceType::GNA,
             DeviceType::Other(s) => DeviceType::Other(Cow::Owned(s.clone().into_owned())),
@@ -40,6 +87,7 @@ impl AsRef<str> for DeviceType<'_> {
             DeviceType::CPU => "CPU",
             DeviceType::GPU => "GPU",
             DeviceType::NPU => "NPU",
+            DeviceType::AUTO => "AUTO",
             #[allow(deprecated)]
             DeviceType::GNA => "GNA",
             DeviceType::Other(s) => s,
@@ -53,6 +101,9 @@ impl<'a> From<&'a DeviceType<'a>> for &'a str {
     }
 }
 
+/// # Panics
+///
+/// Panics if the name of a [`DeviceType::Other`] device contains a NUL byte.
 impl From<DeviceType<'_>> for CString {
     fn from(value: DeviceType) -> Self {
         CString::new(value.as_ref()).expect("a valid C string")
@@ -65,6 +116,7 @@ impl<'a> From<&'a str> for DeviceType<'a> {
             "CPU" => DeviceType::CPU,
             "GPU" => DeviceType::GPU,
             "NPU" => DeviceType::NPU,
+            "AUTO" => DeviceType::AUTO,
             #[allow(deprecated)]
             "GNA" => DeviceType::GNA,
             s => DeviceType::Other(Cow::Borrowed(s)),
@@ -85,3 +137,20 @@ impl Display for DeviceType<'_> {
         f.write_str(self.into())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_names() {
+        for device in DeviceType::ALL {
+            assert_eq!(&DeviceType::from(device.as_ref()), device);
+            assert_eq!(&device.to_string().parse::<DeviceType>().unwrap(), device);
+        }
+        assert_eq!(
+            DeviceType::from("HETERO:GPU,CPU"),
+            DeviceType::Other("HETERO:GPU,CPU".into())
+        );
+    }
+}
@@ -43,11 +43,44 @@ impl Dimension {
     }
 }
 
+/// A dimension of a [`crate::PartialShape`], e.g., to build one with
+/// [`crate::PartialShape::from_dims`] without knowing how OpenVINO encodes dynamic dimensions.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dim {
+    /// A dimension of a fixed size.
+    Static(i64),
+    /// A dimension of any size.
+    Dynamic,
+    /// A dimension whose size is within the inclusive range `min..=max`.
+    Range(i64, i64),
+}
+
+impl From<Dim> for Dimension {
+    fn from(dim: Dim) -> Self {
+        match dim {
+            Dim::Static(size) => Dimension::new(size, size),
+            // OpenVINO reports an unbounded dimension as `[0, -1]`.
+            Dim::Dynamic => Dimension::new(0, -1),
+            Dim::Range(min, max) => Dimension::new(min, max),
+        }
+    }
+}
+
+impl From<Dimension> for Dim {
+    fn from(dimension: Dimension) -> Self {
+        match (dimension.get_min(), dimension.get_max()) {
+            (min, max) if min == max => Dim::Static(min),
+            (0, -1) => Dim::Dynamic,
+            (min, max) => Dim::Range(min, max),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::LoadingError;
 
-    use super::Dimension;
+    use super::{Dim, Dimension};
 
     #[test]
     fn test_static() {
@@ -68,4 +101,13 @@ mod tests {
         let dim = Dimension::new(1, 2);
         assert!(dim.is_dynamic());
     }
+
+    #[test]
+    fn test_dims() {
+        for dim in [Dim::Static(3), Dim::Dynamic, Dim::Range(1, 8)] {
+            assert_eq!(Dim::from(Dimension::from(dim)), dim);
+        }
+        assert_eq!(Dimension::from(Dim::Static(3)), Dimension::new(3, 3));
+        assert_eq!(Dimension::from(Dim::Dynamic), Dimension::new(0, -1));
+    }
 }
@@ -3,7 +3,7 @@ use std::fmt;
 
 /// See
 /// [`ov_element_type_e`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__base__c__api.html#_CPPv417ov_element_type_e).
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u32)]
 pub enum ElementType {
     /// An undefined element type.
@@ -62,6 +62,71 @@ pub enum ElementType {
     F8E8M0,
 }
 
+impl ElementType {
+    /// All of the element types, in declaration order (which matches the order of the C API's
+    /// `ov_element_type_e`).
+    pub const ALL: &'static [ElementType] = &[
+        Self::Undefined,
+        Self::Dynamic,
+        Self::Boolean,
+        Self::Bf16,
+        Self::F16,
+        Self::F32,
+        Self::F64,
+        Self::I4,
+        Self::I8,
+        Self::I16,
+        Self::I32,
+        Self::I64,
+        Self::U1,
+        Self::U2,
+        Self::U3,
+        Self::U4,
+        Self::U6,
+        Self::U8,
+        Self::U16,
+        Self::U32,
+        Self::U64,
+        Self::NF4,
+        Self::F8E4M3,
+        Self::F8E5M3,
+        Self::String,
+        Self::F4E2M1,
+        Self::F8E8M0,
+    ];
+
+    /// Get the number of bits used to store one element of this type, or `None` if the type has
+    /// no fixed size (i.e., [`ElementType::Undefined`], [`ElementType::Dynamic`], and
+    /// [`ElementType::String`]).
+    pub fn bit_width(self) -> Option<usize> {
+        match self {
+            Self::Undefined | Self::Dynamic | Self::String => None,
+            Self::U1 => Some(1),
+            Self::U2 => Some(2),
+            Self::U3 => Some(3),
+            Self::I4 | Self::U4 | Self::NF4 | Self::F4E2M1 => Some(4),
+            Self::U6 => Some(6),
+            Self::Boolean | Self::I8 | Self::U8 | Self::F8E4M3 | Self::F8E5M3 | Self::F8E8M0 => {
+                Some(8)
+            }
+            Self::Bf16 | Self::F16 | Self::I16 | Self::U16 => Some(16),
+            Self::F32 | Self::I32 | Self::U32 => Some(32),
+            Self::F64 | Self::I64 | Self::U64 => Some(64),
+        }
+    }
+
+    /// Get the number of bytes needed to store `element_count` densely-packed elements of this
+    /// type (sub-byte types are packed and rounded up to a whole byte), or `None` if the type has
+    /// no fixed size (see [`ElementType::bit_width`]) or the size does not fit in a `usize`.
+    pub fn byte_size(self, element_count: usize) -> Option<usize> {
+        let bits = self.bit_width()?;
+        // Every 8 elements take exactly `bits` bytes; only the rest is rounded up.
+        (element_count / 8)
+            .checked_mul(bits)?
+            .checked_add((element_count % 8 * bits).div_ceil(8))
+    }
+}
+
 impl From<ov_element_type_e> for ElementType {
     fn from(ty: ov_element_type_e) -> Self {
         match ty {
@@ -163,3 +228,109 @@ impl fmt::Display for ElementType {
         }
     }
 }
+
+/// A Rust type which can be used to read and write the elements of a [`crate::Tensor`] (see
+/// [`crate::Tensor::iter`] and [`crate::Tensor::map_in_place`]).
+///
+/// This trait is sealed: the tensor accessors reinterpret the bytes of a tensor as values of this
+/// type, which is only sound for the primitive numeric types it is implemented for (every bit
+/// pattern of which is a valid value).
+pub trait TensorElement: sealed::Sealed + Copy + 'static {
+    /// Check whether values of this type have the same layout as the elements of `element_type`.
+    fn matches(element_type: ElementType) -> bool;
+}
+
+mod sealed {
+    /// Prevent implementations of [`super::TensorElement`] outside of this crate.
+    pub trait Sealed {}
+}
+
+macro_rules! impl_tensor_element {
+    ($($ty: ty => $($variant: ident)|+),+) => {
+        $(impl sealed::Sealed for $ty {}
+        impl TensorElement for $ty {
+            fn matches(element_type: ElementType) -> bool {
+                matches!(element_type, $(ElementType::$variant)|+)
+            }
+        })+
+    };
+}
+
+impl_tensor_element!(
+    f32 => F32,
+    f64 => F64,
+    i8 => I8,
+    i16 => I16,
+    i32 => I32,
+    i64 => I64,
+    // OpenVINO stores booleans in a byte, but not necessarily as `0` or `1`.
+    u8 => U8 | Boolean,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_c_enum() {
+        for &ty in ElementType::ALL {
+            let c_ty = ov_element_type_e::from(ty);
+            assert_eq!(ElementType::from(c_ty), ty);
+            // Both enumerations are declared in the same order; a mismatch here means a variant
+            // maps to the wrong C value (see https://github.com/intel/openvino-rs/issues/143).
+            assert_eq!(c_ty as u32, ty as u32, "wrong C value for {ty}");
+        }
+    }
+
+    #[test]
+    fn all_is_exhaustive() {
+        // Every C value up to the last one is covered by `ALL`, in order.
+        let last = ov_element_type_e::from(*ElementType::ALL.last().unwrap()) as usize;
+        assert_eq!(ElementType::ALL.len(), last + 1);
+        for (i, &ty) in ElementType::ALL.iter().enumerate() {
+            assert_eq!(ty as usize, i);
+        }
+    }
+
+    #[test]
+    fn bit_widths() {
+        assert_eq!(ElementType::U1.bit_width(), Some(1));
+        assert_eq!(ElementType::Boolean.bit_width(), Some(8));
+        assert_eq!(ElementType::Bf16.bit_width(), Some(16));
+        assert_eq!(ElementType::F64.bit_width(), Some(64));
+        assert_eq!(ElementType::String.bit_width(), None);
+    }
+
+    #[test]
+    fn tensor_elements() {
+        assert!(f32::matches(ElementType::F32));
+        assert!(!f32::matches(ElementType::I32));
+        assert!(u8::matches(ElementType::Boolean));
+        assert!(!i64::matches(ElementType::U64));
+    }
+
+    #[test]
+    fn byte_sizes() {
+        assert_eq!(ElementType::F32.byte_size(6), Some(24));
+        assert_eq!(ElementType::U4.byte_size(3), Some(2));
+        assert_eq!(ElementType::U1.byte_size(9), Some(2));
+        assert_eq!(ElementType::U8.byte_size(0), Some(0));
+        assert_eq!(ElementType::Dynamic.byte_size(1), None);
+        assert_eq!(ElementType::F32.byte_size(usize::MAX), None);
+        assert_eq!(
+            ElementType::U1.byte_size(usize::MAX),
+            Some(usize::MAX.div_ceil(8))
+        );
+    }
+
+    #[test]
+    fn unique_names() {
+        let mut names: Vec<String> = ElementType::ALL.iter().map(ToString::to_string).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), ElementType::ALL.len());
+    }
+}
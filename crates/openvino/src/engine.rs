@@ -0,0 +1,195 @@
+//! Minimal traits over inference execution: an [`InferenceEngine`] (e.g., a [`CompiledModel`])
+//! creates [`InferenceSession`]s (e.g., [`InferRequest`]s), which run inferences on tensors (see
+//! [`TensorApi`]). Application code (e.g., a pipeline pre-processing images and decoding
+//! detections) written against these traits rather than the concrete types can run on OpenVINO, on
+//! the test doubles of the `test_util` module (with the `test-util` feature) or on another runtime
+//! implementing them. The traits are object-safe for a given tensor type, e.g.,
+//! `Box<dyn InferenceSession<Tensor = Tensor>>`.
+//!
+//! ```no_run
+//! # use openvino::{ElementType, InferenceEngine, InferenceError, InferenceSession, TensorApi};
+//! /// Return the index of the highest score, for any engine.
+//! fn classify<E: InferenceEngine>(engine: &E, pixels: &[f32]) -> Result<usize, InferenceError> {
+//!     let mut session = engine.create_infer_request()?;
+//!     let input = TensorApi::from_data(ElementType::F32, &[1, 3, 224, 224], pixels)?;
+//!     session.set_input_tensor(&input)?;
+//!     session.infer()?;
+//!     let output = session.get_output_tensor()?;
+//!     let scores = TensorApi::get_data::<f32>(&output)?;
+//!     Ok((0..scores.len()).max_by(|&a, &b| scores[a].total_cmp(&scores[b])).unwrap_or(0))
+//! }
+//! ```
+
+use crate::{util::Result, CompiledModel, ElementType, InferRequest, InferenceError, Shape};
+use crate::{Tensor, TensorElement};
+
+/// The operations on the tensors of an [`InferenceSession`] that application code typically
+/// needs; implemented by [`Tensor`].
+pub trait TensorApi: Sized {
+    /// Create a tensor of `element_type` with dimensions `dims`, holding a copy of `data`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `T` does not match `element_type` (see
+    /// [`TensorElement`]) or if `data` does not hold as many elements as `dims` describes.
+    fn from_data<T: TensorElement>(
+        element_type: ElementType,
+        dims: &[i64],
+        data: &[T],
+    ) -> Result<Self>;
+
+    /// Get the element type of the tensor.
+    fn get_element_type(&self) -> Result<ElementType>;
+
+    /// Get the dimensions of the tensor, e.g., `[1, 3, 224, 224]`.
+    fn get_dimensions(&self) -> Result<Vec<i64>>;
+
+    /// Get the underlying data of the tensor.
+    fn get_raw_data(&self) -> Result<&[u8]>;
+
+    /// Get the data of the tensor as a slice of `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `T` does not match the tensor's element
+    /// type (see [`TensorElement`]).
+    fn get_data<T: TensorElement>(&self) -> Result<&[T]>;
+}
+
+/// A session running inferences, one at a time, on tensors bound by name or index; implemented by
+/// [`InferRequest`], whose getters here return copies of its tensors (unlike
+/// [`InferRequest::get_tensor`]), which later inferences do not modify.
+pub trait InferenceSession {
+    /// The tensors the session reads and writes.
+    type Tensor: TensorApi;
+
+    /// Assign a tensor to the input or output named `name`.
+    fn set_tensor(&mut self, name: &str, tensor: &Self::Tensor) -> Result<()>;
+
+    /// Retrieve the tensor of the input or output named `name`.
+    fn get_tensor(&self, name: &str) -> Result<Self::Tensor>;
+
+    /// Assign the input tensor of a model with only one input.
+    fn set_input_tensor(&mut self, tensor: &Self::Tensor) -> Result<()>;
+
+    /// Assign an input tensor by its index.
+    fn set_input_tensor_by_index(&mut self, index: usize, tensor: &Self::Tensor) -> Result<()>;
+
+    /// Retrieve the output tensor of a model with only one output.
+    fn get_output_tensor(&self) -> Result<Self::Tensor>;
+
+    /// Retrieve an output tensor by its index.
+    fn get_output_tensor_by_index(&self, index: usize) -> Result<Self::Tensor>;
+
+    /// Run an inference synchronously.
+    fn infer(&mut self) -> Result<()>;
+}
+
+/// An engine ready to run a model, creating the sessions that run it; implemented by
+/// [`CompiledModel`].
+pub trait InferenceEngine {
+    /// The sessions the engine creates.
+    type Session: InferenceSession;
+
+    /// Create a session, e.g., one per thread or per stream of inputs.
+    fn create_infer_request(&self) -> Result<Self::Session>;
+
+    /// Get the number of inputs of the model.
+    fn get_input_size(&self) -> Result<usize>;
+
+    /// Get the number of outputs of the model.
+    fn get_output_size(&self) -> Result<usize>;
+}
+
+/// Check that `data` holds the elements of a tensor of `element_type` with dimensions `dims`.
+pub(crate) fn check_data<T: TensorElement>(
+    element_type: ElementType,
+    dims: &[i64],
+    data: &[T],
+) -> Result<()> {
+    let count = dims.iter().try_fold(1usize, |count, &d| {
+        usize::try_from(d).ok().and_then(|d| count.checked_mul(d))
+    });
+    if T::matches(element_type) && count == Some(data.len()) {
+        Ok(())
+    } else {
+        Err(InferenceError::ParameterMismatch)
+    }
+}
+
+impl TensorApi for Tensor {
+    fn from_data<T: TensorElement>(
+        element_type: ElementType,
+        dims: &[i64],
+        data: &[T],
+    ) -> Result<Self> {
+        check_data(element_type, dims, data)?;
+        let mut tensor = Tensor::new(element_type, &Shape::new(dims)?)?;
+        tensor.data_mut::<T>()?.copy_from_slice(data);
+        Ok(tensor)
+    }
+
+    fn get_element_type(&self) -> Result<ElementType> {
+        Tensor::get_element_type(self)
+    }
+
+    fn get_dimensions(&self) -> Result<Vec<i64>> {
+        Ok(self.get_shape()?.get_dimensions().to_vec())
+    }
+
+    fn get_raw_data(&self) -> Result<&[u8]> {
+        Tensor::get_raw_data(self)
+    }
+
+    fn get_data<T: TensorElement>(&self) -> Result<&[T]> {
+        Tensor::data(self)
+    }
+}
+
+impl InferenceSession for InferRequest {
+    type Tensor = Tensor;
+
+    fn set_tensor(&mut self, name: &str, tensor: &Tensor) -> Result<()> {
+        InferRequest::set_tensor(self, name, tensor)
+    }
+
+    fn get_tensor(&self, name: &str) -> Result<Tensor> {
+        InferRequest::get_tensor(self, name)?.deep_copy()
+    }
+
+    fn set_input_tensor(&mut self, tensor: &Tensor) -> Result<()> {
+        InferRequest::set_input_tensor(self, tensor)
+    }
+
+    fn set_input_tensor_by_index(&mut self, index: usize, tensor: &Tensor) -> Result<()> {
+        InferRequest::set_input_tensor_by_index(self, index, tensor)
+    }
+
+    fn get_output_tensor(&self) -> Result<Tensor> {
+        InferRequest::get_output_tensor(self)?.deep_copy()
+    }
+
+    fn get_output_tensor_by_index(&self, index: usize) -> Result<Tensor> {
+        InferRequest::get_output_tensor_by_index(self, index)?.deep_copy()
+    }
+
+    fn infer(&mut self) -> Result<()> {
+        InferRequest::infer(self)
+    }
+}
+
+impl InferenceEngine for CompiledModel {
+    type Session = InferRequest;
+
+    fn create_infer_request(&self) -> Result<InferRequest> {
+        CompiledModel::create_infer_request(self)
+    }
+
+    fn get_input_size(&self) -> Result<usize> {
+        CompiledModel::get_input_size(self)
+    }
+
+    fn get_output_size(&self) -> Result<usize> {
+        CompiledModel::get_output_size(self)
+    }
+}
@@ -0,0 +1,228 @@
+//! Share compiled models across threads, e.g., between the workers of a web server.
+
+use crate::core::weights_to_tensor;
+use crate::{util::Result, CompiledModel, Core, DeviceType, Model, RwPropertyKey};
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// A thread-safe cache of compiled models: each distinct combination of model, device and
+/// properties is read and compiled once and then shared as an `Arc<CompiledModel>` (from which
+/// each thread can create its own [`crate::InferRequest`]s).
+///
+/// ```no_run
+/// # use openvino::{Core, DeviceType, EngineCache, RwPropertyKey};
+/// # use std::sync::Arc;
+/// let cache = Arc::new(EngineCache::new(Core::new().unwrap()));
+/// let workers: Vec<_> = (0..4)
+///     .map(|_| {
+///         let cache = cache.clone();
+///         std::thread::spawn(move || {
+///             // Only the first worker reads and compiles the model; the others wait for it.
+///             let model = cache
+///                 .get_or_compile_file("model.xml", "model.bin", DeviceType::CPU, &[])
+///                 .unwrap();
+///             let mut request = model.create_infer_request().unwrap();
+///             // ...
+///         })
+///     })
+///     .collect();
+/// ```
+///
+/// Models are compiled with the cache's [`Core`], one at a time; the requested properties are set
+/// on that [`Core`] for the device while compiling and then restored, so they apply to the
+/// compilation of that model only.
+pub struct EngineCache {
+    core: Mutex<Core>,
+    entries: Mutex<HashMap<EngineKey, Arc<Slot>>>,
+}
+
+/// The compiled model for a key, once compiled; locked while compiling.
+type Slot = Mutex<Option<Arc<CompiledModel>>>;
+
+/// Identify a compiled model in an [`EngineCache`].
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct EngineKey {
+    model: ModelId,
+    device: DeviceType<'static>,
+    properties: Vec<(RwPropertyKey, String)>,
+}
+
+/// Identify a model either by the paths to its files or by its contents.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum ModelId {
+    Files { model: PathBuf, weights: PathBuf },
+    Contents(Contents),
+}
+
+/// The contents of an in-memory model. They are looked up by their hash (see
+/// [`Model::compute_hash`]) and lengths but compared byte for byte, so that models whose hashes
+/// collide are not mistaken for each other.
+#[derive(Clone, Debug)]
+struct Contents {
+    hash: u64,
+    model: Box<[u8]>,
+    weights: Box<[u8]>,
+}
+
+impl Contents {
+    fn new(model: &[u8], weights: &[u8]) -> Self {
+        Self {
+            hash: Model::compute_hash(model, weights),
+            model: model.into(),
+            weights: weights.into(),
+        }
+    }
+}
+
+impl PartialEq for Contents {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.model == other.model && self.weights == other.weights
+    }
+}
+
+impl Eq for Contents {}
+
+impl Hash for Contents {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+        self.model.len().hash(state);
+        self.weights.len().hash(state);
+    }
+}
+
+impl EngineCache {
+    /// Create an empty cache which will use `core` to read and compile models.
+    pub fn new(core: Core) -> Self {
+        Self {
+            core: Mutex::new(core),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Retrieve the model compiled from the files at `model_path` and `weights_path` (see
+    /// [`Core::read_model_from_file`]) for `device` with `properties`, reading and compiling it
+    /// if this has not been done before. Models are identified by their paths: modifying the files
+    /// afterwards has no effect on the cache.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while holding one of the cache's locks.
+    pub fn get_or_compile_file(
+        &self,
+        model_path: &str,
+        weights_path: &str,
+        device: DeviceType,
+        properties: &[(RwPropertyKey, &str)],
+    ) -> Result<Arc<CompiledModel>> {
+        let model = ModelId::Files {
+            model: PathBuf::from(model_path),
+            weights: PathBuf::from(weights_path),
+        };
+        self.get_or_compile(model, device, properties, |core| {
+            core.read_model_from_file(model_path, weights_path)
+        })
+    }
+
+    /// Retrieve the model compiled from the in-memory `model` IR and `weights` for `device` with
+    /// `properties`, reading and compiling it if this has not been done before. Models are
+    /// identified by their contents, of which the cache keeps a copy to compare them byte for byte.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while holding one of the cache's locks.
+    pub fn get_or_compile_buffer(
+        &self,
+        model: &[u8],
+        weights: &[u8],
+        device: DeviceType,
+        properties: &[(RwPropertyKey, &str)],
+    ) -> Result<Arc<CompiledModel>> {
+        let id = ModelId::Contents(Contents::new(model, weights));
+        self.get_or_compile(id, device, properties, |core| {
+            let weights = if weights.is_empty() {
+                None
+            } else {
+                Some(weights_to_tensor(weights)?)
+            };
+            core.read_model_from_buffer(model, weights.as_ref())
+        })
+    }
+
+    /// Get the number of compiled models in the cache, not counting the models still compiling or
+    /// whose compilation failed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while holding the cache's lock.
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .unwrap()
+            .values()
+            // A slot is locked while its model compiles.
+            .filter(|slot| slot.try_lock().is_ok_and(|slot| slot.is_some()))
+            .count()
+    }
+
+    /// Remove all models from the cache; models still in use elsewhere are kept alive by their
+    /// `Arc`s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while holding the cache's lock.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn get_or_compile(
+        &self,
+        model: ModelId,
+        device: DeviceType,
+        properties: &[(RwPropertyKey, &str)],
+        read: impl FnOnce(&mut Core) -> Result<Model>,
+    ) -> Result<Arc<CompiledModel>> {
+        let key = EngineKey {
+            model,
+            device: device.to_owned(),
+            properties: properties
+                .iter()
+                .map(|(k, v)| (k.clone(), (*v).to_string()))
+                .collect(),
+        };
+
+        // Find (or reserve) the entry; the map is only locked briefly so that unrelated models can
+        // be retrieved while this one compiles.
+        let entry = self.entries.lock().unwrap().entry(key).or_default().clone();
+
+        // Threads asking for the same model wait here for the first one to compile it. If that
+        // fails, the next thread tries again.
+        let mut slot = entry.lock().unwrap();
+        if let Some(compiled_model) = slot.as_ref() {
+            return Ok(compiled_model.clone());
+        }
+        let mut core = self.core.lock().unwrap();
+        let model = read(&mut core)?;
+        let compiled_model =
+            Arc::new(core.compile_model_with_properties(&model, device, properties)?);
+        *slot = Some(compiled_model.clone());
+        Ok(compiled_model)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colliding_contents() {
+        let model = Contents::new(b"<net name=\"a\"/>", b"\x01");
+        assert_eq!(model, Contents::new(b"<net name=\"a\"/>", b"\x01"));
+
+        // Models whose hashes collide are told apart by their bytes.
+        let mut other = Contents::new(b"<net name=\"b\"/>", b"\x01");
+        other.hash = model.hash;
+        assert_ne!(model, other);
+    }
+}
@@ -6,7 +6,7 @@ use std::fmt;
 /// [`ov_status_e`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__base__c__api.html#_CPPv411ov_status_e);
 /// enumerates errors returned by the OpenVINO implementation.
 #[allow(missing_docs)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum InferenceError {
     GeneralError,
     NotImplemented,
@@ -25,6 +25,8 @@ pub enum InferenceError {
     UnknownCError,
     NotImplementCMethod,
     UnknownException,
+    TimedOut,
+    CompileTimedOut,
     Undefined(i32),
 }
 
@@ -78,6 +80,8 @@ impl fmt::Display for InferenceError {
             Self::UnknownCError => write!(f, "unknown C error"),
             Self::NotImplementCMethod => write!(f, "not implemented C method"),
             Self::UnknownException => write!(f, "unknown exception"),
+            Self::TimedOut => write!(f, "timed out"),
+            Self::CompileTimedOut => write!(f, "model compilation timed out"),
             Self::Undefined(code) => write!(f, "undefined error code: {code}"),
         }
     }
@@ -91,6 +95,8 @@ pub enum LoadingError {
     CannotFindLibraryPath,
     CannotFindPluginPath,
     CannotStringifyPath,
+    InvalidVersionConstraint(String),
+    NoMatchingVersion(String),
 }
 
 impl Error for LoadingError {}
@@ -102,6 +108,8 @@ impl fmt::Display for LoadingError {
             Self::CannotFindLibraryPath => write!(f, "cannot find path to shared libraries (see https://github.com/intel/openvino-rs/blob/main/crates/openvino-finder)"),
             Self::CannotFindPluginPath => write!(f, "cannot find path to XML plugin configuration (see https://github.com/intel/openvino-rs/blob/main/crates/openvino-finder)"),
             Self::CannotStringifyPath => write!(f, "unable to convert path to a UTF-8 string (see https://doc.rust-lang.org/std/path/struct.Path.html#method.to_str)"),
+            Self::InvalidVersionConstraint(msg) => write!(f, "invalid version constraint (see https://docs.rs/semver/latest/semver/struct.VersionReq.html): {msg}"),
+            Self::NoMatchingVersion(msg) => write!(f, "cannot find a shared library with a matching version (see https://github.com/intel/openvino-rs/blob/main/crates/openvino-finder): {msg}"),
         }
     }
 }
@@ -114,15 +122,37 @@ impl fmt::Display for LoadingError {
 pub enum SetupError {
     Inference(InferenceError),
     Loading(LoadingError),
+    Io(std::io::Error),
+    InvalidConfig(String),
+    IncompatibleBlob(String),
+    /// A model could not be read: `message` explains which frontend its format requires and
+    /// `source` is the error OpenVINO returned.
+    UnsupportedFormat {
+        message: String,
+        source: InferenceError,
+    },
 }
 
-impl Error for SetupError {}
+impl Error for SetupError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnsupportedFormat { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
 
 impl fmt::Display for SetupError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Inference(error) => write!(f, "inference error: {error}"),
             Self::Loading(error) => write!(f, "library loading error: {error}"),
+            Self::Io(error) => write!(f, "I/O error: {error}"),
+            Self::InvalidConfig(msg) => write!(f, "invalid configuration: {msg}"),
+            Self::IncompatibleBlob(msg) => write!(f, "incompatible compiled model: {msg}"),
+            Self::UnsupportedFormat { message, .. } => {
+                write!(f, "unsupported model format: {message}")
+            }
         }
     }
 }
@@ -138,3 +168,9 @@ impl From<LoadingError> for SetupError {
         SetupError::Loading(error)
     }
 }
+
+impl From<std::io::Error> for SetupError {
+    fn from(error: std::io::Error) -> Self {
+        SetupError::Io(error)
+    }
+}
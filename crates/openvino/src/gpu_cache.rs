@@ -0,0 +1,128 @@
+//! Configure and inspect where the GPU device caches its compiled kernels; see [`GpuKernelCache`].
+
+use crate::config::path_to_string;
+use crate::SetupError;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The extensions of the files OpenVINO writes to a cache directory: compiled kernels (`cl_cache`)
+/// and compiled models (`blob`).
+const CACHE_FILE_EXTENSIONS: &[&str] = &["cl_cache", "blob"];
+
+/// The directory in which the GPU device caches the kernels it compiles (and the compiled
+/// models it can export), applied with [`crate::Core::set_gpu_kernel_cache`].
+///
+/// This is experimental: OpenVINO only exposes this cache as the GPU's
+/// [`crate::RwPropertyKey::CacheDir`] and silently compiles everything again when it cannot write
+/// there (e.g., because the directory is read-only or was never created), so the first compilation
+/// in every process stays slow. This helper checks that the directory is writable before using it
+/// and lists what was cached, to tell whether caching works.
+///
+/// ```no_run
+/// # use openvino::{Core, GpuKernelCache};
+/// let mut core = Core::new().unwrap();
+/// let cache = GpuKernelCache::new("/var/cache/my-app/gpu-kernels");
+/// core.set_gpu_kernel_cache(&cache).unwrap();
+/// // ... compile models for `DeviceType::GPU` ...
+/// assert!(!cache.entries().unwrap().is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GpuKernelCache {
+    dir: PathBuf,
+}
+
+impl GpuKernelCache {
+    /// Use the cache in directory `dir`, which is created by [`GpuKernelCache::check`] if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The directory holding the cached kernels.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Check that OpenVINO can use the cache: create the directory if needed and write (then
+    /// remove) a file in it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SetupError::InvalidConfig`] if the path is not valid UTF-8 (OpenVINO could not be
+    /// given it) or if the directory cannot be created or written to, with the reason.
+    pub fn check(&self) -> Result<(), SetupError> {
+        path_to_string(&self.dir)?;
+        let not_writable = |e: io::Error| {
+            SetupError::InvalidConfig(format!(
+                "GPU kernel cache {} is not writable: {e}",
+                self.dir.display()
+            ))
+        };
+        fs::create_dir_all(&self.dir).map_err(not_writable)?;
+        let probe = self
+            .dir
+            .join(format!(".openvino-rs-write-check-{}", std::process::id()));
+        fs::write(&probe, b"").map_err(not_writable)?;
+        fs::remove_file(&probe).map_err(not_writable)
+    }
+
+    /// List the files cached by OpenVINO (i.e., `*.cl_cache` and `*.blob`), sorted; this is empty
+    /// if the directory does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory exists but cannot be read.
+    pub fn entries(&self) -> io::Result<Vec<PathBuf>> {
+        let dir = match fs::read_dir(&self.dir) {
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            dir => dir?,
+        };
+        let mut entries = vec![];
+        for entry in dir {
+            let path = entry?.path();
+            let is_cached = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| CACHE_FILE_EXTENSIONS.contains(&e));
+            if is_cached && path.is_file() {
+                entries.push(path);
+            }
+        }
+        entries.sort();
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn writable() {
+        let dir = env::temp_dir().join("openvino-gpu-kernel-cache");
+        let _ = fs::remove_dir_all(&dir);
+        let cache = GpuKernelCache::new(dir.join("kernels"));
+        assert_eq!(cache.entries().unwrap(), Vec::<PathBuf>::new());
+        cache.check().unwrap();
+        assert!(cache.dir().is_dir());
+        assert_eq!(cache.entries().unwrap(), Vec::<PathBuf>::new());
+
+        for file in ["b.cl_cache", "a.blob", "other.txt"] {
+            fs::write(cache.dir().join(file), b"").unwrap();
+        }
+        assert_eq!(
+            cache.entries().unwrap(),
+            vec![dir.join("kernels/a.blob"), dir.join("kernels/b.cl_cache")]
+        );
+
+        // A file where the directory should be cannot be used as a cache.
+        fs::write(dir.join("file"), b"").unwrap();
+        let error = GpuKernelCache::new(dir.join("file")).check().unwrap_err();
+        assert!(
+            matches!(&error, SetupError::InvalidConfig(e) if e.contains("is not writable")),
+            "{error}"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
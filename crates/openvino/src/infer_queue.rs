@@ -0,0 +1,565 @@
+//! Run several asynchronous inference requests for the same model, with back-pressure.
+
+use crate::request::CompletionSignal;
+use crate::{util::Result, CompiledModel, InferRequest, InferenceError, Tensor, TensorPool};
+use std::collections::VecDeque;
+use std::sync::mpsc::{Sender, SyncSender};
+use std::time::{Duration, Instant};
+
+/// The number of most recent inferences over which [`InferStats`] computes latencies.
+const LATENCY_WINDOW: usize = 100;
+
+/// Called with the index of the request, the request itself (e.g., to read its output tensors)
+/// and the result of its inference.
+pub type Completion = Box<dyn FnMut(usize, &InferRequest, Result<()>)>;
+
+/// The completion callback as stored by the queue, which also receives the job's [`JobId`].
+type JobCompletion = Box<dyn FnMut(JobId, usize, &InferRequest, Result<()>)>;
+
+/// Identifies an inference submitted to an [`InferQueue`]: the `n`th inference started by the
+/// queue, counting from zero, has the ID `n`. Refused and failed submissions are not counted; see
+/// [`InferQueue::next_job_id`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JobId(pub u64);
+
+/// The output tensors of a completed inference, by output index, as delivered to a
+/// [`ResultSender`].
+///
+/// The tensors are copies of the request's outputs (which the next inference of the request
+/// overwrites) and share their data with no other tensor, so they can be sent to another thread.
+pub struct Outputs(Vec<Tensor>);
+
+// SAFETY: the tensors are only reachable through this value: no other handle to their data exists.
+unsafe impl Send for Outputs {}
+
+impl Outputs {
+    /// Copy the outputs of a completed `request`.
+    fn copy_from(request: &InferRequest, count: usize) -> Result<Self> {
+        (0..count)
+            .map(|i| request.get_output_tensor_by_index(i)?.deep_copy())
+            .collect::<Result<_>>()
+            .map(Self)
+    }
+
+    /// Get the output tensor at `index`, if any.
+    pub fn get(&self, index: usize) -> Option<&Tensor> {
+        self.0.get(index)
+    }
+
+    /// Get the number of outputs.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Check whether there are no outputs.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Take the output tensors, by output index.
+    pub fn into_tensors(self) -> Vec<Tensor> {
+        self.0
+    }
+}
+
+impl std::fmt::Debug for Outputs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Outputs").field("len", &self.len()).finish()
+    }
+}
+
+/// A channel to which an [`InferQueue`] delivers the result of each inference; see
+/// [`InferQueue::new_with_sender`].
+///
+/// This is implemented for the `std` channels: a [`SyncSender`] blocks the queue while the channel
+/// is full, which extends the queue's back-pressure to the receiving thread. Other channels (e.g.,
+/// `crossbeam-channel`, which this crate does not depend on) can be used by implementing this
+/// trait for a wrapper of their sender.
+pub trait ResultSender {
+    /// Deliver the result of the inference `job`; if the receiver is gone, the result is dropped.
+    fn send_result(&mut self, job: JobId, result: Result<Outputs>);
+}
+
+impl ResultSender for Sender<(JobId, Result<Outputs>)> {
+    fn send_result(&mut self, job: JobId, result: Result<Outputs>) {
+        let _ = self.send((job, result));
+    }
+}
+
+impl ResultSender for SyncSender<(JobId, Result<Outputs>)> {
+    fn send_result(&mut self, job: JobId, result: Result<Outputs>) {
+        let _ = self.send((job, result));
+    }
+}
+
+/// A fixed-size pool of [`InferRequest`]s for one [`CompiledModel`], used to keep several
+/// inferences in flight at once (e.g., for consecutive video frames).
+///
+/// The number of requests is the queue's depth: once all of them are busy,
+/// [`InferQueue::try_submit`] refuses new inputs (so that a producer can drop or skip them) and
+/// [`InferQueue::submit_blocking`] waits for the oldest inference to complete. Completed
+/// inferences are passed to the completion callback, in the calling thread, whenever the queue
+/// checks on its requests (i.e., while submitting or waiting).
+///
+/// ```no_run
+/// # use openvino::{Core, DeviceType, ElementType, InferQueue, Shape, Tensor};
+/// # fn main() -> anyhow::Result<()> {
+/// # let mut core = Core::new()?;
+/// # let model = core.read_model_from_file("model.xml", "model.bin")?;
+/// let model = core.compile_model(&model, DeviceType::CPU)?;
+/// let mut queue = InferQueue::new(&model, 4, |_, request, result| {
+///     if result.is_ok() {
+///         let output = request.get_output_tensor().unwrap();
+///         // ...
+///     }
+/// })?;
+/// # let frames: Vec<Tensor> = vec![];
+/// for frame in &frames {
+///     if queue.try_submit(&[frame])?.is_none() {
+///         // All requests are busy: skip this frame.
+///     }
+/// }
+/// queue.wait_all();
+/// # Ok(())
+/// # }
+/// ```
+pub struct InferQueue {
+    requests: Vec<InferRequest>,
+    inputs: usize,
+    /// The indexes of the busy requests, in submission order.
+    busy: VecDeque<usize>,
+    idle: Vec<usize>,
+    on_complete: JobCompletion,
+    /// The ID of the job running on each busy request.
+    jobs: Vec<JobId>,
+    next_job: JobId,
+    /// When the inference of each request was started.
+    started: Vec<Option<Instant>>,
+    stats: InferStats,
+    latencies: VecDeque<Duration>,
+    /// Where to return the input tensors of pooled submissions once they complete.
+    pool: Option<TensorPool>,
+    /// The input tensors of the pooled submission of each request, if any.
+    pending: Vec<Vec<Tensor>>,
+}
+
+/// A snapshot of the activity of an [`InferQueue`], e.g., for a health endpoint; see
+/// [`InferQueue::stats`].
+///
+/// Latencies are measured from submission until the queue notices the completion (while
+/// submitting or waiting), so they are only as precise as the queue is busy.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InferStats {
+    /// The number of inferences which completed successfully.
+    pub completed: u64,
+    /// The number of inferences which failed, excluding cancelled ones.
+    pub failed: u64,
+    /// The number of inferences which were cancelled.
+    pub cancelled: u64,
+    /// The mean latency of the most recent inferences, if any.
+    pub mean_latency: Option<Duration>,
+    /// The maximum latency of the most recent inferences, if any.
+    pub max_latency: Option<Duration>,
+}
+
+impl InferQueue {
+    /// Create a queue of `depth` inference requests for `model`; `on_complete` is called once for
+    /// each submitted inference.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `depth` is zero.
+    pub fn new(
+        model: &CompiledModel,
+        depth: usize,
+        mut on_complete: impl FnMut(usize, &InferRequest, Result<()>) + 'static,
+    ) -> Result<Self> {
+        Self::with_completion(
+            model,
+            depth,
+            Box::new(move |_, index, request, result| on_complete(index, request, result)),
+        )
+    }
+
+    /// Create a queue of `depth` inference requests for `model` which, instead of calling a
+    /// callback, copies the outputs of each submitted inference and sends them, with its
+    /// [`JobId`], to `sender` (e.g., the [`Sender`] of a channel whose receiver is on another
+    /// thread).
+    ///
+    /// ```no_run
+    /// # use openvino::{Core, DeviceType, InferQueue};
+    /// # use std::sync::mpsc;
+    /// # fn main() -> anyhow::Result<()> {
+    /// # let mut core = Core::new()?;
+    /// # let model = core.read_model_from_file("model.xml", "model.bin")?;
+    /// let model = core.compile_model(&model, DeviceType::CPU)?;
+    /// let (sender, receiver) = mpsc::channel();
+    /// let mut queue = InferQueue::new_with_sender(&model, 4, sender)?;
+    /// std::thread::spawn(move || {
+    ///     for (job, result) in receiver {
+    ///         let outputs = result.unwrap();
+    ///         // ...
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `depth` is zero.
+    pub fn new_with_sender(
+        model: &CompiledModel,
+        depth: usize,
+        mut sender: impl ResultSender + 'static,
+    ) -> Result<Self> {
+        let outputs = model.get_output_size()?;
+        Self::with_completion(
+            model,
+            depth,
+            Box::new(move |job, _, request, result| {
+                let result = result.and_then(|()| Outputs::copy_from(request, outputs));
+                sender.send_result(job, result);
+            }),
+        )
+    }
+
+    fn with_completion(
+        model: &CompiledModel,
+        depth: usize,
+        on_complete: JobCompletion,
+    ) -> Result<Self> {
+        if depth == 0 {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        // Completions are tracked with a callback on each request: OpenVINO's `wait` with a
+        // timeout cannot tell whether an inference completed.
+        let requests = (0..depth)
+            .map(|_| {
+                let mut request = model.create_infer_request()?;
+                request.completion_signal()?;
+                Ok(request)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            requests,
+            inputs: model.get_input_size()?,
+            busy: VecDeque::with_capacity(depth),
+            idle: (0..depth).rev().collect(),
+            on_complete,
+            jobs: vec![JobId(0); depth],
+            next_job: JobId(0),
+            started: vec![None; depth],
+            stats: InferStats::default(),
+            latencies: VecDeque::with_capacity(LATENCY_WINDOW),
+            pool: None,
+            pending: (0..depth).map(|_| Vec::new()).collect(),
+        })
+    }
+
+    /// Return the input tensors of pooled submissions (see [`InferQueue::try_submit_pooled`]) to
+    /// `pool` once their inference completes, so that they can be reused for later inputs.
+    #[must_use]
+    pub fn with_pool(mut self, pool: TensorPool) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Get the queue's [`TensorPool`], if any, e.g., to acquire the tensors of the next inputs.
+    pub fn pool_mut(&mut self) -> Option<&mut TensorPool> {
+        self.pool.as_mut()
+    }
+
+    /// Get the number of inference requests, i.e., the maximum number of inferences in flight.
+    pub fn depth(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Get the number of inferences still in flight, after collecting the completed ones.
+    pub fn in_flight(&mut self) -> usize {
+        self.poll();
+        self.busy.len()
+    }
+
+    /// Get the [`JobId`] which the next inference started by the queue will have.
+    pub fn next_job_id(&self) -> JobId {
+        self.next_job
+    }
+
+    /// Get a snapshot of the number of inferences completed, failed and cancelled so far and of
+    /// the latency of the most recent ones, after collecting the completed inferences.
+    pub fn stats(&mut self) -> InferStats {
+        self.poll();
+        let mut stats = self.stats.clone();
+        if !self.latencies.is_empty() {
+            let total: Duration = self.latencies.iter().sum();
+            let count = u32::try_from(self.latencies.len()).unwrap_or(u32::MAX);
+            stats.mean_latency = Some(total / count);
+            stats.max_latency = self.latencies.iter().max().copied();
+        }
+        stats
+    }
+
+    /// Start an inference with `inputs` (by index) if a request is idle and return the index of
+    /// that request; return `None`, without waiting, if all requests are busy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the number of `inputs` does not match the
+    /// model's, or any error from setting the inputs or starting the inference.
+    pub fn try_submit(&mut self, inputs: &[&Tensor]) -> Result<Option<usize>> {
+        self.poll();
+        if self.idle.is_empty() {
+            return Ok(None);
+        }
+        self.start(inputs).map(Some)
+    }
+
+    /// Start an inference with `inputs` (by index) and return the index of the request used; if
+    /// all requests are busy, first wait for the oldest inference to complete.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the number of `inputs` does not match the
+    /// model's, or any error from setting the inputs or starting the inference.
+    pub fn submit_blocking(&mut self, inputs: &[&Tensor]) -> Result<usize> {
+        self.poll();
+        if self.idle.is_empty() {
+            self.complete_oldest();
+        }
+        self.start(inputs)
+    }
+
+    /// Like [`InferQueue::try_submit`], but take ownership of the `inputs` and return them to the
+    /// queue's pool (see [`InferQueue::with_pool`]) once the inference completes (or immediately,
+    /// if all requests are busy or the inference cannot be started).
+    ///
+    /// # Errors
+    ///
+    /// See [`InferQueue::try_submit`].
+    pub fn try_submit_pooled(&mut self, inputs: Vec<Tensor>) -> Result<Option<usize>> {
+        self.poll();
+        if self.idle.is_empty() {
+            self.recycle(inputs);
+            return Ok(None);
+        }
+        self.start_pooled(inputs).map(Some)
+    }
+
+    /// Like [`InferQueue::submit_blocking`], but take ownership of the `inputs` and return them to
+    /// the queue's pool (see [`InferQueue::with_pool`]) once the inference completes (or
+    /// immediately, if it cannot be started).
+    ///
+    /// # Errors
+    ///
+    /// See [`InferQueue::submit_blocking`].
+    pub fn submit_blocking_pooled(&mut self, inputs: Vec<Tensor>) -> Result<usize> {
+        self.poll();
+        if self.idle.is_empty() {
+            self.complete_oldest();
+        }
+        self.start_pooled(inputs)
+    }
+
+    /// Wait for all inferences in flight to complete.
+    pub fn wait_all(&mut self) {
+        while !self.busy.is_empty() {
+            self.complete_oldest();
+        }
+    }
+
+    /// Wake the task of `waker` when the next inference of any of the requests completes,
+    /// including those started afterwards.
+    #[cfg(feature = "async")]
+    pub(crate) fn wake_on_completion(&self, waker: &std::task::Waker) {
+        for signal in self.requests.iter().filter_map(InferRequest::signal) {
+            signal.wake_on_completion(waker);
+        }
+    }
+
+    /// Set the `inputs` of an idle request and start its inference.
+    fn start(&mut self, inputs: &[&Tensor]) -> Result<usize> {
+        if inputs.len() != self.inputs {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let index = self.idle.pop().expect("an idle request");
+        let request = &mut self.requests[index];
+        let started = inputs
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, tensor)| request.set_input_tensor_by_index(i, tensor))
+            .and_then(|()| request.infer_async());
+        match started {
+            Ok(()) => {
+                self.started[index] = Some(Instant::now());
+                self.jobs[index] = self.next_job;
+                self.next_job.0 += 1;
+                self.busy.push_back(index);
+                Ok(index)
+            }
+            Err(e) => {
+                self.idle.push(index);
+                Err(e)
+            }
+        }
+    }
+
+    /// Start an inference with owned `inputs`, keeping them until it completes; if it cannot be
+    /// started, they are recycled at once.
+    fn start_pooled(&mut self, inputs: Vec<Tensor>) -> Result<usize> {
+        let refs: Vec<&Tensor> = inputs.iter().collect();
+        match self.start(&refs) {
+            Ok(index) => {
+                self.pending[index] = inputs;
+                Ok(index)
+            }
+            Err(e) => {
+                self.recycle(inputs);
+                Err(e)
+            }
+        }
+    }
+
+    /// Return tensors to the pool, if any; otherwise, drop them.
+    fn recycle(&mut self, tensors: Vec<Tensor>) {
+        if let Some(pool) = &mut self.pool {
+            for tensor in tensors {
+                // A tensor whose element type or shape cannot be read is dropped instead.
+                let _ = pool.release(tensor);
+            }
+        }
+    }
+
+    /// Collect the inferences which have completed, without waiting.
+    fn poll(&mut self) {
+        let mut still_busy = VecDeque::with_capacity(self.busy.len());
+        while let Some(index) = self.busy.pop_front() {
+            let request = &mut self.requests[index];
+            if request.signal().is_some_and(CompletionSignal::is_done) {
+                // The inference completed: this only retrieves its result.
+                let result = request.wait_for_result();
+                self.complete(index, result);
+            } else {
+                still_busy.push_back(index);
+            }
+        }
+        self.busy = still_busy;
+    }
+
+    /// Wait for the oldest inference in flight to complete.
+    fn complete_oldest(&mut self) {
+        if let Some(index) = self.busy.pop_front() {
+            let result = self.requests[index].wait_for_result();
+            self.complete(index, result);
+        }
+    }
+
+    fn complete(&mut self, index: usize, result: Result<()>) {
+        match &result {
+            Ok(()) => self.stats.completed += 1,
+            Err(InferenceError::InferCancelled) => self.stats.cancelled += 1,
+            Err(_) => self.stats.failed += 1,
+        }
+        if let Some(started) = self.started[index].take() {
+            if self.latencies.len() == LATENCY_WINDOW {
+                self.latencies.pop_front();
+            }
+            self.latencies.push_back(started.elapsed());
+        }
+        (self.on_complete)(self.jobs[index], index, &self.requests[index], result);
+        let inputs = std::mem::take(&mut self.pending[index]);
+        self.recycle(inputs);
+        self.idle.push(index);
+    }
+}
+
+/// These tests replace the OpenVINO functions with test doubles (see `openvino_sys::mock`).
+#[cfg(all(test, feature = "mock"))]
+mod mock_tests {
+    use super::*;
+    use openvino_sys::mock::{self, Functions};
+    use openvino_sys::{ov_callback_t, ov_compiled_model_t, ov_infer_request_t, ov_status_e};
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+
+    thread_local! {
+        // The callback set on each request, in the order the requests were created.
+        static CALLBACKS: RefCell<Vec<*const ov_callback_t>> = const { RefCell::new(vec![]) };
+    }
+
+    unsafe extern "C" fn create_infer_request(
+        _model: *const ov_compiled_model_t,
+        request: *mut *mut ov_infer_request_t,
+    ) -> ov_status_e {
+        *request = std::ptr::NonNull::dangling().as_ptr();
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn size(_model: *const ov_compiled_model_t, size: *mut usize) -> ov_status_e {
+        *size = 0;
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn set_callback(
+        _request: *mut ov_infer_request_t,
+        callback: *const ov_callback_t,
+    ) -> ov_status_e {
+        CALLBACKS.with_borrow_mut(|c| c.push(callback));
+        ov_status_e::OK
+    }
+
+    /// Complete the inference of the request at `index`, as OpenVINO does from one of its threads.
+    fn complete(index: usize) {
+        let callback = CALLBACKS.with_borrow(|c| c[index]);
+        unsafe { ((*callback).callback_func.unwrap())((*callback).args) };
+    }
+
+    unsafe extern "C" fn ok(_request: *mut ov_infer_request_t) -> ov_status_e {
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn free_request(_request: *mut ov_infer_request_t) {}
+
+    unsafe extern "C" fn free_model(_model: *mut ov_compiled_model_t) {}
+
+    #[test]
+    fn poll_running_requests() {
+        let functions = Functions {
+            ov_compiled_model_create_infer_request: Some(create_infer_request),
+            ov_compiled_model_inputs_size: Some(size),
+            ov_compiled_model_free: Some(free_model),
+            ov_infer_request_set_callback: Some(set_callback),
+            ov_infer_request_start_async: Some(ok),
+            ov_infer_request_wait: Some(ok),
+            ov_infer_request_free: Some(free_request),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            let model = CompiledModel::from_ptr(std::ptr::NonNull::dangling().as_ptr());
+            let completed = Rc::new(Cell::new(0));
+            let mut queue = {
+                let completed = completed.clone();
+                InferQueue::new(&model, 2, move |_, _, result| {
+                    assert_eq!(result, Ok(()));
+                    completed.set(completed.get() + 1);
+                })
+                .unwrap()
+            };
+            let first = queue.try_submit(&[]).unwrap().unwrap();
+            let second = queue.try_submit(&[]).unwrap().unwrap();
+            assert_eq!(queue.try_submit(&[]), Ok(None));
+
+            // The inferences are still running: none completes.
+            assert_eq!(queue.in_flight(), 2);
+            assert_eq!(completed.get(), 0);
+
+            complete(second);
+            assert_eq!(queue.in_flight(), 1);
+            assert_eq!(completed.get(), 1);
+            complete(first);
+            assert_eq!(queue.in_flight(), 0);
+            assert_eq!(completed.get(), 2);
+        });
+    }
+}
@@ -0,0 +1,277 @@
+//! Run a stream of inputs through an [`InferQueue`] asynchronously; see [`InferStream`].
+
+use crate::{util::Result, CompiledModel, InferQueue, Tensor};
+use futures_core::Stream;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+/// The output of each completed inference, by request index.
+type Completed = Rc<RefCell<Vec<(usize, Result<Tensor>)>>>;
+
+/// An asynchronous [`Stream`] of inference results, one for each tensor of a [`Stream`] of inputs,
+/// in input order; see [`CompiledModel::infer_stream`].
+///
+/// At most `depth` inferences are in flight: the inputs are only polled when a request is idle, so
+/// a slow consumer slows down the source (e.g., a video decoder) instead of buffering its frames.
+/// The task is woken when OpenVINO completes an inference, without polling or blocking a thread.
+/// No async runtime is needed, e.g., with `futures`:
+///
+/// ```ignore
+/// use futures::StreamExt;
+/// let frames = decode_frames(); // impl Stream<Item = Tensor>
+/// let mut results = model.infer_stream(4, frames)?;
+/// while let Some(result) = results.next().await {
+///     let output = result?;
+///     // ...
+/// }
+/// ```
+///
+/// The stream is not [`Send`], since it is built on an [`InferQueue`]: run it on the task that
+/// created it (e.g., in `tokio::task::spawn_local` or `block_on`). Only the first output of the
+/// model is returned, copied out of the request so that the request can be reused.
+pub struct InferStream<S> {
+    queue: InferQueue,
+    inputs: Pin<Box<S>>,
+    inputs_done: bool,
+    completed: Completed,
+    /// The sequence number of the input submitted to each busy request.
+    submitted: HashMap<usize, u64>,
+    /// The results which cannot be returned until those of earlier inputs are.
+    ready: BTreeMap<u64, Result<Tensor>>,
+    next_input: u64,
+    next_output: u64,
+}
+
+impl<S> InferStream<S>
+where
+    S: Stream<Item = Tensor>,
+{
+    pub(crate) fn new(model: &CompiledModel, depth: usize, inputs: S) -> Result<Self> {
+        let completed = Completed::default();
+        let queue = {
+            let completed = completed.clone();
+            InferQueue::new(model, depth, move |index, request, result| {
+                // Copy the output, which the next inference of the request overwrites.
+                let output =
+                    result.and_then(|()| request.get_output_tensor_by_index(0)?.deep_copy());
+                completed.borrow_mut().push((index, output));
+            })?
+        };
+        Ok(Self {
+            queue,
+            inputs: Box::pin(inputs),
+            inputs_done: false,
+            completed,
+            submitted: HashMap::with_capacity(depth),
+            ready: BTreeMap::new(),
+            next_input: 0,
+            next_output: 0,
+        })
+    }
+
+    /// Wait for the result of the next input, or `None` once the inputs are exhausted and all of
+    /// their results have been returned; this is `StreamExt::next`, without the `futures` crate.
+    pub async fn next(&mut self) -> Option<Result<Tensor>> {
+        poll_fn(|cx| Pin::new(&mut *self).poll_next(cx)).await
+    }
+}
+
+impl<S> Stream for InferStream<S>
+where
+    S: Stream<Item = Tensor>,
+{
+    type Item = Result<Tensor>;
+
+    /// Attempt to retrieve the result of the next input, submitting inputs while requests are
+    /// idle; this returns `Poll::Pending` and wakes the task of `cx` once a result may be ready.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Tensor>>> {
+        let this = self.get_mut();
+        // Register the waker before checking on the requests, so that no completion goes unseen.
+        this.queue.wake_on_completion(cx.waker());
+
+        // Checking the number of inferences in flight collects the completed ones, which must
+        // happen on every poll, including after the inputs are exhausted.
+        while this.queue.in_flight() < this.queue.depth() && !this.inputs_done {
+            match this.inputs.as_mut().poll_next(cx) {
+                Poll::Ready(Some(input)) => {
+                    let sequence = this.next_input;
+                    this.next_input += 1;
+                    match this.queue.submit_blocking_pooled(vec![input]) {
+                        Ok(index) => {
+                            this.submitted.insert(index, sequence);
+                        }
+                        Err(e) => {
+                            this.ready.insert(sequence, Err(e));
+                        }
+                    }
+                }
+                Poll::Ready(None) => this.inputs_done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        for (index, output) in this.completed.borrow_mut().drain(..) {
+            if let Some(sequence) = this.submitted.remove(&index) {
+                this.ready.insert(sequence, output);
+            }
+        }
+        if let Some(output) = this.ready.remove(&this.next_output) {
+            this.next_output += 1;
+            return Poll::Ready(Some(output));
+        }
+        if this.inputs_done && this.submitted.is_empty() {
+            return Poll::Ready(None);
+        }
+        Poll::Pending
+    }
+}
+
+/// These tests replace the OpenVINO functions with test doubles (see `openvino_sys::mock`).
+#[cfg(all(test, feature = "mock"))]
+mod mock_tests {
+    use super::*;
+    use crate::InferenceError;
+    use openvino_sys::mock::{self, Functions};
+    use openvino_sys::{
+        ov_callback_t, ov_compiled_model_t, ov_infer_request_t, ov_status_e, ov_tensor_t,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    thread_local! {
+        // The callback set on each request, in the order the requests were created.
+        static CALLBACKS: RefCell<Vec<*const ov_callback_t>> = const { RefCell::new(vec![]) };
+    }
+
+    unsafe extern "C" fn create_infer_request(
+        _model: *const ov_compiled_model_t,
+        request: *mut *mut ov_infer_request_t,
+    ) -> ov_status_e {
+        *request = std::ptr::NonNull::dangling().as_ptr();
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn size(_model: *const ov_compiled_model_t, size: *mut usize) -> ov_status_e {
+        *size = 1;
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn set_callback(
+        _request: *mut ov_infer_request_t,
+        callback: *const ov_callback_t,
+    ) -> ov_status_e {
+        CALLBACKS.with_borrow_mut(|c| c.push(callback));
+        ov_status_e::OK
+    }
+
+    /// Complete the inference of the request at `index`, as OpenVINO does from one of its threads.
+    fn complete(index: usize) {
+        let callback = CALLBACKS.with_borrow(|c| c[index]);
+        unsafe { ((*callback).callback_func.unwrap())((*callback).args) };
+    }
+
+    unsafe extern "C" fn set_input(
+        _request: *mut ov_infer_request_t,
+        _index: usize,
+        _tensor: *const ov_tensor_t,
+    ) -> ov_status_e {
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn get_input(
+        _request: *const ov_infer_request_t,
+        _index: usize,
+        tensor: *mut *mut ov_tensor_t,
+    ) -> ov_status_e {
+        *tensor = std::ptr::NonNull::dangling().as_ptr();
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn ok(_request: *mut ov_infer_request_t) -> ov_status_e {
+        ov_status_e::OK
+    }
+
+    // Each inference fails, so that its result needs no output tensor.
+    unsafe extern "C" fn fail(_request: *mut ov_infer_request_t) -> ov_status_e {
+        ov_status_e::GENERAL_ERROR
+    }
+
+    unsafe extern "C" fn free_request(_request: *mut ov_infer_request_t) {}
+
+    unsafe extern "C" fn free_model(_model: *mut ov_compiled_model_t) {}
+
+    unsafe extern "C" fn free_tensor(_tensor: *mut ov_tensor_t) {}
+
+    /// A stream of `count` input tensors, which are never read.
+    struct Inputs(usize);
+
+    impl Stream for Inputs {
+        type Item = Tensor;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Tensor>> {
+            Poll::Ready((self.0 > 0).then(|| {
+                self.0 -= 1;
+                Tensor::from_ptr(std::ptr::NonNull::dangling().as_ptr())
+            }))
+        }
+    }
+
+    /// Count the times the task is woken.
+    struct Wakes(AtomicUsize);
+
+    impl Wake for Wakes {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn results_after_the_source_is_exhausted() {
+        let functions = Functions {
+            ov_compiled_model_create_infer_request: Some(create_infer_request),
+            ov_compiled_model_inputs_size: Some(size),
+            ov_compiled_model_free: Some(free_model),
+            ov_infer_request_set_callback: Some(set_callback),
+            ov_infer_request_set_input_tensor_by_index: Some(set_input),
+            ov_infer_request_get_input_tensor_by_index: Some(get_input),
+            ov_infer_request_start_async: Some(ok),
+            ov_infer_request_wait: Some(fail),
+            ov_infer_request_free: Some(free_request),
+            ov_tensor_free: Some(free_tensor),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            let model = CompiledModel::from_ptr(std::ptr::NonNull::dangling().as_ptr());
+            let mut stream = InferStream::new(&model, 2, Inputs(2)).unwrap();
+            let woken = Arc::new(Wakes(AtomicUsize::new(0)));
+            let waker = Waker::from(woken.clone());
+            let mut cx = Context::from_waker(&waker);
+            let mut poll = || Pin::new(&mut stream).poll_next(&mut cx);
+
+            // Both inputs are submitted and the source is exhausted, with both still running.
+            assert!(poll().is_pending());
+            assert_eq!(woken.0.load(Ordering::SeqCst), 0);
+
+            // Each completion wakes the task, and its result is then returned.
+            complete(0);
+            assert_eq!(woken.0.load(Ordering::SeqCst), 1);
+            assert!(matches!(
+                poll(),
+                Poll::Ready(Some(Err(InferenceError::GeneralError)))
+            ));
+            assert!(poll().is_pending());
+            complete(1);
+            assert_eq!(woken.0.load(Ordering::SeqCst), 2);
+            assert!(matches!(
+                poll(),
+                Poll::Ready(Some(Err(InferenceError::GeneralError)))
+            ));
+            assert!(matches!(poll(), Poll::Ready(None)));
+        });
+    }
+}
@@ -1,5 +1,7 @@
-use crate::{cstr, drop_using_function, try_unsafe, util::Result};
-use openvino_sys::{ov_layout_create, ov_layout_free, ov_layout_t};
+use crate::{cstr, drop_using_function, try_unsafe, util::Result, Shape};
+use openvino_sys::{ov_free, ov_layout_create, ov_layout_free, ov_layout_t, ov_layout_to_string};
+use std::ffi::CStr;
+use std::fmt;
 
 /// See [`ov_layout_t`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__layout__c__api.html).
 pub struct Layout {
@@ -10,13 +12,14 @@ drop_using_function!(Layout, ov_layout_free);
 impl Layout {
     /// Get a pointer to the [`ov_layout_t`].
     #[inline]
+    #[cfg(feature = "prepostprocess")]
     pub(crate) fn as_mut_ptr(&mut self) -> *mut ov_layout_t {
         self.ptr
     }
 
     /// Creates a new layout with the given description.
     pub fn new(layout_desc: &str) -> Result<Self> {
-        let layout_desc = cstr!(layout_desc);
+        let layout_desc = cstr!(layout_desc)?;
         let mut layout = std::ptr::null_mut();
         try_unsafe!(ov_layout_create(
             layout_desc.as_ptr(),
@@ -24,6 +27,179 @@ impl Layout {
         ))?;
         Ok(Self { ptr: layout })
     }
+
+    /// Get the index of the channels dimension (`C`), if the layout names one at a fixed position
+    /// from the start (e.g., `1` for `NCHW`, but `None` for `...C`).
+    pub fn channel_index(&self) -> Option<usize> {
+        channel_index(&self.to_string())
+    }
+
+    /// Check that this layout plausibly describes tensors of `shape`, describing the first problem
+    /// found: the number of dimensions must match (allowing for a variadic `...` part), the batch
+    /// (`N`) and channels (`C`) dimensions must not be empty and, for images, the channels must not
+    /// look swapped with a spatial (`H` or `W`) dimension (e.g., `NCHW` for `[1,224,224,3]`).
+    ///
+    /// OpenVINO only reports these mistakes, if at all, when building the pre-processing pipeline
+    /// or running the model.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first mismatch found.
+    pub fn validate_against(&self, shape: &Shape) -> std::result::Result<(), LayoutMismatch> {
+        check_layout(&self.to_string(), shape.get_dimensions())
+    }
+}
+
+/// Why a [`Layout`] does not describe tensors of a [`Shape`]; see [`Layout::validate_against`].
+/// Each variant holds the layout's description (e.g., `[N,C,H,W]`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutMismatch {
+    /// The number of dimensions differs from the layout's.
+    Rank {
+        /// The layout's description.
+        layout: String,
+        /// The number of dimensions the layout names, besides a variadic part.
+        expected: usize,
+        /// The number of dimensions of the shape.
+        actual: usize,
+    },
+    /// The batch (`N`) or channels (`C`) dimension is empty.
+    EmptyDimension {
+        /// The layout's description.
+        layout: String,
+        /// The name of the dimension.
+        name: &'static str,
+    },
+    /// A spatial dimension (`H` or `W`) looks like the channels, and the channels like a spatial
+    /// dimension.
+    SwappedChannels {
+        /// The layout's description.
+        layout: String,
+        /// The name of the spatial dimension.
+        spatial: &'static str,
+        /// The size of the spatial dimension.
+        size: i64,
+        /// The size of the channels dimension.
+        channels: i64,
+    },
+}
+
+impl fmt::Display for LayoutMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rank {
+                layout,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "layout {layout} has {expected} dimensions but the shape has {actual}"
+            ),
+            Self::EmptyDimension { layout, name } => {
+                write!(f, "dimension {name} of layout {layout} is empty")
+            }
+            Self::SwappedChannels {
+                layout,
+                spatial,
+                size,
+                channels,
+            } => write!(
+                f,
+                "dimension {spatial} ({size}) of layout {layout} looks like the channels and \
+                 dimension C ({channels}) like a spatial dimension; is the layout swapped?"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LayoutMismatch {}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let desc = unsafe { ov_layout_to_string(self.ptr) };
+        if desc.is_null() {
+            return Err(fmt::Error);
+        }
+        let result = f.write_str(&unsafe { CStr::from_ptr(desc) }.to_string_lossy());
+        unsafe { ov_free(desc) };
+        result
+    }
+}
+
+/// Find the channels dimension in a layout description (see [`dimension_names`]).
+pub(crate) fn channel_index(desc: &str) -> Option<usize> {
+    dimension_names(desc)
+        .iter()
+        .take_while(|&&n| n != "..." && n != ".")
+        .position(|n| n.eq_ignore_ascii_case("C"))
+}
+
+/// Split a layout description into its dimension names, either in the short form (`NCHW`) or the
+/// bracketed form OpenVINO prints (`[N,C,H,W]`). Unnamed dimensions are `?` and a variadic part is
+/// `...` (or `.` in the short form).
+pub(crate) fn dimension_names(desc: &str) -> Vec<&str> {
+    match desc.strip_prefix('[').and_then(|d| d.strip_suffix(']')) {
+        Some(names) => names.split(',').map(str::trim).collect(),
+        None => desc
+            .char_indices()
+            .map(|(i, c)| &desc[i..i + c.len_utf8()])
+            .collect(),
+    }
+}
+
+/// Check that the layout description `desc` plausibly describes tensors of `dimensions`; see
+/// [`Layout::validate_against`].
+fn check_layout(desc: &str, dimensions: &[i64]) -> std::result::Result<(), LayoutMismatch> {
+    let names = dimension_names(desc);
+    let variadic = names.iter().position(|n| matches!(*n, "..." | "."));
+    let fixed = names.len() - usize::from(variadic.is_some());
+    if dimensions.len() < fixed || (variadic.is_none() && dimensions.len() > fixed) {
+        return Err(LayoutMismatch::Rank {
+            layout: desc.to_string(),
+            expected: fixed,
+            actual: dimensions.len(),
+        });
+    }
+    // Map each name to its dimension: names after a variadic part count from the end.
+    let dimension = |name: &str| {
+        let i = names.iter().position(|n| n.eq_ignore_ascii_case(name))?;
+        match variadic {
+            Some(v) if i > v => dimensions.get(dimensions.len() - (names.len() - i)),
+            _ => dimensions.get(i),
+        }
+        .copied()
+    };
+    for name in ["N", "C"] {
+        if dimension(name) == Some(0) {
+            return Err(LayoutMismatch::EmptyDimension {
+                layout: desc.to_string(),
+                name,
+            });
+        }
+    }
+    if let Some(channels) = dimension("C") {
+        for spatial in ["H", "W"] {
+            match dimension(spatial) {
+                Some(size) if size <= 4 && channels > 4 => {
+                    return Err(LayoutMismatch::SwappedChannels {
+                        layout: desc.to_string(),
+                        spatial,
+                        size,
+                        channels,
+                    })
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that all dimensions in a layout description are named, i.e., that each can be identified
+/// by its name.
+#[cfg_attr(not(feature = "prepostprocess"), allow(dead_code))]
+pub(crate) fn is_fully_named(names: &[&str]) -> bool {
+    names.iter().all(|n| !matches!(*n, "?" | "..." | "."))
 }
 
 #[cfg(test)]
@@ -41,4 +217,68 @@ mod tests {
         let layout = Layout::new(layout_desc).unwrap();
         assert!(!layout.ptr.is_null());
     }
+
+    #[test]
+    fn test_layout_to_string() {
+        openvino_sys::library::load().unwrap();
+        let layout = Layout::new("NHWC").unwrap();
+        assert_eq!(layout.to_string(), "[N,H,W,C]");
+        assert_eq!(layout.channel_index(), Some(3));
+    }
+
+    #[test]
+    fn test_channel_index() {
+        assert_eq!(channel_index("NCHW"), Some(1));
+        assert_eq!(channel_index("[N,H,W,C]"), Some(3));
+        assert_eq!(channel_index("[batch,C,height,width]"), Some(1));
+        assert_eq!(channel_index("...C"), None);
+        assert_eq!(channel_index("[N,?,H,W]"), None);
+    }
+
+    #[test]
+    fn test_check_layout() {
+        assert_eq!(check_layout("NCHW", &[1, 3, 224, 224]), Ok(()));
+        assert_eq!(check_layout("[N,H,W,C]", &[1, 224, 224, 3]), Ok(()));
+        assert_eq!(check_layout("NC...", &[8, 512, 7, 7]), Ok(()));
+        assert_eq!(check_layout("...C", &[2, 5, 3]), Ok(()));
+        let error = check_layout("NCHW", &[1, 3, 224]).unwrap_err();
+        assert_eq!(
+            error,
+            LayoutMismatch::Rank {
+                layout: "NCHW".into(),
+                expected: 4,
+                actual: 3
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "layout NCHW has 4 dimensions but the shape has 3"
+        );
+        assert!(check_layout("N...C", &[1]).is_err());
+        assert_eq!(
+            check_layout("NC", &[0, 3]),
+            Err(LayoutMismatch::EmptyDimension {
+                layout: "NC".into(),
+                name: "N"
+            })
+        );
+        assert_eq!(
+            check_layout("NCHW", &[1, 224, 224, 3]),
+            Err(LayoutMismatch::SwappedChannels {
+                layout: "NCHW".into(),
+                spatial: "W",
+                size: 3,
+                channels: 224
+            })
+        );
+        assert!(check_layout("...CHW", &[1, 224, 224, 3]).is_err());
+    }
+
+    #[test]
+    fn test_dimension_names() {
+        assert_eq!(dimension_names("NCHW"), ["N", "C", "H", "W"]);
+        assert_eq!(dimension_names("[batch, C, ?]"), ["batch", "C", "?"]);
+        assert!(is_fully_named(&dimension_names("[N,C,H,W]")));
+        assert!(!is_fully_named(&dimension_names("N...C")));
+    }
 }
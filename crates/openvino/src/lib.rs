@@ -6,13 +6,19 @@
 //!
 //! Check the loaded version of OpenVINO:
 //! ```
-//! assert!(openvino::version().build_number.starts_with("2"))
+//! assert!(openvino::try_version().unwrap().build_number.starts_with("2"))
 //! ```
 //!
 //! Most interaction with OpenVINO begins with instantiating a [Core]:
 //! ```
 //! let _ = openvino::Core::new().expect("to instantiate the OpenVINO library");
 //! ```
+//!
+//! Some API areas can be left out of the build with cargo features, all enabled by default:
+//! `prepostprocess` (the [`prepostprocess`] module), `legacy` (deprecated APIs) and `gpu` (the
+//! GPU kernel cache, `GpuKernelCache`). Remote tensors and generative AI have no features: this
+//! crate neither wraps the remote contexts of the C API (`ov_remote_context_*`) nor binds the
+//! separate `openvino_genai` library, so they are not part of the build to begin with.
 
 #![deny(missing_docs)]
 #![deny(clippy::all)]
@@ -25,38 +31,82 @@
     clippy::len_without_is_empty
 )]
 
+mod compile_policy;
+mod config;
+mod constant;
 mod core;
 mod device_type;
 mod dimension;
 mod element_type;
+mod engine;
+mod engine_cache;
 mod error;
+#[cfg(feature = "gpu")]
+mod gpu_cache;
+mod infer_queue;
+#[cfg(feature = "async")]
+mod infer_stream;
 mod layout;
 mod model;
+mod model_format;
 mod node;
+mod output_view;
+mod packed_bits;
 mod partial_shape;
+pub mod pipeline;
+#[cfg(feature = "prepostprocess")]
 pub mod prepostprocess;
 mod property;
 mod rank;
 mod request;
+mod request_tensor;
+#[cfg(feature = "prepostprocess")]
 mod resize_algorithm;
 mod shape;
+#[cfg(feature = "prepostprocess")]
+pub mod tasks;
 mod tensor;
+mod tensor_pool;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 mod util;
 mod version;
 
 pub use crate::core::Core;
+pub use compile_policy::CompilePolicy;
+pub use config::CoreConfig;
+pub use constant::Constant;
 pub use device_type::DeviceType;
-pub use dimension::Dimension;
-pub use element_type::ElementType;
+pub use dimension::{Dim, Dimension};
+pub use element_type::{ElementType, TensorElement};
+pub use engine::{InferenceEngine, InferenceSession, TensorApi};
+pub use engine_cache::EngineCache;
 pub use error::{InferenceError, LoadingError, SetupError};
-pub use layout::Layout;
-pub use model::{CompiledModel, Model};
-pub use node::Node;
+#[cfg(feature = "gpu")]
+pub use gpu_cache::GpuKernelCache;
+pub use infer_queue::{Completion, InferQueue, InferStats, JobId, Outputs, ResultSender};
+#[cfg(feature = "async")]
+pub use infer_stream::InferStream;
+pub use layout::{Layout, LayoutMismatch};
+pub use model::{
+    CompiledModel, IncompatibilityReason, InputExpectation, Model, PerformanceGoal,
+    StreamSuggestion,
+};
+pub use model_format::ModelFormat;
+pub use node::{normalize_tensor_name, Node};
+pub use output_view::OutputView;
+pub use packed_bits::PackedBits;
 pub use partial_shape::PartialShape;
-pub use property::{PropertyKey, RwPropertyKey};
+pub use property::{CpuConfig, ModelPriority, PropertyKey, RwPropertyKey, SchedulingCoreType};
 pub use rank::Rank;
 pub use request::InferRequest;
+pub use request_tensor::RequestTensor;
+#[cfg(feature = "prepostprocess")]
 pub use resize_algorithm::ResizeAlgorithm;
 pub use shape::Shape;
 pub use tensor::Tensor;
-pub use version::{version, Version};
+pub use tensor_pool::TensorPool;
+#[cfg(feature = "legacy")]
+#[allow(deprecated)]
+pub use version::version;
+pub use version::{try_version, BuildInfo, Version};
@@ -2,24 +2,45 @@
 //!  - [`Model`] is the OpenVINO representation of a neural model
 //!  - [`CompiledModel`] is the compiled representation of a [`CompiledModel`] for a device.
 
-use crate::node::Node;
+use crate::node::{find_name_like, next_model_id, Node, Port};
+#[cfg(feature = "prepostprocess")]
+use crate::prepostprocess::PreprocessingHints;
+use crate::property::dump_properties;
 use crate::request::InferRequest;
-use crate::{cstr, drop_using_function, try_unsafe, util::Result, PropertyKey, RwPropertyKey};
+use crate::util::layer_types;
+use crate::{
+    cstr, drop_using_function, try_unsafe, util::Result, Constant, Core, CpuConfig, DeviceType,
+    Dimension, ElementType, InferenceError, ModelPriority, PropertyKey, RwPropertyKey, Shape,
+    Tensor,
+};
 use openvino_sys::{
-    ov_compiled_model_create_infer_request, ov_compiled_model_free, ov_compiled_model_get_property,
-    ov_compiled_model_get_runtime_model, ov_compiled_model_input, ov_compiled_model_input_by_index,
-    ov_compiled_model_input_by_name, ov_compiled_model_inputs_size, ov_compiled_model_output,
-    ov_compiled_model_output_by_index, ov_compiled_model_output_by_name,
-    ov_compiled_model_outputs_size, ov_compiled_model_set_property, ov_compiled_model_t,
-    ov_model_const_input_by_index, ov_model_const_output_by_index, ov_model_free,
-    ov_model_inputs_size, ov_model_is_dynamic, ov_model_outputs_size, ov_model_t,
+    ov_compiled_model_create_infer_request, ov_compiled_model_export_model, ov_compiled_model_free,
+    ov_compiled_model_get_property, ov_compiled_model_get_runtime_model, ov_compiled_model_input,
+    ov_compiled_model_input_by_index, ov_compiled_model_input_by_name,
+    ov_compiled_model_inputs_size, ov_compiled_model_output, ov_compiled_model_output_by_index,
+    ov_compiled_model_output_by_name, ov_compiled_model_outputs_size,
+    ov_compiled_model_set_property, ov_compiled_model_t, ov_model_const_input_by_index,
+    ov_model_const_output_by_index, ov_model_free, ov_model_inputs_size, ov_model_is_dynamic,
+    ov_model_outputs_size, ov_model_t,
 };
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::ffi::CStr;
 
 /// See [`ov_model_t`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__model__c__api.html).
+///
+/// Unlike the C++ `ov::Model`, the C API only exposes a model's inputs and outputs: there is no way
+/// to enumerate its operations, so [`Model::op_count`] and [`Model::op_type_histogram`] read them
+/// from the model's IR. For the same reason, operations cannot be pinned to a device (e.g., through the `affinity` runtime information used by `HETERO`); the
+/// closest alternative is the device order of `HETERO` itself (e.g., `HETERO:GPU,CPU` runs
+/// operations the GPU does not support on the CPU).
 pub struct Model {
     ptr: *mut ov_model_t,
+    // Identifies the model to the nodes retrieved from it; see `Node::port`.
+    id: u64,
+    // The layout of the tensors expected by each input, when set by pre-processing; see
+    // `CompiledModel::input_expectations`.
+    input_layouts: Vec<Option<String>>,
 }
 drop_using_function!(Model, ov_model_free);
 
@@ -30,7 +51,32 @@ impl Model {
     /// Create a new [`Model`] from an internal pointer.
     #[inline]
     pub(crate) fn from_ptr(ptr: *mut ov_model_t) -> Self {
-        Self { ptr }
+        Self {
+            ptr,
+            id: next_model_id(),
+            input_layouts: Vec::new(),
+        }
+    }
+
+    /// Get the identifier of this model within the process; see [`Node`].
+    #[inline]
+    #[cfg(feature = "prepostprocess")]
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Record the layouts the inputs expect, by index, after pre-processing.
+    #[inline]
+    #[cfg(feature = "prepostprocess")]
+    pub(crate) fn with_input_layouts(mut self, layouts: Vec<Option<String>>) -> Self {
+        self.input_layouts = layouts;
+        self
+    }
+
+    /// Get the layouts the inputs expect, by index, if known.
+    #[inline]
+    pub(crate) fn input_layouts(&self) -> &[Option<String>] {
+        &self.input_layouts
     }
 
     /// Get the pointer to the underlying [`ov_model_t`].
@@ -61,7 +107,7 @@ impl Model {
             index,
             std::ptr::addr_of_mut!(node)
         ))?;
-        Ok(Node::from_ptr(node))
+        Ok(Node::from_ptr(node).with_port(self.id, Port::Input(index)))
     }
 
     /// Retrieve the output node by index.
@@ -72,7 +118,7 @@ impl Model {
             index,
             std::ptr::addr_of_mut!(node)
         ))?;
-        Ok(Node::from_ptr(node))
+        Ok(Node::from_ptr(node).with_port(self.id, Port::Output(index)))
     }
 
     /// Retrieve the constant output node by index.
@@ -83,32 +129,190 @@ impl Model {
             index,
             std::ptr::addr_of_mut!(node)
         ))?;
-        Ok(Node::from_ptr(node))
+        Ok(Node::from_ptr(node).with_port(self.id, Port::Output(index)))
+    }
+
+    /// Retrieve all of the input nodes, in order.
+    pub fn inputs(&self) -> Result<Vec<Node>> {
+        (0..self.get_inputs_len()?)
+            .map(|i| self.get_input_by_index(i))
+            .collect()
+    }
+
+    /// Retrieve all of the output nodes, in order.
+    pub fn outputs(&self) -> Result<Vec<Node>> {
+        (0..self.get_outputs_len()?)
+            .map(|i| self.get_output_by_index(i))
+            .collect()
+    }
+
+    /// Find the output whose name looks like `pattern` (e.g., `softmax` for `prob/Softmax:0`),
+    /// so that lookups survive changes to the naming conventions of model converters; see
+    /// [`crate::normalize_tensor_name`] for how names are compared. Only the outputs' tensor names
+    /// are compared: the C API does not expose the friendly names of their nodes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::NotFound`] if no output matches and
+    /// [`InferenceError::ParameterMismatch`] if several outputs match equally well.
+    pub fn find_output_like(&self, pattern: &str) -> Result<Node> {
+        let mut outputs = self.outputs()?;
+        let names = outputs
+            .iter()
+            .map(Node::get_name)
+            .collect::<Result<Vec<_>>>()?;
+        let index = find_name_like(&names, pattern)?;
+        Ok(outputs.swap_remove(index))
     }
 
     /// Returns `true` if the model contains dynamic shapes.
     pub fn is_dynamic(&self) -> bool {
         unsafe { ov_model_is_dynamic(self.ptr) }
     }
+
+    /// Check that a tensor of `shape` and `element_type` can be fed to the input `name`, e.g., so
+    /// that a serving layer rejects a bad payload with a precise message (`dimension 2: expected
+    /// 224, got 256`) before calling OpenVINO. Dynamic dimensions accept any size within their
+    /// bounds and a dynamic rank accepts any shape.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [`IncompatibilityReason`] found, checking the element type, then the rank,
+    /// then each dimension.
+    pub fn check_input(
+        &self,
+        name: &str,
+        shape: &Shape,
+        element_type: ElementType,
+    ) -> std::result::Result<(), IncompatibilityReason> {
+        let inputs = self.inputs().map_err(IncompatibilityReason::Error)?;
+        let input = inputs
+            .iter()
+            .find(|i| i.get_name().is_ok_and(|n| n == name))
+            .ok_or_else(|| IncompatibilityReason::UnknownInput(name.to_string()))?;
+        let expected = input
+            .get_element_type()
+            .map_err(IncompatibilityReason::Error)?;
+        let partial_shape = input
+            .get_partial_shape()
+            .map_err(IncompatibilityReason::Error)?;
+        let dimensions =
+            (!partial_shape.get_rank().is_dynamic()).then(|| partial_shape.get_dimensions());
+        check_compatibility(expected, dimensions, element_type, shape.get_dimensions())
+    }
+
+    /// Compute a 64-bit content hash of a model's IR and weights (e.g., as passed to
+    /// [`crate::Core::read_model_from_buffer`]), to identify it in application-level caches or
+    /// audit logs. The hash (64-bit FNV-1a) is stable across platforms and releases of this crate
+    /// but is not cryptographic: it does not protect against deliberate collisions.
+    ///
+    /// A [`Model`] does not retain the data it was read from and the C API can neither serialize
+    /// it nor expose the key OpenVINO uses for its own model cache (see `CACHE_DIR`), so the hash
+    /// is computed from the original data.
+    pub fn compute_hash(model: &[u8], weights: &[u8]) -> u64 {
+        // Include the IR length so that moving bytes between the IR and the weights changes the
+        // hash.
+        let length = model.len() as u64;
+        fnv1a(
+            fnv1a(fnv1a(FNV_OFFSET_BASIS, &length.to_le_bytes()), model),
+            weights,
+        )
+    }
+
+    /// Read the preprocessing a model expects of its input (e.g., mean and scale values) from its
+    /// IR (i.e., the `.xml` file), so that it can be added to a [`crate::prepostprocess::Pipeline`]
+    /// rather than hard-coded for each model.
+    ///
+    /// The C API does not expose a model's `rt_info`, where these are recorded, so they are read
+    /// from the original IR, like [`Model::compute_hash`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the recorded values are not numbers.
+    #[cfg(feature = "prepostprocess")]
+    pub fn preprocessing_hints(model: &[u8]) -> Result<PreprocessingHints> {
+        PreprocessingHints::from_ir(&String::from_utf8_lossy(model))
+    }
+
+    /// Read the constants of a model (e.g., its weights) from its IR (i.e., the `.xml` file): their
+    /// names, element types, shapes and positions in the weights (i.e., the `.bin` file), from
+    /// which [`Constant::data`] reads their data. This lets tools check whether a model's weights
+    /// are quantized or compute checksums of them.
+    ///
+    /// The C API does not enumerate a model's operations, so the constants are read from the
+    /// original IR, like [`Model::compute_hash`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if a constant's attributes are missing or
+    /// malformed.
+    pub fn constants(model: &[u8]) -> Result<Vec<Constant>> {
+        crate::constant::from_ir(&String::from_utf8_lossy(model))
+    }
+
+    /// Count the operations of a model (including its `Parameter`, `Const` and `Result`
+    /// operations) from its IR (i.e., the `.xml` file), like [`Model::constants`].
+    pub fn op_count(model: &[u8]) -> usize {
+        layer_types(&String::from_utf8_lossy(model)).count()
+    }
+
+    /// Count the operations of a model by type (e.g., `Convolution`) from its IR (i.e., the `.xml`
+    /// file), like [`Model::constants`], e.g., to find operations a device does not support before
+    /// compiling the model.
+    pub fn op_type_histogram(model: &[u8]) -> BTreeMap<String, usize> {
+        let mut histogram = BTreeMap::new();
+        for op_type in layer_types(&String::from_utf8_lossy(model)) {
+            *histogram.entry(op_type.to_string()).or_insert(0) += 1;
+        }
+        histogram
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Continue a 64-bit FNV-1a hash from `hash` over `bytes`.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .fold(hash, |h, &b| (h ^ u64::from(b)).wrapping_mul(FNV_PRIME))
 }
 
 /// See
 /// [`ov_compiled_model_t`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__compiled__model__c__api.html).
 pub struct CompiledModel {
     ptr: *mut ov_compiled_model_t,
+    // Identifies the compiled model to the nodes retrieved from it; see `Node::port`.
+    id: u64,
+    input_layouts: Vec<Option<String>>,
 }
 drop_using_function!(CompiledModel, ov_compiled_model_free);
 
 unsafe impl Send for CompiledModel {}
+// OpenVINO documents `ov::CompiledModel::create_infer_request` as thread-safe; the methods that
+// modify the compiled model (e.g., `set_property`) require `&mut self`.
+unsafe impl Sync for CompiledModel {}
 
 impl CompiledModel {
     /// Create a new [`CompiledModel`] from an internal `ov_compiled_model_t` pointer.
     pub(crate) fn from_ptr(ptr: *mut ov_compiled_model_t) -> Self {
-        Self { ptr }
+        Self {
+            ptr,
+            id: next_model_id(),
+            input_layouts: Vec::new(),
+        }
+    }
+
+    /// Record the layouts the inputs expect, by index (see [`Model`]).
+    #[inline]
+    pub(crate) fn with_input_layouts(mut self, layouts: &[Option<String>]) -> Self {
+        self.input_layouts = layouts.to_vec();
+        self
     }
 
-    /// Create an [`InferRequest`].
-    pub fn create_infer_request(&mut self) -> Result<InferRequest> {
+    /// Create an [`InferRequest`]. This may be called concurrently from several threads, e.g., on
+    /// an `Arc<CompiledModel>` retrieved from an [`crate::EngineCache`].
+    pub fn create_infer_request(&self) -> Result<InferRequest> {
         let mut infer_request = std::ptr::null_mut();
         try_unsafe!(ov_compiled_model_create_infer_request(
             self.ptr,
@@ -131,7 +335,7 @@ impl CompiledModel {
             self.ptr,
             std::ptr::addr_of_mut!(port)
         ))?;
-        Ok(Node::from_ptr(port))
+        Ok(Node::from_ptr(port).with_port(self.id, Port::Input(0)))
     }
 
     /// Get an input port of the compiled model by port index.
@@ -142,12 +346,12 @@ impl CompiledModel {
             index,
             std::ptr::addr_of_mut!(port)
         ))?;
-        Ok(Node::from_ptr(port))
+        Ok(Node::from_ptr(port).with_port(self.id, Port::Input(index)))
     }
 
     /// Get an input port of the compiled model by name.
     pub fn get_input_by_name(&self, name: &str) -> Result<Node> {
-        let name = cstr!(name);
+        let name = cstr!(name)?;
         let mut port = std::ptr::null_mut();
         try_unsafe!(ov_compiled_model_input_by_name(
             self.ptr,
@@ -171,7 +375,7 @@ impl CompiledModel {
             self.ptr,
             std::ptr::addr_of_mut!(port)
         ))?;
-        Ok(Node::from_ptr(port))
+        Ok(Node::from_ptr(port).with_port(self.id, Port::Output(0)))
     }
 
     /// Get an output port of the compiled model by port index.
@@ -182,12 +386,12 @@ impl CompiledModel {
             index,
             std::ptr::addr_of_mut!(port)
         ))?;
-        Ok(Node::from_ptr(port))
+        Ok(Node::from_ptr(port).with_port(self.id, Port::Output(index)))
     }
 
     /// Get an output port of the compiled model by name.
     pub fn get_output_by_name(&self, name: &str) -> Result<Node> {
-        let name = cstr!(name);
+        let name = cstr!(name)?;
         let mut port = std::ptr::null_mut();
         try_unsafe!(ov_compiled_model_output_by_name(
             self.ptr,
@@ -197,6 +401,58 @@ impl CompiledModel {
         Ok(Node::from_ptr(port))
     }
 
+    /// Infer each of the tensors of the stream `inputs`, keeping at most `depth` inferences in
+    /// flight, and return the stream of their results (i.e., the model's first output), in input
+    /// order; see [`InferStream`](crate::InferStream).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `depth` is zero, or any error from creating
+    /// the requests.
+    #[cfg(feature = "async")]
+    pub fn infer_stream<S>(&self, depth: usize, inputs: S) -> Result<crate::InferStream<S>>
+    where
+        S: futures_core::Stream<Item = Tensor>,
+    {
+        crate::InferStream::new(self, depth, inputs)
+    }
+
+    /// Describe the tensors each input expects, in input order, e.g., to validate incoming frames
+    /// with [`InputExpectation::check`] rather than trust that they match the pre-processing baked
+    /// into the model (see [`crate::prepostprocess::Pipeline::build_new_model`]).
+    ///
+    /// The element type and shape are those OpenVINO reports for the compiled inputs. The C API
+    /// cannot query layouts back, so the layout is only known when this model was compiled (with
+    /// [`Core::compile_model`]) from a model built by a pre-processing pipeline that set the
+    /// input's tensor layout; it is `None` otherwise (e.g., for imported models).
+    pub fn input_expectations(&self) -> Result<Vec<InputExpectation>> {
+        (0..self.get_input_size()?)
+            .map(|i| {
+                let input = self.get_input_by_index(i)?;
+                let shape = input.get_partial_shape()?;
+                Ok(InputExpectation {
+                    name: input.get_name().ok(),
+                    element_type: input.get_element_type()?,
+                    dimensions: (!shape.get_rank().is_dynamic())
+                        .then(|| shape.get_dimensions().to_vec()),
+                    layout: self.input_layouts.get(i).cloned().flatten(),
+                })
+            })
+            .collect()
+    }
+
+    /// Find the output whose name looks like `pattern`; see [`Model::find_output_like`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Model::find_output_like`].
+    pub fn find_output_like(&self, pattern: &str) -> Result<Node> {
+        let names = (0..self.get_output_size()?)
+            .map(|i| self.get_output_by_index(i)?.get_name())
+            .collect::<Result<Vec<_>>>()?;
+        self.get_output_by_index(find_name_like(&names, pattern)?)
+    }
+
     /// Gets runtime model information from a device.
     pub fn get_runtime_model(&self) -> Result<Model> {
         let mut ptr = std::ptr::null_mut();
@@ -204,12 +460,12 @@ impl CompiledModel {
             self.ptr,
             std::ptr::addr_of_mut!(ptr)
         ))?;
-        Ok(Model { ptr })
+        Ok(Model::from_ptr(ptr))
     }
 
     /// Gets a property for the compiled model.
     pub fn get_property(&self, key: &PropertyKey) -> Result<Cow<str>> {
-        let ov_prop_key = cstr!(key.as_ref());
+        let ov_prop_key = cstr!(key.as_ref())?;
         let mut ov_prop_value = std::ptr::null_mut();
         try_unsafe!(ov_compiled_model_get_property(
             self.ptr,
@@ -220,10 +476,30 @@ impl CompiledModel {
         Ok(rust_prop)
     }
 
+    /// Export the compiled model to a file, from which it can be imported without compiling it
+    /// again (see [`Core::import_model_from_file`]), e.g., to deploy precompiled NPU blobs.
+    pub fn export_model(&self, path: &str) -> Result<()> {
+        let path = cstr!(path)?;
+        try_unsafe!(ov_compiled_model_export_model(self.ptr, path.as_ptr()))
+    }
+
+    /// Read the current value of each property the compiled model supports, by key; see
+    /// [`Core::dump_properties`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the supported properties cannot be listed.
+    pub fn dump_properties(&self) -> Result<BTreeMap<String, String>> {
+        let supported = self.get_property(&PropertyKey::SupportedProperties)?;
+        Ok(dump_properties(&supported, |key| {
+            self.get_property(key).map(Cow::into_owned)
+        }))
+    }
+
     /// Sets a property for the compiled model.
     pub fn set_property(&mut self, key: &RwPropertyKey, value: &str) -> Result<()> {
-        let ov_prop_key = cstr!(key.as_ref());
-        let ov_prop_value = cstr!(value);
+        let ov_prop_key = cstr!(key.as_ref())?;
+        let ov_prop_value = cstr!(value)?;
         try_unsafe!(ov_compiled_model_set_property(
             self.ptr,
             ov_prop_key.as_ptr(),
@@ -231,4 +507,430 @@ impl CompiledModel {
         ))?;
         Ok(())
     }
+
+    /// Get the priority the model was compiled with (see [`ModelPriority`] and
+    /// [`Core::compile_model_with_priority`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the device does not report [`RwPropertyKey::HintModelPriority`], or
+    /// [`InferenceError::ParameterMismatch`] if it reports an unknown priority.
+    pub fn model_priority(&self) -> Result<ModelPriority> {
+        self.get_property(&RwPropertyKey::HintModelPriority.into())?
+            .parse()
+            .map_err(|_| InferenceError::ParameterMismatch)
+    }
+
+    /// Configure how the CPU device runs the inference threads of this model (see
+    /// [`CpuConfig`]). Devices may refuse to change some of these properties once the model is
+    /// compiled; prefer [`Core::set_cpu_config`] before compiling.
+    pub fn set_cpu_config(&mut self, config: &CpuConfig) -> Result<()> {
+        for (key, value) in config.properties() {
+            self.set_property(&key, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Run `iterations` inferences on zero-filled input tensors of each of the given `shapes`, so
+    /// that the device pays its one-time costs (e.g., allocating device memory, compiling GPU
+    /// kernels for a new shape) now rather than on the first real inference. This is mostly
+    /// useful for models with dynamic shapes, e.g., in a service's start-up code.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the model does not have exactly one input,
+    /// or any error from OpenVINO (e.g., if a shape is not compatible with the model's input).
+    pub fn warm_up(&self, shapes: &[Shape], iterations: usize) -> Result<()> {
+        if self.get_input_size()? != 1 {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let element_type = self.get_input()?.get_element_type()?;
+        let mut request = self.create_infer_request()?;
+        for shape in shapes {
+            let mut tensor = Tensor::new(element_type, shape)?;
+            tensor.get_raw_data_mut()?.fill(0);
+            request.set_input_tensor(&tensor)?;
+            for _ in 0..iterations {
+                request.infer()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Recommend numbers of streams and inference requests for `goal`, following OpenVINO's
+    /// performance guidance: for latency, a single stream and request; for throughput, as many
+    /// requests as OpenVINO reports as optimal for this compiled model
+    /// ([`PropertyKey::OptimalNumberOfInferRequests`]), each with its own stream, within the
+    /// device's [`PropertyKey::RangeForStreams`] and the number of available cores. A property the
+    /// device does not report is left out of the recommendation.
+    ///
+    /// Streams are fixed when a model is compiled: use [`StreamSuggestion::apply`] to compile the
+    /// next models with the suggestion, and create `requests` inference requests to use it.
+    pub fn suggest_streams(&self, goal: PerformanceGoal) -> StreamSuggestion {
+        let optimal_requests = self
+            .get_property(&PropertyKey::OptimalNumberOfInferRequests)
+            .ok()
+            .and_then(|v| v.trim().parse().ok());
+        let streams_range = self
+            .get_property(&PropertyKey::RangeForStreams)
+            .ok()
+            .and_then(|v| parse_range(&v));
+        let cores = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        suggest_streams(goal, optimal_requests, streams_range, cores)
+    }
+}
+
+/// The tensors an input of a [`CompiledModel`] expects; see [`CompiledModel::input_expectations`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputExpectation {
+    /// The name of the input, if it has one.
+    pub name: Option<String>,
+    /// The element type of the input, after pre-processing (e.g., `U8` for images).
+    pub element_type: ElementType,
+    /// The dimensions of the input, which may be dynamic; `None` if its rank is dynamic.
+    pub dimensions: Option<Vec<Dimension>>,
+    /// The layout of the input (e.g., `[N,H,W,C]`), if known.
+    pub layout: Option<String>,
+}
+
+impl InputExpectation {
+    /// Check that `tensor` can be fed to this input: its element type must match and each of its
+    /// dimensions must lie within the bounds of the input's (when the rank is known).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the tensor does not match.
+    pub fn check(&self, tensor: &Tensor) -> Result<()> {
+        let shape = tensor.get_shape()?;
+        if tensor.get_element_type()? == self.element_type
+            && self
+                .dimensions
+                .as_ref()
+                .is_none_or(|d| fits(d, shape.get_dimensions()))
+        {
+            Ok(())
+        } else {
+            Err(InferenceError::ParameterMismatch)
+        }
+    }
+}
+
+/// Check whether the static `shape` lies within `dimensions`, whose upper bound is `-1` when it is
+/// unbounded.
+fn fits(dimensions: &[Dimension], shape: &[i64]) -> bool {
+    check_dimensions(dimensions, shape).is_ok()
+}
+
+/// Check a tensor of `element_type` and `shape` against an input of `expected` element type and
+/// `dimensions` (`None` if its rank is dynamic); see [`Model::check_input`].
+fn check_compatibility(
+    expected: ElementType,
+    dimensions: Option<&[Dimension]>,
+    element_type: ElementType,
+    shape: &[i64],
+) -> std::result::Result<(), IncompatibilityReason> {
+    if element_type != expected {
+        return Err(IncompatibilityReason::ElementType {
+            expected,
+            actual: element_type,
+        });
+    }
+    dimensions.map_or(Ok(()), |d| check_dimensions(d, shape))
+}
+
+/// Check that the static `shape` has the rank of `dimensions` and lies within their bounds; see
+/// [`fits`].
+fn check_dimensions(
+    dimensions: &[Dimension],
+    shape: &[i64],
+) -> std::result::Result<(), IncompatibilityReason> {
+    if dimensions.len() != shape.len() {
+        return Err(IncompatibilityReason::Rank {
+            expected: dimensions.len(),
+            actual: shape.len(),
+        });
+    }
+    for (index, (d, &actual)) in dimensions.iter().zip(shape).enumerate() {
+        let max = (d.get_max() >= 0).then(|| d.get_max());
+        if actual < d.get_min() || max.is_some_and(|max| actual > max) {
+            return Err(IncompatibilityReason::Dimension {
+                index,
+                min: d.get_min(),
+                max,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Why a tensor cannot be fed to an input of a [`Model`]; see [`Model::check_input`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IncompatibilityReason {
+    /// The model has no input of this name.
+    UnknownInput(String),
+    /// The element type differs from the input's.
+    ElementType {
+        /// The element type of the input.
+        expected: ElementType,
+        /// The element type of the tensor.
+        actual: ElementType,
+    },
+    /// The number of dimensions differs from the input's.
+    Rank {
+        /// The rank of the input.
+        expected: usize,
+        /// The rank of the tensor.
+        actual: usize,
+    },
+    /// A dimension lies outside of the bounds of the input's.
+    Dimension {
+        /// The index of the dimension.
+        index: usize,
+        /// The smallest size the input accepts.
+        min: i64,
+        /// The largest size the input accepts, or `None` if unbounded.
+        max: Option<i64>,
+        /// The size of the tensor's dimension.
+        actual: i64,
+    },
+    /// OpenVINO failed to describe the input.
+    Error(InferenceError),
+}
+
+impl std::fmt::Display for IncompatibilityReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownInput(name) => write!(f, "no input named `{name}`"),
+            Self::ElementType { expected, actual } => {
+                write!(f, "element type: expected {expected}, got {actual}")
+            }
+            Self::Rank { expected, actual } => {
+                write!(f, "rank: expected {expected} dimensions, got {actual}")
+            }
+            Self::Dimension {
+                index,
+                min,
+                max,
+                actual,
+            } => match max {
+                Some(max) if max == min => {
+                    write!(f, "dimension {index}: expected {min}, got {actual}")
+                }
+                Some(max) => write!(f, "dimension {index}: expected {min}..={max}, got {actual}"),
+                None => write!(
+                    f,
+                    "dimension {index}: expected at least {min}, got {actual}"
+                ),
+            },
+            Self::Error(e) => write!(f, "unable to describe the input: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for IncompatibilityReason {}
+
+/// What to optimize inference for; see [`CompiledModel::suggest_streams`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PerformanceGoal {
+    /// Minimize the time each inference takes, e.g., for interactive use.
+    Latency,
+    /// Maximize the number of inferences per second, e.g., for batch processing.
+    Throughput,
+}
+
+/// A recommendation from [`CompiledModel::suggest_streams`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreamSuggestion {
+    /// The number of streams (i.e., [`RwPropertyKey::NumStreams`]) to compile the model with.
+    pub streams: usize,
+    /// The number of inference requests to keep in flight (e.g., the depth of an
+    /// [`crate::InferQueue`]).
+    pub requests: usize,
+}
+
+impl StreamSuggestion {
+    /// Set the suggested numbers of streams and requests (i.e., [`RwPropertyKey::NumStreams`] and
+    /// [`RwPropertyKey::HintNumRequests`]) on `core` for `device`, for the models compiled next.
+    pub fn apply(&self, core: &mut Core, device: &DeviceType) -> Result<()> {
+        core.set_property(
+            device,
+            &RwPropertyKey::NumStreams,
+            &self.streams.to_string(),
+        )?;
+        core.set_property(
+            device,
+            &RwPropertyKey::HintNumRequests,
+            &self.requests.to_string(),
+        )
+    }
+}
+
+/// Implement [`CompiledModel::suggest_streams`] given the properties OpenVINO reported, if any.
+fn suggest_streams(
+    goal: PerformanceGoal,
+    optimal_requests: Option<usize>,
+    streams_range: Option<(usize, usize)>,
+    cores: usize,
+) -> StreamSuggestion {
+    match goal {
+        PerformanceGoal::Latency => StreamSuggestion {
+            streams: 1,
+            requests: 1,
+        },
+        PerformanceGoal::Throughput => {
+            let (min, max) = streams_range.unwrap_or((1, cores));
+            let requests = optimal_requests.unwrap_or(max).max(1);
+            let streams = requests.min(max).min(cores).max(min).max(1);
+            StreamSuggestion { streams, requests }
+        }
+    }
+}
+
+/// Parse a range property, e.g., `RANGE_FOR_STREAMS`, printed by OpenVINO as `1 8` (or `1,8`).
+fn parse_range(value: &str) -> Option<(usize, usize)> {
+    let mut bounds = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(str::parse);
+    match (bounds.next()?.ok()?, bounds.next()?.ok()?, bounds.next()) {
+        (min, max, None) if min <= max => Some((min, max)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fitting_shapes() {
+        let dimensions = [
+            Dimension::new(1, 1),
+            Dimension::new(1, -1),
+            Dimension::new(3, 4),
+        ];
+        assert!(fits(&dimensions, &[1, 227, 3]));
+        assert!(fits(&dimensions, &[1, 1, 4]));
+        assert!(!fits(&dimensions, &[2, 227, 3]));
+        assert!(!fits(&dimensions, &[1, 227, 5]));
+        assert!(!fits(&dimensions, &[1, 227]));
+    }
+
+    #[test]
+    fn op_types() {
+        let ir = br#"<net name="model" version="11">
+    <layers>
+        <layer id="0" name="input" type="Parameter" version="opset1"/>
+        <layer id="1" name="weights" type="Const" version="opset1">
+            <data element_type="f32" shape="4, 3" offset="0" size="48"/>
+        </layer>
+        <layer id="2" name="bias" type="Const" version="opset1"/>
+        <layer id="3" name="dense" type="MatMul" version="opset1"/>
+        <layer id="4" name="output" type="Result" version="opset1"/>
+    </layers>
+</net>"#;
+        assert_eq!(Model::op_count(ir), 5);
+        assert_eq!(
+            Model::op_type_histogram(ir),
+            BTreeMap::from([
+                ("Const".to_string(), 2),
+                ("MatMul".to_string(), 1),
+                ("Parameter".to_string(), 1),
+                ("Result".to_string(), 1),
+            ])
+        );
+        assert_eq!(Model::op_count(b"<net/>"), 0);
+    }
+
+    #[test]
+    fn compatibility() {
+        let dimensions = [
+            Dimension::new(1, 1),
+            Dimension::new(3, 3),
+            Dimension::new(224, 224),
+            Dimension::new(1, -1),
+        ];
+        let check = |element_type, shape: &[i64]| {
+            check_compatibility(ElementType::F32, Some(&dimensions), element_type, shape)
+        };
+        assert_eq!(check(ElementType::F32, &[1, 3, 224, 500]), Ok(()));
+        assert_eq!(
+            check(ElementType::U8, &[1, 3, 224, 224]),
+            Err(IncompatibilityReason::ElementType {
+                expected: ElementType::F32,
+                actual: ElementType::U8
+            })
+        );
+        assert_eq!(
+            check(ElementType::F32, &[1, 3, 224])
+                .unwrap_err()
+                .to_string(),
+            "rank: expected 4 dimensions, got 3"
+        );
+        assert_eq!(
+            check(ElementType::F32, &[1, 3, 256, 224])
+                .unwrap_err()
+                .to_string(),
+            "dimension 2: expected 224, got 256"
+        );
+        assert_eq!(
+            check(ElementType::F32, &[1, 3, 224, 0])
+                .unwrap_err()
+                .to_string(),
+            "dimension 3: expected at least 1, got 0"
+        );
+        let range = IncompatibilityReason::Dimension {
+            index: 0,
+            min: 1,
+            max: Some(8),
+            actual: 9,
+        };
+        assert_eq!(range.to_string(), "dimension 0: expected 1..=8, got 9");
+        assert_eq!(
+            check_compatibility(ElementType::F32, None, ElementType::F32, &[7]),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn stable_hash() {
+        // Reference values of 64-bit FNV-1a.
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(
+            Model::compute_hash(b"<net/>", b"\x01\x02"),
+            Model::compute_hash(b"<net/>", b"\x01\x02")
+        );
+        assert_ne!(
+            Model::compute_hash(b"<net/>", b"\x01\x02"),
+            Model::compute_hash(b"<net/>\x01", b"\x02")
+        );
+    }
+
+    #[test]
+    fn stream_suggestions() {
+        let latency = suggest_streams(PerformanceGoal::Latency, Some(4), Some((1, 8)), 16);
+        assert_eq!((latency.streams, latency.requests), (1, 1));
+
+        let throughput = suggest_streams(PerformanceGoal::Throughput, Some(4), Some((1, 8)), 16);
+        assert_eq!((throughput.streams, throughput.requests), (4, 4));
+
+        // More requests than streams keep the device busy, e.g., on GPUs.
+        let throughput = suggest_streams(PerformanceGoal::Throughput, Some(8), Some((1, 2)), 16);
+        assert_eq!((throughput.streams, throughput.requests), (2, 8));
+
+        // Without OpenVINO's estimates, use the cores.
+        let throughput = suggest_streams(PerformanceGoal::Throughput, None, None, 6);
+        assert_eq!((throughput.streams, throughput.requests), (6, 6));
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(parse_range("1 8"), Some((1, 8)));
+        assert_eq!(parse_range("1,16"), Some((1, 16)));
+        assert_eq!(parse_range("8 1"), None);
+        assert_eq!(parse_range("1 2 3"), None);
+        assert_eq!(parse_range("one"), None);
+    }
 }
@@ -0,0 +1,157 @@
+//! Recognize the format of a model file, to explain why OpenVINO cannot read it; see
+//! [`crate::Core::read_model_from_path`].
+
+use std::io::Read;
+use std::path::Path;
+
+/// The formats of the model files OpenVINO may read, each through its own frontend library.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ModelFormat {
+    /// OpenVINO's intermediate representation (`.xml`, with the weights in a `.bin` file).
+    Ir,
+    /// ONNX (`.onnx`).
+    Onnx,
+    /// A frozen `TensorFlow` graph (`.pb`).
+    TensorFlow,
+    /// `TensorFlow` Lite (`.tflite`).
+    TensorFlowLite,
+    /// `PaddlePaddle` (`.pdmodel`).
+    Paddle,
+    /// A `PyTorch` checkpoint or `TorchScript` archive (`.pt`, `.pth`).
+    PyTorch,
+}
+
+impl ModelFormat {
+    /// Recognize the format of the model file at `path` by its extension or, failing that, by its
+    /// first bytes (e.g., the `TFL3` identifier of `TensorFlow` Lite files); `None` if unknown.
+    pub fn detect(path: &Path) -> Option<Self> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase);
+        let format = match extension.as_deref() {
+            Some("xml") => Some(Self::Ir),
+            Some("onnx") => Some(Self::Onnx),
+            Some("pb") => Some(Self::TensorFlow),
+            Some("tflite") => Some(Self::TensorFlowLite),
+            Some("pdmodel") => Some(Self::Paddle),
+            Some("pt" | "pth") => Some(Self::PyTorch),
+            _ => None,
+        };
+        format.or_else(|| {
+            let mut header = [0; 8];
+            let read = std::fs::File::open(path)
+                .and_then(|mut f| f.read(&mut header))
+                .ok()?;
+            Self::from_header(&header[..read])
+        })
+    }
+
+    /// Recognize a format by the first bytes of a file, where it has a signature.
+    fn from_header(header: &[u8]) -> Option<Self> {
+        if header.get(4..8) == Some(b"TFL3") {
+            Some(Self::TensorFlowLite)
+        } else if header.starts_with(b"PK\x03\x04") {
+            // `PyTorch` saves models as ZIP archives.
+            Some(Self::PyTorch)
+        } else if header.starts_with(b"<?xml") || header.starts_with(b"<net") {
+            Some(Self::Ir)
+        } else {
+            None
+        }
+    }
+
+    /// The name of the frontend which reads this format, as listed by
+    /// [`openvino_finder::find_frontends`].
+    pub fn frontend(self) -> &'static str {
+        match self {
+            Self::Ir => "ir",
+            Self::Onnx => "onnx",
+            Self::TensorFlow => "tensorflow",
+            Self::TensorFlowLite => "tensorflow_lite",
+            Self::Paddle => "paddle",
+            Self::PyTorch => "pytorch",
+        }
+    }
+
+    /// Explain why a model of this format could not be read, given the `available` frontends.
+    pub(crate) fn guidance(self, available: &[String]) -> String {
+        let listed = if available.is_empty() {
+            "no frontends were found beside the OpenVINO libraries".to_string()
+        } else {
+            format!("the available frontends are: {}", available.join(", "))
+        };
+        let frontend = self.frontend();
+        if self == Self::PyTorch {
+            // The `PyTorch` frontend only converts models traced in Python.
+            format!(
+                "{self} models cannot be read from a file, even with the `{frontend}` frontend; \
+                 convert the model to OpenVINO IR first (e.g., with `ovc model.pt`) ({listed})"
+            )
+        } else if available.iter().any(|a| a == frontend) {
+            format!("the `{frontend}` frontend failed to read this {self} model ({listed})")
+        } else {
+            format!(
+                "reading {self} models requires the `{frontend}` frontend, which is missing from \
+                 this OpenVINO installation; install it or convert the model to OpenVINO IR first \
+                 ({listed})"
+            )
+        }
+    }
+}
+
+impl std::fmt::Display for ModelFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Ir => "OpenVINO IR",
+            Self::Onnx => "ONNX",
+            Self::TensorFlow => "TensorFlow",
+            Self::TensorFlowLite => "TensorFlow Lite",
+            Self::Paddle => "PaddlePaddle",
+            Self::PyTorch => "PyTorch",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_by_extension() {
+        assert_eq!(
+            ModelFormat::detect(Path::new("model.TFLite")),
+            Some(ModelFormat::TensorFlowLite)
+        );
+        assert_eq!(
+            ModelFormat::detect(Path::new("frozen_graph.pb")),
+            Some(ModelFormat::TensorFlow)
+        );
+        assert_eq!(ModelFormat::detect(Path::new("missing.bin")), None);
+    }
+
+    #[test]
+    fn detect_by_header() {
+        assert_eq!(
+            ModelFormat::from_header(b"\x1c\0\0\0TFL3"),
+            Some(ModelFormat::TensorFlowLite)
+        );
+        assert_eq!(
+            ModelFormat::from_header(b"PK\x03\x04\x14\0"),
+            Some(ModelFormat::PyTorch)
+        );
+        assert_eq!(ModelFormat::from_header(b"\x08\x07"), None);
+    }
+
+    #[test]
+    fn guidance() {
+        let available = vec!["ir".to_string(), "onnx".to_string()];
+        let message = ModelFormat::TensorFlowLite.guidance(&available);
+        assert!(message.contains("`tensorflow_lite` frontend, which is missing"));
+        assert!(message.contains("ir, onnx"));
+        assert!(ModelFormat::Onnx
+            .guidance(&available)
+            .starts_with("the `onnx` frontend failed"));
+        assert!(ModelFormat::PyTorch.guidance(&[]).contains("ovc"));
+    }
+}
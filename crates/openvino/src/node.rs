@@ -1,21 +1,58 @@
-use crate::{try_unsafe, util::Result, ElementType, PartialShape, Shape};
+use crate::{try_unsafe, util::Result, ElementType, InferenceError, PartialShape, Shape};
 use openvino_sys::{
     ov_const_port_get_shape, ov_element_type_e, ov_output_const_port_t, ov_partial_shape_t,
     ov_port_get_any_name, ov_port_get_element_type, ov_port_get_partial_shape, ov_rank_t,
     ov_shape_t,
 };
 use std::ffi::CStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 /// See [`ov_node_c_api`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__node__c__api.html).
 pub struct Node {
     ptr: *mut ov_output_const_port_t,
+    // The model the node was retrieved from (see `next_model_id`) and its position there.
+    port: Option<(u64, Port)>,
+}
+
+/// The position of a [`Node`] among its model's inputs or outputs, when known. The C API cannot
+/// compare ports, so this is recorded when the node is retrieved by index; it allows finding the
+/// node again when its tensor names are absent or duplicated (e.g., for pre-processing).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Port {
+    Input(usize),
+    Output(usize),
+}
+
+/// Get a new identifier for a model (or compiled model), unique within the process, so that the
+/// [`Port`] of a [`Node`] is only used with the model the node was retrieved from.
+pub(crate) fn next_model_id() -> u64 {
+    static NEXT_MODEL_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_MODEL_ID.fetch_add(1, Ordering::Relaxed)
 }
 
 impl Node {
     /// Create a new [`Port`] from [`ov_output_const_port_t`].
     #[inline]
     pub(crate) fn from_ptr(ptr: *mut ov_output_const_port_t) -> Self {
-        Self { ptr }
+        Self { ptr, port: None }
+    }
+
+    /// Record the position of this node among the inputs or outputs of the model identified by
+    /// `model_id`.
+    #[inline]
+    pub(crate) fn with_port(mut self, model_id: u64, port: Port) -> Self {
+        self.port = Some((model_id, port));
+        self
+    }
+
+    /// Get the position of this node among the inputs or outputs of the model identified by
+    /// `model_id`, if known; `None` if the node was retrieved from another model.
+    #[inline]
+    #[cfg(feature = "prepostprocess")]
+    pub(crate) fn port(&self, model_id: u64) -> Option<Port> {
+        self.port
+            .filter(|(id, _)| *id == model_id)
+            .map(|(_, port)| port)
     }
 
     /// Get name of a port.
@@ -71,3 +108,86 @@ impl Node {
         Ok(PartialShape::from_c_struct(shape))
     }
 }
+
+/// Normalize a tensor name so that the names different OpenVINO versions and model converters
+/// give to the same tensor compare equal: strip the legacy output port suffixes (e.g., the `:0` of
+/// `prob:0` or the `/sink_port_0` of `prob/sink_port_0`) and ignore case.
+///
+/// ```
+/// # use openvino::normalize_tensor_name;
+/// assert_eq!(normalize_tensor_name("prob:0"), "prob");
+/// assert_eq!(normalize_tensor_name("Prob/sink_port_0"), "prob");
+/// ```
+pub fn normalize_tensor_name(name: &str) -> String {
+    let mut name = name.trim();
+    for separator in [":", "/sink_port_"] {
+        if let Some((base, port)) = name.rsplit_once(separator) {
+            if !base.is_empty() && !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()) {
+                name = base;
+            }
+        }
+    }
+    name.to_lowercase()
+}
+
+/// Find the index of the only name in `names` which looks like `pattern`, trying in turn: an exact
+/// match, a match of the normalized names (see [`normalize_tensor_name`]), a match of the last
+/// path segment of the normalized names (e.g., `softmax` for `model/prob/softmax`) and a
+/// normalized name containing `pattern`.
+///
+/// Returns [`InferenceError::NotFound`] if no name matches and
+/// [`InferenceError::ParameterMismatch`] if several names match equally well.
+pub(crate) fn find_name_like(names: &[String], pattern: &str) -> Result<usize> {
+    let normalized: Vec<String> = names.iter().map(|n| normalize_tensor_name(n)).collect();
+    let pattern_normalized = normalize_tensor_name(pattern);
+    let rules: [&dyn Fn(usize) -> bool; 4] = [
+        &|i| names[i] == pattern,
+        &|i| normalized[i] == pattern_normalized,
+        &|i| normalized[i].rsplit('/').next() == Some(pattern_normalized.as_str()),
+        &|i| normalized[i].contains(&pattern_normalized),
+    ];
+    for rule in rules {
+        let mut matches = (0..names.len()).filter(|&i| rule(i));
+        match (matches.next(), matches.next()) {
+            (Some(i), None) => return Ok(i),
+            (Some(_), Some(_)) => return Err(InferenceError::ParameterMismatch),
+            (None, _) => {}
+        }
+    }
+    Err(InferenceError::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalization() {
+        assert_eq!(normalize_tensor_name("prob"), "prob");
+        assert_eq!(normalize_tensor_name("Softmax:0"), "softmax");
+        assert_eq!(normalize_tensor_name("prob/sink_port_0"), "prob");
+        assert_eq!(normalize_tensor_name("model/prob:1"), "model/prob");
+        assert_eq!(normalize_tensor_name("prob:x"), "prob:x");
+        assert_eq!(normalize_tensor_name(":0"), ":0");
+    }
+
+    #[test]
+    fn fuzzy_lookup() {
+        let names: Vec<String> = ["logits", "model/Softmax:0", "model/softmax_1:0"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        assert_eq!(find_name_like(&names, "model/Softmax:0"), Ok(1));
+        assert_eq!(find_name_like(&names, "model/softmax"), Ok(1));
+        assert_eq!(find_name_like(&names, "softmax"), Ok(1));
+        assert_eq!(find_name_like(&names, "LOG"), Ok(0));
+        assert_eq!(
+            find_name_like(&names, "soft"),
+            Err(InferenceError::ParameterMismatch)
+        );
+        assert_eq!(
+            find_name_like(&names, "prob"),
+            Err(InferenceError::NotFound)
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::element_type::TensorElement;
+use crate::util::Result;
+use crate::{InferRequest, Shape, Tensor};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+/// A typed, mutable view of an output of an [`InferRequest`], returned by
+/// [`InferRequest::output_view`].
+///
+/// The view mutably borrows the request: while it is alive, the request cannot run another
+/// inference (which would overwrite the data), set its tensors or be dropped, e.g.:
+///
+/// ```compile_fail
+/// # fn example(request: &mut openvino::InferRequest) -> Result<(), openvino::InferenceError> {
+/// let scores = request.output_view::<f32>("scores")?;
+/// request.infer()?; // Error: `request` is still borrowed by `scores`.
+/// println!("{}", scores[0]);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Unlike the read-only handles returned by [`InferRequest::get_tensor`] (see
+/// [`crate::RequestTensor`]), the view borrows the request mutably and may modify the data. Since
+/// [`InferRequest::output_view`] refuses the tensors bound by the caller, whose own [`Tensor`]
+/// shares their data, the view is the only borrow of the data while it is alive.
+pub struct OutputView<'req, T> {
+    // Keep the tensor alive for as long as its data is borrowed.
+    tensor: Tensor,
+    data: NonNull<[T]>,
+    _request: PhantomData<&'req mut InferRequest>,
+}
+
+impl<T: TensorElement> OutputView<'_, T> {
+    pub(crate) fn new(mut tensor: Tensor) -> Result<Self> {
+        let data = NonNull::from(tensor.data_mut::<T>()?);
+        Ok(Self {
+            tensor,
+            data,
+            _request: PhantomData,
+        })
+    }
+
+    /// Get the shape of the output.
+    pub fn shape(&self) -> Result<Shape> {
+        self.tensor.get_shape()
+    }
+}
+
+impl<T> Deref for OutputView<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        // SAFETY: the data is owned by `tensor`, checked to hold elements of type `T` and cannot
+        // be modified by the request, which is borrowed for the lifetime of the view.
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T> DerefMut for OutputView<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: see `deref`; the view is borrowed mutably, so no other slice of it exists.
+        unsafe { self.data.as_mut() }
+    }
+}
@@ -0,0 +1,161 @@
+//! Read and write the elements of tensors with sub-byte element types.
+
+use crate::{util::Result, ElementType, InferenceError};
+
+/// A view of the elements of a sub-byte tensor (see [`crate::Tensor::get_packed_bits`]), i.e.,
+/// with an element type of [`ElementType::U1`], [`ElementType::U2`], [`ElementType::U4`],
+/// [`ElementType::I4`] or [`ElementType::NF4`].
+///
+/// Elements are packed as OpenVINO packs them: 1- and 2-bit elements start from the most
+/// significant bits of each byte, 4-bit elements from the least significant bits. Values are
+/// exposed as `i8`: [`ElementType::I4`] elements are signed (`-8..=7`), the others are unsigned
+/// (e.g., `0..=15` for [`ElementType::U4`]; [`ElementType::NF4`] elements are indexes into the
+/// NF4 lookup table).
+pub struct PackedBits<B> {
+    bytes: B,
+    element_type: ElementType,
+    bits: usize,
+    len: usize,
+}
+
+impl<B: AsRef<[u8]>> PackedBits<B> {
+    /// View `len` elements of `element_type` packed in `bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::NotImplemented`] if `element_type` is not one of the supported
+    /// sub-byte types and [`InferenceError::ParameterMismatch`] if `bytes` is too small to hold
+    /// `len` elements.
+    pub fn new(bytes: B, element_type: ElementType, len: usize) -> Result<Self> {
+        let bits = match element_type {
+            ElementType::U1 => 1,
+            ElementType::U2 => 2,
+            ElementType::U4 | ElementType::I4 | ElementType::NF4 => 4,
+            _ => return Err(InferenceError::NotImplemented),
+        };
+        if bytes.as_ref().len() < (len * bits).div_ceil(8) {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        Ok(Self {
+            bytes,
+            element_type,
+            bits,
+            len,
+        })
+    }
+
+    /// Get the number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the element at `index`, or `None` if `index` is out of bounds.
+    pub fn get(&self, index: usize) -> Option<i8> {
+        if index >= self.len {
+            return None;
+        }
+        let (byte, shift) = self.position(index);
+        let value = (self.bytes.as_ref()[byte] >> shift) & self.mask();
+        if self.element_type == ElementType::I4 {
+            // Sign-extend from 4 bits.
+            Some(i8::from_ne_bytes([value << 4]) >> 4)
+        } else {
+            Some(i8::from_ne_bytes([value]))
+        }
+    }
+
+    /// Iterate over the elements.
+    pub fn iter(&self) -> impl Iterator<Item = i8> + '_ {
+        (0..self.len).filter_map(|i| self.get(i))
+    }
+
+    /// Find the byte holding the element at `index` and the shift of its lowest bit.
+    fn position(&self, index: usize) -> (usize, usize) {
+        let per_byte = 8 / self.bits;
+        let slot = index % per_byte;
+        let shift = if self.bits == 4 {
+            slot * self.bits
+        } else {
+            8 - self.bits * (slot + 1)
+        };
+        (index / per_byte, shift)
+    }
+
+    fn mask(&self) -> u8 {
+        (1 << self.bits) - 1
+    }
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> PackedBits<B> {
+    /// Set the element at `index` to `value`, leaving the other elements in the same byte
+    /// untouched.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::OutOfBounds`] if `index` is out of bounds and
+    /// [`InferenceError::ParameterMismatch`] if `value` does not fit the element type.
+    pub fn set(&mut self, index: usize, value: i8) -> Result<()> {
+        if index >= self.len {
+            return Err(InferenceError::OutOfBounds);
+        }
+        let range = if self.element_type == ElementType::I4 {
+            -8..=7
+        } else {
+            0..=i8::from_ne_bytes([self.mask()])
+        };
+        if !range.contains(&value) {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let (byte, shift) = self.position(index);
+        let mask = self.mask();
+        let byte = &mut self.bytes.as_mut()[byte];
+        *byte = (*byte & !(mask << shift)) | ((value.to_ne_bytes()[0] & mask) << shift);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packing() {
+        // 1-bit elements start from the most significant bit.
+        let bits = PackedBits::new([0b1000_0001u8, 0b0100_0000], ElementType::U1, 10).unwrap();
+        let values: Vec<i8> = bits.iter().collect();
+        assert_eq!(values, [1, 0, 0, 0, 0, 0, 0, 1, 0, 1]);
+
+        // 4-bit elements start from the least significant bits.
+        let bits = PackedBits::new([0x21u8, 0xf3], ElementType::U4, 4).unwrap();
+        assert_eq!(bits.iter().collect::<Vec<_>>(), [1, 2, 3, 15]);
+        let bits = PackedBits::new([0x21u8, 0xf3], ElementType::I4, 4).unwrap();
+        assert_eq!(bits.iter().collect::<Vec<_>>(), [1, 2, 3, -1]);
+        assert_eq!(bits.get(4), None);
+    }
+
+    #[test]
+    fn setting() {
+        let mut bits = PackedBits::new([0u8; 2], ElementType::U2, 6).unwrap();
+        bits.set(0, 3).unwrap();
+        bits.set(5, 2).unwrap();
+        assert_eq!(bits.bytes, [0b1100_0000, 0b0010_0000]);
+        assert_eq!(bits.set(1, 4), Err(InferenceError::ParameterMismatch));
+        assert_eq!(bits.set(6, 0), Err(InferenceError::OutOfBounds));
+
+        let mut bits = PackedBits::new([0xffu8], ElementType::I4, 2).unwrap();
+        bits.set(1, -8).unwrap();
+        assert_eq!(bits.bytes, [0x8f]);
+        assert_eq!(bits.get(1), Some(-8));
+    }
+
+    #[test]
+    fn validation() {
+        assert!(PackedBits::new([0u8; 4], ElementType::U8, 4).is_err());
+        assert!(PackedBits::new([0u8; 1], ElementType::U4, 3).is_err());
+    }
+}
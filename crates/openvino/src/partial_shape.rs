@@ -1,8 +1,10 @@
-use crate::{dimension::Dimension, try_unsafe, util::Result, Rank};
+use crate::util::{slice_from_raw, Result};
+use crate::{dimension::Dimension, try_unsafe, Dim, InferenceError, Rank, Shape};
 use openvino_sys::{
     ov_dimension_t, ov_partial_shape_create, ov_partial_shape_create_dynamic,
     ov_partial_shape_create_static, ov_partial_shape_free, ov_partial_shape_is_dynamic,
-    ov_partial_shape_t, ov_rank_t,
+    ov_partial_shape_t, ov_partial_shape_to_shape, ov_rank_t, ov_shape_t,
+    ov_shape_to_partial_shape,
 };
 
 use std::convert::TryInto;
@@ -76,6 +78,23 @@ impl PartialShape {
         })
     }
 
+    /// Create a new [`PartialShape`] with a static rank from its dimensions, e.g.:
+    ///
+    /// ```no_run
+    /// # use openvino::{Dim, PartialShape};
+    /// let shape = PartialShape::from_dims([Dim::Static(1), Dim::Dynamic, Dim::Range(1, 8)])?;
+    /// assert!(shape.is_dynamic());
+    /// # Ok::<(), openvino::InferenceError>(())
+    /// ```
+    pub fn from_dims(dims: impl IntoIterator<Item = Dim>) -> Result<Self> {
+        let dimensions: Vec<Dimension> = dims.into_iter().map(Dimension::from).collect();
+        let rank = dimensions
+            .len()
+            .try_into()
+            .map_err(|_| InferenceError::OutOfBounds)?;
+        Self::new(rank, &dimensions)
+    }
+
     /// Returns the rank of the partial shape.
     pub fn get_rank(&self) -> Rank {
         let rank = self.c_struct.rank;
@@ -88,16 +107,10 @@ impl PartialShape {
     ///
     /// Panics in the unlikely case the rank cannot be represented as a `usize`.
     pub fn get_dimensions(&self) -> &[Dimension] {
-        if self.c_struct.dims.is_null() {
-            &[]
-        } else {
-            unsafe {
-                std::slice::from_raw_parts(
-                    self.c_struct.dims.cast::<Dimension>(),
-                    self.c_struct.rank.max.try_into().unwrap(),
-                )
-            }
-        }
+        let rank = self.c_struct.rank.max.max(0).try_into().unwrap();
+        // SAFETY: the partial shape owns `rank` dimensions at `dims`, if any; `Dimension` wraps
+        // `ov_dimension_t`.
+        unsafe { slice_from_raw(self.c_struct.dims.cast::<Dimension>(), rank) }
     }
 
     /// Returns `true` if the partial shape is dynamic.
@@ -106,6 +119,43 @@ impl PartialShape {
     }
 }
 
+impl TryFrom<&Shape> for PartialShape {
+    type Error = InferenceError;
+
+    fn try_from(shape: &Shape) -> Result<Self> {
+        let mut partial_shape = ov_partial_shape_t {
+            rank: ov_rank_t { min: 0, max: 0 },
+            dims: std::ptr::null_mut(),
+        };
+        try_unsafe!(ov_shape_to_partial_shape(
+            shape.as_c_struct(),
+            std::ptr::addr_of_mut!(partial_shape)
+        ))?;
+        Ok(Self::from_c_struct(partial_shape))
+    }
+}
+
+/// Convert a fully static [`PartialShape`] to a [`Shape`]; a dynamic one fails with
+/// [`InferenceError::ParameterMismatch`].
+impl TryFrom<&PartialShape> for Shape {
+    type Error = InferenceError;
+
+    fn try_from(partial_shape: &PartialShape) -> Result<Self> {
+        if partial_shape.is_dynamic() {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let mut shape = ov_shape_t {
+            rank: 0,
+            dims: std::ptr::null_mut(),
+        };
+        try_unsafe!(ov_partial_shape_to_shape(
+            partial_shape.c_struct,
+            std::ptr::addr_of_mut!(shape)
+        ))?;
+        Ok(Shape::from_c_struct(shape))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::LoadingError;
@@ -174,4 +224,40 @@ mod tests {
 
         assert_eq!(dims, &dimensions);
     }
+
+    #[test]
+    fn test_from_dims() {
+        openvino_sys::library::load()
+            .map_err(LoadingError::SystemFailure)
+            .unwrap();
+
+        let shape =
+            PartialShape::from_dims([Dim::Static(1), Dim::Dynamic, Dim::Range(1, 8)]).unwrap();
+        assert_eq!(shape.get_rank(), Rank::new(3, 3));
+        assert_eq!(
+            shape
+                .get_dimensions()
+                .iter()
+                .map(|d| Dim::from(*d))
+                .collect::<Vec<_>>(),
+            [Dim::Static(1), Dim::Dynamic, Dim::Range(1, 8)]
+        );
+        assert_eq!(
+            Shape::try_from(&shape).err(),
+            Some(InferenceError::ParameterMismatch)
+        );
+    }
+
+    #[test]
+    fn test_shape_conversions() {
+        openvino_sys::library::load()
+            .map_err(LoadingError::SystemFailure)
+            .unwrap();
+
+        let shape = Shape::new(&[1, 3, 224, 224]).unwrap();
+        let partial_shape = PartialShape::try_from(&shape).unwrap();
+        assert!(!partial_shape.is_dynamic());
+        let round_trip = Shape::try_from(&partial_shape).unwrap();
+        assert_eq!(round_trip.get_dimensions(), &[1, 3, 224, 224]);
+    }
 }
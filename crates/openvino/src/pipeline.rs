@@ -0,0 +1,241 @@
+//! Chain several [`CompiledModel`]s so that the outputs of one stage feed the inputs of the next,
+//! e.g., for detection → classification cascades.
+//!
+//! Each stage owns an [`InferRequest`]; by default, the output tensors of a stage are passed (by
+//! index, without copying) as the input tensors of the next. An adapter can be inserted between
+//! two stages to reshape, convert or otherwise transform the intermediate tensors (see [`reshape`]
+//! and [`convert`]).
+//!
+//! ```no_run
+//! # use openvino::{pipeline::{self, Pipeline}, Core, DeviceType, ElementType, Shape, Tensor};
+//! # fn main() -> anyhow::Result<()> {
+//! # let mut core = Core::new()?;
+//! # let detector = core.read_model_from_file("detector.xml", "detector.bin")?;
+//! # let classifier = core.read_model_from_file("classifier.xml", "classifier.bin")?;
+//! let detector = core.compile_model(&detector, DeviceType::CPU)?;
+//! let classifier = core.compile_model(&classifier, DeviceType::CPU)?;
+//! let mut pipeline = Pipeline::new(&detector)?
+//!     .then_with(&classifier, pipeline::reshape(&[1, 3, 224, 224]))?;
+//! # let image = Tensor::new(ElementType::F32, &Shape::new(&[1, 3, 300, 300])?)?;
+//! let outputs = pipeline.infer(&[&image])?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    util::Result, CompiledModel, ElementType, InferRequest, InferenceError, Shape, Tensor,
+};
+
+/// Transform the output tensors of one stage into the input tensors of the next.
+pub type Adapter = Box<dyn FnMut(Vec<Tensor>) -> Result<Vec<Tensor>>>;
+
+/// A sequence of models, each stage feeding the next; see the [module](crate::pipeline)
+/// documentation.
+pub struct Pipeline {
+    first: Stage,
+    rest: Vec<Stage>,
+}
+
+struct Stage {
+    request: InferRequest,
+    inputs: usize,
+    outputs: usize,
+    adapter: Option<Adapter>,
+}
+
+impl Stage {
+    fn new(model: &CompiledModel, adapter: Option<Adapter>) -> Result<Self> {
+        Ok(Self {
+            request: model.create_infer_request()?,
+            inputs: model.get_input_size()?,
+            outputs: model.get_output_size()?,
+            adapter,
+        })
+    }
+}
+
+impl Pipeline {
+    /// Start a pipeline with `model` as its first stage.
+    pub fn new(model: &CompiledModel) -> Result<Self> {
+        Ok(Self {
+            first: Stage::new(model, None)?,
+            rest: vec![],
+        })
+    }
+
+    /// Append a stage whose inputs are the outputs of the previous stage, by index.
+    pub fn then(mut self, model: &CompiledModel) -> Result<Self> {
+        self.rest.push(Stage::new(model, None)?);
+        Ok(self)
+    }
+
+    /// Append a stage whose inputs are computed by `adapter` from the outputs of the previous
+    /// stage.
+    pub fn then_with(
+        mut self,
+        model: &CompiledModel,
+        adapter: impl FnMut(Vec<Tensor>) -> Result<Vec<Tensor>> + 'static,
+    ) -> Result<Self> {
+        self.rest.push(Stage::new(model, Some(Box::new(adapter)))?);
+        Ok(self)
+    }
+
+    /// Get the number of stages.
+    pub fn len(&self) -> usize {
+        1 + self.rest.len()
+    }
+
+    /// Get the inference request of a stage, e.g., to inspect its intermediate tensors after
+    /// [`Pipeline::infer`].
+    pub fn request(&self, stage: usize) -> Option<&InferRequest> {
+        match stage {
+            0 => Some(&self.first.request),
+            n => self.rest.get(n - 1).map(|s| &s.request),
+        }
+    }
+
+    /// Run all stages in order, starting with `inputs` (by index) for the first stage, and return
+    /// the output tensors of the last stage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the number of tensors passed to a stage
+    /// does not match its number of inputs, or any error from the stages or adapters.
+    pub fn infer(&mut self, inputs: &[&Tensor]) -> Result<Vec<Tensor>> {
+        let first = &mut self.first;
+        if inputs.len() != first.inputs {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        for (index, tensor) in inputs.iter().enumerate() {
+            first.request.set_input_tensor_by_index(index, tensor)?;
+        }
+        first.request.infer()?;
+        let mut outputs = collect_outputs(first)?;
+
+        for stage in &mut self.rest {
+            let inputs = match &mut stage.adapter {
+                Some(adapter) => adapter(outputs)?,
+                None => outputs,
+            };
+            if inputs.len() != stage.inputs {
+                return Err(InferenceError::ParameterMismatch);
+            }
+            for (index, tensor) in inputs.iter().enumerate() {
+                stage.request.set_input_tensor_by_index(index, tensor)?;
+            }
+            stage.request.infer()?;
+            outputs = collect_outputs(stage)?;
+        }
+        Ok(outputs)
+    }
+}
+
+fn collect_outputs(stage: &Stage) -> Result<Vec<Tensor>> {
+    (0..stage.outputs)
+        .map(|i| stage.request.get_output_tensor_by_index(i)?.deep_copy())
+        .collect()
+}
+
+/// Create an adapter that copies the single output of a stage into a tensor of a new `shape`
+/// with the same number of elements.
+pub fn reshape(shape: &[i64]) -> impl FnMut(Vec<Tensor>) -> Result<Vec<Tensor>> {
+    let shape = shape.to_vec();
+    move |tensors| {
+        let [tensor] = tensors.as_slice() else {
+            return Err(InferenceError::ParameterMismatch);
+        };
+        Ok(vec![reshape_tensor(tensor, &shape)?])
+    }
+}
+
+/// Create an adapter that converts the elements of every output of a stage to `element_type`
+/// (numeric types only: `F32`, `F64`, `I8` through `I64` and `U8` through `U64`). Values are
+/// converted as with Rust's `as` casts, i.e., saturating and truncating towards zero.
+pub fn convert(element_type: ElementType) -> impl FnMut(Vec<Tensor>) -> Result<Vec<Tensor>> {
+    move |tensors| {
+        tensors
+            .iter()
+            .map(|t| convert_tensor(t, element_type))
+            .collect()
+    }
+}
+
+/// Copy `tensor` into a new tensor with the same element type and the given `shape`.
+fn reshape_tensor(tensor: &Tensor, shape: &[i64]) -> Result<Tensor> {
+    let mut reshaped = Tensor::new(tensor.get_element_type()?, &Shape::new(shape)?)?;
+    if reshaped.get_byte_size()? != tensor.get_byte_size()? {
+        return Err(InferenceError::ParameterMismatch);
+    }
+    reshaped
+        .get_raw_data_mut()?
+        .copy_from_slice(tensor.get_raw_data()?);
+    Ok(reshaped)
+}
+
+/// Copy `tensor` into a new tensor of the same shape with elements of type `to`.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_lossless
+)]
+fn convert_tensor(tensor: &Tensor, to: ElementType) -> Result<Tensor> {
+    macro_rules! read {
+        ($tensor: expr, $($variant: ident => $ty: ty),+) => {
+            match $tensor.get_element_type()? {
+                $(ElementType::$variant => $tensor.data::<$ty>()?.iter().map(|&v| v as f64).collect::<Vec<f64>>(),)+
+                _ => return Err(InferenceError::NotImplemented),
+            }
+        };
+    }
+    macro_rules! write {
+        ($tensor: expr, $values: expr, $($variant: ident => $ty: ty),+) => {
+            match to {
+                $(ElementType::$variant => {
+                    for (d, &v) in $tensor.data_mut::<$ty>()?.iter_mut().zip($values) {
+                        *d = v as $ty;
+                    }
+                })+
+                _ => return Err(InferenceError::NotImplemented),
+            }
+        };
+    }
+    let values = read!(tensor, F32 => f32, F64 => f64, I8 => i8, I16 => i16, I32 => i32, I64 => i64, U8 => u8, U16 => u16, U32 => u32, U64 => u64);
+    let mut converted = Tensor::new(to, &tensor.get_shape()?)?;
+    write!(converted, &values, F32 => f32, F64 => f64, I8 => i8, I16 => i16, I32 => i32, I64 => i64, U8 => u8, U16 => u16, U32 => u32, U64 => u64);
+    Ok(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reshape() {
+        openvino_sys::library::load().unwrap();
+        let mut tensor = Tensor::new(ElementType::U8, &Shape::new(&[2, 3]).unwrap()).unwrap();
+        tensor
+            .data_mut::<u8>()
+            .unwrap()
+            .copy_from_slice(&[1, 2, 3, 4, 5, 6]);
+        let reshaped = reshape(&[3, 2])(vec![tensor]).unwrap();
+        assert_eq!(reshaped[0].get_shape().unwrap().get_dimensions(), &[3, 2]);
+        assert_eq!(reshaped[0].data::<u8>().unwrap(), &[1, 2, 3, 4, 5, 6]);
+
+        let tensor = Tensor::new(ElementType::U8, &Shape::new(&[2, 3]).unwrap()).unwrap();
+        assert!(reshape(&[4, 2])(vec![tensor]).is_err());
+    }
+
+    #[test]
+    fn test_convert() {
+        openvino_sys::library::load().unwrap();
+        let mut tensor = Tensor::new(ElementType::F32, &Shape::new(&[3]).unwrap()).unwrap();
+        tensor
+            .data_mut::<f32>()
+            .unwrap()
+            .copy_from_slice(&[-1.5, 2.7, 300.0]);
+        let converted = convert(ElementType::U8)(vec![tensor]).unwrap();
+        assert_eq!(converted[0].get_element_type().unwrap(), ElementType::U8);
+        assert_eq!(converted[0].data::<u8>().unwrap(), &[0, 2, 255]);
+    }
+}
@@ -32,9 +32,13 @@
 //! model_info.set_layout(Layout::new("NCHW").expect("to create a new layout")).expect("to set layout");
 //! let new_model = pipeline.build_new_model().expect("to build new model with above prepostprocess steps");
 //! ```
+use crate::node::Port;
 use crate::{
-    cstr, drop_using_function, layout::Layout, try_unsafe, util::Result, ElementType, Model,
-    ResizeAlgorithm, Tensor,
+    cstr, drop_using_function,
+    layout::{channel_index, dimension_names, is_fully_named, Layout},
+    try_unsafe,
+    util::{attribute_value, Result},
+    ElementType, InferenceError, Model, Node, ResizeAlgorithm, Shape, Tensor,
 };
 use openvino_sys::{
     ov_preprocess_input_info_free, ov_preprocess_input_info_get_model_info,
@@ -54,14 +58,27 @@ use openvino_sys::{
     ov_preprocess_prepostprocessor_get_output_info_by_name, ov_preprocess_prepostprocessor_t,
     ov_preprocess_preprocess_steps_convert_element_type,
     ov_preprocess_preprocess_steps_convert_layout, ov_preprocess_preprocess_steps_free,
-    ov_preprocess_preprocess_steps_resize, ov_preprocess_preprocess_steps_t,
+    ov_preprocess_preprocess_steps_mean, ov_preprocess_preprocess_steps_mean_multi_channels,
+    ov_preprocess_preprocess_steps_resize, ov_preprocess_preprocess_steps_reverse_channels,
+    ov_preprocess_preprocess_steps_scale, ov_preprocess_preprocess_steps_scale_multi_channels,
+    ov_preprocess_preprocess_steps_t,
 };
+use std::cell::RefCell;
+use std::rc::Rc;
 
 /// See
 /// [`ov_preprocess_prepostprocessor_t`](https://docs.openvino.ai/2024/api/c_cpp_api/structov__preprocess__prepostprocessor__t.html).
 #[derive(Debug)]
 pub struct Pipeline {
     ptr: *mut ov_preprocess_prepostprocessor_t,
+    // Identifies the model, to find its nodes by index; see `Node::port`.
+    model_id: u64,
+    // What is known about each of the model's inputs, by index, along with their names (if any) to
+    // find them by name; this is passed on to the built model.
+    inputs: Vec<(Option<String>, Rc<RefCell<TensorDescription>>)>,
+    // What is known about the inputs retrieved by a name not recorded in `inputs` (e.g., another
+    // of their tensor names), by that name, so that retrieving them again finds it.
+    named_inputs: RefCell<Vec<(String, Rc<RefCell<TensorDescription>>)>>,
 }
 drop_using_function!(Pipeline, ov_preprocess_prepostprocessor_free);
 impl Pipeline {
@@ -72,7 +89,17 @@ impl Pipeline {
             model.as_ptr(),
             std::ptr::addr_of_mut!(ptr)
         ))?;
-        Ok(Self { ptr })
+        let inputs = model
+            .inputs()?
+            .iter()
+            .map(|n| (n.get_name().ok(), Rc::default()))
+            .collect();
+        Ok(Self {
+            ptr,
+            model_id: model.id(),
+            inputs,
+            named_inputs: RefCell::default(),
+        })
     }
 
     /// Retrieves the input information by index.
@@ -84,25 +111,25 @@ impl Pipeline {
             std::ptr::addr_of_mut!(ptr)
         ))?;
 
-        Ok(InputInfo { ptr })
+        Ok(InputInfo::from_ptr(ptr, self.input_description(index)))
     }
 
     /// Retrieves the input information by name.
     pub fn get_input_info_by_name(&self, name: &str) -> Result<InputInfo> {
-        let name = cstr!(name);
+        let c_name = cstr!(name)?;
         let mut ptr = std::ptr::null_mut();
         try_unsafe!(ov_preprocess_prepostprocessor_get_input_info_by_name(
             self.ptr,
-            name.as_ptr(),
+            c_name.as_ptr(),
             std::ptr::addr_of_mut!(ptr)
         ))?;
 
-        Ok(InputInfo { ptr })
+        Ok(InputInfo::from_ptr(ptr, self.named_input_description(name)))
     }
 
     /// Retrieves the output information by name.
     pub fn get_output_info_by_name(&self, name: &str) -> Result<OutputInfo> {
-        let name = cstr!(name);
+        let name = cstr!(name)?;
         let mut ptr = std::ptr::null_mut();
         try_unsafe!(ov_preprocess_prepostprocessor_get_output_info_by_name(
             self.ptr,
@@ -124,6 +151,39 @@ impl Pipeline {
         Ok(OutputInfo { ptr })
     }
 
+    /// Retrieves the input information for an input [`Node`] of the model (e.g., from
+    /// [`Model::inputs`]). Nodes retrieved by index from the model this pipeline was created for
+    /// are found by that index, which allows configuring pre-processing generically even when
+    /// tensor names are absent or duplicated; other nodes (e.g., of another model or of a
+    /// [`crate::CompiledModel`]) are found by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `node` is known to be an output of the
+    /// model.
+    pub fn get_input_info_by_node(&self, node: &Node) -> Result<InputInfo> {
+        match node.port(self.model_id) {
+            Some(Port::Input(index)) => self.get_input_info_by_index(index),
+            Some(Port::Output(_)) => Err(InferenceError::ParameterMismatch),
+            None => self.get_input_info_by_name(&node.get_name()?),
+        }
+    }
+
+    /// Retrieves the output information for an output [`Node`] of the model (e.g., from
+    /// [`Model::outputs`]); see [`Pipeline::get_input_info_by_node`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `node` is known to be an input of the
+    /// model.
+    pub fn get_output_info_by_node(&self, node: &Node) -> Result<OutputInfo> {
+        match node.port(self.model_id) {
+            Some(Port::Output(index)) => self.get_output_info_by_index(index),
+            Some(Port::Input(_)) => Err(InferenceError::ParameterMismatch),
+            None => self.get_output_info_by_name(&node.get_name()?),
+        }
+    }
+
     /// Retrieves the input information.
     ///
     /// # Panics
@@ -136,7 +196,7 @@ impl Pipeline {
             std::ptr::addr_of_mut!(ptr)
         ))?;
         assert!(!ptr.is_null());
-        Ok(InputInfo { ptr })
+        Ok(InputInfo::from_ptr(ptr, self.input_description(0)))
     }
 
     /// Builds a new model with all steps from pre/postprocessing.
@@ -146,7 +206,41 @@ impl Pipeline {
             self.ptr,
             std::ptr::addr_of_mut!(ptr)
         ))?;
-        Ok(Model::from_ptr(ptr))
+        let layouts = self
+            .inputs
+            .iter()
+            .map(|(_, t)| t.borrow().tensor_layout.clone())
+            .collect();
+        Ok(Model::from_ptr(ptr).with_input_layouts(layouts))
+    }
+
+    /// Get the description of the input at `index`, shared by all of the [`InputInfo`]s retrieved
+    /// for it; inputs that cannot be identified get a description of their own.
+    fn input_description(&self, index: usize) -> Rc<RefCell<TensorDescription>> {
+        self.inputs
+            .get(index)
+            .map(|(_, t)| Rc::clone(t))
+            .unwrap_or_default()
+    }
+
+    /// Get the description of the input named `name`, shared by all of the [`InputInfo`]s
+    /// retrieved for it: that of the input recorded with this name or, failing that, one kept for
+    /// the name.
+    fn named_input_description(&self, name: &str) -> Rc<RefCell<TensorDescription>> {
+        if let Some(index) = self
+            .inputs
+            .iter()
+            .position(|(n, _)| n.as_deref() == Some(name))
+        {
+            return self.input_description(index);
+        }
+        let mut named_inputs = self.named_inputs.borrow_mut();
+        if let Some((_, t)) = named_inputs.iter().find(|(n, _)| n == name) {
+            return Rc::clone(t);
+        }
+        let tensor = Rc::default();
+        named_inputs.push((name.to_string(), Rc::clone(&tensor)));
+        tensor
     }
 }
 
@@ -154,10 +248,18 @@ impl Pipeline {
 /// [`ov_preprocess_input_info_t`](https://docs.openvino.ai/2024/api/c_cpp_api/structov__preprocess__input__info__t.html).
 pub struct InputInfo {
     ptr: *mut ov_preprocess_input_info_t,
+    tensor: Rc<RefCell<TensorDescription>>,
 }
 drop_using_function!(InputInfo, ov_preprocess_input_info_free);
 
 impl InputInfo {
+    fn from_ptr(
+        ptr: *mut ov_preprocess_input_info_t,
+        tensor: Rc<RefCell<TensorDescription>>,
+    ) -> Self {
+        Self { ptr, tensor }
+    }
+
     /// Retrieves the preprocessing model input information.
     pub fn get_model_info(&self) -> Result<InputModelInfo> {
         let mut ptr = std::ptr::null_mut();
@@ -175,7 +277,10 @@ impl InputInfo {
             self.ptr,
             std::ptr::addr_of_mut!(ptr)
         ))?;
-        Ok(InputTensorInfo { ptr })
+        Ok(InputTensorInfo {
+            ptr,
+            tensor: Rc::clone(&self.tensor),
+        })
     }
 
     /// Retrieves the preprocessing steps.
@@ -185,7 +290,10 @@ impl InputInfo {
             self.ptr,
             std::ptr::addr_of_mut!(ptr)
         ))?;
-        Ok(Steps { ptr })
+        Ok(Steps {
+            ptr,
+            tensor: Rc::clone(&self.tensor),
+        })
     }
 }
 
@@ -227,6 +335,7 @@ impl InputModelInfo {
 /// [`ov_preprocess_input_tensor_info_t`](https://docs.openvino.ai/2024/api/c_cpp_api/structov__preprocess__input__tensor__info__t.html).
 pub struct InputTensorInfo {
     ptr: *mut ov_preprocess_input_tensor_info_t,
+    tensor: Rc<RefCell<TensorDescription>>,
 }
 drop_using_function!(InputTensorInfo, ov_preprocess_input_tensor_info_free);
 impl InputTensorInfo {
@@ -235,7 +344,12 @@ impl InputTensorInfo {
         try_unsafe!(ov_preprocess_input_tensor_info_set_layout(
             self.ptr,
             layout.as_mut_ptr()
-        ))
+        ))?;
+        let mut tensor = self.tensor.borrow_mut();
+        tensor.channel_index = layout.channel_index();
+        tensor.layout = Some(layout.to_string());
+        tensor.tensor_layout = Some(layout.to_string());
+        Ok(())
     }
 
     /// Sets the input tensor info from an existing tensor.
@@ -243,7 +357,9 @@ impl InputTensorInfo {
         try_unsafe!(ov_preprocess_input_tensor_info_set_from(
             self.ptr,
             tensor.as_ptr()
-        ))
+        ))?;
+        self.tensor.borrow_mut().dimensions = Some(tensor.get_shape()?.get_dimensions().to_vec());
+        Ok(())
     }
 }
 
@@ -267,6 +383,7 @@ impl OutputTensorInfo {
 /// [`ov_preprocess_preprocess_steps_t`](https://docs.openvino.ai/2024/api/c_cpp_api/structov__preprocess__preprocess__steps__t.html).
 pub struct Steps {
     ptr: *mut ov_preprocess_preprocess_steps_t,
+    tensor: Rc<RefCell<TensorDescription>>,
 }
 drop_using_function!(Steps, ov_preprocess_preprocess_steps_free);
 impl Steps {
@@ -283,7 +400,31 @@ impl Steps {
         try_unsafe!(ov_preprocess_preprocess_steps_convert_layout(
             self.ptr,
             new_layout.as_mut_ptr(),
-        ))
+        ))?;
+        self.tensor
+            .borrow_mut()
+            .convert_layout(new_layout.to_string());
+        Ok(())
+    }
+
+    /// Converts the layout of the data in a [`Tensor`] by permuting its dimensions: dimension `i`
+    /// of the result is dimension `order[i]` of the input (e.g., `[0, 3, 1, 2]` converts `NHWC`
+    /// data to `NCHW`).
+    ///
+    /// The C API only converts between named layouts, so this names the target layout after the
+    /// tensor's: the tensor's layout must have been set (see [`InputTensorInfo::set_layout`]),
+    /// with a name for each dimension (e.g., `[a,b,c,d]` when no standard name fits).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the tensor's layout is unknown or not fully
+    /// named, or if `order` is not a permutation of its dimensions.
+    pub fn convert_layout_by_permutation(&mut self, order: &[usize]) -> Result<()> {
+        let layout = self.tensor.borrow().layout.clone();
+        match layout.and_then(|l| permuted_layout(&l, order)) {
+            Some(layout) => self.convert_layout(Layout::new(&layout)?),
+            None => Err(InferenceError::ParameterMismatch),
+        }
     }
 
     /// Converts the element type of data in tensor.
@@ -293,4 +434,629 @@ impl Steps {
             new_element_type.into()
         ))
     }
+
+    /// Subtracts `values` from each element of the input: either a single value for all elements
+    /// or one value per channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `values` is empty or if the number of
+    /// values does not match the number of channels of the input tensor. The number of channels is
+    /// only known once the layout (with a `C` dimension) and the tensor (see
+    /// [`InputTensorInfo::set_from`]) have been set on the [`InputTensorInfo`] retrieved from the
+    /// same [`InputInfo`] as these steps.
+    ///
+    /// # Panics
+    ///
+    /// Panics in the unlikely case the number of values cannot be represented as an `i32`.
+    pub fn mean(&mut self, values: &[f32]) -> Result<()> {
+        self.check_channels(values)?;
+        if let [value] = values {
+            try_unsafe!(ov_preprocess_preprocess_steps_mean(self.ptr, *value))
+        } else {
+            try_unsafe!(ov_preprocess_preprocess_steps_mean_multi_channels(
+                self.ptr,
+                values.as_ptr(),
+                i32::try_from(values.len()).unwrap()
+            ))
+        }
+    }
+
+    /// Divides each element of the input by `values`: either a single value for all elements or
+    /// one value per channel.
+    ///
+    /// # Errors
+    ///
+    /// See [`Steps::mean`].
+    ///
+    /// # Panics
+    ///
+    /// Panics in the unlikely case the number of values cannot be represented as an `i32`.
+    pub fn scale(&mut self, values: &[f32]) -> Result<()> {
+        self.check_channels(values)?;
+        if let [value] = values {
+            try_unsafe!(ov_preprocess_preprocess_steps_scale(self.ptr, *value))
+        } else {
+            try_unsafe!(ov_preprocess_preprocess_steps_scale_multi_channels(
+                self.ptr,
+                values.as_ptr(),
+                i32::try_from(values.len()).unwrap()
+            ))
+        }
+    }
+
+    /// Reverses the order of the channels of the input, e.g., to feed BGR images to a model
+    /// trained on RGB ones. The tensor's layout must have a `C` dimension (see
+    /// [`InputTensorInfo::set_layout`]).
+    pub fn reverse_channels(&mut self) -> Result<()> {
+        try_unsafe!(ov_preprocess_preprocess_steps_reverse_channels(self.ptr))
+    }
+
+    /// Check that per-channel `values` match the number of channels, when known; unlike the C++
+    /// API, which silently accepts mismatched channel counts.
+    fn check_channels(&self, values: &[f32]) -> Result<()> {
+        match (values.len(), self.tensor.borrow().channels()) {
+            (0, _) => Err(InferenceError::ParameterMismatch),
+            (1, _) | (_, None) => Ok(()),
+            (len, Some(channels)) if len == channels => Ok(()),
+            _ => Err(InferenceError::ParameterMismatch),
+        }
+    }
+}
+
+/// A declarative description of the pre- and postprocessing of a model, defined once and applied
+/// to any number of [`Model`]s with compatible inputs and outputs (e.g., one model per camera).
+///
+/// Applying a spec configures a [`Pipeline`] with the same calls as done by hand, in the order
+/// described, and builds the new model; a spec which does not fit a model (e.g., an input it lacks
+/// or mean values that do not match the number of channels) fails to apply.
+///
+/// ```no_run
+/// # use openvino::{prepostprocess::{InputSpec, Spec}, Core, ElementType, ResizeAlgorithm};
+/// # fn main() -> anyhow::Result<()> {
+/// # let mut core = Core::new()?;
+/// let spec = Spec::new().input(
+///     0,
+///     InputSpec::new()
+///         .tensor(ElementType::U8, &[1, 480, 640, 3])
+///         .tensor_layout("NHWC")
+///         .convert_element_type(ElementType::F32)
+///         .resize(ResizeAlgorithm::Linear)
+///         .mean(&[123.675, 116.28, 103.53])
+///         .model_layout("NCHW"),
+/// );
+/// let models = ["camera-1.xml", "camera-2.xml"]
+///     .iter()
+///     .map(|xml| spec.apply(&core.read_model_from_file(xml, &xml.replace(".xml", ".bin"))?))
+///     .collect::<Result<Vec<_>, _>>()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Spec {
+    inputs: Vec<(Selector, InputSpec)>,
+    outputs: Vec<(Selector, ElementType)>,
+}
+
+/// Identify an input or output of a model.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Selector {
+    Index(usize),
+    Name(String),
+}
+
+impl Selector {
+    /// Check that a model has the port this selects among its ports `nodes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::OutOfBounds`] or [`InferenceError::NotFound`] if it has not.
+    fn check(&self, nodes: &[Node]) -> Result<()> {
+        match self {
+            Self::Index(index) if *index >= nodes.len() => Err(InferenceError::OutOfBounds),
+            Self::Name(name)
+                if !nodes
+                    .iter()
+                    .any(|n| n.get_name().ok().as_ref() == Some(name)) =>
+            {
+                Err(InferenceError::NotFound)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Spec {
+    /// Create a spec which leaves models unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preprocess the input at `index` as described by `input`.
+    #[must_use]
+    pub fn input(mut self, index: usize, input: InputSpec) -> Self {
+        self.inputs.push((Selector::Index(index), input));
+        self
+    }
+
+    /// Preprocess the input named `name` as described by `input`.
+    #[must_use]
+    pub fn input_by_name(mut self, name: &str, input: InputSpec) -> Self {
+        self.inputs.push((Selector::Name(name.to_string()), input));
+        self
+    }
+
+    /// Convert the output at `index` to `element_type`.
+    #[must_use]
+    pub fn output_element_type(mut self, index: usize, element_type: ElementType) -> Self {
+        self.outputs.push((Selector::Index(index), element_type));
+        self
+    }
+
+    /// Convert the output named `name` to `element_type`.
+    #[must_use]
+    pub fn output_element_type_by_name(mut self, name: &str, element_type: ElementType) -> Self {
+        self.outputs
+            .push((Selector::Name(name.to_string()), element_type));
+        self
+    }
+
+    /// Check that `model` has every input and output this spec refers to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::OutOfBounds`] for a missing index or [`InferenceError::NotFound`]
+    /// for a missing name.
+    pub fn check(&self, model: &Model) -> Result<()> {
+        let inputs = model.inputs()?;
+        for (selector, _) in &self.inputs {
+            selector.check(&inputs)?;
+        }
+        let outputs = model.outputs()?;
+        for (selector, _) in &self.outputs {
+            selector.check(&outputs)?;
+        }
+        Ok(())
+    }
+
+    /// Build a new model from `model` with the pre- and postprocessing of this spec; `model` is
+    /// unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `model` does not fit this spec (see [`Spec::check`]) or if a step fails
+    /// (e.g., see [`Steps::mean`]).
+    pub fn apply(&self, model: &Model) -> Result<Model> {
+        self.check(model)?;
+        let pipeline = Pipeline::new(model)?;
+        for (selector, input) in &self.inputs {
+            let info = match selector {
+                Selector::Index(index) => pipeline.get_input_info_by_index(*index)?,
+                Selector::Name(name) => pipeline.get_input_info_by_name(name)?,
+            };
+            input.apply(&info)?;
+        }
+        for (selector, element_type) in &self.outputs {
+            let info = match selector {
+                Selector::Index(index) => pipeline.get_output_info_by_index(*index)?,
+                Selector::Name(name) => pipeline.get_output_info_by_name(name)?,
+            };
+            info.get_tensor_info()?.set_element_type(*element_type)?;
+        }
+        pipeline.build_new_model()
+    }
+}
+
+/// The preprocessing of one input in a [`Spec`]: what the tensors fed to the model look like, the
+/// steps converting them and the layout the model expects.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InputSpec {
+    tensor: Option<(ElementType, Vec<i64>)>,
+    tensor_layout: Option<String>,
+    steps: Vec<Step>,
+    model_layout: Option<String>,
+}
+
+/// A preprocessing step of an [`InputSpec`]; each matches a method of [`Steps`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    /// See [`Steps::resize`].
+    Resize(ResizeAlgorithm),
+    /// See [`Steps::convert_layout`].
+    ConvertLayout(String),
+    /// See [`Steps::convert_layout_by_permutation`].
+    ConvertLayoutByPermutation(Vec<usize>),
+    /// See [`Steps::convert_element_type`].
+    ConvertElementType(ElementType),
+    /// See [`Steps::mean`].
+    Mean(Vec<f32>),
+    /// See [`Steps::scale`].
+    Scale(Vec<f32>),
+    /// See [`Steps::reverse_channels`].
+    ReverseChannels,
+}
+
+impl InputSpec {
+    /// Create an input spec which leaves the input unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed tensors of `element_type` and `shape` to the input (see [`InputTensorInfo::set_from`]).
+    #[must_use]
+    pub fn tensor(mut self, element_type: ElementType, shape: &[i64]) -> Self {
+        self.tensor = Some((element_type, shape.to_vec()));
+        self
+    }
+
+    /// Feed tensors of `layout` (e.g., `NHWC`) to the input (see [`InputTensorInfo::set_layout`]).
+    #[must_use]
+    pub fn tensor_layout(mut self, layout: &str) -> Self {
+        self.tensor_layout = Some(layout.to_string());
+        self
+    }
+
+    /// Add `step` after the previous steps.
+    #[must_use]
+    pub fn step(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Add a [`Step::Resize`].
+    #[must_use]
+    pub fn resize(self, resize_algo: ResizeAlgorithm) -> Self {
+        self.step(Step::Resize(resize_algo))
+    }
+
+    /// Add a [`Step::ConvertLayout`] to `layout`.
+    #[must_use]
+    pub fn convert_layout(self, layout: &str) -> Self {
+        self.step(Step::ConvertLayout(layout.to_string()))
+    }
+
+    /// Add a [`Step::ConvertElementType`].
+    #[must_use]
+    pub fn convert_element_type(self, element_type: ElementType) -> Self {
+        self.step(Step::ConvertElementType(element_type))
+    }
+
+    /// Add a [`Step::Mean`].
+    #[must_use]
+    pub fn mean(self, values: &[f32]) -> Self {
+        self.step(Step::Mean(values.to_vec()))
+    }
+
+    /// Add a [`Step::Scale`].
+    #[must_use]
+    pub fn scale(self, values: &[f32]) -> Self {
+        self.step(Step::Scale(values.to_vec()))
+    }
+
+    /// Add a [`Step::ReverseChannels`].
+    #[must_use]
+    pub fn reverse_channels(self) -> Self {
+        self.step(Step::ReverseChannels)
+    }
+
+    /// Add the steps of `hints` (see [`PreprocessingHints::apply`]).
+    #[must_use]
+    pub fn hints(mut self, hints: &PreprocessingHints) -> Self {
+        if hints.reverse_input_channels {
+            self = self.reverse_channels();
+        }
+        if let Some(mean_values) = &hints.mean_values {
+            self = self.mean(mean_values);
+        }
+        if let Some(scale_values) = &hints.scale_values {
+            self = self.scale(scale_values);
+        }
+        self
+    }
+
+    /// Expect the model's input to have `layout` (e.g., `NCHW`; see [`InputModelInfo::set_layout`]).
+    #[must_use]
+    pub fn model_layout(mut self, layout: &str) -> Self {
+        self.model_layout = Some(layout.to_string());
+        self
+    }
+
+    /// Configure `info` as described.
+    fn apply(&self, info: &InputInfo) -> Result<()> {
+        let mut tensor_info = info.get_tensor_info()?;
+        if let Some((element_type, shape)) = &self.tensor {
+            tensor_info.set_from(&Tensor::new(*element_type, &Shape::new(shape)?)?)?;
+        }
+        if let Some(layout) = &self.tensor_layout {
+            tensor_info.set_layout(Layout::new(layout)?)?;
+        }
+        let mut steps = info.get_steps()?;
+        for step in &self.steps {
+            match step {
+                Step::Resize(resize_algo) => steps.resize(*resize_algo)?,
+                Step::ConvertLayout(layout) => steps.convert_layout(Layout::new(layout)?)?,
+                Step::ConvertLayoutByPermutation(order) => {
+                    steps.convert_layout_by_permutation(order)?;
+                }
+                Step::ConvertElementType(element_type) => {
+                    steps.convert_element_type(*element_type)?;
+                }
+                Step::Mean(values) => steps.mean(values)?,
+                Step::Scale(values) => steps.scale(values)?,
+                Step::ReverseChannels => steps.reverse_channels()?,
+            }
+        }
+        if let Some(layout) = &self.model_layout {
+            info.get_model_info()?.set_layout(Layout::new(layout)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// The preprocessing a model expects of its input, as recorded in the `model_info` section of its
+/// IR's `rt_info` (e.g., by OpenVINO's Model API); see [`Model::preprocessing_hints`].
+///
+/// Unlike the `conversion_parameters` recorded by the model conversion tools, which are already
+/// part of the converted model, these describe steps the application must add itself, e.g., with
+/// [`PreprocessingHints::apply`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PreprocessingHints {
+    /// The values to subtract from the input: one for all elements or one per channel.
+    pub mean_values: Option<Vec<f32>>,
+    /// The values to divide the input by, after subtracting the mean values: one for all elements
+    /// or one per channel.
+    pub scale_values: Option<Vec<f32>>,
+    /// Whether the order of the input channels must be reversed (e.g., from BGR to RGB).
+    pub reverse_input_channels: bool,
+}
+
+impl PreprocessingHints {
+    /// Check whether no preprocessing is recorded.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Add the recorded preprocessing to `steps`: reversing the channels, then subtracting the
+    /// mean values, then dividing by the scale values.
+    ///
+    /// # Errors
+    ///
+    /// See [`Steps::mean`] and [`Steps::reverse_channels`].
+    pub fn apply(&self, steps: &mut Steps) -> Result<()> {
+        if self.reverse_input_channels {
+            steps.reverse_channels()?;
+        }
+        if let Some(mean_values) = &self.mean_values {
+            steps.mean(mean_values)?;
+        }
+        if let Some(scale_values) = &self.scale_values {
+            steps.scale(scale_values)?;
+        }
+        Ok(())
+    }
+
+    /// Read the hints from the `model_info` section of an IR (i.e., the `.xml` file), ignoring
+    /// any other entry.
+    ///
+    /// This is not a full XML parser: it expects each entry as an element with a `value`
+    /// attribute, e.g., `<mean_values value="123.675 116.28 103.53"/>`, as OpenVINO writes them.
+    pub(crate) fn from_ir(ir: &str) -> Result<Self> {
+        let mut hints = Self::default();
+        let Some(start) = ir.find("<model_info>") else {
+            return Ok(hints);
+        };
+        let section = &ir[start + "<model_info>".len()..];
+        let section = &section[..section.find("</model_info>").unwrap_or(section.len())];
+        for element in section.split('<').skip(1) {
+            let name = element
+                .split(|c: char| c.is_whitespace() || c == '/' || c == '>')
+                .next()
+                .unwrap_or_default();
+            let Some(value) = attribute_value(element, "value") else {
+                continue;
+            };
+            match name {
+                "mean_values" => hints.mean_values = Some(parse_values(value)?),
+                "scale_values" => hints.scale_values = Some(parse_values(value)?),
+                "reverse_input_channels" => {
+                    hints.reverse_input_channels =
+                        matches!(value.trim(), "True" | "true" | "YES" | "1");
+                }
+                _ => {}
+            }
+        }
+        Ok(hints)
+    }
+}
+
+/// Parse a list of numbers separated by spaces or commas, optionally within brackets (e.g.,
+/// `[0.5, 0.5]`).
+fn parse_values(values: &str) -> Result<Vec<f32>> {
+    let values: Vec<f32> = values
+        .trim_matches(|c: char| c == '[' || c == ']' || c.is_whitespace())
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|v| !v.is_empty())
+        .map(str::parse)
+        .collect::<std::result::Result<_, _>>()
+        .map_err(|_| InferenceError::ParameterMismatch)?;
+    if values.is_empty() {
+        return Err(InferenceError::ParameterMismatch);
+    }
+    Ok(values)
+}
+
+/// What is known about an input tensor from the calls to [`InputTensorInfo`]; the C API provides
+/// no way to query this back.
+#[derive(Debug, Default)]
+struct TensorDescription {
+    channel_index: Option<usize>,
+    dimensions: Option<Vec<i64>>,
+    layout: Option<String>,
+    // The layout of the tensors fed to the model, unlike `layout`, which follows the steps.
+    tensor_layout: Option<String>,
+}
+
+impl TensorDescription {
+    /// Get the number of channels, if known.
+    fn channels(&self) -> Option<usize> {
+        let dimension = *self.dimensions.as_ref()?.get(self.channel_index?)?;
+        usize::try_from(dimension).ok()
+    }
+
+    /// Record the conversion of the tensor to the layout described by `to`, moving the known
+    /// dimensions along with their names.
+    fn convert_layout(&mut self, to: String) {
+        let names = dimension_names(&to);
+        self.dimensions = match (&self.layout, &self.dimensions) {
+            (Some(from), Some(dimensions)) if is_fully_named(&names) => {
+                let from = dimension_names(from);
+                names
+                    .iter()
+                    .map(|n| {
+                        let i = from.iter().position(|f| f == n)?;
+                        dimensions.get(i).copied()
+                    })
+                    .collect()
+            }
+            _ => None,
+        };
+        self.channel_index = channel_index(&to);
+        self.layout = Some(to);
+    }
+}
+
+/// Describe the layout resulting from permuting the dimensions of the `from` layout by `order`, if
+/// `from` is fully named and `order` is a permutation of its dimensions.
+fn permuted_layout(from: &str, order: &[usize]) -> Option<String> {
+    let names = dimension_names(from);
+    if names.len() != order.len() || !is_fully_named(&names) {
+        return None;
+    }
+    let mut seen = vec![false; order.len()];
+    for &i in order {
+        if std::mem::replace(seen.get_mut(i)?, true) {
+            return None;
+        }
+    }
+    let permuted: Vec<&str> = order.iter().map(|&i| names[i]).collect();
+    Some(format!("[{}]", permuted.join(",")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preprocessing_hints() {
+        let ir = r#"<net name="model" version="11">
+    <layers></layers>
+    <rt_info>
+        <conversion_parameters>
+            <mean_values value="[1,2,3]"/>
+        </conversion_parameters>
+        <model_info>
+            <mean_values value="123.675 116.28 103.53" />
+            <scale_values value="[58.395, 57.12, 57.375]"/>
+            <reverse_input_channels value="True"/>
+            <labels value="cat dog"/>
+        </model_info>
+    </rt_info>
+</net>"#;
+        assert_eq!(
+            PreprocessingHints::from_ir(ir).unwrap(),
+            PreprocessingHints {
+                mean_values: Some(vec![123.675, 116.28, 103.53]),
+                scale_values: Some(vec![58.395, 57.12, 57.375]),
+                reverse_input_channels: true,
+            }
+        );
+
+        let without_model_info = ir.replace("model_info", "other_info");
+        assert!(PreprocessingHints::from_ir(&without_model_info)
+            .unwrap()
+            .is_empty());
+        let malformed = ir.replace("116.28", "x");
+        assert!(PreprocessingHints::from_ir(&malformed).is_err());
+    }
+
+    #[test]
+    fn spec() {
+        let hints = PreprocessingHints {
+            mean_values: Some(vec![127.5]),
+            scale_values: None,
+            reverse_input_channels: true,
+        };
+        let input = InputSpec::new()
+            .tensor(ElementType::U8, &[1, 224, 224, 3])
+            .tensor_layout("NHWC")
+            .convert_element_type(ElementType::F32)
+            .hints(&hints)
+            .step(Step::ConvertLayoutByPermutation(vec![0, 3, 1, 2]))
+            .model_layout("NCHW");
+        assert_eq!(
+            input.steps,
+            vec![
+                Step::ConvertElementType(ElementType::F32),
+                Step::ReverseChannels,
+                Step::Mean(vec![127.5]),
+                Step::ConvertLayoutByPermutation(vec![0, 3, 1, 2]),
+            ]
+        );
+
+        let spec = Spec::new()
+            .input(0, input.clone())
+            .output_element_type_by_name("logits", ElementType::F16);
+        assert_eq!(spec.inputs, vec![(Selector::Index(0), input)]);
+        assert_eq!(
+            spec.outputs,
+            vec![(Selector::Name("logits".into()), ElementType::F16)]
+        );
+        assert_eq!(spec.clone(), spec);
+        assert!(Spec::new().inputs.is_empty());
+    }
+
+    #[test]
+    fn tensor_channels() {
+        let mut tensor = TensorDescription::default();
+        assert_eq!(tensor.channels(), None);
+        tensor.channel_index = Some(3);
+        assert_eq!(tensor.channels(), None);
+        tensor.dimensions = Some(vec![1, 224, 224, 3]);
+        assert_eq!(tensor.channels(), Some(3));
+        tensor.channel_index = Some(4);
+        assert_eq!(tensor.channels(), None);
+    }
+
+    #[test]
+    fn layout_conversion() {
+        let mut tensor = TensorDescription {
+            channel_index: Some(3),
+            dimensions: Some(vec![1, 224, 224, 3]),
+            layout: Some("[N,H,W,C]".into()),
+            tensor_layout: Some("[N,H,W,C]".into()),
+        };
+        tensor.convert_layout("[N,C,H,W]".into());
+        assert_eq!(tensor.channel_index, Some(1));
+        assert_eq!(tensor.tensor_layout.as_deref(), Some("[N,H,W,C]"));
+        assert_eq!(tensor.dimensions, Some(vec![1, 3, 224, 224]));
+        assert_eq!(tensor.channels(), Some(3));
+        tensor.convert_layout("[N,C,?,?]".into());
+        assert_eq!(tensor.dimensions, None);
+    }
+
+    #[test]
+    fn permutations() {
+        assert_eq!(
+            permuted_layout("[N,H,W,C]", &[0, 3, 1, 2]).as_deref(),
+            Some("[N,C,H,W]")
+        );
+        assert_eq!(
+            permuted_layout("[a,b,c]", &[2, 0, 1]).as_deref(),
+            Some("[c,a,b]")
+        );
+        assert_eq!(permuted_layout("[N,H,W,C]", &[0, 1, 2]), None);
+        assert_eq!(permuted_layout("[N,H,W,C]", &[0, 1, 1, 2]), None);
+        assert_eq!(permuted_layout("[N,H,W,C]", &[0, 1, 2, 4]), None);
+        assert_eq!(permuted_layout("[N,?,W,C]", &[0, 1, 2, 3]), None);
+    }
 }
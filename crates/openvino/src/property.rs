@@ -1,4 +1,7 @@
 use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::str::FromStr;
 
 /// See
 /// [`ov_property_c_api`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__property__c__api.html).
@@ -53,7 +56,8 @@ pub enum RwPropertyKey {
     HintEnableHyperThreading,
     /// High-level OpenVINO Performance Hints.
     HintPerformanceMode,
-    /// High-level OpenVINO Hints for the type of CPU core used during inference.
+    /// High-level OpenVINO Hints for the type of CPU core used during inference; see
+    /// [`SchedulingCoreType`].
     HintSchedulingCoreType,
     /// Hint for device to use specified precision for inference.
     HintInferencePrecision,
@@ -63,12 +67,13 @@ pub enum RwPropertyKey {
     HintNumRequests,
     /// Desirable log level.
     LogLevel,
-    /// High-level OpenVINO model priority hint.
+    /// High-level OpenVINO model priority hint; see [`ModelPriority`] and
+    /// [`crate::Core::set_model_priority`].
     HintModelPriority,
     /// Performance counters.
     EnableProfiling,
     /// Device priorities configuration, with comma-separated devices listed in the desired
-    /// priority.
+    /// priority; see [`crate::Core::set_device_priorities`].
     DevicePriorities,
     /// A high-level OpenVINO execution hint. Unlike low-level properties that are individual
     /// (per-device), the hints are something that every device accepts and turns into
@@ -85,10 +90,249 @@ pub enum RwPropertyKey {
     EnableMmap,
     /// ?
     AutoBatchTimeout,
+    /// Whether the NPU plugin bypasses the caching of compiled models by the NPU driver (the "UMD"
+    /// cache, enabled by default); see [`crate::Core::set_npu_driver_caching`]. Ignored when
+    /// [`RwPropertyKey::CacheDir`] is set, in which case OpenVINO's own cache is used.
+    NpuBypassUmdCaching,
+    /// Which compiler the NPU plugin uses: `DRIVER` (the compiler in the NPU driver) or `MLIR`
+    /// (the compiler in the plugin).
+    NpuCompilerType,
     /// An arbitrary key.
     Other(Cow<'static, str>),
 }
 
+/// The values of [`RwPropertyKey::HintModelPriority`]: when several models share a device (e.g.,
+/// in a multi-tenant server), a higher-priority model is scheduled first and, with the `AUTO`
+/// device, gets the best device.
+///
+/// Only some devices honor the priority: the GPU device maps it to the priority of its command
+/// queue, and the `AUTO` device uses it to assign devices; the CPU device schedules all models
+/// alike. The priority belongs to a compiled model, not to its inference requests: OpenVINO has
+/// no per-request priority. To mix interactive and batch inferences of one model on a device,
+/// compile the model twice with different priorities (see [`crate::Core::compile_model_with_priority`])
+/// and create each kind of request from the matching compiled model.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ModelPriority {
+    /// Low priority, e.g., for background models.
+    Low,
+    /// Medium priority; OpenVINO's default.
+    Medium,
+    /// High priority, e.g., for latency-critical models.
+    High,
+}
+
+impl AsRef<str> for ModelPriority {
+    fn as_ref(&self) -> &str {
+        match self {
+            ModelPriority::Low => "LOW",
+            ModelPriority::Medium => "MEDIUM",
+            ModelPriority::High => "HIGH",
+        }
+    }
+}
+
+impl FromStr for ModelPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "LOW" => Ok(ModelPriority::Low),
+            "MEDIUM" => Ok(ModelPriority::Medium),
+            "HIGH" => Ok(ModelPriority::High),
+            s => Err(format!("invalid model priority: {s}")),
+        }
+    }
+}
+
+/// The values of [`RwPropertyKey::HintSchedulingCoreType`]: on hybrid CPUs, which cores may run
+/// inference threads.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SchedulingCoreType {
+    /// Any core; OpenVINO's default.
+    Any,
+    /// Only performance cores (P-cores), e.g., for latency-sensitive models.
+    PCoreOnly,
+    /// Only efficient cores (E-cores), e.g., to leave the P-cores to other workloads.
+    ECoreOnly,
+}
+
+impl AsRef<str> for SchedulingCoreType {
+    fn as_ref(&self) -> &str {
+        match self {
+            SchedulingCoreType::Any => "ANY_CORE",
+            SchedulingCoreType::PCoreOnly => "PCORE_ONLY",
+            SchedulingCoreType::ECoreOnly => "ECORE_ONLY",
+        }
+    }
+}
+
+impl FromStr for SchedulingCoreType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ANY_CORE" => Ok(SchedulingCoreType::Any),
+            "PCORE_ONLY" => Ok(SchedulingCoreType::PCoreOnly),
+            "ECORE_ONLY" => Ok(SchedulingCoreType::ECoreOnly),
+            s => Err(format!("invalid scheduling core type: {s}")),
+        }
+    }
+}
+
+/// How the CPU device runs inference threads, applied in one call with
+/// [`crate::Core::set_cpu_config`] or [`crate::CompiledModel::set_cpu_config`]; unset fields keep
+/// OpenVINO's defaults.
+///
+/// ```
+/// # use openvino::{CpuConfig, SchedulingCoreType};
+/// // Leave room for other workloads: four pinned threads, on P-cores only.
+/// let config = CpuConfig {
+///     threads: Some(4),
+///     pinning: Some(true),
+///     core_type: Some(SchedulingCoreType::PCoreOnly),
+///     ..CpuConfig::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CpuConfig {
+    /// The maximum number of inference threads (i.e., [`RwPropertyKey::InferenceNumThreads`]).
+    pub threads: Option<usize>,
+    /// Whether to pin inference threads to cores (i.e., [`RwPropertyKey::HintEnableCpuPinning`]).
+    pub pinning: Option<bool>,
+    /// Which cores may run inference threads (i.e., [`RwPropertyKey::HintSchedulingCoreType`]).
+    pub core_type: Option<SchedulingCoreType>,
+    /// Whether to use both logical processors of each physical core (i.e.,
+    /// [`RwPropertyKey::HintEnableHyperThreading`]).
+    pub hyper_threading: Option<bool>,
+}
+
+impl CpuConfig {
+    /// List the properties to set for this configuration.
+    pub(crate) fn properties(&self) -> Vec<(RwPropertyKey, String)> {
+        let yes_no = |enabled: bool| if enabled { "YES" } else { "NO" }.to_string();
+        let mut properties = vec![];
+        if let Some(threads) = self.threads {
+            properties.push((RwPropertyKey::InferenceNumThreads, threads.to_string()));
+        }
+        if let Some(pinning) = self.pinning {
+            properties.push((RwPropertyKey::HintEnableCpuPinning, yes_no(pinning)));
+        }
+        if let Some(core_type) = self.core_type {
+            properties.push((
+                RwPropertyKey::HintSchedulingCoreType,
+                core_type.as_ref().to_string(),
+            ));
+        }
+        if let Some(hyper_threading) = self.hyper_threading {
+            properties.push((
+                RwPropertyKey::HintEnableHyperThreading,
+                yes_no(hyper_threading),
+            ));
+        }
+        properties
+    }
+}
+
+/// List the properties which make `device` compute reproducible outputs; see
+/// [`crate::Core::set_deterministic_mode`]. Only the CPU device accepts a number of `threads`.
+pub(crate) fn deterministic_properties(
+    device: &crate::DeviceType,
+    threads: usize,
+) -> Vec<(RwPropertyKey, String)> {
+    let mut properties = vec![
+        (RwPropertyKey::NumStreams, "1".to_string()),
+        (RwPropertyKey::HintExecutionMode, "ACCURACY".to_string()),
+        (RwPropertyKey::HintInferencePrecision, "f32".to_string()),
+    ];
+    if *device == crate::DeviceType::CPU {
+        properties.push((RwPropertyKey::InferenceNumThreads, threads.to_string()));
+        properties.push((RwPropertyKey::HintEnableCpuPinning, "YES".to_string()));
+    }
+    properties
+}
+
+/// Read the value of each of the `supported` properties (i.e., the value of
+/// [`PropertyKey::SupportedProperties`], a space-separated list of keys) with `get`, skipping the
+/// list itself and the properties whose value cannot be read as a string.
+pub(crate) fn dump_properties(
+    supported: &str,
+    get: impl Fn(&PropertyKey) -> crate::util::Result<String>,
+) -> BTreeMap<String, String> {
+    supported
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|key| !key.is_empty() && *key != PropertyKey::SupportedProperties.as_ref())
+        .filter_map(|key| {
+            let value = get(&PropertyKey::from(key)).ok()?;
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+impl PropertyKey {
+    /// All of the known property keys (i.e., excluding [`PropertyKey::Other`]), including the
+    /// read-write ones.
+    pub const ALL: &'static [PropertyKey] = &[
+        PropertyKey::SupportedProperties,
+        PropertyKey::AvailableDevices,
+        PropertyKey::OptimalNumberOfInferRequests,
+        PropertyKey::RangeForAsyncInferRequests,
+        PropertyKey::RangeForStreams,
+        PropertyKey::DeviceFullName,
+        PropertyKey::DeviceCapabilities,
+        PropertyKey::ModelName,
+        PropertyKey::OptimalBatchSize,
+        PropertyKey::MaxBatchSize,
+        PropertyKey::Rw(RwPropertyKey::CacheDir),
+        PropertyKey::Rw(RwPropertyKey::CacheMode),
+        PropertyKey::Rw(RwPropertyKey::NumStreams),
+        PropertyKey::Rw(RwPropertyKey::Affinity),
+        PropertyKey::Rw(RwPropertyKey::InferenceNumThreads),
+        PropertyKey::Rw(RwPropertyKey::HintEnableCpuPinning),
+        PropertyKey::Rw(RwPropertyKey::HintEnableHyperThreading),
+        PropertyKey::Rw(RwPropertyKey::HintPerformanceMode),
+        PropertyKey::Rw(RwPropertyKey::HintSchedulingCoreType),
+        PropertyKey::Rw(RwPropertyKey::HintInferencePrecision),
+        PropertyKey::Rw(RwPropertyKey::HintNumRequests),
+        PropertyKey::Rw(RwPropertyKey::LogLevel),
+        PropertyKey::Rw(RwPropertyKey::HintModelPriority),
+        PropertyKey::Rw(RwPropertyKey::EnableProfiling),
+        PropertyKey::Rw(RwPropertyKey::DevicePriorities),
+        PropertyKey::Rw(RwPropertyKey::HintExecutionMode),
+        PropertyKey::Rw(RwPropertyKey::ForceTbbTerminate),
+        PropertyKey::Rw(RwPropertyKey::EnableMmap),
+        PropertyKey::Rw(RwPropertyKey::AutoBatchTimeout),
+        PropertyKey::Rw(RwPropertyKey::NpuBypassUmdCaching),
+        PropertyKey::Rw(RwPropertyKey::NpuCompilerType),
+    ];
+}
+
+impl RwPropertyKey {
+    /// All of the known read-write property keys (i.e., excluding [`RwPropertyKey::Other`]).
+    pub const ALL: &'static [RwPropertyKey] = &[
+        RwPropertyKey::CacheDir,
+        RwPropertyKey::CacheMode,
+        RwPropertyKey::NumStreams,
+        RwPropertyKey::Affinity,
+        RwPropertyKey::InferenceNumThreads,
+        RwPropertyKey::HintEnableCpuPinning,
+        RwPropertyKey::HintEnableHyperThreading,
+        RwPropertyKey::HintPerformanceMode,
+        RwPropertyKey::HintSchedulingCoreType,
+        RwPropertyKey::HintInferencePrecision,
+        RwPropertyKey::HintNumRequests,
+        RwPropertyKey::LogLevel,
+        RwPropertyKey::HintModelPriority,
+        RwPropertyKey::EnableProfiling,
+        RwPropertyKey::DevicePriorities,
+        RwPropertyKey::HintExecutionMode,
+        RwPropertyKey::ForceTbbTerminate,
+        RwPropertyKey::EnableMmap,
+        RwPropertyKey::AutoBatchTimeout,
+        RwPropertyKey::NpuBypassUmdCaching,
+        RwPropertyKey::NpuCompilerType,
+    ];
+}
+
 impl AsRef<str> for PropertyKey {
     fn as_ref(&self) -> &str {
         match self {
@@ -130,6 +374,8 @@ impl AsRef<str> for RwPropertyKey {
             RwPropertyKey::ForceTbbTerminate => "FORCE_TBB_TERMINATE",
             RwPropertyKey::EnableMmap => "ENABLE_MMAP",
             RwPropertyKey::AutoBatchTimeout => "AUTO_BATCH_TIMEOUT",
+            RwPropertyKey::NpuBypassUmdCaching => "NPU_BYPASS_UMD_CACHING",
+            RwPropertyKey::NpuCompilerType => "NPU_COMPILER_TYPE",
             RwPropertyKey::Other(s) => s,
         }
     }
@@ -140,3 +386,176 @@ impl From<RwPropertyKey> for PropertyKey {
         PropertyKey::Rw(key)
     }
 }
+
+impl From<&str> for PropertyKey {
+    fn from(s: &str) -> Self {
+        match s {
+            "SUPPORTED_PROPERTIES" => PropertyKey::SupportedProperties,
+            "AVAILABLE_DEVICES" => PropertyKey::AvailableDevices,
+            "OPTIMAL_NUMBER_OF_INFER_REQUESTS" => PropertyKey::OptimalNumberOfInferRequests,
+            "RANGE_FOR_ASYNC_INFER_REQUESTS" => PropertyKey::RangeForAsyncInferRequests,
+            "RANGE_FOR_STREAMS" => PropertyKey::RangeForStreams,
+            "FULL_DEVICE_NAME" => PropertyKey::DeviceFullName,
+            "OPTIMIZATION_CAPABILITIES" => PropertyKey::DeviceCapabilities,
+            "NETWORK_NAME" => PropertyKey::ModelName,
+            "OPTIMAL_BATCH_SIZE" => PropertyKey::OptimalBatchSize,
+            "MAX_BATCH_SIZE" => PropertyKey::MaxBatchSize,
+            s => match RwPropertyKey::from(s) {
+                RwPropertyKey::Other(s) => PropertyKey::Other(s),
+                rw => PropertyKey::Rw(rw),
+            },
+        }
+    }
+}
+
+impl From<&str> for RwPropertyKey {
+    fn from(s: &str) -> Self {
+        match s {
+            "CACHE_DIR" => RwPropertyKey::CacheDir,
+            "CACHE_MODE" => RwPropertyKey::CacheMode,
+            "NUM_STREAMS" => RwPropertyKey::NumStreams,
+            "AFFINITY" => RwPropertyKey::Affinity,
+            "INFERENCE_NUM_THREADS" => RwPropertyKey::InferenceNumThreads,
+            "ENABLE_CPU_PINNING" => RwPropertyKey::HintEnableCpuPinning,
+            "ENABLE_HYPER_THREADING" => RwPropertyKey::HintEnableHyperThreading,
+            "PERFORMANCE_HINT" => RwPropertyKey::HintPerformanceMode,
+            "SCHEDULING_CORE_TYPE" => RwPropertyKey::HintSchedulingCoreType,
+            "INFERENCE_PRECISION_HINT" => RwPropertyKey::HintInferencePrecision,
+            "PERFORMANCE_HINT_NUM_REQUESTS" => RwPropertyKey::HintNumRequests,
+            "LOG_LEVEL" => RwPropertyKey::LogLevel,
+            "MODEL_PRIORITY" => RwPropertyKey::HintModelPriority,
+            "PERF_COUNT" => RwPropertyKey::EnableProfiling,
+            "MULTI_DEVICE_PRIORITIES" => RwPropertyKey::DevicePriorities,
+            "EXECUTION_MODE_HINT" => RwPropertyKey::HintExecutionMode,
+            "FORCE_TBB_TERMINATE" => RwPropertyKey::ForceTbbTerminate,
+            "ENABLE_MMAP" => RwPropertyKey::EnableMmap,
+            "AUTO_BATCH_TIMEOUT" => RwPropertyKey::AutoBatchTimeout,
+            "NPU_BYPASS_UMD_CACHING" => RwPropertyKey::NpuBypassUmdCaching,
+            "NPU_COMPILER_TYPE" => RwPropertyKey::NpuCompilerType,
+            s => RwPropertyKey::Other(Cow::Owned(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for PropertyKey {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(PropertyKey::from(s))
+    }
+}
+
+impl FromStr for RwPropertyKey {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(RwPropertyKey::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn round_trip_keys() {
+        for key in PropertyKey::ALL {
+            assert_eq!(&PropertyKey::from(key.as_ref()), key);
+        }
+        for key in RwPropertyKey::ALL {
+            assert_eq!(&RwPropertyKey::from(key.as_ref()), key);
+        }
+    }
+
+    #[test]
+    fn unique_keys() {
+        let names: HashSet<&str> = PropertyKey::ALL.iter().map(AsRef::as_ref).collect();
+        assert_eq!(names.len(), PropertyKey::ALL.len());
+    }
+
+    #[test]
+    fn all_includes_rw_keys() {
+        for key in RwPropertyKey::ALL {
+            assert!(PropertyKey::ALL.contains(&PropertyKey::Rw(key.clone())));
+        }
+    }
+
+    #[test]
+    fn model_priorities() {
+        for priority in [
+            ModelPriority::Low,
+            ModelPriority::Medium,
+            ModelPriority::High,
+        ] {
+            assert_eq!(priority.as_ref().parse(), Ok(priority));
+        }
+        assert!("low".parse::<ModelPriority>().is_err());
+    }
+
+    #[test]
+    fn deterministic() {
+        let cpu = deterministic_properties(&crate::DeviceType::CPU, 4);
+        assert!(cpu.contains(&(RwPropertyKey::NumStreams, "1".into())));
+        assert!(cpu.contains(&(RwPropertyKey::HintExecutionMode, "ACCURACY".into())));
+        assert!(cpu.contains(&(RwPropertyKey::InferenceNumThreads, "4".into())));
+
+        // Other devices do not accept a number of threads.
+        let gpu = deterministic_properties(&crate::DeviceType::GPU, 4);
+        assert_eq!(gpu.len(), 3);
+        assert!(!gpu
+            .iter()
+            .any(|(key, _)| *key == RwPropertyKey::InferenceNumThreads));
+    }
+
+    #[test]
+    fn dump() {
+        let properties = dump_properties(
+            "SUPPORTED_PROPERTIES AVAILABLE_DEVICES NUM_STREAMS  CACHE_DIR",
+            |key| match key {
+                PropertyKey::AvailableDevices => Ok("CPU".into()),
+                PropertyKey::Rw(RwPropertyKey::NumStreams) => Ok("2".into()),
+                _ => Err(crate::InferenceError::GeneralError),
+            },
+        );
+        assert_eq!(
+            properties.into_iter().collect::<Vec<_>>(),
+            vec![
+                ("AVAILABLE_DEVICES".into(), "CPU".into()),
+                ("NUM_STREAMS".into(), "2".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn cpu_config() {
+        assert!(CpuConfig::default().properties().is_empty());
+        let config = CpuConfig {
+            threads: Some(4),
+            pinning: Some(false),
+            core_type: Some(SchedulingCoreType::PCoreOnly),
+            hyper_threading: None,
+        };
+        assert_eq!(
+            config.properties(),
+            vec![
+                (RwPropertyKey::InferenceNumThreads, "4".into()),
+                (RwPropertyKey::HintEnableCpuPinning, "NO".into()),
+                (RwPropertyKey::HintSchedulingCoreType, "PCORE_ONLY".into()),
+            ]
+        );
+        assert_eq!("ECORE_ONLY".parse(), Ok(SchedulingCoreType::ECoreOnly));
+    }
+
+    #[test]
+    fn unknown_keys_are_other() {
+        assert_eq!(
+            PropertyKey::from("SOME_PLUGIN_KEY"),
+            PropertyKey::Other("SOME_PLUGIN_KEY".into())
+        );
+        assert_eq!(
+            RwPropertyKey::from("SOME_PLUGIN_KEY"),
+            RwPropertyKey::Other("SOME_PLUGIN_KEY".into())
+        );
+    }
+}
@@ -1,21 +1,65 @@
+use crate::element_type::TensorElement;
 use crate::tensor::Tensor;
-use crate::{cstr, drop_using_function, try_unsafe, util::Result};
+use crate::util::{with_c_str, Result};
+use crate::{drop_using_function, try_unsafe, InferenceError, OutputView, RequestTensor};
 use openvino_sys::{
-    ov_infer_request_cancel, ov_infer_request_free, ov_infer_request_get_input_tensor,
+    ov_callback_t, ov_infer_request_cancel, ov_infer_request_free,
+    ov_infer_request_get_input_tensor, ov_infer_request_get_input_tensor_by_index,
     ov_infer_request_get_output_tensor, ov_infer_request_get_output_tensor_by_index,
-    ov_infer_request_get_tensor, ov_infer_request_infer, ov_infer_request_set_input_tensor,
-    ov_infer_request_set_input_tensor_by_index, ov_infer_request_set_output_tensor,
-    ov_infer_request_set_output_tensor_by_index, ov_infer_request_set_tensor,
-    ov_infer_request_start_async, ov_infer_request_t, ov_infer_request_wait_for,
+    ov_infer_request_get_tensor, ov_infer_request_infer, ov_infer_request_set_callback,
+    ov_infer_request_set_input_tensor, ov_infer_request_set_input_tensor_by_index,
+    ov_infer_request_set_output_tensor, ov_infer_request_set_output_tensor_by_index,
+    ov_infer_request_set_tensor, ov_infer_request_start_async, ov_infer_request_t,
+    ov_infer_request_wait, ov_infer_request_wait_for,
 };
+use std::borrow::Cow;
+use std::ffi::c_void;
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
 
 /// See
 /// [`ov_infer_request_t`](https://docs.openvino.ai/2024/api/c_cpp_api/group__ov__infer__request__c__api.html).
+///
+/// Once tensors are bound to a request (and its first asynchronous inference has installed the
+/// completion callback), repeated inferences perform no heap allocation in this crate:
+/// [`InferRequest::infer`], [`InferRequest::infer_async`], [`InferRequest::wait`], the tensor
+/// getters and setters (by index, or by names shorter than 128 bytes) and
+/// [`Tensor::get_raw_data`] only pass pointers to OpenVINO, whose own allocations are out of this
+/// crate's control.
+///
+/// The tensors returned by the getters (e.g., [`InferRequest::get_tensor`]) are read-only handles
+/// to the request's tensors which borrow the request (see [`RequestTensor`]), so that the request
+/// cannot change their data while it is being read. The setters only borrow the caller's tensors,
+/// so a tensor bound with them stays shared with the caller, whose [`Tensor`] the handles do not
+/// restrict. Handles to the same tensor alias its data (see [`Tensor::shares_data_with`]).
+///
+/// Stateful models (e.g., a GRU keyword spotter fed chunked audio) can be run, and their variables
+/// carry over from one inference to the next, but the C API exposes no variable state (unlike
+/// `ov::InferRequest::query_state` in C++): states cannot be read, set or reset individually. To
+/// start a new sequence (e.g., a new utterance), create a new request with
+/// [`crate::CompiledModel::create_infer_request`], whose variables hold their initial values.
+///
+/// A request has no priority of its own: it is scheduled with the priority of the model it was
+/// created from (see [`crate::ModelPriority`]).
 pub struct InferRequest {
     ptr: *mut ov_infer_request_t,
+    // Notified by OpenVINO when an asynchronous inference completes, once installed; it is dropped
+    // after the request is freed, so OpenVINO cannot call back into it afterwards.
+    signal: Option<Box<CompletionSignal>>,
+    // The request's handles to the tensors bound by the caller, which shares their data; see
+    // `InferRequest::output_view`.
+    bound: Vec<(Port<'static>, Tensor)>,
 }
 drop_using_function!(InferRequest, ov_infer_request_free);
 
+/// A tensor of a request, as named by its setters.
+#[derive(PartialEq)]
+enum Port<'a> {
+    Name(Cow<'a, str>),
+    Input(usize),
+    Output(usize),
+}
+
 unsafe impl Send for InferRequest {}
 unsafe impl Sync for InferRequest {}
 
@@ -23,45 +67,92 @@ impl InferRequest {
     /// Create a new [`InferRequest`] from [`ov_infer_request_t`].
     #[inline]
     pub(crate) fn from_ptr(ptr: *mut ov_infer_request_t) -> Self {
-        Self { ptr }
+        Self {
+            ptr,
+            signal: None,
+            bound: Vec::new(),
+        }
+    }
+
+    /// Record that the caller bound a tensor to `port`, keeping the request's handle to it so that
+    /// [`InferRequest::output_view`] can tell that the caller shares its data.
+    fn record_binding(&mut self, port: Port<'_>) -> Result<()> {
+        let mut tensor = std::ptr::null_mut();
+        match &port {
+            Port::Name(name) => with_c_str(name, |name| {
+                try_unsafe!(ov_infer_request_get_tensor(
+                    self.ptr,
+                    name,
+                    std::ptr::addr_of_mut!(tensor)
+                ))
+            })?,
+            Port::Input(index) => try_unsafe!(ov_infer_request_get_input_tensor_by_index(
+                self.ptr,
+                *index,
+                std::ptr::addr_of_mut!(tensor)
+            ))?,
+            Port::Output(index) => try_unsafe!(ov_infer_request_get_output_tensor_by_index(
+                self.ptr,
+                *index,
+                std::ptr::addr_of_mut!(tensor)
+            ))?,
+        }
+        let tensor = Tensor::from_ptr(tensor);
+        if let Some(binding) = self.bound.iter_mut().find(|(bound, _)| *bound == port) {
+            binding.1 = tensor;
+        } else {
+            let port = match port {
+                Port::Name(name) => Port::Name(Cow::Owned(name.into_owned())),
+                Port::Input(index) => Port::Input(index),
+                Port::Output(index) => Port::Output(index),
+            };
+            self.bound.push((port, tensor));
+        }
+        Ok(())
     }
 
     /// Assign a [`Tensor`] to the input on the model.
+    ///
+    /// The request shares the data of `tensor`, which cannot be borrowed with
+    /// [`InferRequest::output_view`] afterwards.
     pub fn set_tensor(&mut self, name: &str, tensor: &Tensor) -> Result<()> {
-        let name = cstr!(name);
-        try_unsafe!(ov_infer_request_set_tensor(
-            self.ptr,
-            name.as_ptr(),
-            tensor.as_ptr()
-        ))?;
-        Ok(())
+        with_c_str(name, |name| {
+            try_unsafe!(ov_infer_request_set_tensor(self.ptr, name, tensor.as_ptr()))
+        })?;
+        self.record_binding(Port::Name(Cow::Borrowed(name)))
     }
 
     /// Retrieve a [`Tensor`] from the output on the model.
-    pub fn get_tensor(&self, name: &str) -> Result<Tensor> {
-        let name = cstr!(name);
+    ///
+    /// The tensor is a read-only handle borrowing the request; see [`RequestTensor`].
+    pub fn get_tensor(&self, name: &str) -> Result<RequestTensor<'_>> {
         let mut tensor = std::ptr::null_mut();
-        try_unsafe!(ov_infer_request_get_tensor(
-            self.ptr,
-            name.as_ptr(),
-            std::ptr::addr_of_mut!(tensor)
-        ))?;
-        Ok(Tensor::from_ptr(tensor))
+        with_c_str(name, |name| {
+            try_unsafe!(ov_infer_request_get_tensor(
+                self.ptr,
+                name,
+                std::ptr::addr_of_mut!(tensor)
+            ))
+        })?;
+        Ok(RequestTensor::new(Tensor::from_ptr(tensor)))
     }
 
     /// Get an input tensor from the model with only one input tensor.
-    pub fn get_input_tensor(&self) -> Result<Tensor> {
+    ///
+    /// The tensor is a read-only handle borrowing the request; see [`RequestTensor`].
+    pub fn get_input_tensor(&self) -> Result<RequestTensor<'_>> {
         let mut tensor = std::ptr::null_mut();
         try_unsafe!(ov_infer_request_get_input_tensor(
             self.ptr,
             std::ptr::addr_of_mut!(tensor)
         ))?;
-        Ok(Tensor::from_ptr(tensor))
+        Ok(RequestTensor::new(Tensor::from_ptr(tensor)))
     }
 
     /// Set an input tensor for infer models with single input.
     pub fn set_input_tensor(&mut self, tensor: &Tensor) -> Result<()> {
-        try_unsafe!(ov_infer_request_set_input_tensor(self.ptr, tensor.as_ptr()))
+        try_unsafe!(ov_infer_request_set_input_tensor(self.ptr, tensor.as_ptr()))?;
+        self.record_binding(Port::Input(0))
     }
 
     /// Assing an input [`Tensor`] to the model by its index.
@@ -71,28 +162,61 @@ impl InferRequest {
             index,
             tensor.as_ptr()
         ))?;
-        Ok(())
+        self.record_binding(Port::Input(index))
     }
 
     /// Retrieve an output [`Tensor`] from the model by its index.
-    pub fn get_output_tensor_by_index(&self, index: usize) -> Result<Tensor> {
+    ///
+    /// The tensor is a read-only handle borrowing the request; see [`RequestTensor`].
+    pub fn get_output_tensor_by_index(&self, index: usize) -> Result<RequestTensor<'_>> {
         let mut tensor = std::ptr::null_mut();
         try_unsafe!(ov_infer_request_get_output_tensor_by_index(
             self.ptr,
             index,
             std::ptr::addr_of_mut!(tensor)
         ))?;
-        Ok(Tensor::from_ptr(tensor))
+        Ok(RequestTensor::new(Tensor::from_ptr(tensor)))
     }
 
     /// Get an output tensor from the model with only one output tensor.
-    pub fn get_output_tensor(&self) -> Result<Tensor> {
+    ///
+    /// The tensor is a read-only handle borrowing the request; see [`RequestTensor`].
+    pub fn get_output_tensor(&self) -> Result<RequestTensor<'_>> {
         let mut tensor = std::ptr::null_mut();
         try_unsafe!(ov_infer_request_get_output_tensor(
             self.ptr,
             std::ptr::addr_of_mut!(tensor)
         ))?;
-        Ok(Tensor::from_ptr(tensor))
+        Ok(RequestTensor::new(Tensor::from_ptr(tensor)))
+    }
+
+    /// Borrow the output named `name` as a mutable slice of `T`, e.g., to read (or post-process in
+    /// place) the results of an inference. Unlike [`InferRequest::get_tensor`], the returned view
+    /// borrows the request mutably, so no other handle to its tensors can be held while the
+    /// results are being read or modified; see [`OutputView`].
+    ///
+    /// Tensors bound by the caller (e.g., with [`InferRequest::set_output_tensor`]) are refused:
+    /// the caller's [`Tensor`] shares their data, which it could read or write during the borrow.
+    /// Read these through the caller's [`Tensor`] instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::RequestBusy`] while an inference started with
+    /// [`InferRequest::infer_async`] is running, since it may be writing the output, and
+    /// [`InferenceError::ParameterMismatch`] if the caller bound the tensor, if `T` does not match
+    /// the output's element type (see [`crate::TensorElement`]) or if the output's data is not
+    /// aligned for `T` (see [`Tensor::data`]).
+    pub fn output_view<T: TensorElement>(&mut self, name: &str) -> Result<OutputView<'_, T>> {
+        if self.signal().is_some_and(|signal| !signal.is_done()) {
+            return Err(InferenceError::RequestBusy);
+        }
+        let tensor = self.get_tensor(name)?.into_tensor();
+        for (_, bound) in &self.bound {
+            if bound.shares_data_with(&tensor)? {
+                return Err(InferenceError::ParameterMismatch);
+            }
+        }
+        OutputView::new(tensor)
     }
 
     /// Set an output tensor to infer models with single output.
@@ -100,7 +224,8 @@ impl InferRequest {
         try_unsafe!(ov_infer_request_set_output_tensor(
             self.ptr,
             tensor.as_ptr()
-        ))
+        ))?;
+        self.record_binding(Port::Output(0))
     }
 
     /// Set an output tensor to infer by the index of output tensor.
@@ -109,7 +234,8 @@ impl InferRequest {
             self.ptr,
             index,
             tensor.as_ptr()
-        ))
+        ))?;
+        self.record_binding(Port::Output(index))
     }
 
     /// Execute the inference request.
@@ -123,12 +249,322 @@ impl InferRequest {
     }
 
     /// Execute the inference request asynchronously.
+    ///
+    /// The first call installs a callback on the request, which tracks whether the inference has
+    /// completed (see [`InferRequest::output_view`]).
     pub fn infer_async(&mut self) -> Result<()> {
-        try_unsafe!(ov_infer_request_start_async(self.ptr))
+        let ptr = self.ptr;
+        let signal = self.completion_signal()?;
+        signal.reset();
+        let started = try_unsafe!(ov_infer_request_start_async(ptr));
+        if started.is_err() {
+            signal.notify();
+        }
+        started
     }
 
-    /// Wait for the result of the inference asynchronous request.
+    /// Wait for the result of the inference asynchronous request for at most `timeout`
+    /// milliseconds.
+    ///
+    /// OpenVINO also returns successfully when the timeout elapses before the result is ready, so
+    /// this cannot tell whether the inference completed; use [`InferRequest::infer_with_timeout`]
+    /// for that.
     pub fn wait(&mut self, timeout: i64) -> Result<()> {
         try_unsafe!(ov_infer_request_wait_for(self.ptr, timeout))
     }
+
+    /// Get the signal of the completion of this request's asynchronous inferences, first having
+    /// OpenVINO notify it (which replaces any other callback) if this was not done yet. This must
+    /// not be called for the first time while an inference is running.
+    pub(crate) fn completion_signal(&mut self) -> Result<&CompletionSignal> {
+        let signal = if let Some(signal) = self.signal.take() {
+            signal
+        } else {
+            let mut signal = Box::new(CompletionSignal {
+                callback: ov_callback_t {
+                    callback_func: Some(notify_completion),
+                    args: std::ptr::null_mut(),
+                },
+                done: Mutex::new(true),
+                completed: Condvar::new(),
+                #[cfg(feature = "async")]
+                waker: Mutex::new(None),
+            });
+            signal.callback.args = std::ptr::addr_of!(*signal).cast_mut().cast::<c_void>();
+            // SAFETY: the boxed signal does not move and outlives the request (see `signal`).
+            try_unsafe!(ov_infer_request_set_callback(
+                self.ptr,
+                std::ptr::addr_of!(signal.callback)
+            ))?;
+            signal
+        };
+        Ok(self.signal.insert(signal))
+    }
+
+    /// Get the signal of the completion of this request's asynchronous inferences, if installed
+    /// (see [`InferRequest::completion_signal`]).
+    pub(crate) fn signal(&self) -> Option<&CompletionSignal> {
+        self.signal.as_deref()
+    }
+
+    /// Wait, without a timeout, for the result of the inference asynchronous request.
+    pub(crate) fn wait_for_result(&mut self) -> Result<()> {
+        try_unsafe!(ov_infer_request_wait(self.ptr))
+    }
+
+    /// Execute the inference request asynchronously and wait for at most `timeout` for its
+    /// result; if it is not ready by then, cancel the request and wait for the cancellation to
+    /// complete, leaving the request ready to be reused.
+    ///
+    /// The completion is detected with a callback on the request, which replaces any callback set
+    /// otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::TimedOut`] if the deadline passed (the output tensors must then
+    /// not be relied upon), or any error from the inference itself.
+    pub fn infer_with_timeout(&mut self, timeout: Duration) -> Result<()> {
+        self.infer_async()?;
+        if self
+            .signal
+            .as_deref()
+            .is_some_and(|signal| signal.wait(timeout))
+        {
+            return self.wait_for_result();
+        }
+        self.cancel()?;
+        match self.wait_for_result() {
+            // The inference may have finished between the timeout and the cancellation.
+            Ok(()) | Err(InferenceError::InferCancelled) => Err(InferenceError::TimedOut),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Tracks whether the asynchronous inference started last on a request has completed: OpenVINO
+/// calls `callback`, from one of its threads, when it does; see
+/// [`InferRequest::completion_signal`].
+pub(crate) struct CompletionSignal {
+    callback: ov_callback_t,
+    done: Mutex<bool>,
+    completed: Condvar,
+    /// The task to wake on completion, if any.
+    #[cfg(feature = "async")]
+    waker: Mutex<Option<std::task::Waker>>,
+}
+
+impl CompletionSignal {
+    fn lock(&self) -> MutexGuard<'_, bool> {
+        self.done.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Record that an inference is starting.
+    fn reset(&self) {
+        *self.lock() = false;
+    }
+
+    /// Record that the inference completed and wake the threads (and the task) waiting for it.
+    fn notify(&self) {
+        *self.lock() = true;
+        self.completed.notify_all();
+        #[cfg(feature = "async")]
+        if let Some(waker) = self
+            .waker
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+        {
+            waker.wake();
+        }
+    }
+
+    /// Check whether the inference started last has completed (or none was started).
+    pub(crate) fn is_done(&self) -> bool {
+        *self.lock()
+    }
+
+    /// Wake the task of `waker` when the inference completes (once; register it again to be woken
+    /// by the next completion).
+    #[cfg(feature = "async")]
+    pub(crate) fn wake_on_completion(&self, waker: &std::task::Waker) {
+        *self.waker.lock().unwrap_or_else(PoisonError::into_inner) = Some(waker.clone());
+    }
+
+    /// Wait for at most `timeout` for the inference to complete; return whether it did.
+    pub(crate) fn wait(&self, timeout: Duration) -> bool {
+        let (done, _) = self
+            .completed
+            .wait_timeout_while(self.lock(), timeout, |done| !*done)
+            .unwrap_or_else(PoisonError::into_inner);
+        *done
+    }
+}
+
+/// Notify the [`CompletionSignal`] of a request; OpenVINO calls this, from one of its threads, when
+/// an inference completes.
+unsafe extern "C" fn notify_completion(args: *mut c_void) {
+    // SAFETY: `args` points to a `CompletionSignal`, which outlives the request.
+    let signal = unsafe { &*args.cast::<CompletionSignal>() };
+    signal.notify();
+}
+
+/// These tests replace the OpenVINO functions with test doubles (see `openvino_sys::mock`).
+#[cfg(all(test, feature = "mock"))]
+mod mock_tests {
+    use super::*;
+    use openvino_sys::mock::{self, Functions};
+    use openvino_sys::ov_status_e;
+    use std::cell::Cell;
+
+    thread_local! {
+        static CALLBACK: Cell<*const ov_callback_t> = const { Cell::new(std::ptr::null()) };
+        // Whether the inference completes as soon as it starts, rather than hangs.
+        static COMPLETES: Cell<bool> = const { Cell::new(true) };
+        static CANCELLED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    unsafe extern "C" fn set_callback(
+        _request: *mut ov_infer_request_t,
+        callback: *const ov_callback_t,
+    ) -> ov_status_e {
+        CALLBACK.set(callback);
+        ov_status_e::OK
+    }
+
+    unsafe fn call_back() {
+        let callback = &*CALLBACK.get();
+        (callback.callback_func.unwrap())(callback.args);
+    }
+
+    unsafe extern "C" fn start_async(_request: *mut ov_infer_request_t) -> ov_status_e {
+        CANCELLED.set(false);
+        if COMPLETES.get() {
+            call_back();
+        }
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn cancel(_request: *mut ov_infer_request_t) -> ov_status_e {
+        CANCELLED.set(true);
+        call_back();
+        ov_status_e::OK
+    }
+
+    unsafe extern "C" fn wait(_request: *mut ov_infer_request_t) -> ov_status_e {
+        if CANCELLED.get() {
+            ov_status_e::INFER_CANCELLED
+        } else {
+            ov_status_e::OK
+        }
+    }
+
+    unsafe extern "C" fn free(_request: *mut ov_infer_request_t) {}
+
+    #[test]
+    fn timeouts() {
+        let functions = Functions {
+            ov_infer_request_set_callback: Some(set_callback),
+            ov_infer_request_start_async: Some(start_async),
+            ov_infer_request_cancel: Some(cancel),
+            ov_infer_request_wait: Some(wait),
+            ov_infer_request_free: Some(free),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            let mut request = InferRequest::from_ptr(std::ptr::NonNull::dangling().as_ptr());
+            assert_eq!(request.infer_with_timeout(Duration::from_secs(10)), Ok(()));
+            assert!(!CANCELLED.get());
+
+            // The inference hangs: the timeout expires and the inference is cancelled.
+            COMPLETES.set(false);
+            assert_eq!(
+                request.infer_with_timeout(Duration::from_millis(10)),
+                Err(InferenceError::TimedOut)
+            );
+            assert!(CANCELLED.get());
+
+            // The request can be reused.
+            COMPLETES.set(true);
+            assert_eq!(request.infer_with_timeout(Duration::from_secs(10)), Ok(()));
+        });
+    }
+
+    #[test]
+    fn busy_outputs() {
+        let functions = Functions {
+            ov_infer_request_set_callback: Some(set_callback),
+            ov_infer_request_start_async: Some(start_async),
+            ov_infer_request_free: Some(free),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            let mut request = InferRequest::from_ptr(std::ptr::NonNull::dangling().as_ptr());
+            COMPLETES.set(false);
+            request.infer_async().unwrap();
+            assert!(matches!(
+                request.output_view::<f32>("scores"),
+                Err(InferenceError::RequestBusy)
+            ));
+            unsafe { call_back() };
+            assert!(request.signal().is_some_and(CompletionSignal::is_done));
+            COMPLETES.set(true);
+        });
+    }
+
+    #[test]
+    fn output_views_of_bound_tensors() {
+        use openvino_sys::ov_tensor_t;
+        use std::os::raw::c_char;
+        thread_local! {
+            static BOUND: Cell<*const ov_tensor_t> = const { Cell::new(std::ptr::null()) };
+        }
+        unsafe extern "C" fn set_tensor(
+            _request: *mut ov_infer_request_t,
+            _name: *const c_char,
+            tensor: *const ov_tensor_t,
+        ) -> ov_status_e {
+            BOUND.set(tensor);
+            ov_status_e::OK
+        }
+        // The request's handle to the bound tensor is the caller's tensor.
+        unsafe extern "C" fn get_tensor(
+            _request: *const ov_infer_request_t,
+            _name: *const c_char,
+            tensor: *mut *mut ov_tensor_t,
+        ) -> ov_status_e {
+            *tensor = BOUND.get().cast_mut();
+            ov_status_e::OK
+        }
+        // Each tensor has its own data, which its handles share.
+        unsafe extern "C" fn data(
+            tensor: *const ov_tensor_t,
+            data: *mut *mut c_void,
+        ) -> ov_status_e {
+            *data = tensor.cast_mut().cast();
+            ov_status_e::OK
+        }
+        unsafe extern "C" fn free_tensor(_tensor: *mut ov_tensor_t) {}
+        let functions = Functions {
+            ov_infer_request_set_tensor: Some(set_tensor),
+            ov_infer_request_get_tensor: Some(get_tensor),
+            ov_tensor_data: Some(data),
+            ov_tensor_free: Some(free_tensor),
+            ov_infer_request_free: Some(free),
+            ..Default::default()
+        };
+        mock::with_functions(functions, || {
+            let mut request = InferRequest::from_ptr(std::ptr::NonNull::dangling().as_ptr());
+            let caller_data = [0u64; 1];
+            let tensor = Tensor::from_ptr(caller_data.as_ptr().cast_mut().cast());
+            request.set_tensor("bound", &tensor).unwrap();
+            assert!(matches!(
+                request.output_view::<f32>("bound"),
+                Err(InferenceError::ParameterMismatch)
+            ));
+
+            // Binding the port again replaces its handle.
+            request.set_tensor("bound", &tensor).unwrap();
+            assert_eq!(request.bound.len(), 1);
+        });
+    }
 }
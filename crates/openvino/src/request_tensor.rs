@@ -0,0 +1,56 @@
+use crate::{InferRequest, Tensor};
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+/// A read-only handle to a tensor of an [`InferRequest`], returned by the request's getters (e.g.,
+/// [`InferRequest::get_tensor`]); it dereferences to the [`Tensor`].
+///
+/// The handle borrows the request: while it is alive, the request cannot run another inference
+/// (which would overwrite the data), lend its data mutably (see [`InferRequest::output_view`]), set
+/// its tensors or be dropped, e.g.:
+///
+/// ```compile_fail
+/// # fn example(request: &mut openvino::InferRequest) -> Result<(), openvino::InferenceError> {
+/// let scores = request.get_output_tensor()?;
+/// request.infer()?; // Error: `request` is still borrowed by `scores`.
+/// println!("{:?}", scores.data::<f32>()?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The borrow only covers the request's own tensors. A tensor bound by the caller (e.g., with
+/// [`InferRequest::set_tensor`]) is also shared with the caller's [`Tensor`], which the borrow does
+/// not restrict: it keeps the data alive after the request is dropped and can modify it while a
+/// handle is reading it, so do not write to a bound tensor while handles to it are alive.
+///
+/// Several handles to the same tensor may be held at once, since none of them can modify the data.
+/// To write to a tensor of the request, use [`InferRequest::output_view`] or bind a tensor of your
+/// own (e.g., with [`InferRequest::set_tensor`]); to keep the data after the request runs again,
+/// copy it (e.g., with [`Tensor::to_f32_vec`]).
+pub struct RequestTensor<'req> {
+    tensor: Tensor,
+    _request: PhantomData<&'req InferRequest>,
+}
+
+impl RequestTensor<'_> {
+    pub(crate) fn new(tensor: Tensor) -> Self {
+        Self {
+            tensor,
+            _request: PhantomData,
+        }
+    }
+
+    /// Take the handle out of the borrow, e.g., to lend it with a borrow of the request of the
+    /// caller's choice (see [`InferRequest::output_view`]).
+    pub(crate) fn into_tensor(self) -> Tensor {
+        self.tensor
+    }
+}
+
+impl Deref for RequestTensor<'_> {
+    type Target = Tensor;
+
+    fn deref(&self) -> &Tensor {
+        &self.tensor
+    }
+}
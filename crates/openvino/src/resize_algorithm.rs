@@ -1,7 +1,7 @@
 use openvino_sys::ov_preprocess_resize_algorithm_e;
 
 /// Interpolation mode when resizing during preprocess steps.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum ResizeAlgorithm {
     /// Linear interpolation
@@ -12,6 +12,11 @@ pub enum ResizeAlgorithm {
     Nearest,
 }
 
+impl ResizeAlgorithm {
+    /// All of the resize algorithms.
+    pub const ALL: &'static [ResizeAlgorithm] = &[Self::Linear, Self::Cubic, Self::Nearest];
+}
+
 impl From<ov_preprocess_resize_algorithm_e> for ResizeAlgorithm {
     fn from(algo: ov_preprocess_resize_algorithm_e) -> Self {
         match algo {
@@ -31,3 +36,17 @@ impl From<ResizeAlgorithm> for ov_preprocess_resize_algorithm_e {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_c_enum() {
+        for &algo in ResizeAlgorithm::ALL {
+            let c_algo = ov_preprocess_resize_algorithm_e::from(algo);
+            assert_eq!(ResizeAlgorithm::from(c_algo), algo);
+            assert_eq!(c_algo as u32, algo as u32);
+        }
+    }
+}
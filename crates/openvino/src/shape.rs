@@ -1,4 +1,5 @@
-use crate::{try_unsafe, util::Result};
+use crate::util::{slice_from_raw, Result};
+use crate::{try_unsafe, InferenceError};
 use openvino_sys::{ov_shape_create, ov_shape_free, ov_shape_t, ov_status_e};
 use std::convert::TryInto;
 
@@ -22,16 +23,37 @@ impl Drop for Shape {
 impl Shape {
     /// Creates a new [`Shape`] with the given dimensions.
     ///
-    /// # Panics
+    /// # Errors
     ///
-    /// Panics in the unlikely case the dimension length cannot be represented as an `i64`.
+    /// Returns [`InferenceError::ParameterMismatch`] if a dimension is negative and
+    /// [`InferenceError::OutOfBounds`] if the number of elements of the shape (the product of its
+    /// dimensions) cannot be represented as a `usize`.
     pub fn new(dimensions: &[i64]) -> Result<Self> {
+        let mut elements: usize = 1;
+        for &dimension in dimensions {
+            let dimension =
+                usize::try_from(dimension).map_err(|_| InferenceError::ParameterMismatch)?;
+            elements = elements
+                .checked_mul(dimension)
+                .ok_or(InferenceError::OutOfBounds)?;
+        }
+        Self::new_unchecked(dimensions)
+    }
+
+    /// Creates a new [`Shape`] with the given dimensions without validating them first, e.g., on
+    /// a hot path where the dimensions are known to be valid. OpenVINO still rejects negative
+    /// dimensions, but an oversized shape is only detected when a tensor is allocated for it.
+    pub fn new_unchecked(dimensions: &[i64]) -> Result<Self> {
         let mut shape = ov_shape_t {
             rank: 8,
             dims: std::ptr::null_mut(),
         };
+        let rank = dimensions
+            .len()
+            .try_into()
+            .map_err(|_| InferenceError::OutOfBounds)?;
         try_unsafe!(ov_shape_create(
-            dimensions.len().try_into().unwrap(),
+            rank,
             dimensions.as_ptr(),
             std::ptr::addr_of_mut!(shape)
         ))?;
@@ -62,16 +84,9 @@ impl Shape {
     ///
     /// Panics in the unlikely case the rank cannot be represented as a `usize`.
     pub fn get_dimensions(&self) -> &[i64] {
-        if self.c_struct.dims.is_null() || self.c_struct.rank <= 0 {
-            &[]
-        } else {
-            unsafe {
-                std::slice::from_raw_parts(
-                    self.c_struct.dims,
-                    self.c_struct.rank.try_into().unwrap(),
-                )
-            }
-        }
+        let rank = self.c_struct.rank.max(0).try_into().unwrap();
+        // SAFETY: the shape owns `rank` dimensions at `dims`, if any.
+        unsafe { slice_from_raw(self.c_struct.dims, rank) }
     }
 }
 
@@ -89,4 +104,17 @@ mod tests {
         let shape = Shape::new(&dimensions).unwrap();
         assert_eq!(shape.get_rank(), 4);
     }
+
+    #[test]
+    fn test_invalid_dimensions() {
+        // These are rejected before calling into OpenVINO.
+        assert_eq!(
+            Shape::new(&[1, -1, 3]).err(),
+            Some(InferenceError::ParameterMismatch)
+        );
+        assert_eq!(
+            Shape::new(&[i64::MAX, i64::MAX, 2]).err(),
+            Some(InferenceError::OutOfBounds)
+        );
+    }
 }
@@ -0,0 +1,649 @@
+//! Ready-made pipelines for common tasks, wiring together pre-processing, inference and
+//! post-processing; see [`ImageClassifier`] and [`Detector`].
+
+use crate::prepostprocess::Pipeline;
+use crate::{
+    util::Result, Core, DeviceType, ElementType, InferRequest, InferenceError, Layout,
+    RequestTensor, ResizeAlgorithm, SetupError, Shape, Tensor,
+};
+use std::path::Path;
+
+/// The number of classes [`ImageClassifier::classify`] returns by default.
+const DEFAULT_TOP_K: usize = 5;
+
+/// The minimum score of the detections [`Detector::detect`] returns by default.
+const DEFAULT_THRESHOLD: f32 = 0.5;
+
+/// The overlap above which [`Detector::detect`] suppresses boxes by default.
+const DEFAULT_IOU_THRESHOLD: f32 = 0.45;
+
+/// Classify images with a model taking a single `NCHW` image and producing one score per class
+/// (e.g., the `alexnet` and `mobilenet` test fixtures).
+///
+/// Images are passed as interleaved 8-bit pixels (i.e., `HWC`) in the model's channel order
+/// (e.g., BGR for models converted from Caffe), either at the model's input resolution or at the
+/// size given to [`ImageClassifier::new_with_image_size`]; their resizing and conversion to the
+/// model's element type and layout are compiled into the model. Any normalization (e.g., mean and
+/// scale values) is expected to be part of the model, as it is for models converted with mean and
+/// scale values.
+///
+/// ```no_run
+/// # use openvino::{tasks::ImageClassifier, Core, DeviceType};
+/// # fn main() -> anyhow::Result<()> {
+/// let mut core = Core::new()?;
+/// // Classify 480p frames, which the model resizes to its input resolution.
+/// let mut classifier =
+///     ImageClassifier::new_with_image_size(&mut core, "alexnet.xml", DeviceType::CPU, (480, 640))?;
+/// # let pixels = vec![0u8; 480 * 640 * 3];
+/// for (class, score) in classifier.classify(&pixels)? {
+///     println!("{class}: {score}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct ImageClassifier {
+    model: ImageModel,
+    top_k: usize,
+}
+
+impl ImageClassifier {
+    /// Read the model IR at `model_path` (an `.xml` file, with its weights in the `.bin` file of
+    /// the same name) and compile it for `device`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SetupError::InvalidConfig`] if the model does not take a single `NCHW` image with
+    /// three channels, and any error from reading or compiling the model.
+    pub fn new(
+        core: &mut Core,
+        model_path: &str,
+        device: DeviceType,
+    ) -> std::result::Result<Self, SetupError> {
+        Ok(Self {
+            model: ImageModel::new(core, model_path, device, None)?,
+            top_k: DEFAULT_TOP_K,
+        })
+    }
+
+    /// Like [`ImageClassifier::new`], but for images of `image_size` (i.e., height and width),
+    /// which the model resizes to its input resolution.
+    ///
+    /// # Errors
+    ///
+    /// See [`ImageClassifier::new`].
+    pub fn new_with_image_size(
+        core: &mut Core,
+        model_path: &str,
+        device: DeviceType,
+        image_size: (usize, usize),
+    ) -> std::result::Result<Self, SetupError> {
+        Ok(Self {
+            model: ImageModel::new(core, model_path, device, Some(image_size))?,
+            top_k: DEFAULT_TOP_K,
+        })
+    }
+
+    /// Return at most `top_k` classes from [`ImageClassifier::classify`] (by default, 5).
+    #[must_use]
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Get the height and width of the images the classifier expects.
+    pub fn image_size(&self) -> (usize, usize) {
+        self.model.image_size
+    }
+
+    /// Classify an image given as interleaved 8-bit pixels (see [`ImageClassifier`]), returning
+    /// the indexes of the most likely classes with their probabilities, from the most likely.
+    ///
+    /// The model's scores are passed through a softmax, unless they already are probabilities
+    /// (i.e., the model ends with a softmax).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the image does not have the size expected
+    /// (see [`ImageClassifier::image_size`]), and any error from the inference.
+    pub fn classify(&mut self, image: &[u8]) -> Result<Vec<(usize, f32)>> {
+        let mut scores = self.model.infer(image)?.to_f32_vec(None)?;
+        if !is_distribution(&scores) {
+            softmax(&mut scores);
+        }
+        Ok(top_k(&scores, self.top_k))
+    }
+}
+
+/// Detect objects with a model taking a single `NCHW` image and producing boxes in one of the
+/// conventions of [`Decoder`] (e.g., SSD or YOLO).
+///
+/// Images are passed as for [`ImageClassifier`], and the model stretches them to its input
+/// resolution; the detected boxes are mapped back to the coordinates of the image passed. If that
+/// image was itself transformed from an original image (e.g., letterboxed to keep its aspect ratio,
+/// see [`Transform::letterbox`]), the [`Transform`] maps the boxes on to the original image.
+///
+/// ```no_run
+/// # use openvino::{tasks::{Decoder, Detector, Transform}, Core, DeviceType};
+/// # fn main() -> anyhow::Result<()> {
+/// let mut core = Core::new()?;
+/// // Detect objects in 1080p frames, which the model resizes to its input resolution.
+/// let mut detector = Detector::new_with_image_size(
+///     &mut core,
+///     "yolov8n.xml",
+///     DeviceType::CPU,
+///     Decoder::YoloV8,
+///     (1080, 1920),
+/// )?;
+/// # let pixels = vec![0u8; 1080 * 1920 * 3];
+/// for detection in detector.detect(&pixels, &Transform::identity())? {
+///     println!("{}: {} at ({}, {})", detection.class, detection.score, detection.x_min, detection.y_min);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct Detector {
+    model: ImageModel,
+    decoder: Decoder,
+    threshold: f32,
+    iou_threshold: f32,
+}
+
+/// The conventions of the output of a detection model, for [`Detector`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decoder {
+    /// A `DetectionOutput` of shape `[1, 1, N, 7]`, i.e., `[image, class, score, x_min, y_min,
+    /// x_max, y_max]` with coordinates relative to the input size (e.g., SSD models); the boxes
+    /// are already suppressed.
+    Ssd,
+    /// An output of shape `[1, N, 5 + classes]`, i.e., `[x_center, y_center, width, height,
+    /// objectness, class scores...]` in input pixels (e.g., `YOLOv5`).
+    YoloV5,
+    /// An output of shape `[1, 4 + classes, N]`, i.e., `[x_center, y_center, width, height, class
+    /// scores...]` for each box, in input pixels (e.g., `YOLOv8`).
+    YoloV8,
+}
+
+/// An object found by a [`Detector`], in the coordinates of the original image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Detection {
+    /// The index of the object's class.
+    pub class: usize,
+    /// The confidence of the detection.
+    pub score: f32,
+    /// The left edge of the box.
+    pub x_min: f32,
+    /// The top edge of the box.
+    pub y_min: f32,
+    /// The right edge of the box.
+    pub x_max: f32,
+    /// The bottom edge of the box.
+    pub y_max: f32,
+}
+
+/// How an original image was resized into a model's input: its pixels were scaled and then offset
+/// by the padding, i.e., `input = original * scale + padding`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    /// The horizontal scale.
+    pub scale_x: f32,
+    /// The vertical scale.
+    pub scale_y: f32,
+    /// The padding to the left of the image.
+    pub pad_x: f32,
+    /// The padding above the image.
+    pub pad_y: f32,
+}
+
+impl Transform {
+    /// The image was not resized.
+    pub fn identity() -> Self {
+        Self {
+            scale_x: 1.0,
+            scale_y: 1.0,
+            pad_x: 0.0,
+            pad_y: 0.0,
+        }
+    }
+
+    /// The image of size `image` (i.e., height and width) was stretched to `input`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn stretch(image: (usize, usize), input: (usize, usize)) -> Self {
+        Self {
+            scale_x: input.1 as f32 / image.1 as f32,
+            scale_y: input.0 as f32 / image.0 as f32,
+            pad_x: 0.0,
+            pad_y: 0.0,
+        }
+    }
+
+    /// The image of size `image` (i.e., height and width) was scaled to fit in `input`, keeping
+    /// its aspect ratio, and centered with padding (i.e., letterboxed).
+    #[allow(clippy::cast_precision_loss)]
+    pub fn letterbox(image: (usize, usize), input: (usize, usize)) -> Self {
+        let scale = (input.0 as f32 / image.0 as f32).min(input.1 as f32 / image.1 as f32);
+        Self {
+            scale_x: scale,
+            scale_y: scale,
+            pad_x: (input.1 as f32 - image.1 as f32 * scale) / 2.0,
+            pad_y: (input.0 as f32 - image.0 as f32 * scale) / 2.0,
+        }
+    }
+
+    /// Map a point of the model's input back to the original image.
+    pub fn to_image(&self, x: f32, y: f32) -> (f32, f32) {
+        (
+            (x - self.pad_x) / self.scale_x,
+            (y - self.pad_y) / self.scale_y,
+        )
+    }
+}
+
+impl Detector {
+    /// Read the model IR at `model_path` (an `.xml` file, with its weights in the `.bin` file of
+    /// the same name) and compile it for `device`; its output is decoded by `decoder`.
+    ///
+    /// # Errors
+    ///
+    /// See [`ImageClassifier::new`].
+    pub fn new(
+        core: &mut Core,
+        model_path: &str,
+        device: DeviceType,
+        decoder: Decoder,
+    ) -> std::result::Result<Self, SetupError> {
+        Ok(Self {
+            model: ImageModel::new(core, model_path, device, None)?,
+            decoder,
+            threshold: DEFAULT_THRESHOLD,
+            iou_threshold: DEFAULT_IOU_THRESHOLD,
+        })
+    }
+
+    /// Like [`Detector::new`], but for images of `image_size` (i.e., height and width), which the
+    /// model resizes to its input resolution.
+    ///
+    /// # Errors
+    ///
+    /// See [`ImageClassifier::new`].
+    pub fn new_with_image_size(
+        core: &mut Core,
+        model_path: &str,
+        device: DeviceType,
+        decoder: Decoder,
+        image_size: (usize, usize),
+    ) -> std::result::Result<Self, SetupError> {
+        Ok(Self {
+            model: ImageModel::new(core, model_path, device, Some(image_size))?,
+            decoder,
+            threshold: DEFAULT_THRESHOLD,
+            iou_threshold: DEFAULT_IOU_THRESHOLD,
+        })
+    }
+
+    /// Only return detections with a score of at least `threshold` (by default, 0.5).
+    #[must_use]
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Suppress the boxes overlapping a better box of the same class by more than `iou_threshold`
+    /// (by default, 0.45), measured as intersection over union; this only applies to YOLO
+    /// decoders, since SSD models suppress their boxes themselves.
+    #[must_use]
+    pub fn with_iou_threshold(mut self, iou_threshold: f32) -> Self {
+        self.iou_threshold = iou_threshold;
+        self
+    }
+
+    /// Get the height and width of the images the detector expects.
+    pub fn image_size(&self) -> (usize, usize) {
+        self.model.image_size
+    }
+
+    /// Detect the objects in an image given as interleaved 8-bit pixels (see [`Detector`]),
+    /// which was transformed from the original image by `transform` (e.g.,
+    /// [`Transform::identity`] if it is the original image); the detections are ordered from the
+    /// most confident.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if the image does not have the size expected
+    /// (see [`Detector::image_size`]) or if the output does not have the shape expected by the
+    /// decoder, and any error from the inference.
+    pub fn detect(&mut self, image: &[u8], transform: &Transform) -> Result<Vec<Detection>> {
+        let output = self.model.infer(image)?;
+        let dimensions: Vec<usize> = output
+            .get_shape()?
+            .get_dimensions()
+            .iter()
+            .map(|&d| usize::try_from(d).map_err(|_| InferenceError::ParameterMismatch))
+            .collect::<Result<_>>()?;
+        let values = output.to_f32_vec(None)?;
+        let (height, width) = self.model.input_size;
+        #[allow(clippy::cast_precision_loss)]
+        let size = (height as f32, width as f32);
+        let mut detections = match self.decoder {
+            Decoder::Ssd => decode_ssd(&values, &dimensions, size, self.threshold)?,
+            Decoder::YoloV5 | Decoder::YoloV8 => {
+                let detections = decode_yolo(
+                    &values,
+                    &dimensions,
+                    self.decoder == Decoder::YoloV8,
+                    self.threshold,
+                )?;
+                suppress(detections, self.iou_threshold)
+            }
+        };
+        // Undo the model's resizing, then the caller's transform.
+        let resized = Transform::stretch(self.model.image_size, self.model.input_size);
+        let to_image = |x, y| {
+            let (x, y) = resized.to_image(x, y);
+            transform.to_image(x, y)
+        };
+        for detection in &mut detections {
+            (detection.x_min, detection.y_min) = to_image(detection.x_min, detection.y_min);
+            (detection.x_max, detection.y_max) = to_image(detection.x_max, detection.y_max);
+        }
+        detections.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(detections)
+    }
+}
+
+/// Decode a `DetectionOutput` (see [`Decoder::Ssd`]) into boxes in input pixels; `size` is the
+/// input's height and width.
+fn decode_ssd(
+    values: &[f32],
+    dimensions: &[usize],
+    size: (f32, f32),
+    threshold: f32,
+) -> Result<Vec<Detection>> {
+    if dimensions.last() != Some(&7) {
+        return Err(InferenceError::ParameterMismatch);
+    }
+    let mut detections = vec![];
+    for row in values.chunks_exact(7) {
+        // A negative image index marks the end of the detections.
+        if row[0] < 0.0 {
+            break;
+        }
+        if row[2] >= threshold {
+            detections.push(Detection {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                class: row[1] as usize,
+                score: row[2],
+                x_min: row[3] * size.1,
+                y_min: row[4] * size.0,
+                x_max: row[5] * size.1,
+                y_max: row[6] * size.0,
+            });
+        }
+    }
+    Ok(detections)
+}
+
+/// Decode a YOLO output (see [`Decoder::YoloV5`] and [`Decoder::YoloV8`]) into boxes in input
+/// pixels, keeping the best class of each box.
+fn decode_yolo(
+    values: &[f32],
+    dimensions: &[usize],
+    transposed: bool,
+    threshold: f32,
+) -> Result<Vec<Detection>> {
+    // The class scores follow the box (and, without transposition, the objectness).
+    let (boxes, attributes, first_class) = match (dimensions, transposed) {
+        (&[1, boxes, attributes], false) if attributes > 5 => (boxes, attributes, 5),
+        (&[1, attributes, boxes], true) if attributes > 4 => (boxes, attributes, 4),
+        _ => return Err(InferenceError::ParameterMismatch),
+    };
+    let value = |b: usize, a: usize| {
+        if transposed {
+            values[a * boxes + b]
+        } else {
+            values[b * attributes + a]
+        }
+    };
+    let mut detections = vec![];
+    for b in 0..boxes {
+        let objectness = if transposed { 1.0 } else { value(b, 4) };
+        let Some((class, score)) = (first_class..attributes)
+            .map(|a| (a - first_class, value(b, a) * objectness))
+            .max_by(|x, y| x.1.total_cmp(&y.1))
+        else {
+            continue;
+        };
+        if score < threshold {
+            continue;
+        }
+        let (x, y, w, h) = (value(b, 0), value(b, 1), value(b, 2), value(b, 3));
+        detections.push(Detection {
+            class,
+            score,
+            x_min: x - w / 2.0,
+            y_min: y - h / 2.0,
+            x_max: x + w / 2.0,
+            y_max: y + h / 2.0,
+        });
+    }
+    Ok(detections)
+}
+
+/// Keep the best boxes, dropping those which overlap a better box of the same class by more than
+/// `iou_threshold` (i.e., non-maximum suppression).
+fn suppress(mut detections: Vec<Detection>, iou_threshold: f32) -> Vec<Detection> {
+    detections.sort_by(|a, b| b.score.total_cmp(&a.score));
+    let mut kept: Vec<Detection> = vec![];
+    for detection in detections {
+        if kept
+            .iter()
+            .all(|k| k.class != detection.class || iou(k, &detection) <= iou_threshold)
+        {
+            kept.push(detection);
+        }
+    }
+    kept
+}
+
+/// Compute the intersection over union of two boxes.
+fn iou(a: &Detection, b: &Detection) -> f32 {
+    let width = (a.x_max.min(b.x_max) - a.x_min.max(b.x_min)).max(0.0);
+    let height = (a.y_max.min(b.y_max) - a.y_min.max(b.y_min)).max(0.0);
+    let intersection = width * height;
+    let area = |d: &Detection| (d.x_max - d.x_min) * (d.y_max - d.y_min);
+    let union = area(a) + area(b) - intersection;
+    if union > 0.0 {
+        intersection / union
+    } else {
+        0.0
+    }
+}
+
+/// A compiled model taking a single image as interleaved 8-bit pixels, shared by the tasks.
+struct ImageModel {
+    request: InferRequest,
+    input: Tensor,
+    /// The height and width of the images passed.
+    image_size: (usize, usize),
+    /// The height and width of the model's input, to which the images are resized.
+    input_size: (usize, usize),
+}
+
+impl ImageModel {
+    /// Compile the model at `model_path` for images of `image_size`, if given, or else of the
+    /// model's input resolution.
+    fn new(
+        core: &mut Core,
+        model_path: &str,
+        device: DeviceType,
+        image_size: Option<(usize, usize)>,
+    ) -> std::result::Result<Self, SetupError> {
+        let weights_path = Path::new(model_path).with_extension("bin");
+        let model = core.read_model_from_file(model_path, &weights_path.to_string_lossy())?;
+
+        let invalid = || {
+            SetupError::InvalidConfig(format!(
+                "{model_path} does not take a single NCHW image with 3 channels"
+            ))
+        };
+        if model.get_inputs_len()? != 1 {
+            return Err(invalid());
+        }
+        let shape = model.get_input_by_index(0)?.get_shape()?;
+        let &[1, 3, height, width] = shape.get_dimensions() else {
+            return Err(invalid());
+        };
+
+        let input_size = (
+            usize::try_from(height).map_err(|_| invalid())?,
+            usize::try_from(width).map_err(|_| invalid())?,
+        );
+        let image_size = image_size.unwrap_or(input_size);
+        let dimension = |d: usize| {
+            i64::try_from(d).map_err(|_| {
+                SetupError::InvalidConfig(format!("the image size {image_size:?} is too large"))
+            })
+        };
+
+        // Let OpenVINO resize the 8-bit `NHWC` pixels and convert them into the model's input.
+        let input = Tensor::new(
+            ElementType::U8,
+            &Shape::new(&[1, dimension(image_size.0)?, dimension(image_size.1)?, 3])?,
+        )?;
+        let pipeline = Pipeline::new(&model)?;
+        let info = pipeline.get_input_info()?;
+        let mut tensor_info = info.get_tensor_info()?;
+        tensor_info.set_from(&input)?;
+        tensor_info.set_layout(Layout::new("NHWC")?)?;
+        let mut steps = info.get_steps()?;
+        if image_size != input_size {
+            steps.resize(ResizeAlgorithm::Linear)?;
+        }
+        steps.convert_element_type(ElementType::F32)?;
+        info.get_model_info()?.set_layout(Layout::new("NCHW")?)?;
+        pipeline
+            .get_output_info_by_index(0)?
+            .get_tensor_info()?
+            .set_element_type(ElementType::F32)?;
+        let model = core.compile_model(&pipeline.build_new_model()?, device)?;
+
+        let mut request = model.create_infer_request()?;
+        request.set_input_tensor(&input)?;
+        Ok(Self {
+            request,
+            input,
+            image_size,
+            input_size,
+        })
+    }
+
+    /// Infer the (first) output of the model for `image`.
+    fn infer(&mut self, image: &[u8]) -> Result<RequestTensor<'_>> {
+        let data = self.input.get_raw_data_mut()?;
+        if image.len() != data.len() {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        data.copy_from_slice(image);
+        self.request.infer()?;
+        self.request.get_output_tensor_by_index(0)
+    }
+}
+
+/// Check whether `scores` already are probabilities, allowing for rounding errors.
+fn is_distribution(scores: &[f32]) -> bool {
+    scores.iter().all(|s| (0.0..=1.0).contains(s))
+        && (scores.iter().sum::<f32>() - 1.0).abs() < 1e-3
+}
+
+/// Replace `scores` with their softmax, i.e., `exp(s) / sum(exp(s))`.
+fn softmax(scores: &mut [f32]) {
+    let max = scores.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let mut sum = 0.0;
+    for score in scores.iter_mut() {
+        *score = (*score - max).exp();
+        sum += *score;
+    }
+    for score in scores.iter_mut() {
+        *score /= sum;
+    }
+}
+
+/// Get the indexes and values of the `k` highest `scores`, from the highest.
+fn top_k(scores: &[f32], k: usize) -> Vec<(usize, f32)> {
+    let mut indexed: Vec<(usize, f32)> = scores.iter().copied().enumerate().collect();
+    indexed.sort_by(|a, b| b.1.total_cmp(&a.1));
+    indexed.truncate(k);
+    indexed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post_processing() {
+        let mut scores = [1.0, 3.0, 2.0];
+        assert!(!is_distribution(&scores));
+        softmax(&mut scores);
+        assert!(is_distribution(&scores));
+        let top = top_k(&scores, 2);
+        assert_eq!(top.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [1, 2]);
+        assert!((top[0].1 - 0.665_241).abs() < 1e-5);
+        assert!(is_distribution(&[0.25, 0.75, 0.0]));
+        assert_eq!(top_k(&[0.5], 5).len(), 1);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn transforms() {
+        // A 100x200 image letterboxed into 100x100 is halved and padded vertically.
+        let transform = Transform::letterbox((100, 200), (100, 100));
+        assert_eq!(transform.scale_x, 0.5);
+        assert_eq!(transform.pad_y, 25.0);
+        assert_eq!(transform.to_image(50.0, 25.0), (100.0, 0.0));
+        let transform = Transform::stretch((50, 400), (100, 100));
+        assert_eq!(transform.to_image(100.0, 100.0), (400.0, 50.0));
+        assert_eq!(Transform::identity().to_image(1.0, 2.0), (1.0, 2.0));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn decoding() {
+        // Two SSD detections, one below the threshold, then the end marker.
+        let ssd = [
+            0.0, 3.0, 0.9, 0.1, 0.2, 0.5, 0.6, //
+            0.0, 1.0, 0.2, 0.0, 0.0, 1.0, 1.0, //
+            -1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+        ];
+        let detections = decode_ssd(&ssd, &[1, 1, 3, 7], (100.0, 200.0), 0.5).unwrap();
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].class, 3);
+        assert!((detections[0].x_min - 20.0).abs() < 1e-4);
+        assert!((detections[0].y_max - 60.0).abs() < 1e-4);
+
+        // Three YOLOv5 boxes of two classes: the second overlaps the first and is suppressed.
+        let yolo = [
+            50.0, 50.0, 20.0, 20.0, 0.9, 0.1, 0.9, //
+            51.0, 50.0, 20.0, 20.0, 0.8, 0.1, 0.9, //
+            10.0, 10.0, 4.0, 4.0, 1.0, 0.7, 0.3,
+        ];
+        let detections = decode_yolo(&yolo, &[1, 3, 7], false, 0.5).unwrap();
+        assert_eq!(detections.len(), 3);
+        let detections = suppress(detections, 0.45);
+        assert_eq!(
+            detections.iter().map(|d| d.class).collect::<Vec<_>>(),
+            [1, 0]
+        );
+        assert_eq!(detections[1].x_min, 8.0);
+
+        // The same boxes, transposed as YOLOv8 outputs them (without objectness).
+        let mut transposed = vec![];
+        for attribute in [0, 1, 2, 3, 5, 6] {
+            transposed.extend((0..3).map(|b| yolo[b * 7 + attribute]));
+        }
+        let detections = decode_yolo(&transposed, &[1, 6, 3], true, 0.5).unwrap();
+        assert_eq!(suppress(detections, 0.45).len(), 2);
+
+        assert!(decode_yolo(&yolo, &[1, 7, 3], false, 0.5).is_err());
+        assert!(decode_ssd(&ssd, &[1, 1, 7, 3], (1.0, 1.0), 0.5).is_err());
+    }
+}
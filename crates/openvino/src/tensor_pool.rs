@@ -0,0 +1,109 @@
+//! Recycle tensors across inferences instead of creating new ones for each.
+
+use crate::{util::Result, ElementType, Shape, Tensor};
+use std::collections::HashMap;
+
+/// A pool of scratch [`Tensor`]s, keyed by element type and shape, e.g., to fill a new input
+/// tensor for each frame of a video without creating (and allocating) a tensor each time.
+///
+/// Tensors are taken from the pool with [`TensorPool::acquire`] and returned to it with
+/// [`TensorPool::release`]; an [`crate::InferQueue`] can return its input tensors to a pool once
+/// their inference completes (see [`crate::InferQueue::with_pool`]).
+///
+/// ```no_run
+/// # use openvino::{ElementType, Shape, TensorPool};
+/// # fn main() -> anyhow::Result<()> {
+/// let mut pool = TensorPool::new();
+/// let shape = Shape::new(&[1, 3, 227, 227])?;
+/// for _frame in 0..100 {
+///     let tensor = pool.acquire(ElementType::F32, &shape)?;
+///     // ...fill the tensor and infer...
+///     pool.release(tensor)?;
+/// }
+/// assert_eq!(pool.created(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct TensorPool {
+    free: HashMap<(ElementType, Vec<i64>), Vec<Tensor>>,
+    created: usize,
+}
+
+impl TensorPool {
+    /// Create an empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a tensor of `element_type` and `shape` from the pool, creating one if none is free.
+    ///
+    /// A recycled tensor keeps the data it held when it was released.
+    pub fn acquire(&mut self, element_type: ElementType, shape: &Shape) -> Result<Tensor> {
+        let key = (element_type, shape.get_dimensions().to_vec());
+        if let Some(tensor) = self.free.get_mut(&key).and_then(Vec::pop) {
+            return Ok(tensor);
+        }
+        let tensor = Tensor::new(element_type, shape)?;
+        self.created += 1;
+        Ok(tensor)
+    }
+
+    /// Return a tensor to the pool, to be handed out again by [`TensorPool::acquire`].
+    ///
+    /// Any tensor can be released, not only those acquired from the pool.
+    pub fn release(&mut self, tensor: Tensor) -> Result<()> {
+        let key = (
+            tensor.get_element_type()?,
+            tensor.get_shape()?.get_dimensions().to_vec(),
+        );
+        self.free.entry(key).or_default().push(tensor);
+        Ok(())
+    }
+
+    /// Get the number of free tensors in the pool.
+    pub fn len(&self) -> usize {
+        self.free.values().map(Vec::len).sum()
+    }
+
+    /// Check whether the pool holds no free tensors.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of tensors the pool has created, i.e., the number of times
+    /// [`TensorPool::acquire`] found no free tensor.
+    pub fn created(&self) -> usize {
+        self.created
+    }
+
+    /// Drop all free tensors.
+    pub fn clear(&mut self) {
+        self.free.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recycle() {
+        openvino_sys::library::load().unwrap();
+        let mut pool = TensorPool::new();
+        let shape = Shape::new(&[1, 2, 3]).unwrap();
+        let mut tensor = pool.acquire(ElementType::F32, &shape).unwrap();
+        tensor.data_mut::<f32>().unwrap()[0] = 42.0;
+        pool.release(tensor).unwrap();
+        assert_eq!(pool.len(), 1);
+
+        // The same tensor is handed out again, but only for the same element type and shape.
+        let tensor = pool.acquire(ElementType::F32, &shape).unwrap();
+        assert!((tensor.data::<f32>().unwrap()[0] - 42.0).abs() < f32::EPSILON);
+        assert!(pool.is_empty());
+        pool.acquire(ElementType::U8, &shape).unwrap();
+        pool.acquire(ElementType::F32, &Shape::new(&[1, 2]).unwrap())
+            .unwrap();
+        assert_eq!(pool.created(), 3);
+    }
+}
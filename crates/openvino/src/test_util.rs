@@ -0,0 +1,411 @@
+//! Test doubles implementing [`InferenceEngine`], [`InferenceSession`] and [`TensorApi`], so that
+//! applications embedding this crate can unit-test their pipelines without an OpenVINO installation
+//! or model files.
+//!
+//! A [`MockCompiledModel`] declares the names of the model's inputs and the canned tensors its
+//! outputs return; each [`MockInferRequest`] it creates checks that the inputs are set, returns
+//! these outputs once [`InferenceSession::infer`] runs and records its inputs in the model's
+//! history (see [`MockCompiledModel::inferences`]):
+//!
+//! ```
+//! # use openvino::test_util::{MockCompiledModel, MockTensor};
+//! # use openvino::{ElementType, InferenceEngine, InferenceSession, TensorApi};
+//! let scores = MockTensor::from_data(ElementType::F32, &[1, 3], &[0.1f32, 0.7, 0.2]).unwrap();
+//! let model = MockCompiledModel::new()
+//!     .with_input("image")
+//!     .with_output("scores", scores);
+//!
+//! let mut request = model.create_infer_request().unwrap();
+//! let image = MockTensor::from_data(ElementType::U8, &[1, 2, 2, 3], &[0u8; 12]).unwrap();
+//! request.set_tensor("image", &image).unwrap();
+//! request.infer().unwrap();
+//! let output = request.get_tensor("scores").unwrap();
+//! assert_eq!(output.get_data::<f32>().unwrap(), &[0.1, 0.7, 0.2]);
+//! assert_eq!(model.inferences()[0]["image"], image);
+//! ```
+
+use crate::engine::{check_data, InferenceEngine, InferenceSession, TensorApi};
+use crate::{util::Result, ElementType, InferenceError, TensorElement};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A tensor held in memory, for use with [`MockInferRequest`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MockTensor {
+    element_type: ElementType,
+    dims: Vec<i64>,
+    len: usize,
+    // Stored as 8-byte words, so that the data can be viewed as any `TensorElement`.
+    words: Vec<u64>,
+}
+
+impl MockTensor {
+    /// Create a zeroed tensor of `element_type` with dimensions `dims`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if a dimension is negative or if
+    /// `element_type` has no fixed size (e.g., [`ElementType::String`]).
+    pub fn new(element_type: ElementType, dims: &[i64]) -> Result<Self> {
+        let count = dims.iter().try_fold(1usize, |count, &d| {
+            count.checked_mul(usize::try_from(d).ok()?)
+        });
+        let len = count
+            .and_then(|count| element_type.byte_size(count))
+            .ok_or(InferenceError::ParameterMismatch)?;
+        Ok(Self {
+            element_type,
+            dims: dims.to_vec(),
+            len,
+            words: vec![0; len.div_ceil(8)],
+        })
+    }
+
+    /// Get a mutable view of the underlying data of the tensor.
+    pub fn get_raw_data_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the words hold at least `len` initialized bytes, borrowed with the tensor.
+        unsafe { std::slice::from_raw_parts_mut(self.words.as_mut_ptr().cast::<u8>(), self.len) }
+    }
+
+    /// Get the data of the tensor as a mutable slice of `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InferenceError::ParameterMismatch`] if `T` does not match the tensor's element
+    /// type (see [`TensorElement`]).
+    pub fn get_data_mut<T: TensorElement>(&mut self) -> Result<&mut [T]> {
+        if !T::matches(self.element_type) {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let count = self.len / std::mem::size_of::<T>();
+        // SAFETY: the words are aligned to 8 bytes, at least `T`'s alignment, and hold `count`
+        // values of `T`, whose bit patterns are all valid.
+        Ok(unsafe { std::slice::from_raw_parts_mut(self.words.as_mut_ptr().cast::<T>(), count) })
+    }
+}
+
+impl TensorApi for MockTensor {
+    fn from_data<T: TensorElement>(
+        element_type: ElementType,
+        dims: &[i64],
+        data: &[T],
+    ) -> Result<Self> {
+        check_data(element_type, dims, data)?;
+        let mut tensor = Self::new(element_type, dims)?;
+        tensor.get_data_mut::<T>()?.copy_from_slice(data);
+        Ok(tensor)
+    }
+
+    fn get_element_type(&self) -> Result<ElementType> {
+        Ok(self.element_type)
+    }
+
+    fn get_dimensions(&self) -> Result<Vec<i64>> {
+        Ok(self.dims.clone())
+    }
+
+    fn get_raw_data(&self) -> Result<&[u8]> {
+        // SAFETY: the words hold at least `len` initialized bytes, borrowed with the tensor.
+        Ok(unsafe { std::slice::from_raw_parts(self.words.as_ptr().cast::<u8>(), self.len) })
+    }
+
+    fn get_data<T: TensorElement>(&self) -> Result<&[T]> {
+        if !T::matches(self.element_type) {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let count = self.len / std::mem::size_of::<T>();
+        // SAFETY: see `MockTensor::get_data_mut`.
+        Ok(unsafe { std::slice::from_raw_parts(self.words.as_ptr().cast::<T>(), count) })
+    }
+}
+
+/// The inputs of one inference, by name, as recorded by [`MockCompiledModel::inferences`].
+pub type Inference = HashMap<String, MockTensor>;
+
+/// A compiled model returning canned outputs; see the [module](crate::test_util) documentation.
+#[derive(Clone, Debug, Default)]
+pub struct MockCompiledModel {
+    inputs: Vec<String>,
+    outputs: Vec<(String, MockTensor)>,
+    error: Option<InferenceError>,
+    inferences: Arc<Mutex<Vec<Inference>>>,
+}
+
+impl MockCompiledModel {
+    /// Create a model with no inputs or outputs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare an input named `name`; inputs are indexed in the order they are declared.
+    #[must_use]
+    pub fn with_input(mut self, name: &str) -> Self {
+        self.inputs.push(name.to_string());
+        self
+    }
+
+    /// Declare an output named `name`, which holds `tensor` after each inference; outputs are
+    /// indexed in the order they are declared.
+    #[must_use]
+    pub fn with_output(mut self, name: &str, tensor: MockTensor) -> Self {
+        self.outputs.push((name.to_string(), tensor));
+        self
+    }
+
+    /// Make each inference fail with `error` (e.g., to test how a pipeline reports a device
+    /// failure); the inputs are still recorded.
+    #[must_use]
+    pub fn with_error(mut self, error: InferenceError) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Get the inputs of each inference run so far by the requests of this model, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while recording an inference.
+    pub fn inferences(&self) -> Vec<Inference> {
+        self.inferences.lock().unwrap().clone()
+    }
+}
+
+impl InferenceEngine for MockCompiledModel {
+    type Session = MockInferRequest;
+
+    fn create_infer_request(&self) -> Result<MockInferRequest> {
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|(name, tensor)| {
+                let zeroed = MockTensor::new(tensor.element_type, &tensor.dims)?;
+                Ok((name.clone(), zeroed))
+            })
+            .collect::<Result<_>>()?;
+        Ok(MockInferRequest {
+            model: self.clone(),
+            inputs: self
+                .inputs
+                .iter()
+                .map(|name| (name.clone(), None))
+                .collect(),
+            outputs,
+            infer_count: 0,
+        })
+    }
+
+    fn get_input_size(&self) -> Result<usize> {
+        Ok(self.inputs.len())
+    }
+
+    fn get_output_size(&self) -> Result<usize> {
+        Ok(self.outputs.len())
+    }
+}
+
+/// An infer request created by [`MockCompiledModel::create_infer_request`].
+///
+/// Before the first inference, the outputs hold zeroed tensors of the canned outputs' element
+/// types and dimensions, as a real request would. Unknown names are reported with
+/// [`InferenceError::NotFound`], out-of-range indices with [`InferenceError::OutOfBounds`] and
+/// inferring with an unset input with [`InferenceError::NotAllocated`].
+#[derive(Debug)]
+pub struct MockInferRequest {
+    model: MockCompiledModel,
+    inputs: Vec<(String, Option<MockTensor>)>,
+    outputs: Vec<(String, MockTensor)>,
+    infer_count: usize,
+}
+
+impl MockInferRequest {
+    /// Get the number of inferences this request has run, including failed ones.
+    pub fn infer_count(&self) -> usize {
+        self.infer_count
+    }
+
+    /// Get the only element of `items`, as for a model with only one input or output.
+    fn single<T>(items: &mut [T]) -> Result<&mut T> {
+        match items {
+            [item] => Ok(item),
+            _ => Err(InferenceError::GeneralError),
+        }
+    }
+}
+
+impl InferenceSession for MockInferRequest {
+    type Tensor = MockTensor;
+
+    fn set_tensor(&mut self, name: &str, tensor: &MockTensor) -> Result<()> {
+        if let Some((_, input)) = self.inputs.iter_mut().find(|(n, _)| n == name) {
+            *input = Some(tensor.clone());
+        } else if let Some((_, output)) = self.outputs.iter_mut().find(|(n, _)| n == name) {
+            *output = tensor.clone();
+        } else {
+            return Err(InferenceError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn get_tensor(&self, name: &str) -> Result<MockTensor> {
+        let input = self.inputs.iter().find(|(n, _)| n == name);
+        if let Some((_, input)) = input {
+            return input.clone().ok_or(InferenceError::NotAllocated);
+        }
+        self.outputs
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, output)| output.clone())
+            .ok_or(InferenceError::NotFound)
+    }
+
+    fn set_input_tensor(&mut self, tensor: &MockTensor) -> Result<()> {
+        Self::single(&mut self.inputs)?.1 = Some(tensor.clone());
+        Ok(())
+    }
+
+    fn set_input_tensor_by_index(&mut self, index: usize, tensor: &MockTensor) -> Result<()> {
+        let (_, input) = self
+            .inputs
+            .get_mut(index)
+            .ok_or(InferenceError::OutOfBounds)?;
+        *input = Some(tensor.clone());
+        Ok(())
+    }
+
+    fn get_output_tensor(&self) -> Result<MockTensor> {
+        match self.outputs.as_slice() {
+            [(_, output)] => Ok(output.clone()),
+            _ => Err(InferenceError::GeneralError),
+        }
+    }
+
+    fn get_output_tensor_by_index(&self, index: usize) -> Result<MockTensor> {
+        self.outputs
+            .get(index)
+            .map(|(_, output)| output.clone())
+            .ok_or(InferenceError::OutOfBounds)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if a thread panicked while recording an inference of the same model.
+    fn infer(&mut self) -> Result<()> {
+        self.infer_count += 1;
+        let inference = self
+            .inputs
+            .iter()
+            .map(|(name, input)| Some((name.clone(), input.clone()?)))
+            .collect::<Option<Inference>>()
+            .ok_or(InferenceError::NotAllocated)?;
+        self.model.inferences.lock().unwrap().push(inference);
+        if let Some(error) = &self.model.error {
+            return Err(error.clone());
+        }
+        self.outputs.clone_from(&self.model.outputs);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn classifier() -> MockCompiledModel {
+        let scores = MockTensor::from_data(ElementType::F32, &[1, 2], &[0.25f32, 0.75]).unwrap();
+        MockCompiledModel::new()
+            .with_input("image")
+            .with_output("scores", scores)
+    }
+
+    #[test]
+    fn tensors() {
+        let tensor = MockTensor::from_data(ElementType::I32, &[2, 2], &[1i32, 2, 3, 4]).unwrap();
+        assert_eq!(tensor.get_dimensions().unwrap(), vec![2, 2]);
+        assert_eq!(tensor.get_raw_data().unwrap().len(), 16);
+        assert_eq!(tensor.get_data::<i32>().unwrap(), &[1, 2, 3, 4]);
+        assert_eq!(
+            tensor.get_data::<f32>().unwrap_err(),
+            InferenceError::ParameterMismatch
+        );
+
+        let mut tensor = MockTensor::new(ElementType::U8, &[3]).unwrap();
+        tensor.get_raw_data_mut()[1] = 7;
+        assert_eq!(tensor.get_data::<u8>().unwrap(), &[0, 7, 0]);
+
+        assert!(MockTensor::from_data(ElementType::F32, &[2, 2], &[1f32]).is_err());
+        assert!(MockTensor::new(ElementType::F32, &[-1]).is_err());
+        assert!(MockTensor::new(ElementType::String, &[1]).is_err());
+    }
+
+    #[test]
+    fn canned_outputs() {
+        let model = classifier();
+        assert_eq!(model.get_input_size().unwrap(), 1);
+        assert_eq!(model.get_output_size().unwrap(), 1);
+
+        let mut request = model.create_infer_request().unwrap();
+        assert_eq!(
+            request
+                .get_output_tensor()
+                .unwrap()
+                .get_data::<f32>()
+                .unwrap(),
+            &[0.0, 0.0]
+        );
+        assert_eq!(request.infer(), Err(InferenceError::NotAllocated));
+
+        let image = MockTensor::from_data(ElementType::U8, &[1, 4], &[1u8, 2, 3, 4]).unwrap();
+        request.set_input_tensor(&image).unwrap();
+        request.infer().unwrap();
+        assert_eq!(request.infer_count(), 2);
+        assert_eq!(request.get_tensor("image").unwrap(), image);
+        assert_eq!(
+            request
+                .get_output_tensor_by_index(0)
+                .unwrap()
+                .get_data::<f32>()
+                .unwrap(),
+            &[0.25, 0.75]
+        );
+        assert_eq!(
+            model.inferences(),
+            vec![Inference::from([("image".into(), image)])]
+        );
+    }
+
+    #[test]
+    fn trait_objects() {
+        let engine: Box<dyn InferenceEngine<Session = MockInferRequest>> = Box::new(classifier());
+        let mut session: Box<dyn InferenceSession<Tensor = MockTensor>> =
+            Box::new(engine.create_infer_request().unwrap());
+        let image = MockTensor::new(ElementType::U8, &[1, 4]).unwrap();
+        session.set_input_tensor(&image).unwrap();
+        session.infer().unwrap();
+        assert_eq!(
+            session
+                .get_tensor("scores")
+                .unwrap()
+                .get_dimensions()
+                .unwrap(),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn request_errors() {
+        let model = classifier().with_error(InferenceError::InferCancelled);
+        let mut request = model.create_infer_request().unwrap();
+        let tensor = MockTensor::new(ElementType::U8, &[1]).unwrap();
+        assert_eq!(
+            request.set_tensor("missing", &tensor),
+            Err(InferenceError::NotFound)
+        );
+        assert_eq!(
+            request.set_input_tensor_by_index(1, &tensor),
+            Err(InferenceError::OutOfBounds)
+        );
+        request.set_tensor("image", &tensor).unwrap();
+        assert_eq!(request.infer(), Err(InferenceError::InferCancelled));
+        assert_eq!(model.inferences().len(), 1);
+    }
+}
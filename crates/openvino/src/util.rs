@@ -1,18 +1,110 @@
 //! A collection of utility types and macros for use inside this crate.
 use crate::InferenceError;
+use std::ptr::NonNull;
 
 /// This alias makes the implementation slightly less verbose.
 pub(crate) type Result<T> = std::result::Result<T, InferenceError>;
 
-/// Convert a Rust string into a string to pass across the C boundary.
+/// Convert a Rust string into a string to pass across the C boundary, failing with
+/// [`InferenceError::ParameterMismatch`] if it contains a NUL byte (e.g., a user-supplied name).
 #[doc(hidden)]
 #[macro_export]
 macro_rules! cstr {
     ($str: expr) => {
-        std::ffi::CString::new($str).expect("a valid C string")
+        std::ffi::CString::new($str).map_err(|_| $crate::InferenceError::ParameterMismatch)
     };
 }
 
+/// Call `f` with `s` as a C string. Short strings (e.g., tensor names) are copied to the stack
+/// rather than allocated, so that name-based lookups stay allocation-free on the inference hot
+/// path (see [`crate::InferRequest`]). Like [`cstr!`], fails with
+/// [`InferenceError::ParameterMismatch`] if `s` contains a NUL byte.
+pub(crate) fn with_c_str<T>(
+    s: &str,
+    f: impl FnOnce(*const std::ffi::c_char) -> Result<T>,
+) -> Result<T> {
+    const CAPACITY: usize = 128;
+    let bytes = s.as_bytes();
+    if bytes.len() < CAPACITY {
+        if bytes.contains(&0) {
+            return Err(InferenceError::ParameterMismatch);
+        }
+        let mut buffer = [0u8; CAPACITY];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        f(buffer.as_ptr().cast())
+    } else {
+        let s = cstr!(s)?;
+        f(s.as_ptr())
+    }
+}
+
+/// Borrow the `len` elements of `T` at `ptr`, as returned by OpenVINO, as a slice. A null pointer
+/// (e.g., the data of an empty tensor) or a zero length yields an empty slice, since
+/// [`std::slice::from_raw_parts`] requires a non-null, aligned pointer even for empty slices.
+///
+/// # Panics
+///
+/// Panics if a non-null `ptr` is not aligned for `T` or if the slice would span more than
+/// `isize::MAX` bytes.
+///
+/// # Safety
+///
+/// Unless null, `ptr` must point to `len` initialized elements of `T` which remain valid, and are
+/// not modified, for `'a`.
+pub(crate) unsafe fn slice_from_raw<'a, T>(ptr: *const T, len: usize) -> &'a [T] {
+    match checked_slice(ptr.cast_mut(), len) {
+        Some(slice) => unsafe { slice.as_ref() },
+        None => &[],
+    }
+}
+
+/// Like [`slice_from_raw`], but mutable.
+///
+/// # Panics
+///
+/// See [`slice_from_raw`].
+///
+/// # Safety
+///
+/// As for [`slice_from_raw`]; additionally, no other reference to the elements may exist for
+/// `'a`.
+pub(crate) unsafe fn slice_from_raw_mut<'a, T>(ptr: *mut T, len: usize) -> &'a mut [T] {
+    match checked_slice(ptr, len) {
+        Some(mut slice) => unsafe { slice.as_mut() },
+        None => &mut [],
+    }
+}
+
+/// Check the requirements of [`std::slice::from_raw_parts`] that do not depend on what `ptr`
+/// points to; `None` if the slice is empty.
+fn checked_slice<T>(ptr: *mut T, len: usize) -> Option<NonNull<[T]>> {
+    let ptr = NonNull::new(ptr).filter(|_| len > 0)?;
+    assert!(ptr.as_ptr().is_aligned(), "a pointer aligned for `T`");
+    assert!(
+        len.checked_mul(std::mem::size_of::<T>())
+            .is_some_and(|bytes| isize::try_from(bytes).is_ok()),
+        "a slice of at most `isize::MAX` bytes"
+    );
+    Some(NonNull::slice_from_raw_parts(ptr, len))
+}
+
+/// Find the value of the attribute `name` in the contents of an XML element (e.g., `name="value"`),
+/// e.g., to read the parts of a model's IR that the C API does not expose.
+pub(crate) fn attribute_value<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!(" {name}=\""))? + name.len() + 3;
+    let rest = &element[start..];
+    Some(&rest[..rest.find('"')?])
+}
+
+/// Iterate over the types of the `<layer>` elements (i.e., the operations) of a model's IR (i.e.,
+/// the `.xml` file), in order.
+pub(crate) fn layer_types(ir: &str) -> impl Iterator<Item = &str> {
+    // Splitting at `<layer` also yields the `<layers>` element, which has no `type`.
+    ir.split("<layer").skip(1).filter_map(|layer| {
+        attribute_value(&layer[..layer.find('>').unwrap_or(layer.len())], "type")
+    })
+}
+
 /// Convert an unsafe call to openvino-sys into an [`InferenceError`].
 #[doc(hidden)]
 #[macro_export]
@@ -36,3 +128,68 @@ macro_rules! drop_using_function {
         }
     };
 }
+
+/// Overwrite a buffer with zeroes in a way the compiler will not optimize away, e.g., to erase
+/// decrypted model data once OpenVINO has parsed it.
+pub(crate) fn wipe(buffer: &mut [u8]) {
+    for byte in buffer.iter_mut() {
+        unsafe { std::ptr::write_volatile(byte, 0) };
+    }
+    std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reject_interior_nul() {
+        assert!(matches!(
+            cstr!("input\0name"),
+            Err(InferenceError::ParameterMismatch)
+        ));
+        assert!(matches!(
+            with_c_str("input\0name", |_| Ok(())),
+            Err(InferenceError::ParameterMismatch)
+        ));
+        let long = format!("{}\0", "x".repeat(200));
+        assert!(matches!(
+            with_c_str(&long, |_| Ok(())),
+            Err(InferenceError::ParameterMismatch)
+        ));
+        let read = with_c_str("input", |p| {
+            Ok(unsafe { std::ffi::CStr::from_ptr(p) }.to_owned())
+        });
+        assert_eq!(read.unwrap().to_str(), Ok("input"));
+    }
+
+    #[test]
+    fn slices_from_raw_parts() {
+        let empty: &[u32] = unsafe { slice_from_raw(std::ptr::null(), 4) };
+        assert!(empty.is_empty());
+        let empty: &mut [u32] = unsafe { slice_from_raw_mut(std::ptr::null_mut(), 0) };
+        assert!(empty.is_empty());
+
+        let mut data = [1u32, 2, 3];
+        let slice = unsafe { slice_from_raw_mut(data.as_mut_ptr(), data.len()) };
+        slice[2] = 4;
+        assert_eq!(unsafe { slice_from_raw(data.as_ptr(), 2) }, &[1, 2]);
+        assert_eq!(data, [1, 2, 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "a pointer aligned for `T`")]
+    fn misaligned_slice() {
+        let data = [0u32; 2];
+        #[allow(clippy::cast_ptr_alignment)] // Deliberately misaligned.
+        let misaligned = data.as_ptr().cast::<u8>().wrapping_add(1).cast::<u32>();
+        let _ = unsafe { slice_from_raw(misaligned, 1) };
+    }
+
+    #[test]
+    #[should_panic(expected = "a slice of at most `isize::MAX` bytes")]
+    fn oversized_slice() {
+        let data = [0u32; 2];
+        let _ = unsafe { slice_from_raw(data.as_ptr(), usize::MAX / 2) };
+    }
+}
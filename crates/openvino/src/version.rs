@@ -1,24 +1,72 @@
+use crate::LoadingError;
 use openvino_sys::{ov_status_e, ov_version_t};
+use std::fmt;
+use std::sync::Mutex;
+
+/// The version of the currently-loaded library, once retrieved; see [`try_version`].
+static VERSION: Mutex<Option<Version>> = Mutex::new(None);
 
 /// Emit the version of the OpenVINO C library backing this implementation.
 ///
 /// # Panics
 ///
 /// Panics if no OpenVINO library can be found.
+#[cfg(feature = "legacy")]
+#[deprecated(note = "use `try_version`, which returns an error rather than panicking")]
 pub fn version() -> Version {
-    openvino_sys::load().expect("to have an OpenVINO shared library available");
+    try_version().expect("to have an OpenVINO shared library available")
+}
+
+/// Emit the version of the OpenVINO C library backing this implementation, loading the library if
+/// necessary.
+///
+/// The version is retrieved once and then cached (until this crate loads another library, e.g.,
+/// with [`crate::Core::new_for_version`]), so this is cheap enough for health checks; it is safe to
+/// call from several threads.
+///
+/// # Errors
+///
+/// Returns [`LoadingError::SystemFailure`] if no OpenVINO library can be loaded or if it fails to
+/// report its version.
+pub fn try_version() -> Result<Version, LoadingError> {
+    let mut cached = VERSION
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if let Some(version) = cached.as_ref() {
+        return Ok(version.clone());
+    }
+    openvino_sys::library::load().map_err(LoadingError::SystemFailure)?;
+    let version = loaded_version()?;
+    *cached = Some(version.clone());
+    Ok(version)
+}
+
+/// Forget the cached version (see [`try_version`]), e.g., once another library is loaded.
+pub(crate) fn forget_version() {
+    *VERSION
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+}
+
+/// Retrieve the version of the currently-loaded OpenVINO C library, without (re)loading it.
+pub(crate) fn loaded_version() -> Result<Version, LoadingError> {
     let mut ov_version = openvino_sys::ov_version_t {
         buildNumber: std::ptr::null(),
         description: std::ptr::null(),
     };
     let code = unsafe { openvino_sys::ov_get_openvino_version(&mut ov_version) };
-    assert_eq!(code, ov_status_e::OK);
+    if code != ov_status_e::OK {
+        return Err(LoadingError::SystemFailure(format!(
+            "failed to get OpenVINO version: {code:?}"
+        )));
+    }
     let version = Version::from(&ov_version);
     unsafe { openvino_sys::ov_version_free(std::ptr::addr_of_mut!(ov_version)) };
-    version
+    Ok(version)
 }
 
 /// See [`ov_version`](https://docs.openvino.ai/2024/api/c_cpp_api/structov__version.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Version {
     /// A string representing OpenVINO version.
     pub build_number: String,
@@ -42,6 +90,93 @@ impl Version {
     pub fn parts(&self) -> impl Iterator<Item = &str> {
         self.build_number.split(['.', '-'])
     }
+
+    /// Parse the build number into its structured parts (see [`BuildInfo`]), e.g., to log which
+    /// build of a plugin is in use. Returns `None` for build numbers that do not follow the
+    /// `<major>.<minor>.<patch>[-<build>-<hash>[-<branch>]]` format (e.g., custom builds).
+    pub fn build_info(&self) -> Option<BuildInfo> {
+        BuildInfo::parse(&self.build_number)
+    }
+
+    /// Convert the `<year>.<minor>.<patch>` prefix of the build number (e.g.,
+    /// `2024.6.0-17404-4c0f47d2335-releases/2024/6`) to a semantic version, dropping the build
+    /// metadata.
+    #[cfg(feature = "runtime-linking")]
+    pub(crate) fn to_semver(&self) -> Option<semver::Version> {
+        let mut parts = self.parts().map(str::parse::<u64>);
+        let major = parts.next()?.ok()?;
+        let minor = parts.next()?.ok()?;
+        let patch = parts.next().and_then(std::result::Result::ok).unwrap_or(0);
+        Some(semver::Version::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.build_info() {
+            Some(info) => write!(f, "{} {}", self.description, info),
+            None => write!(f, "{} {}", self.description, self.build_number),
+        }
+    }
+}
+
+/// The parts of an OpenVINO build number, e.g., `2024.6.0-17404-4c0f47d2335-releases/2024/6`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// The major version, i.e., the release year (e.g., `2024`).
+    pub major: u32,
+    /// The minor version (e.g., `6`).
+    pub minor: u32,
+    /// The patch version (e.g., `0`).
+    pub patch: u32,
+    /// The build number (e.g., `17404`), if present.
+    pub build: Option<u32>,
+    /// The commit hash the library was built from (e.g., `4c0f47d2335`), if present.
+    pub hash: Option<String>,
+    /// The branch the library was built from (e.g., `releases/2024/6`), if present.
+    pub branch: Option<String>,
+}
+
+impl BuildInfo {
+    fn parse(build_number: &str) -> Option<Self> {
+        let mut parts = build_number.splitn(4, '-');
+        let mut version = parts.next()?.split('.').map(str::parse::<u32>);
+        let major = version.next()?.ok()?;
+        let minor = version.next()?.ok()?;
+        let patch = version.next()?.ok()?;
+        if version.next().is_some() {
+            return None;
+        }
+        let build = match parts.next() {
+            Some(build) => Some(build.parse().ok()?),
+            None => None,
+        };
+        Some(Self {
+            major,
+            minor,
+            patch,
+            build,
+            hash: parts.next().map(str::to_string),
+            branch: parts.next().map(str::to_string),
+        })
+    }
+}
+
+impl fmt::Display for BuildInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if let Some(build) = self.build {
+            write!(f, " (build {build}")?;
+            if let Some(hash) = &self.hash {
+                write!(f, ", commit {hash}")?;
+            }
+            if let Some(branch) = &self.branch {
+                write!(f, ", branch {branch}")?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -50,9 +185,73 @@ mod tests {
 
     #[test]
     fn test_version_parts() -> anyhow::Result<()> {
-        let version = version();
+        let version = try_version()?;
         let year: usize = version.parts().next().unwrap().parse()?;
         assert!(year > 2020);
         Ok(())
     }
+
+    #[test]
+    fn test_version_is_cached() -> anyhow::Result<()> {
+        assert_eq!(try_version()?, try_version()?);
+        #[cfg(feature = "legacy")]
+        #[allow(deprecated)]
+        {
+            assert_eq!(version(), try_version()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_info() {
+        let version = Version {
+            build_number: "2024.6.0-17404-4c0f47d2335-releases/2024/6".into(),
+            description: "openvino_intel_cpu_plugin".into(),
+        };
+        let info = version.build_info().unwrap();
+        assert_eq!(
+            info,
+            BuildInfo {
+                major: 2024,
+                minor: 6,
+                patch: 0,
+                build: Some(17404),
+                hash: Some("4c0f47d2335".into()),
+                branch: Some("releases/2024/6".into()),
+            }
+        );
+        assert_eq!(
+            version.to_string(),
+            "openvino_intel_cpu_plugin 2024.6.0 (build 17404, commit 4c0f47d2335, branch releases/2024/6)"
+        );
+
+        assert_eq!(
+            BuildInfo::parse("2025.0.0").unwrap().to_string(),
+            "2025.0.0"
+        );
+        assert_eq!(BuildInfo::parse("custom_master_4c0f47d"), None);
+        let version = Version {
+            build_number: "custom_master_4c0f47d".into(),
+            description: "OpenVINO Runtime".into(),
+        };
+        assert_eq!(
+            version.to_string(),
+            "OpenVINO Runtime custom_master_4c0f47d"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "runtime-linking")]
+    fn test_version_to_semver() {
+        let version = Version {
+            build_number: "2024.6.0-17404-4c0f47d2335-releases/2024/6".into(),
+            description: "OpenVINO Runtime".into(),
+        };
+        assert_eq!(version.to_semver(), Some(semver::Version::new(2024, 6, 0)));
+        let version = Version {
+            build_number: "custom_build".into(),
+            description: String::new(),
+        };
+        assert_eq!(version.to_semver(), None);
+    }
 }
@@ -0,0 +1,68 @@
+//! These tests check that repeated inferences with pre-bound tensors do not allocate in the Rust
+//! layer.
+
+mod fixtures;
+
+use fixtures::alexnet as fixture;
+use openvino::{Core, DeviceType, ElementType, Shape, Tensor};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Count the allocations of the current thread; OpenVINO's own (C++) allocations do not go through
+/// Rust's global allocator.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn repeated_inference_does_not_allocate() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let input_name = model.get_input().unwrap().get_name().unwrap();
+    let mut request = model.create_infer_request().unwrap();
+    let input = Tensor::new(ElementType::F32, &Shape::new(&[1, 3, 227, 227]).unwrap()).unwrap();
+    request.set_tensor(&input_name, &input).unwrap();
+    // Let OpenVINO (and the Rust runtime) settle before counting; the first asynchronous
+    // inference installs the completion callback of the request.
+    request.infer().unwrap();
+    request.infer_async().unwrap();
+    request.wait(10_000).unwrap();
+
+    let before = allocations();
+    for _ in 0..3 {
+        request.set_input_tensor_by_index(0, &input).unwrap();
+        request.infer().unwrap();
+        request.infer_async().unwrap();
+        request.wait(10_000).unwrap();
+        let output = request.get_output_tensor_by_index(0).unwrap();
+        assert!(!output.get_raw_data().unwrap().is_empty());
+        request.get_tensor(&input_name).unwrap();
+    }
+    assert_eq!(allocations() - before, 0);
+}
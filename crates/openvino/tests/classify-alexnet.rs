@@ -50,14 +50,17 @@ fn classify_alexnet() -> anyhow::Result<()> {
     let new_model = pre_post_process.build_new_model()?;
 
     // Compile the model and infer the results.
-    let mut executable_model = core.compile_model(&new_model, DeviceType::CPU)?;
+    let executable_model = core.compile_model(&new_model, DeviceType::CPU)?;
+    let expectation = &executable_model.input_expectations()?[0];
+    assert_eq!(expectation.layout.as_deref(), Some("[N,H,W,C]"));
+    expectation.check(&tensor)?;
     let mut infer_request = executable_model.create_infer_request()?;
     infer_request.set_tensor("data", &tensor)?;
     infer_request.infer()?;
     let results = infer_request.get_tensor(&output_port.get_name()?)?;
 
     // Sort results.
-    let buffer = results.get_data::<f32>()?.to_vec();
+    let buffer = results.data::<f32>()?.to_vec();
     let mut results: Predictions = buffer
         .iter()
         .enumerate()
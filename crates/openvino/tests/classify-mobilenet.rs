@@ -49,7 +49,7 @@ fn classify_mobilenet() -> anyhow::Result<()> {
     let new_model = pre_post_process.build_new_model()?;
 
     // Compile the model and infer the results.
-    let mut executable_model = core.compile_model(&new_model, DeviceType::CPU)?;
+    let executable_model = core.compile_model(&new_model, DeviceType::CPU)?;
     let mut infer_request = executable_model.create_infer_request()?;
     infer_request.set_tensor("input", &tensor)?;
     infer_request.infer()?;
@@ -58,7 +58,7 @@ fn classify_mobilenet() -> anyhow::Result<()> {
     // Sort results. It is unclear why the MobileNet output indices are "off by one" but the
     // `.skip(1)` below seems necessary to get results that make sense (e.g. 763 = "revolver" vs 762
     // = "restaurant").
-    let buffer = results.get_data::<f32>()?.to_vec();
+    let buffer = results.data::<f32>()?.to_vec();
     let mut results: Predictions = buffer
         .iter()
         .skip(1)
@@ -0,0 +1,50 @@
+//! Demonstrates classifying an image with the ready-made `ImageClassifier`.
+
+mod fixtures;
+
+use fixtures::alexnet as fixture;
+use openvino::{tasks::ImageClassifier, Core, DeviceType};
+use std::fs;
+
+#[test]
+fn classify_alexnet() -> anyhow::Result<()> {
+    let mut core = Core::new()?;
+    let mut classifier = ImageClassifier::new(
+        &mut core,
+        &fixture::graph().to_string_lossy(),
+        DeviceType::CPU,
+    )?
+    .with_top_k(3);
+    assert_eq!(classifier.image_size(), (227, 227));
+
+    // The fixture tensor holds the pixels of the image as `f32` values; the classifier takes
+    // bytes.
+    let pixels: Vec<u8> = fs::read(fixture::tensor())?
+        .chunks_exact(4)
+        .map(|b| f32::from_ne_bytes([b[0], b[1], b[2], b[3]]).round() as u8)
+        .collect();
+    let results = classifier.classify(&pixels)?;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].0, 963); // pizza
+    assert!(results.windows(2).all(|w| w[0].1 >= w[1].1));
+
+    // The size of the image is checked.
+    assert!(classifier.classify(&pixels[1..]).is_err());
+
+    // Images of another size are resized by the model: here, the image upscaled twice.
+    let mut classifier = ImageClassifier::new_with_image_size(
+        &mut core,
+        &fixture::graph().to_string_lossy(),
+        DeviceType::CPU,
+        (454, 454),
+    )?;
+    assert_eq!(classifier.image_size(), (454, 454));
+    let upscaled: Vec<u8> = (0..454 * 454)
+        .flat_map(|i| {
+            let (y, x) = (i / 454 / 2, i % 454 / 2);
+            pixels[(y * 227 + x) * 3..][..3].to_vec()
+        })
+        .collect();
+    assert_eq!(classifier.classify(&upscaled)?[0].0, 963); // pizza
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! These tests demonstrate how to share compiled models between threads.
+
+mod fixtures;
+
+use fixtures::alexnet as fixture;
+use openvino::{Core, DeviceType, EngineCache};
+use std::sync::Arc;
+
+#[test]
+fn compile_once() {
+    let cache = Arc::new(EngineCache::new(Core::new().unwrap()));
+    let graph = fixture::graph().to_string_lossy().to_string();
+    let weights = fixture::weights().to_string_lossy().to_string();
+
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let cache = cache.clone();
+            let (graph, weights) = (graph.clone(), weights.clone());
+            std::thread::spawn(move || {
+                let model = cache
+                    .get_or_compile_file(&graph, &weights, DeviceType::CPU, &[])
+                    .unwrap();
+                model.create_infer_request().unwrap();
+                model
+            })
+        })
+        .collect();
+    let models: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+
+    // All threads share the same compiled model.
+    assert!(models.iter().all(|m| Arc::ptr_eq(m, &models[0])));
+    assert_eq!(cache.len(), 1);
+
+    // A different device or different properties result in a different compiled model.
+    let other = cache
+        .get_or_compile_file(
+            &graph,
+            &weights,
+            DeviceType::CPU,
+            &[(openvino::RwPropertyKey::NumStreams, "1")],
+        )
+        .unwrap();
+    assert!(!Arc::ptr_eq(&other, &models[0]));
+    assert_eq!(cache.len(), 2);
+}
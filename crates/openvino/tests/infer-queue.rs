@@ -0,0 +1,192 @@
+//! These tests demonstrate how to keep several inferences in flight with back-pressure.
+
+mod fixtures;
+
+use fixtures::alexnet as fixture;
+use openvino::{Core, DeviceType, ElementType, InferQueue, JobId, Shape, Tensor, TensorPool};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc;
+
+#[test]
+fn submit_with_back_pressure() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let completed = Rc::new(RefCell::new(vec![]));
+    let mut queue = {
+        let completed = completed.clone();
+        InferQueue::new(&model, 2, move |index, request, result| {
+            result.unwrap();
+            request.get_output_tensor().unwrap();
+            completed.borrow_mut().push(index);
+        })
+        .unwrap()
+    };
+    assert_eq!(queue.depth(), 2);
+    let frame = Tensor::new(ElementType::F32, &Shape::new(&[1, 3, 227, 227]).unwrap()).unwrap();
+
+    // Blocking submissions always succeed, waiting for a request if necessary.
+    for _ in 0..5 {
+        queue.submit_blocking(&[&frame]).unwrap();
+        assert!(queue.in_flight() <= 2);
+    }
+
+    // Once all requests are busy, non-blocking submissions are refused.
+    let mut refused = 0;
+    for _ in 0..8 {
+        if queue.try_submit(&[&frame]).unwrap().is_none() {
+            refused += 1;
+        }
+    }
+    queue.wait_all();
+    assert_eq!(queue.in_flight(), 0);
+    assert_eq!(completed.borrow().len(), 5 + 8 - refused);
+    let stats = queue.stats();
+    assert_eq!(stats.completed, completed.borrow().len() as u64);
+    assert_eq!((stats.failed, stats.cancelled), (0, 0));
+    assert!(stats.max_latency >= stats.mean_latency);
+
+    // The number of inputs is checked.
+    assert!(queue.try_submit(&[]).is_err());
+}
+
+#[test]
+fn recycle_pooled_inputs() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let mut queue = InferQueue::new(&model, 2, |_, _, result| result.unwrap())
+        .unwrap()
+        .with_pool(TensorPool::new());
+    let shape = Shape::new(&[1, 3, 227, 227]).unwrap();
+
+    for _ in 0..6 {
+        let pool = queue.pool_mut().unwrap();
+        let frame = pool.acquire(ElementType::F32, &shape).unwrap();
+        queue.submit_blocking_pooled(vec![frame]).unwrap();
+    }
+    queue.wait_all();
+
+    // Only as many tensors as requests in flight (plus the one being filled) are ever created.
+    let pool = queue.pool_mut().unwrap();
+    assert!(pool.created() <= 3);
+    assert_eq!(pool.len(), pool.created());
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn stream_results_in_order() {
+    use futures_core::Stream;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::Thread;
+
+    // A stream of the frames of an iterator, each ready at once.
+    struct Frames<I>(I);
+    impl<I: Iterator<Item = Tensor> + Unpin> Stream for Frames<I> {
+        type Item = Tensor;
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Tensor>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    // A minimal executor: park the thread until the stream wakes it.
+    struct Unpark(Thread);
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let shape = Shape::new(&[1, 3, 227, 227]).unwrap();
+
+    // Frames of different constant values, to check that the results come back in order.
+    let frames = (0..5).map(|i| {
+        let mut frame = Tensor::new(ElementType::F32, &shape).unwrap();
+        frame.data_mut::<f32>().unwrap().fill(i as f32);
+        frame
+    });
+    let mut stream = model.infer_stream(2, Frames(frames)).unwrap();
+    let waker = Waker::from(Arc::new(Unpark(std::thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut results = vec![];
+    loop {
+        match Pin::new(&mut stream).poll_next(&mut context) {
+            Poll::Ready(Some(result)) => results.push(result.unwrap()),
+            Poll::Ready(None) => break,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+    assert_eq!(results.len(), 5);
+
+    // Each result matches the synchronous inference of the same frame.
+    let mut request = model.create_infer_request().unwrap();
+    for (i, result) in results.iter().enumerate() {
+        let mut frame = Tensor::new(ElementType::F32, &shape).unwrap();
+        frame.data_mut::<f32>().unwrap().fill(i as f32);
+        request.set_input_tensor(&frame).unwrap();
+        request.infer().unwrap();
+        let expected = request.get_output_tensor().unwrap();
+        assert_eq!(
+            result.get_raw_data().unwrap(),
+            expected.get_raw_data().unwrap()
+        );
+    }
+}
+
+#[test]
+fn deliver_results_over_channel() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    let mut queue = InferQueue::new_with_sender(&model, 2, sender).unwrap();
+    let frame = Tensor::new(ElementType::F32, &Shape::new(&[1, 3, 227, 227]).unwrap()).unwrap();
+
+    let mut submitted = vec![];
+    for _ in 0..4 {
+        submitted.push(queue.next_job_id());
+        queue.submit_blocking(&[&frame]).unwrap();
+    }
+    queue.wait_all();
+    drop(queue);
+
+    // Each job's outputs arrive, in completion order, on another thread.
+    let mut received = std::thread::spawn(move || {
+        receiver
+            .into_iter()
+            .map(|(job, outputs)| (job, outputs.unwrap().len()))
+            .collect::<Vec<_>>()
+    })
+    .join()
+    .unwrap();
+    received.sort();
+    let expected: Vec<_> = submitted.into_iter().map(|job| (job, 1)).collect();
+    assert_eq!(received, expected);
+    assert_eq!(expected[3].0, JobId(3));
+}
@@ -34,3 +34,49 @@ fn memory_safety() -> anyhow::Result<()> {
     assert!(core.compile_model(&model, DeviceType::CPU).is_ok());
     Ok(())
 }
+
+#[test]
+fn request_tensors_borrow_the_request() -> anyhow::Result<()> {
+    let mut core = Core::new()?;
+    let model = core.read_model_from_file(
+        &fixture::graph().to_string_lossy(),
+        &fixture::weights().to_string_lossy(),
+    )?;
+    let model = core.compile_model(&model, DeviceType::CPU)?;
+    let input_name = model.get_input()?.get_name()?;
+    let mut request = model.create_infer_request()?;
+
+    // Data is written through a view, which borrows the request mutably...
+    request.output_view::<f32>(&input_name)?[0] = 42.0;
+
+    // ...and read through handles, which alias it but cannot modify it.
+    let first = request.get_input_tensor()?;
+    let second = request.get_tensor(&input_name)?;
+    assert!(first.shares_data_with(&second)?);
+    assert_eq!(first.data::<f32>()?[0], 42.0);
+    assert_eq!(second.data::<f32>()?[0], 42.0);
+    Ok(())
+}
+
+#[test]
+fn output_views_refuse_bound_tensors() -> anyhow::Result<()> {
+    let mut core = Core::new()?;
+    let model = core.read_model_from_file(
+        &fixture::graph().to_string_lossy(),
+        &fixture::weights().to_string_lossy(),
+    )?;
+    let model = core.compile_model(&model, DeviceType::CPU)?;
+    let output = model.get_output()?;
+    let output_name = output.get_name()?;
+    let mut request = model.create_infer_request()?;
+
+    // The caller's tensor shares its data with the request, so it cannot be viewed...
+    let mut scores = Tensor::new(output.get_element_type()?, &output.get_shape()?)?;
+    request.set_output_tensor(&scores)?;
+    assert!(request.output_view::<f32>(&output_name).is_err());
+
+    // ...and the caller reads and writes it through its own tensor instead.
+    scores.data_mut::<f32>()?[0] = 42.0;
+    assert_eq!(request.get_output_tensor()?.data::<f32>()?[0], 42.0);
+    Ok(())
+}
@@ -0,0 +1,68 @@
+//! These tests repeatedly exercise the unsafe code paths of the bindings: the slices over data
+//! owned by OpenVINO and the handles whose lifetimes OpenVINO tracks. On their own, they only
+//! check the results; run under AddressSanitizer (see the README), they catch the kind of
+//! use-after-free and out-of-bounds accesses behind the segfaults `memory-safety.rs` originates
+//! from. They are only built with the `sanitize` feature.
+
+mod fixtures;
+
+use fixtures::mobilenet as fixture;
+use openvino::{Core, DeviceType, ElementType, Shape, Tensor};
+use std::fs;
+
+/// The number of times to repeat each scenario, so that a freed allocation is likely to be reused
+/// (and corrupted) before it is read again.
+const ITERATIONS: usize = 32;
+
+#[test]
+fn empty_data() -> anyhow::Result<()> {
+    Core::new()?;
+    for _ in 0..ITERATIONS {
+        let mut tensor = Tensor::new(ElementType::F32, &Shape::new(&[0, 3])?)?;
+        assert!(tensor.get_raw_data()?.is_empty());
+        assert!(tensor.data_mut::<f32>()?.is_empty());
+        assert_eq!(tensor.get_shape()?.get_dimensions(), &[0, 3]);
+        assert!(Shape::new(&[])?.get_dimensions().is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn device_lists() -> anyhow::Result<()> {
+    let core = Core::new()?;
+    for _ in 0..ITERATIONS {
+        let devices = core.available_devices()?;
+        assert!(devices.contains(&DeviceType::CPU));
+        assert!(!core.versions("CPU")?.is_empty());
+    }
+    Ok(())
+}
+
+#[test]
+fn handles_outlive_their_owners() -> anyhow::Result<()> {
+    let mut core = Core::new()?;
+    let xml = fs::read_to_string(fixture::graph())?;
+    let weights = fs::read(fixture::weights())?;
+    for _ in 0..ITERATIONS {
+        // The model keeps a reference to the weights tensor (see `memory-safety.rs`).
+        let mut weights_tensor =
+            Tensor::new(ElementType::U8, &Shape::new(&[1, weights.len() as i64])?)?;
+        weights_tensor.get_raw_data_mut()?.copy_from_slice(&weights);
+        let model = core.read_model_from_buffer(xml.as_bytes(), Some(&weights_tensor))?;
+        drop(weights_tensor);
+
+        // The tensors of a request keep their data alive after the model is freed.
+        let compiled = core.compile_model(&model, DeviceType::CPU)?;
+        drop(model);
+        let output_name = compiled.get_output()?.get_name()?;
+        let mut request = compiled.create_infer_request()?;
+        drop(compiled);
+        request.infer()?;
+        let expected = request.output_view::<f32>(&output_name)?.to_vec();
+        let input = request.get_input_tensor()?;
+        let output = request.get_tensor(&output_name)?;
+        assert!(!input.get_raw_data()?.is_empty());
+        assert_eq!(output.data::<f32>()?, &expected[..]);
+    }
+    Ok(())
+}
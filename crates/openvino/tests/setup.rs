@@ -3,8 +3,12 @@
 mod fixtures;
 
 use fixtures::alexnet as fixture;
-use openvino::{Core, ElementType, Shape, Tensor};
+use openvino::{
+    Core, DeviceType, ElementType, InferenceError, ModelPriority, RwPropertyKey, SetupError, Shape,
+    Tensor,
+};
 use std::fs;
+use std::time::Duration;
 
 #[test]
 fn read_network() {
@@ -40,3 +44,204 @@ fn read_network_from_buffers() {
     assert_eq!(read_model.get_inputs_len(), Ok(1));
     assert_eq!(read_model.get_outputs_len(), Ok(1));
 }
+
+#[test]
+fn read_network_with_decryption() {
+    // A toy "encryption" for testing: XOR each byte with a key.
+    fn xor(data: &[u8]) -> std::io::Result<Vec<u8>> {
+        Ok(data.iter().map(|b| b ^ 0x5a).collect())
+    }
+    let mut core = Core::new().unwrap();
+    let graph = xor(&fs::read(fixture::graph()).unwrap()).unwrap();
+    let weights = xor(&fs::read(fixture::weights()).unwrap()).unwrap();
+
+    let read_model = core
+        .read_model_with_decryption(&graph, &weights, xor)
+        .unwrap();
+    assert_eq!(read_model.get_inputs_len(), Ok(1));
+
+    // A failing decryption is reported as such.
+    let error = core
+        .read_model_with_decryption(&graph, &weights, |_| {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "bad key",
+            ))
+        })
+        .err()
+        .unwrap();
+    assert!(matches!(error, openvino::SetupError::Io(_)));
+}
+
+#[test]
+fn read_network_from_readers() {
+    let mut core = Core::new().unwrap();
+    let graph = fs::File::open(fixture::graph()).unwrap();
+    let weights = fs::File::open(fixture::weights()).unwrap();
+
+    let read_model = core.read_model_from_reader(graph, weights).unwrap();
+
+    // Check the number of inputs and outputs.
+    assert_eq!(read_model.get_inputs_len(), Ok(1));
+    assert_eq!(read_model.get_outputs_len(), Ok(1));
+}
+
+#[test]
+fn warm_up() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let compiled_model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let shape = Shape::new(&[1, 3, 227, 227]).unwrap();
+    compiled_model.warm_up(&[shape], 2).unwrap();
+
+    // An incompatible shape is reported.
+    let shape = Shape::new(&[1, 2]).unwrap();
+    assert!(compiled_model.warm_up(&[shape], 1).is_err());
+}
+
+#[test]
+fn compile_with_priority() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let interactive = core
+        .compile_model_with_priority(&model, DeviceType::AUTO, ModelPriority::High)
+        .unwrap();
+    let batch = core
+        .compile_model_with_priority(&model, DeviceType::AUTO, ModelPriority::Low)
+        .unwrap();
+    assert_eq!(interactive.model_priority(), Ok(ModelPriority::High));
+    assert_eq!(batch.model_priority(), Ok(ModelPriority::Low));
+
+    // The priority of the models compiled next is left unchanged.
+    assert_eq!(
+        core.get_property(&DeviceType::AUTO, &RwPropertyKey::HintModelPriority.into())
+            .unwrap(),
+        "MEDIUM"
+    );
+}
+
+#[test]
+fn infer_with_timeout() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let compiled_model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let mut request = compiled_model.create_infer_request().unwrap();
+    let tensor = Tensor::new(ElementType::F32, &Shape::new(&[1, 3, 227, 227]).unwrap()).unwrap();
+    request.set_input_tensor(&tensor).unwrap();
+    request.infer_with_timeout(Duration::from_secs(60)).unwrap();
+
+    // An impossible deadline cancels the inference; the request remains usable afterwards.
+    assert_eq!(
+        request.infer_with_timeout(Duration::ZERO),
+        Err(InferenceError::TimedOut)
+    );
+    request.infer().unwrap();
+}
+
+#[test]
+#[cfg(feature = "prepostprocess")]
+fn preprocess_by_node() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let pipeline = openvino::prepostprocess::Pipeline::new(&model).unwrap();
+
+    // Configure every input and output without referring to their names.
+    for input in model.inputs().unwrap() {
+        let info = pipeline.get_input_info_by_node(&input).unwrap();
+        info.get_tensor_info()
+            .unwrap()
+            .set_layout(openvino::Layout::new("NCHW").unwrap())
+            .unwrap();
+    }
+    for output in model.outputs().unwrap() {
+        let info = pipeline.get_output_info_by_node(&output).unwrap();
+        info.get_tensor_info()
+            .unwrap()
+            .set_element_type(ElementType::F32)
+            .unwrap();
+        assert!(pipeline.get_input_info_by_node(&output).is_err());
+    }
+    pipeline.build_new_model().unwrap();
+}
+
+#[test]
+fn export_and_import_model() {
+    let mut core = Core::new().unwrap();
+    let model = core
+        .read_model_from_file(
+            &fixture::graph().to_string_lossy(),
+            &fixture::weights().to_string_lossy(),
+        )
+        .unwrap();
+    let compiled_model = core.compile_model(&model, DeviceType::CPU).unwrap();
+    let dir = std::env::temp_dir().join("openvino-rs-export-and-import-model");
+    fs::create_dir_all(&dir).unwrap();
+    let blob = dir.join("alexnet.blob");
+    let blob = blob.to_str().unwrap();
+    compiled_model.export_model(blob).unwrap();
+
+    let imported = core.import_model_from_file(blob, DeviceType::CPU).unwrap();
+    assert_eq!(imported.get_input_size(), Ok(1));
+
+    // A blob the device does not recognize is reported as incompatible.
+    fs::write(blob, b"not a compiled model").unwrap();
+    assert!(matches!(
+        core.import_model_from_file(blob, DeviceType::CPU),
+        Err(SetupError::IncompatibleBlob(_))
+    ));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "gpu")]
+fn gpu_kernel_cache() {
+    let core = Core::new().unwrap();
+    // Only machines with a GPU can run this test.
+    if !core.available_devices().unwrap().contains(&DeviceType::GPU) {
+        return;
+    }
+    let dir = std::env::temp_dir().join("openvino-rs-gpu-kernel-cache");
+    let _ = fs::remove_dir_all(&dir);
+    let cache = openvino::GpuKernelCache::new(&dir);
+
+    // Each compilation uses a new `Core`, so that only the on-disk cache can be reused.
+    let compile = || {
+        let mut core = Core::new().unwrap();
+        core.set_gpu_kernel_cache(&cache).unwrap();
+        assert_eq!(core.gpu_kernel_cache().unwrap(), Some(cache.clone()));
+        let model = core
+            .read_model_from_file(
+                &fixture::graph().to_string_lossy(),
+                &fixture::weights().to_string_lossy(),
+            )
+            .unwrap();
+        core.compile_model(&model, DeviceType::GPU).unwrap();
+    };
+    compile();
+    let entries = cache.entries().unwrap();
+    assert!(!entries.is_empty());
+    // The second compilation reads the cache rather than adding to it.
+    compile();
+    assert_eq!(cache.entries().unwrap(), entries);
+    fs::remove_dir_all(&dir).unwrap();
+}
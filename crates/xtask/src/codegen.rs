@@ -13,6 +13,11 @@ pub struct CodegenCommand {
     #[arg(short = 'i', long = "input-header-file")]
     header_file: Option<PathBuf>,
 
+    /// The path to OpenVINO's version header, from which to read the release of the headers; by
+    /// default, `.../openvino-sys/upstream/src/core/include/openvino/core/version.hpp`.
+    #[arg(long = "version-header-file")]
+    version_header_file: Option<PathBuf>,
+
     /// The path to the directory in which to output the generated files; by default,
     /// `.../openvino-sys/crates/src/generated`.
     #[arg(short = 'o', long = "output-directory")]
@@ -74,9 +79,56 @@ impl CodegenCommand {
             .open(&function_bindings_path)?;
         function_bindings_file.write_all(b"\n")?;
         function_bindings_file.write_all(b"}\n")?;
+
+        // Record the release of the headers into `.../version.rs`.
+        let version = Self::read_version(&self.path_to_version_header_file()?)?;
+        let version_path = output_directory.join(VERSION_FILE);
+        std::fs::write(
+            &version_path,
+            format!(
+                "/// The release of the OpenVINO C API headers these bindings were generated from.\n\
+                 pub(crate) const BINDING_VERSION: &str = \"{version}\";\n"
+            ),
+        )
+        .with_context(|| format!("Failed to write version to: {}", version_path.display()))?;
         Ok(())
     }
 
+    fn path_to_version_header_file(&self) -> Result<PathBuf> {
+        Ok(match self.version_header_file.clone() {
+            Some(path) => {
+                ensure!(
+                    path.is_file(),
+                    "The version header file must be an actual file."
+                );
+                path
+            }
+            None => path_to_crates()?.join(DEFAULT_VERSION_HEADER_FILE),
+        })
+    }
+
+    /// Read the release (e.g., `2025.0`) from the `OPENVINO_VERSION_MAJOR` and
+    /// `OPENVINO_VERSION_MINOR` definitions of OpenVINO's version header.
+    fn read_version(version_header_file: &Path) -> Result<String> {
+        let header = std::fs::read_to_string(version_header_file).with_context(|| {
+            format!(
+                "Failed to read version header: {}",
+                version_header_file.display()
+            )
+        })?;
+        let define = |name: &str| -> Result<String> {
+            let re = Regex::new(&format!(r"(?m)^\s*#define\s+{name}\s+(\d+)")).unwrap();
+            re.captures(&header)
+                .map(|caps| caps[1].to_string())
+                .ok_or_else(|| anyhow!("unable to find {name} in the version header"))
+        };
+        Ok(format!(
+            "{}.{}",
+            define("OPENVINO_VERSION_MAJOR")?,
+            define("OPENVINO_VERSION_MINOR")?
+        ))
+    }
+
     fn path_to_header_file(&self) -> Result<PathBuf> {
         Ok(match self.header_file.clone() {
             Some(path) => {
@@ -149,6 +201,9 @@ impl CodegenCommand {
 
 const TYPES_FILE: &str = "types.rs";
 const FUNCTIONS_FILE: &str = "functions.rs";
+const VERSION_FILE: &str = "version.rs";
 const DEFAULT_OUTPUT_DIRECTORY: &str = "openvino-sys/src/generated";
 const DEFAULT_HEADER_FILE: &str =
     "openvino-sys/upstream/src/bindings/c/include/openvino/c/openvino.h";
+const DEFAULT_VERSION_HEADER_FILE: &str =
+    "openvino-sys/upstream/src/core/include/openvino/core/version.hpp";